@@ -0,0 +1,12 @@
+-- Reusable task description templates, either global or scoped to a project
+CREATE TABLE task_templates (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB,  -- NULL for global templates
+    title         TEXT NOT NULL,
+    description   TEXT NOT NULL DEFAULT '',
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_templates_project_id ON task_templates(project_id);
@@ -0,0 +1,10 @@
+-- Per-repo environment variables injected into executions, stored as a JSON object
+ALTER TABLE repos ADD COLUMN env_vars TEXT NOT NULL DEFAULT '{}';
+
+-- Redacted snapshot of the environment each execution process was started with
+CREATE TABLE execution_process_env (
+    execution_process_id BLOB PRIMARY KEY,
+    vars                 TEXT NOT NULL DEFAULT '[]',
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
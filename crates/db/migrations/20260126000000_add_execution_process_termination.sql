@@ -0,0 +1,3 @@
+-- Record whether a stopped execution exited after a graceful interrupt or was force killed
+ALTER TABLE execution_processes ADD COLUMN termination TEXT
+    CHECK (termination IN ('graceful_interrupt', 'force_kill'));
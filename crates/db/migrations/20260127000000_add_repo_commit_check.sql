@@ -0,0 +1,3 @@
+-- Which changes in a repo make the agent commit before it stops: 'all', 'tracked_only' or 'off'
+ALTER TABLE repos ADD COLUMN commit_check TEXT NOT NULL DEFAULT 'all'
+    CHECK (commit_check IN ('all', 'tracked_only', 'off'));
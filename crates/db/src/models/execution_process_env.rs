@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where an environment variable given to an execution process came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvVarSource {
+    /// Set by Vibe Kanban itself (`VK_*` vars).
+    Runtime,
+    /// Configured on one of the workspace's repositories.
+    Repo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExecutionEnvVar {
+    pub key: String,
+    /// Already redacted for secrets.
+    pub value: String,
+    pub source: EnvVarSource,
+    /// Repository the var was configured on, when `source` is `repo`.
+    pub repo_name: Option<String>,
+}
+
+/// Redacted snapshot of the environment an execution process was started with. Profile
+/// env and request-specific vars are applied later by the executor and are not included.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionProcessEnv {
+    pub execution_process_id: Uuid,
+    #[ts(type = "Array<ExecutionEnvVar>")]
+    pub vars: sqlx::types::Json<Vec<ExecutionEnvVar>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessEnv {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        vars: Vec<ExecutionEnvVar>,
+    ) -> Result<(), sqlx::Error> {
+        let vars = sqlx::types::Json(vars);
+        sqlx::query!(
+            r#"INSERT INTO execution_process_env (execution_process_id, vars)
+               VALUES ($1, $2)
+               ON CONFLICT(execution_process_id) DO UPDATE SET vars = excluded.vars"#,
+            execution_process_id,
+            vars
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessEnv,
+            r#"SELECT
+                    execution_process_id as "execution_process_id!: Uuid",
+                    vars as "vars!: sqlx::types::Json<Vec<ExecutionEnvVar>>",
+                    created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_env
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -1,5 +1,6 @@
 pub mod coding_agent_turn;
 pub mod execution_process;
+pub mod execution_process_env;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
 pub mod image;
@@ -11,5 +12,6 @@ pub mod scratch;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_template;
 pub mod workspace;
 pub mod workspace_repo;
@@ -55,6 +55,8 @@ pub enum SearchMatchType {
     FileName,
     DirectoryName,
     FullPath,
+    /// The file's contents matched, rather than its path
+    Content,
 }
 
 impl Project {
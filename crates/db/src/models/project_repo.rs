@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -7,7 +7,7 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{CommitCheck, Repo};
 
 #[derive(Debug, Error)]
 pub enum ProjectRepoError {
@@ -83,6 +83,8 @@ impl ProjectRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      r.commit_check as "commit_check!: CommitCheck",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
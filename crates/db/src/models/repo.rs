@@ -1,9 +1,13 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use executors::env::{REDACTED_ENV_VALUE, redact_env_value};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_with::rust::double_option;
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -16,6 +20,21 @@ pub enum RepoError {
     NotFound,
 }
 
+/// Which changes in a repo make the agent commit before it stops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CommitCheck {
+    /// Any tracked or untracked change
+    #[default]
+    All,
+    /// Only changes to tracked files, e.g. when the repo produces untracked build output
+    TrackedOnly,
+    /// Never check this repo, e.g. vendored code
+    Off,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Repo {
     pub id: Uuid,
@@ -28,6 +47,12 @@ pub struct Repo {
     pub parallel_setup_script: bool,
     pub dev_server_script: Option<String>,
     pub default_target_branch: Option<String>,
+    /// Environment variables injected into every execution in this repo. Values are redacted
+    /// when serialized unless the key is allowlisted as safe.
+    #[serde(serialize_with = "serialize_redacted_env")]
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: sqlx::types::Json<HashMap<String, String>>,
+    pub commit_check: CommitCheck,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -92,6 +117,27 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub default_target_branch: Option<Option<String>>,
+
+    /// Replaces the repo's env vars. A value of `***` keeps the stored value for that key,
+    /// so a redacted map read from the API can be sent back unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub env_vars: Option<HashMap<String, String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub commit_check: Option<CommitCheck>,
+}
+
+fn serialize_redacted_env<S: Serializer>(
+    env_vars: &sqlx::types::Json<HashMap<String, String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    env_vars
+        .iter()
+        .map(|(k, v)| (k, redact_env_value(k, v)))
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
 }
 
 impl Repo {
@@ -110,6 +156,8 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      commit_check as "commit_check!: CommitCheck",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -149,6 +197,8 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      commit_check as "commit_check!: CommitCheck",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -205,6 +255,8 @@ impl Repo {
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
                          default_target_branch,
+                         env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                         commit_check as "commit_check!: CommitCheck",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -240,6 +292,8 @@ impl Repo {
                       parallel_setup_script as "parallel_setup_script!: bool",
                       dev_server_script,
                       default_target_branch,
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      commit_check as "commit_check!: CommitCheck",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -289,6 +343,22 @@ impl Repo {
             None => existing.default_target_branch,
             Some(v) => v.clone(),
         };
+        let env_vars = match &payload.env_vars {
+            None => existing.env_vars,
+            Some(v) => sqlx::types::Json(
+                v.iter()
+                    .filter_map(|(key, value)| {
+                        let value = if value == REDACTED_ENV_VALUE {
+                            existing.env_vars.get(key)?.clone()
+                        } else {
+                            value.clone()
+                        };
+                        Some((key.clone(), value))
+                    })
+                    .collect(),
+            ),
+        };
+        let commit_check = payload.commit_check.unwrap_or(existing.commit_check);
 
         sqlx::query_as!(
             Repo,
@@ -300,8 +370,10 @@ impl Repo {
                    parallel_setup_script = $5,
                    dev_server_script = $6,
                    default_target_branch = $7,
+                   env_vars = $8,
+                   commit_check = $9,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $8
+               WHERE id = $10
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -312,6 +384,8 @@ impl Repo {
                          parallel_setup_script as "parallel_setup_script!: bool",
                          dev_server_script,
                          default_target_branch,
+                         env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                         commit_check as "commit_check!: CommitCheck",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -321,6 +395,8 @@ impl Repo {
             parallel_setup_script,
             dev_server_script,
             default_target_branch,
+            env_vars,
+            commit_check,
             id
         )
         .fetch_one(pool)
@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>, // None for global templates
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskTemplate {
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskTemplate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+impl TaskTemplate {
+    /// Returns true if the template can be used for tasks in the given project.
+    pub fn is_available_for(&self, project_id: Uuid) -> bool {
+        self.project_id.is_none_or(|id| id == project_id)
+    }
+
+    /// Prepends the template content to a task description. The result is
+    /// copied into the task, so later template edits or deletion do not affect it.
+    pub fn apply(&self, description: Option<&str>) -> Option<String> {
+        let template = self.description.trim_end();
+        let description = description.map(str::trim).filter(|d| !d.is_empty());
+        match (template.is_empty(), description) {
+            (true, description) => description.map(str::to_string),
+            (false, None) => Some(template.to_string()),
+            (false, Some(description)) => Some(format!("{template}\n\n{description}")),
+        }
+    }
+
+    pub async fn find_global(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", title, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id IS NULL
+               ORDER BY title ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Templates usable in a project: its own templates followed by global ones.
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", title, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id = $1 OR project_id IS NULL
+               ORDER BY project_id IS NULL ASC, title ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", title, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        data: &CreateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"INSERT INTO task_templates (id, project_id, title, description)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", title, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.title,
+            data.description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let description = data.description.as_ref().unwrap_or(&existing.description);
+
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"UPDATE task_templates
+               SET title = $2, description = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", title, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            title,
+            description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(project_id: Option<Uuid>, description: &str) -> TaskTemplate {
+        TaskTemplate {
+            id: Uuid::new_v4(),
+            project_id,
+            title: "Bug report".to_string(),
+            description: description.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn global_templates_are_available_everywhere() {
+        let global = template(None, "");
+        assert!(global.is_available_for(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn project_templates_are_only_available_in_their_project() {
+        let project_id = Uuid::new_v4();
+        let scoped = template(Some(project_id), "");
+        assert!(scoped.is_available_for(project_id));
+        assert!(!scoped.is_available_for(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn apply_prepends_template_to_description() {
+        let t = template(None, "## Steps\n");
+        assert_eq!(
+            t.apply(Some("  crashes on save ")).as_deref(),
+            Some("## Steps\n\ncrashes on save")
+        );
+    }
+
+    #[test]
+    fn apply_without_description_uses_template() {
+        let t = template(None, "## Steps\n");
+        assert_eq!(t.apply(None).as_deref(), Some("## Steps"));
+        assert_eq!(t.apply(Some("   ")).as_deref(), Some("## Steps"));
+    }
+
+    #[test]
+    fn apply_with_empty_template_keeps_description() {
+        let t = template(None, "  \n");
+        assert_eq!(t.apply(Some("details")).as_deref(), Some("details"));
+        assert_eq!(t.apply(None), None);
+    }
+}
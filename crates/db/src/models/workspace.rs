@@ -371,6 +371,35 @@ impl Workspace {
         .await
     }
 
+    /// Find unarchived workspaces in a project whose parent task is done.
+    pub async fn find_unarchived_for_done_tasks(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Workspace>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT
+                w.id as "id!: Uuid",
+                w.task_id as "task_id!: Uuid",
+                w.container_ref,
+                w.branch as "branch!",
+                w.agent_working_dir,
+                w.setup_completed_at as "setup_completed_at: DateTime<Utc>",
+                w.created_at as "created_at!: DateTime<Utc>",
+                w.updated_at as "updated_at!: DateTime<Utc>",
+                w.archived as "archived!: bool",
+                w.pinned as "pinned!: bool",
+                w.name
+            FROM workspaces w
+            JOIN tasks t ON w.task_id = t.id
+            WHERE t.project_id = $1 AND t.status = 'done' AND w.archived = 0
+            ORDER BY w.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn create(
         pool: &SqlitePool,
         data: &CreateWorkspace,
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -6,7 +6,7 @@ use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{CommitCheck, Repo};
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct WorkspaceRepo {
@@ -121,6 +121,8 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      r.commit_check as "commit_check!: CommitCheck",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -148,6 +150,8 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      r.commit_check as "commit_check!: CommitCheck",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -174,6 +178,8 @@ impl WorkspaceRepo {
                     parallel_setup_script: row.parallel_setup_script,
                     dev_server_script: row.dev_server_script,
                     default_target_branch: row.default_target_branch,
+                    env_vars: row.env_vars,
+                    commit_check: row.commit_check,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -261,6 +267,8 @@ impl WorkspaceRepo {
                       r.parallel_setup_script as "parallel_setup_script!: bool",
                       r.dev_server_script,
                       r.default_target_branch,
+                      r.env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
+                      r.commit_check as "commit_check!: CommitCheck",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
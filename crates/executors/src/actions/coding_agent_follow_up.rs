@@ -77,9 +77,11 @@ impl Executable for CodingAgentFollowUpRequest {
 
             agent.use_approvals(approvals.clone());
 
-            agent
+            let mut spawned = agent
                 .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
-                .await
+                .await?;
+            spawned.executor = Some(agent);
+            Ok(spawned)
         }
     }
 }
@@ -69,7 +69,9 @@ impl Executable for CodingAgentInitialRequest {
 
             agent.use_approvals(approvals.clone());
 
-            agent.spawn(&effective_dir, &self.prompt, env).await
+            let mut spawned = agent.spawn(&effective_dir, &self.prompt, env).await?;
+            spawned.executor = Some(agent);
+            Ok(spawned)
         }
     }
 }
@@ -69,13 +69,15 @@ impl Executable for ReviewRequest {
 
         agent.use_approvals(approvals.clone());
 
-        agent
+        let mut spawned = agent
             .spawn_review(
                 &effective_dir,
                 &self.prompt,
                 self.session_id.as_deref(),
                 env,
             )
-            .await
+            .await?;
+        spawned.executor = Some(agent);
+        Ok(spawned)
     }
 }
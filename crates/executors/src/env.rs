@@ -1,4 +1,7 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
 
 use tokio::process::Command;
 
@@ -10,6 +13,10 @@ pub struct RepoContext {
     pub workspace_root: PathBuf,
     /// Names of repositories in the workspace (subdirectory names)
     pub repo_names: Vec<String>,
+    /// Repositories skipped by the uncommitted-changes check, e.g. vendored or generated code
+    pub ignored_repo_paths: HashSet<PathBuf>,
+    /// Repositories where untracked files don't count as uncommitted changes
+    pub tracked_only_repo_paths: HashSet<PathBuf>,
 }
 
 impl RepoContext {
@@ -17,6 +24,8 @@ impl RepoContext {
         Self {
             workspace_root,
             repo_names,
+            ignored_repo_paths: HashSet::new(),
+            tracked_only_repo_paths: HashSet::new(),
         }
     }
 
@@ -26,12 +35,69 @@ impl RepoContext {
             .map(|name| self.workspace_root.join(name))
             .collect()
     }
+
+    /// Porcelain status of every repo that should be committed before the agent stops,
+    /// or an empty string when there is nothing to commit.
+    pub async fn check_uncommitted_changes(&self) -> String {
+        let repo_paths: Vec<PathBuf> = self
+            .repo_paths()
+            .into_iter()
+            .filter(|path| !self.ignored_repo_paths.contains(path))
+            .collect();
+        workspace_utils::git::check_uncommitted_changes(&repo_paths, &self.tracked_only_repo_paths)
+            .await
+    }
+}
+
+/// Placeholder shown instead of the value of a secret environment variable.
+pub const REDACTED_ENV_VALUE: &str = "***";
+
+/// Variables whose values are known not to carry secrets and are shown as-is. Every other
+/// value is redacted, since a key name alone can't tell whether it holds a credential.
+const SAFE_ENV_VARS: &[&str] = &[
+    "VK_PROJECT_ID",
+    "VK_PROJECT_NAME",
+    "VK_TASK_ID",
+    "VK_WORKSPACE_BRANCH",
+    "VK_WORKSPACE_ID",
+    "CI",
+    "GOFLAGS",
+    "HOME",
+    "LANG",
+    "LC_ALL",
+    "NODE_ENV",
+    "PATH",
+    "RUST_BACKTRACE",
+    "RUST_LOG",
+    "SHELL",
+    "TERM",
+    "TZ",
+];
+
+/// Whether an environment variable's value must be hidden, i.e. its key is not in the
+/// allowlist of safe variables.
+pub fn is_secret_env_var(key: &str) -> bool {
+    !SAFE_ENV_VARS
+        .iter()
+        .any(|safe| safe.eq_ignore_ascii_case(key))
+}
+
+/// Value of an environment variable that is safe to log or return over the API.
+pub fn redact_env_value(key: &str, value: &str) -> String {
+    if is_secret_env_var(key) {
+        REDACTED_ENV_VALUE.to_string()
+    } else {
+        value.to_string()
+    }
 }
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// Vars configured on the workspace's repositories. They take precedence over profile
+    /// env, so they are re-applied whenever a profile is merged in.
+    pub repo_vars: HashMap<String, String>,
     pub repo_context: RepoContext,
     pub commit_reminder: bool,
 }
@@ -40,6 +106,7 @@ impl ExecutionEnv {
     pub fn new(repo_context: RepoContext, commit_reminder: bool) -> Self {
         Self {
             vars: HashMap::new(),
+            repo_vars: HashMap::new(),
             repo_context,
             commit_reminder,
         }
@@ -62,15 +129,32 @@ impl ExecutionEnv {
         self
     }
 
+    /// Merge repository-level vars into this env. They override runtime and profile vars;
+    /// vars an executor inserts afterwards for a specific request still win.
+    pub fn merge_repo_vars(&mut self, repo_vars: &HashMap<String, String>) {
+        self.repo_vars
+            .extend(repo_vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.merge(repo_vars);
+    }
+
     /// Return a new env with profile env from CmdOverrides merged in.
     pub fn with_profile(self, cmd: &CmdOverrides) -> Self {
         if let Some(ref profile_env) = cmd.env {
-            self.with_overrides(profile_env)
+            let repo_vars = self.repo_vars.clone();
+            self.with_overrides(profile_env).with_overrides(&repo_vars)
         } else {
             self
         }
     }
 
+    /// All vars with secret values replaced by [`REDACTED_ENV_VALUE`], sorted by key.
+    pub fn redacted_vars(&self) -> BTreeMap<String, String> {
+        self.vars
+            .iter()
+            .map(|(k, v)| (k.clone(), redact_env_value(k, v)))
+            .collect()
+    }
+
     /// Apply all environment variables to a Command
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
@@ -89,8 +173,40 @@ impl ExecutionEnv {
 
 #[cfg(test)]
 mod tests {
+    use std::process::Command as StdCommand;
+
     use super::*;
 
+    fn init_repo_with_untracked_file(path: &std::path::Path) {
+        std::fs::create_dir_all(path).unwrap();
+        let status = StdCommand::new("git")
+            .args(["init", "-q"])
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        std::fs::write(path.join("new.txt"), "changed").unwrap();
+    }
+
+    #[tokio::test]
+    async fn ignored_repo_does_not_block_stop() {
+        let root = std::env::temp_dir().join(format!("vk-commit-check-{}", uuid::Uuid::new_v4()));
+        init_repo_with_untracked_file(&root.join("app"));
+        init_repo_with_untracked_file(&root.join("vendor"));
+
+        let mut ctx = RepoContext::new(root.clone(), vec!["app".into(), "vendor".into()]);
+        ctx.ignored_repo_paths.insert(root.join("vendor"));
+        let status = ctx.check_uncommitted_changes().await;
+
+        assert!(status.contains(&root.join("app").display().to_string()));
+        assert!(!status.contains(&root.join("vendor").display().to_string()));
+
+        ctx.tracked_only_repo_paths.insert(root.join("app"));
+        assert!(ctx.check_uncommitted_changes().await.is_empty());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
     #[test]
     fn profile_overrides_runtime_env() {
         let mut base = ExecutionEnv::new(RepoContext::default(), false);
@@ -107,4 +223,45 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn repo_vars_override_profile_env() {
+        let mut base = ExecutionEnv::new(RepoContext::default(), false);
+        base.insert("VK_PROJECT_NAME", "runtime");
+        base.insert("GOFLAGS", "runtime");
+
+        let mut repo = HashMap::new();
+        repo.insert("GOFLAGS".to_string(), "repo".to_string());
+        base.merge_repo_vars(&repo);
+
+        let mut profile_env = HashMap::new();
+        profile_env.insert("GOFLAGS".to_string(), "profile".to_string());
+        profile_env.insert("BAR".to_string(), "profile".to_string());
+        let cmd = CmdOverrides {
+            env: Some(profile_env),
+            ..Default::default()
+        };
+
+        let merged = base.with_profile(&cmd);
+
+        assert_eq!(merged.vars.get("VK_PROJECT_NAME").unwrap(), "runtime");
+        assert_eq!(merged.vars.get("GOFLAGS").unwrap(), "repo");
+        assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
+    }
+
+    #[test]
+    fn values_are_redacted_unless_allowlisted() {
+        assert_eq!(
+            redact_env_value("GITHUB_TOKEN", "ghp_abc"),
+            REDACTED_ENV_VALUE
+        );
+        assert_eq!(
+            redact_env_value("DATABASE_URL", "postgres://localhost/db"),
+            REDACTED_ENV_VALUE
+        );
+        assert_eq!(redact_env_value("MY_SERVICE", "opaque"), REDACTED_ENV_VALUE);
+        assert_eq!(redact_env_value("GOFLAGS", "-mod=vendor"), "-mod=vendor");
+        assert_eq!(redact_env_value("rust_log", "debug"), "debug");
+        assert_eq!(redact_env_value("VK_TASK_ID", "1234"), "1234");
+    }
 }
@@ -117,12 +117,27 @@ impl acp::Client for AcpClient {
         };
 
         // Map our ApprovalStatus to ACP outcome
+        if matches!(status, ApprovalStatus::ApprovedWithEdits { .. }) {
+            warn!("ACP agents cannot apply edited tool input; approving the original call");
+        }
         let outcome = match &status {
-            ApprovalStatus::Approved => {
+            ApprovalStatus::Approved
+            | ApprovalStatus::ApprovedForSession
+            | ApprovalStatus::ApprovedWithEdits { .. }
+            | ApprovalStatus::AutoAllowlisted => {
+                // A session approval picks "allow always" when the agent offers it
+                let for_session = matches!(status, ApprovalStatus::ApprovedForSession);
                 let chosen = args
                     .options
                     .iter()
-                    .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce));
+                    .find(|o| {
+                        for_session && matches!(o.kind, acp::PermissionOptionKind::AllowAlways)
+                    })
+                    .or_else(|| {
+                        args.options
+                            .iter()
+                            .find(|o| matches!(o.kind, acp::PermissionOptionKind::AllowOnce))
+                    });
                 if let Some(opt) = chosen {
                     acp::RequestPermissionOutcome::Selected(acp::SelectedPermissionOutcome::new(
                         opt.option_id.clone(),
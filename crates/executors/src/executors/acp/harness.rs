@@ -113,6 +113,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            executor: None,
         })
     }
 
@@ -163,6 +164,7 @@ impl AcpAgentHarness {
             child,
             exit_signal: Some(exit_rx),
             interrupt_sender: None,
+            executor: None,
         })
     }
 
@@ -8,7 +8,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     process::Stdio,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use async_trait::async_trait;
@@ -24,7 +24,10 @@ use workspace_utils::{
 };
 
 use self::{
-    client::{AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient, STOP_GIT_CHECK_CALLBACK_ID},
+    client::{
+        AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient, SESSION_COMPLETE_SUBTYPE,
+        STOP_GIT_CHECK_CALLBACK_ID,
+    },
     protocol::ProtocolPeer,
     types::{ControlRequestType, ControlResponseType, PermissionMode},
 };
@@ -33,8 +36,8 @@ use crate::{
     command::{CmdOverrides, CommandBuildError, CommandBuilder, CommandParts, apply_overrides},
     env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
-        codex::client::LogWriter, utils::reorder_slash_commands,
+        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SpawnedChild,
+        StandardCodingAgentExecutor, codex::client::LogWriter, utils::reorder_slash_commands,
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
@@ -58,8 +61,16 @@ fn base_command(claude_code_router: bool) -> &'static str {
 
 use derivative::Derivative;
 
-#[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
-#[derivative(Debug, PartialEq)]
+type SessionClientSlot = Arc<Mutex<Option<Arc<ClaudeAgentClient>>>>;
+
+/// Clones start without a session so instances built from a shared config don't see each
+/// other's sessions.
+fn empty_session_slot(_: &SessionClientSlot) -> SessionClientSlot {
+    SessionClientSlot::default()
+}
+
+#[derive(Derivative, Serialize, Deserialize, TS, JsonSchema)]
+#[derivative(Clone, Debug, PartialEq)]
 pub struct ClaudeCode {
     #[serde(default)]
     pub append_prompt: AppendPrompt,
@@ -75,6 +86,10 @@ pub struct ClaudeCode {
     pub dangerously_skip_permissions: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_api_key: Option<bool>,
+    /// Tools allowed without prompting when approvals are enabled. Supports exact names
+    /// and `*` globs, e.g. `["Read", "Grep", "mcp__*"]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_approve_tools: Option<Vec<String>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 
@@ -82,6 +97,15 @@ pub struct ClaudeCode {
     #[ts(skip)]
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     approvals_service: Option<Arc<dyn ExecutorApprovalService>>,
+    /// Client of the session this instance spawned, handed to `post_session` once it ends.
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(
+        Clone(clone_with = "empty_session_slot"),
+        Debug = "ignore",
+        PartialEq = "ignore"
+    )]
+    session_client: SessionClientSlot,
 }
 
 impl ClaudeCode {
@@ -186,6 +210,14 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         self.approvals_service = Some(approvals);
     }
 
+    async fn post_session(&self, result: &ExecutorExitResult) {
+        // Taking the client drops its log pipe once the hook is done with it.
+        let client = self.session_client.lock().unwrap().take();
+        if let Some(client) = client {
+            client.on_session_complete(result).await;
+        }
+    }
+
     async fn spawn(
         &self,
         current_dir: &Path,
@@ -331,10 +363,16 @@ impl ClaudeCode {
         // Spawn task to handle the SDK client with control protocol
         let prompt_clone = combined_prompt.clone();
         let approvals_clone = self.approvals_service.clone();
-        let repo_context = env.repo_context.clone();
+        let auto_approve_tools = self.auto_approve_tools.clone().unwrap_or_default();
+        let log_writer = LogWriter::new(new_stdout);
+        let client = ClaudeAgentClient::new(
+            log_writer.clone(),
+            approvals_clone,
+            auto_approve_tools,
+            env.repo_context.clone(),
+        );
+        *self.session_client.lock().unwrap() = Some(client.clone());
         tokio::spawn(async move {
-            let log_writer = LogWriter::new(new_stdout);
-            let client = ClaudeAgentClient::new(log_writer.clone(), approvals_clone, repo_context);
             let protocol_peer =
                 ProtocolPeer::spawn(child_stdin, child_stdout, client.clone(), interrupt_rx);
 
@@ -364,6 +402,7 @@ impl ClaudeCode {
             child,
             exit_signal: None,
             interrupt_sender: Some(interrupt_tx),
+            executor: None,
         })
     }
 }
@@ -848,7 +887,7 @@ impl ClaudeLogProcessor {
                             patches.push(add_system_message(status.clone(), entry_index_provider));
                         }
                     }
-                    Some("compact_boundary") => {}
+                    Some("compact_boundary") | Some(SESSION_COMPLETE_SUBTYPE) => {}
                     Some(subtype) => {
                         let entry = NormalizedEntry {
                             timestamp: None,
@@ -1316,7 +1355,10 @@ impl ClaudeLogProcessor {
                 // Convert denials and timeouts to visible entries (matching Codex behavior)
                 let entry_opt = match approval_status {
                     ApprovalStatus::Pending => None,
-                    ApprovalStatus::Approved => None,
+                    ApprovalStatus::Approved
+                    | ApprovalStatus::ApprovedForSession
+                    | ApprovalStatus::ApprovedWithEdits { .. }
+                    | ApprovalStatus::AutoAllowlisted => None,
                     ApprovalStatus::Denied { reason } => Some(NormalizedEntry {
                         timestamp: None,
                         entry_type: NormalizedEntryType::UserFeedback {
@@ -2265,6 +2307,35 @@ mod tests {
         assert_eq!(absolute_result, "src/main.rs");
     }
 
+    #[tokio::test]
+    async fn post_session_completes_the_spawned_session() {
+        use tokio::io::AsyncReadExt;
+
+        let executor: ClaudeCode = serde_json::from_value(serde_json::json!({})).unwrap();
+        let (writer, mut reader) = tokio::io::duplex(4096);
+        let client = ClaudeAgentClient::new(
+            LogWriter::new(writer),
+            None,
+            Vec::new(),
+            crate::env::RepoContext::default(),
+        );
+        *executor.session_client.lock().unwrap() = Some(client);
+
+        // Clones are built from the shared config and must not pick up this session.
+        let clone = executor.clone();
+        assert!(clone.session_client.lock().unwrap().is_none());
+
+        executor.post_session(&ExecutorExitResult::Failure).await;
+        assert!(executor.session_client.lock().unwrap().is_none());
+
+        // The client was the last owner of the pipe, so the reader now sees EOF.
+        let mut output = String::new();
+        reader.read_to_string(&mut output).await.unwrap();
+        let event: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(event["subtype"], SESSION_COMPLETE_SUBTYPE);
+        assert_eq!(event["status"], "failure");
+    }
+
     #[tokio::test]
     async fn test_streaming_patch_generation() {
         use std::sync::Arc;
@@ -2284,7 +2355,9 @@ mod tests {
                 env: None,
             },
             approvals_service: None,
+            session_client: Default::default(),
             disable_api_key: None,
+            auto_approve_tools: None,
         };
         let msg_store = Arc::new(MsgStore::new());
         let current_dir = std::path::PathBuf::from("/tmp/test-worktree");
@@ -7,7 +7,7 @@ use crate::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService},
     env::RepoContext,
     executors::{
-        ExecutorError,
+        ExecutorError, ExecutorExitResult,
         claude::{
             ClaudeJson,
             types::{
@@ -22,6 +22,8 @@ use crate::{
 const EXIT_PLAN_MODE_NAME: &str = "ExitPlanMode";
 pub const AUTO_APPROVE_CALLBACK_ID: &str = "AUTO_APPROVE_CALLBACK_ID";
 pub const STOP_GIT_CHECK_CALLBACK_ID: &str = "STOP_GIT_CHECK_CALLBACK_ID";
+/// Subtype of the system event written when a session ends.
+pub const SESSION_COMPLETE_SUBTYPE: &str = "session_complete";
 // Prefix for denial messages from the user, mirrors claude code CLI behavior
 const TOOL_DENY_PREFIX: &str = "The user doesn't want to proceed with this tool use. The tool use was rejected (eg. if it was a file edit, the new_string was NOT written to the file). To tell you how to proceed, the user said: ";
 
@@ -30,14 +32,17 @@ pub struct ClaudeAgentClient {
     log_writer: LogWriter,
     approvals: Option<Arc<dyn ExecutorApprovalService>>,
     auto_approve: bool, // true when approvals is None
+    auto_approve_tools: Vec<String>,
     repo_context: RepoContext,
 }
 
 impl ClaudeAgentClient {
-    /// Create a new client with optional approval service
+    /// Create a new client with optional approval service. Tools matching `auto_approve_tools`
+    /// (exact names or `*` globs) are allowed without going through the approval service.
     pub fn new(
         log_writer: LogWriter,
         approvals: Option<Arc<dyn ExecutorApprovalService>>,
+        auto_approve_tools: Vec<String>,
         repo_context: RepoContext,
     ) -> Arc<Self> {
         let auto_approve = approvals.is_none();
@@ -45,10 +50,20 @@ impl ClaudeAgentClient {
             log_writer,
             approvals,
             auto_approve,
+            auto_approve_tools,
             repo_context,
         })
     }
 
+    /// ExitPlanMode is never allowlisted so the plan is always reviewed before execution.
+    fn is_allowlisted(&self, tool_name: &str) -> bool {
+        tool_name != EXIT_PLAN_MODE_NAME
+            && self
+                .auto_approve_tools
+                .iter()
+                .any(|pattern| matches_tool_pattern(pattern, tool_name))
+    }
+
     async fn handle_approval(
         &self,
         tool_use_id: String,
@@ -73,39 +88,47 @@ impl ClaudeAgentClient {
                         approval_status: status.clone(),
                     })?)
                     .await?;
-                match status {
-                    ApprovalStatus::Approved => {
-                        if tool_name == EXIT_PLAN_MODE_NAME {
-                            Ok(PermissionResult::Allow {
-                                updated_input: tool_input,
-                                updated_permissions: Some(vec![PermissionUpdate {
-                                    update_type: PermissionUpdateType::SetMode,
-                                    mode: Some(PermissionMode::BypassPermissions),
-                                    destination: Some(PermissionUpdateDestination::Session),
-                                    rules: None,
-                                    behavior: None,
-                                    directories: None,
-                                }]),
-                            })
-                        } else {
-                            Ok(PermissionResult::Allow {
-                                updated_input: tool_input,
-                                updated_permissions: None,
-                            })
-                        }
+                let updated_input = match status {
+                    ApprovalStatus::Approved
+                    | ApprovalStatus::ApprovedForSession
+                    | ApprovalStatus::AutoAllowlisted => tool_input,
+                    ApprovalStatus::ApprovedWithEdits { updated_input } => updated_input,
+                    ApprovalStatus::Denied { reason } => {
+                        return Ok(PermissionResult::Deny {
+                            message: format!("{}{}", TOOL_DENY_PREFIX, reason.unwrap_or_default()),
+                            interrupt: Some(false),
+                        });
+                    }
+                    ApprovalStatus::TimedOut => {
+                        return Ok(PermissionResult::Deny {
+                            message: "Approval request timed out".to_string(),
+                            interrupt: Some(false),
+                        });
                     }
-                    ApprovalStatus::Denied { reason } => Ok(PermissionResult::Deny {
-                        message: format!("{}{}", TOOL_DENY_PREFIX, reason.unwrap_or_default()),
-                        interrupt: Some(false),
-                    }),
-                    ApprovalStatus::TimedOut => Ok(PermissionResult::Deny {
-                        message: "Approval request timed out".to_string(),
-                        interrupt: Some(false),
-                    }),
-                    ApprovalStatus::Pending => Ok(PermissionResult::Deny {
-                        message: "Approval still pending (unexpected)".to_string(),
-                        interrupt: Some(false),
-                    }),
+                    ApprovalStatus::Pending => {
+                        return Ok(PermissionResult::Deny {
+                            message: "Approval still pending (unexpected)".to_string(),
+                            interrupt: Some(false),
+                        });
+                    }
+                };
+                if tool_name == EXIT_PLAN_MODE_NAME {
+                    Ok(PermissionResult::Allow {
+                        updated_input,
+                        updated_permissions: Some(vec![PermissionUpdate {
+                            update_type: PermissionUpdateType::SetMode,
+                            mode: Some(PermissionMode::BypassPermissions),
+                            destination: Some(PermissionUpdateDestination::Session),
+                            rules: None,
+                            behavior: None,
+                            directories: None,
+                        }]),
+                    })
+                } else {
+                    Ok(PermissionResult::Allow {
+                        updated_input,
+                        updated_permissions: None,
+                    })
                 }
             }
             Err(e) => {
@@ -130,6 +153,20 @@ impl ClaudeAgentClient {
                 updated_input: input,
                 updated_permissions: None,
             })
+        } else if self.is_allowlisted(&tool_name) {
+            if let Some(tool_use_id) = tool_use_id {
+                self.log_writer
+                    .log_raw(&serde_json::to_string(&ClaudeJson::ApprovalResponse {
+                        call_id: tool_use_id,
+                        tool_name,
+                        approval_status: ApprovalStatus::AutoAllowlisted,
+                    })?)
+                    .await?;
+            }
+            Ok(PermissionResult::Allow {
+                updated_input: input,
+                updated_permissions: None,
+            })
         } else if let Some(latest_tool_use_id) = tool_use_id {
             self.handle_approval(latest_tool_use_id, tool_name, input)
                 .await
@@ -162,9 +199,7 @@ impl ClaudeAgentClient {
             {
                 return Ok(serde_json::json!({"decision": "approve"}));
             }
-            let status =
-                workspace_utils::git::check_uncommitted_changes(&self.repo_context.repo_paths())
-                    .await;
+            let status = self.repo_context.check_uncommitted_changes().await;
             return Ok(if status.is_empty() {
                 serde_json::json!({"decision": "approve"})
             } else {
@@ -214,4 +249,72 @@ impl ClaudeAgentClient {
     pub async fn log_message(&self, line: &str) -> Result<(), ExecutorError> {
         self.log_writer.log_raw(line).await
     }
+
+    /// Called once the Claude session has ended. Flushes buffered log output and records a final
+    /// `session_complete` system event. The completion notification itself is sent by the
+    /// container when it finalizes the task.
+    pub async fn on_session_complete(&self, result: &ExecutorExitResult) {
+        let success = matches!(result, ExecutorExitResult::Success);
+        if let Err(e) = self.log_writer.flush().await {
+            tracing::warn!("Failed to flush Claude log writer: {e}");
+        }
+
+        let event = serde_json::json!({
+            "type": "system",
+            "subtype": SESSION_COMPLETE_SUBTYPE,
+            "status": if success { "success" } else { "failure" },
+        });
+        if let Err(e) = self.log_writer.log_raw(&event.to_string()).await {
+            tracing::warn!("Failed to write Claude session completion event: {e}");
+        }
+    }
+}
+
+/// Match a tool name against an allowlist entry. `*` matches any run of characters;
+/// everything else must match exactly.
+fn matches_tool_pattern(pattern: &str, tool_name: &str) -> bool {
+    let mut segments = pattern.split('*');
+    let first = segments.next().unwrap_or_default();
+    let Some(mut rest) = tool_name.strip_prefix(first) else {
+        return false;
+    };
+    let segments: Vec<&str> = segments.collect();
+    let Some((last, middle)) = segments.split_last() else {
+        return rest.is_empty();
+    };
+    for segment in middle {
+        match rest.find(segment) {
+            Some(index) => rest = &rest[index + segment.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches_tool_pattern;
+
+    #[test]
+    fn exact_names_match_only_themselves() {
+        assert!(matches_tool_pattern("Read", "Read"));
+        assert!(!matches_tool_pattern("Read", "ReadFile"));
+        assert!(!matches_tool_pattern("Read", "read"));
+    }
+
+    #[test]
+    fn globs_match_prefixes_and_infixes() {
+        assert!(matches_tool_pattern("mcp__*", "mcp__github__get_issue"));
+        assert!(!matches_tool_pattern("mcp__*", "Bash"));
+        assert!(matches_tool_pattern(
+            "mcp__*__get_*",
+            "mcp__github__get_issue"
+        ));
+        assert!(!matches_tool_pattern(
+            "mcp__*__get_*",
+            "mcp__github__create_issue"
+        ));
+        assert!(matches_tool_pattern("*", "Write"));
+        assert!(!matches_tool_pattern("a*a", "a"));
+    }
 }
@@ -76,7 +76,10 @@ impl ProtocolPeer {
                                     self.handle_control_request(&client, request_id, request)
                                         .await;
                                 }
-                                Ok(CLIMessage::Result(_)) => {
+                                Ok(CLIMessage::Result(result)) => {
+                                    if let Some(err) = permission_denied_from_result(&result) {
+                                        return Err(err);
+                                    }
                                     break;
                                 }
                                 _ => {}
@@ -210,3 +213,63 @@ impl ProtocolPeer {
         .await
     }
 }
+
+/// Markers the Claude CLI uses in error results when the Anthropic credentials are missing,
+/// invalid or expired.
+const AUTH_FAILURE_MARKERS: &[&str] = &[
+    "invalid api key",
+    "please run /login",
+    "oauth token has expired",
+    "api error: 401",
+    "api error: 403",
+];
+
+/// Maps an error `result` message caused by an authentication failure to
+/// [`ExecutorError::PermissionDenied`].
+fn permission_denied_from_result(result: &serde_json::Value) -> Option<ExecutorError> {
+    if !result
+        .get("is_error")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let reason = result.get("result").and_then(serde_json::Value::as_str)?;
+    let lowered = reason.to_lowercase();
+    AUTH_FAILURE_MARKERS
+        .iter()
+        .any(|marker| lowered.contains(marker))
+        .then(|| ExecutorError::PermissionDenied {
+            provider: Some("anthropic".to_string()),
+            reason: reason.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn auth_failure_result_is_permission_denied() {
+        let result = json!({
+            "type": "result",
+            "is_error": true,
+            "result": "Invalid API key · Please run /login"
+        });
+        assert!(matches!(
+            permission_denied_from_result(&result),
+            Some(ExecutorError::PermissionDenied { provider: Some(ref p), .. }) if p == "anthropic"
+        ));
+    }
+
+    #[test]
+    fn other_results_are_ignored() {
+        let failed = json!({ "type": "result", "is_error": true, "result": "Tool crashed" });
+        assert!(permission_denied_from_result(&failed).is_none());
+
+        let ok = json!({ "type": "result", "is_error": false, "result": "Invalid API key" });
+        assert!(permission_denied_from_result(&ok).is_none());
+    }
+}
@@ -550,6 +550,7 @@ impl Codex {
             child,
             exit_signal: Some(exit_signal_rx),
             interrupt_sender: None,
+            executor: None,
         })
     }
 }
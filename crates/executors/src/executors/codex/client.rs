@@ -337,7 +337,14 @@ impl AppServerClient {
         }
 
         let outcome = match status {
-            ApprovalStatus::Approved => (ReviewDecision::Approved, None),
+            ApprovalStatus::Approved | ApprovalStatus::AutoAllowlisted => {
+                (ReviewDecision::Approved, None)
+            }
+            ApprovalStatus::ApprovedForSession => (ReviewDecision::ApprovedForSession, None),
+            ApprovalStatus::ApprovedWithEdits { .. } => {
+                tracing::warn!("Codex cannot apply edited tool input; approving the original call");
+                (ReviewDecision::Approved, None)
+            }
             ApprovalStatus::Denied { reason } => {
                 let feedback = reason
                     .as_ref()
@@ -496,9 +503,7 @@ impl JsonRpcCallbacks for AppServerClient {
             && self.commit_reminder
             && !self.commit_reminder_sent.swap(true, Ordering::SeqCst)
         {
-            let status =
-                workspace_utils::git::check_uncommitted_changes(&self.repo_context.repo_paths())
-                    .await;
+            let status = self.repo_context.check_uncommitted_changes().await;
             if !status.is_empty()
                 && let Some(conversation_id) = *self.conversation_id.lock().await
             {
@@ -575,4 +580,13 @@ impl LogWriter {
         guard.flush().await.map_err(ExecutorError::Io)?;
         Ok(())
     }
+
+    pub async fn flush(&self) -> Result<(), ExecutorError> {
+        self.writer
+            .lock()
+            .await
+            .flush()
+            .await
+            .map_err(ExecutorError::Io)
+    }
 }
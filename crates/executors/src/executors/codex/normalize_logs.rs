@@ -1240,7 +1240,10 @@ impl ToNormalizedEntryOpt for Approval {
 
         match approval_status {
             ApprovalStatus::Pending => None,
-            ApprovalStatus::Approved => None,
+            ApprovalStatus::Approved
+            | ApprovalStatus::ApprovedForSession
+            | ApprovalStatus::ApprovedWithEdits { .. }
+            | ApprovalStatus::AutoAllowlisted => None,
             ApprovalStatus::Denied { reason } => Some(NormalizedEntry {
                 timestamp: None,
                 entry_type: NormalizedEntryType::UserFeedback {
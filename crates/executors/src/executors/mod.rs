@@ -85,6 +85,13 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Permission denied: {reason}")]
+    PermissionDenied {
+        provider: Option<String>,
+        reason: String,
+    },
 }
 
 #[enum_dispatch]
@@ -194,9 +201,13 @@ impl CodingAgent {
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 #[ts(export)]
 pub enum AvailabilityInfo {
-    LoginDetected { last_auth_timestamp: i64 },
+    LoginDetected {
+        last_auth_timestamp: i64,
+    },
     InstallationFound,
     NotFound,
+    /// The agent is launched through `npx`, which is not on PATH.
+    NpxNotFound,
 }
 
 impl AvailabilityInfo {
@@ -250,6 +261,10 @@ pub trait StandardCodingAgentExecutor {
         }
     }
 
+    /// Lifecycle hook invoked once a coding agent session has ended, whether it succeeded
+    /// or failed. Executors that need post-session cleanup override this.
+    async fn post_session(&self, _result: &ExecutorExitResult) {}
+
     fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &Path);
 
     // MCP configuration methods
@@ -298,6 +313,9 @@ pub struct SpawnedChild {
     pub exit_signal: Option<ExecutorExitSignal>,
     /// Container → Executor: signals when container wants to interrupt
     pub interrupt_sender: Option<InterruptSender>,
+    /// The executor instance that spawned the child; the container runs its
+    /// `post_session` hook once the child exits
+    pub executor: Option<CodingAgent>,
 }
 
 impl From<AsyncGroupChild> for SpawnedChild {
@@ -306,6 +324,7 @@ impl From<AsyncGroupChild> for SpawnedChild {
             child,
             exit_signal: None,
             interrupt_sender: None,
+            executor: None,
         }
     }
 }
@@ -1,35 +1,43 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{collections::VecDeque, path::Path, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use derivative::Derivative;
-use futures::StreamExt;
+use futures::{StreamExt, stream::BoxStream};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use tokio::{io::AsyncBufReadExt, process::Command};
+use tokio::{io::AsyncBufReadExt, process::Command, sync::watch};
+use tokio_util::io::ReaderStream;
 use ts_rs::TS;
-use workspace_utils::msg_store::MsgStore;
+use workspace_utils::{
+    msg_store::MsgStore,
+    shell::{get_shell_command, resolve_executable_path_blocking},
+};
 
 use crate::{
     approvals::ExecutorApprovalService,
-    command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuilder, apply_overrides},
     env::ExecutionEnv,
     executors::{
         AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SpawnedChild,
         StandardCodingAgentExecutor, opencode::types::OpencodeExecutorEvent,
     },
     logs::utils::patch,
-    stdout_dup::create_stdout_pipe_writer,
+    stdout_dup::{create_stdout_pipe_writer, duplicate_stderr},
 };
 
+mod event_types;
 mod models;
 mod normalize_logs;
 mod sdk;
 mod slash_commands;
 mod types;
 
-use sdk::{LogWriter, RunConfig, generate_server_password, run_session, run_slash_command};
+use sdk::{
+    DEFAULT_CONTROL_CHANNEL_CAPACITY, DEFAULT_MAX_RECONNECT_ATTEMPTS, LogWriter, RunConfig,
+    generate_server_password, run_session, run_slash_command,
+};
 use slash_commands::{OpencodeSlashCommand, hardcoded_slash_commands};
 
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
@@ -46,15 +54,36 @@ pub struct Opencode {
     /// Auto-approve agent actions
     #[serde(default = "default_to_true")]
     pub auto_approve: bool,
+    /// Reply "always" to permission requests approved for the session so OpenCode stops asking
+    /// for the same tool and pattern; other approvals are still answered "once"
+    #[serde(default)]
+    pub remember_approvals: bool,
     /// Enable auto-compaction when the context length approaches the model's context window limit
     #[serde(default = "default_to_true")]
     pub auto_compact: bool,
+    /// Seconds to wait for the OpenCode server to start (defaults to 180)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_timeout_secs: Option<u64>,
+    /// Shell command to run before starting OpenCode, e.g. to pre-warm the npx cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_start_command: Option<String>,
+    /// Consecutive event stream reconnect failures tolerated before giving up (defaults to 20)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_reconnect_attempts: Option<u32>,
+    /// Buffered control events between the event stream and the session loop (defaults to 32)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_channel_capacity: Option<usize>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
     #[serde(skip)]
     #[ts(skip)]
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
+    /// Invoked when the event stream cannot be re-established within `max_reconnect_attempts`.
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub on_max_attempts_exceeded: Option<Arc<dyn Fn() + Send + Sync>>,
 }
 
 /// Represents a spawned OpenCode server with its base URL
@@ -67,13 +96,81 @@ struct OpencodeServer {
 
 type ServerPassword = String;
 
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 180;
+
 impl Opencode {
-    fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
+    fn startup_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.startup_timeout_secs
+                .unwrap_or(DEFAULT_STARTUP_TIMEOUT_SECS),
+        )
+    }
+
+    /// Run the configured pre-start command, failing the spawn if it exits unsuccessfully.
+    async fn run_pre_start_command(
+        &self,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<(), ExecutorError> {
+        let Some(pre_start) = self
+            .pre_start_command
+            .as_deref()
+            .filter(|cmd| !cmd.trim().is_empty())
+        else {
+            return Ok(());
+        };
+
+        let (shell_cmd, shell_arg) = get_shell_command();
+        let mut command = Command::new(shell_cmd);
+        command
+            .kill_on_drop(true)
+            .stdin(std::process::Stdio::null())
+            .current_dir(current_dir)
+            .arg(shell_arg)
+            .arg(pre_start);
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let output = command.output().await?;
+        if !output.status.success() {
+            return Err(ExecutorError::Io(std::io::Error::other(format!(
+                "OpenCode pre-start command failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))));
+        }
+        Ok(())
+    }
+
+    /// Check that `npx` is on PATH. Always passes when the base command is overridden,
+    /// since the override may not go through `npx` at all.
+    pub fn check_availability(&self) -> AvailabilityInfo {
+        if self.cmd.base_command_override.is_some() {
+            return AvailabilityInfo::InstallationFound;
+        }
+
+        let npx_found = resolve_executable_path_blocking("npx").is_some();
+
+        if npx_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NpxNotFound
+        }
+    }
+
+    fn build_command_builder(&self) -> Result<CommandBuilder, ExecutorError> {
+        if matches!(self.check_availability(), AvailabilityInfo::NpxNotFound) {
+            return Err(ExecutorError::ExecutableNotFound {
+                program: "npx".to_string(),
+            });
+        }
+
         let builder = CommandBuilder::new("npx -y opencode-ai@1.1.25")
             // Pass hostname/port as separate args so OpenCode treats them as explicitly set
             // (it checks `process.argv.includes(\"--port\")` / `\"--hostname\"`).
             .extend_params(["serve", "--hostname", "127.0.0.1", "--port", "0"]);
-        apply_overrides(builder, &self.cmd)
+        Ok(apply_overrides(builder, &self.cmd)?)
     }
 
     /// Compute a cache key for model context windows based on configuration that can affect the list of available models.
@@ -87,6 +184,8 @@ impl Opencode {
         current_dir: &Path,
         env: &ExecutionEnv,
     ) -> Result<(AsyncGroupChild, ServerPassword), ExecutorError> {
+        self.run_pre_start_command(current_dir, env).await?;
+
         let command_parts = self.build_command_builder()?.build_initial()?;
         let (program_path, args) = command_parts.into_resolved().await?;
 
@@ -125,7 +224,18 @@ impl Opencode {
             ExecutorError::Io(std::io::Error::other("OpenCode server missing stdout"))
         })?;
 
-        let base_url = wait_for_server_url(server_stdout, None).await?;
+        // Nothing else reads this server's stderr, so consume it directly.
+        let stderr_tail = match child.inner().stderr.take() {
+            Some(stderr) => StderrTail::collect(
+                ReaderStream::new(stderr)
+                    .map(|chunk| chunk.map(|data| String::from_utf8_lossy(&data).into_owned()))
+                    .boxed(),
+            ),
+            None => StderrTail::default(),
+        };
+
+        let base_url =
+            wait_for_server_url(server_stdout, None, &stderr_tail, self.startup_timeout()).await?;
 
         Ok(OpencodeServer {
             child,
@@ -155,6 +265,8 @@ impl Opencode {
 
         let stdout = create_stdout_pipe_writer(&mut child)?;
         let log_writer = LogWriter::new(stdout);
+        // npm/npx failures are usually only reported on stderr
+        let stderr_tail = StderrTail::collect(duplicate_stderr(&mut child)?);
 
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
         let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel();
@@ -170,12 +282,29 @@ impl Opencode {
         let model_variant = self.variant.clone();
         let agent = self.mode.clone();
         let auto_approve = self.auto_approve;
+        let remember_approvals = self.remember_approvals;
         let resume_session_id = resume_session.map(|s| s.to_string());
         let models_cache_key = self.compute_models_cache_key();
+        let startup_timeout = self.startup_timeout();
+        let max_reconnect_attempts = self
+            .max_reconnect_attempts
+            .unwrap_or(DEFAULT_MAX_RECONNECT_ATTEMPTS);
+        // A zero-capacity channel would panic on creation.
+        let control_channel_capacity = self
+            .control_channel_capacity
+            .unwrap_or(DEFAULT_CONTROL_CHANNEL_CAPACITY)
+            .max(1);
+        let on_max_attempts_exceeded = self.on_max_attempts_exceeded.clone();
 
         tokio::spawn(async move {
             // Wait for server to print listening URL
-            let base_url = match wait_for_server_url(server_stdout, Some(log_writer.clone())).await
+            let base_url = match wait_for_server_url(
+                server_stdout,
+                Some(log_writer.clone()),
+                &stderr_tail,
+                startup_timeout,
+            )
+            .await
             {
                 Ok(url) => url,
                 Err(err) => {
@@ -197,8 +326,12 @@ impl Opencode {
                 agent,
                 approvals,
                 auto_approve,
+                remember_approvals,
                 server_password,
                 models_cache_key,
+                control_channel_capacity,
+                max_reconnect_attempts,
+                on_max_attempts_exceeded,
             };
 
             let result = match slash_command {
@@ -216,6 +349,7 @@ impl Opencode {
                     ExecutorExitResult::Failure
                 }
             };
+            let _ = log_writer.flush().await;
             let _ = exit_signal_tx.send(exit_result);
         });
 
@@ -223,10 +357,87 @@ impl Opencode {
             child,
             exit_signal: Some(exit_signal_rx),
             interrupt_sender: Some(interrupt_tx),
+            executor: None,
         })
     }
 }
 
+/// Number of trailing stderr lines kept for startup error messages.
+const STDERR_TAIL_LINES: usize = 100;
+
+/// How long to wait for stderr to be fully read once the server has exited.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The most recent lines an OpenCode server wrote to stderr.
+#[derive(Clone)]
+struct StderrTail {
+    lines: Arc<std::sync::Mutex<VecDeque<String>>>,
+    closed: watch::Receiver<bool>,
+}
+
+impl Default for StderrTail {
+    fn default() -> Self {
+        let (_, closed) = watch::channel(true);
+        Self {
+            lines: Arc::default(),
+            closed,
+        }
+    }
+}
+
+impl StderrTail {
+    /// Collect lines from `stream` in the background until the server closes stderr.
+    fn collect(mut stream: BoxStream<'static, std::io::Result<String>>) -> Self {
+        let (closed_tx, closed) = watch::channel(false);
+        let tail = Self {
+            lines: Arc::default(),
+            closed,
+        };
+        let lines = tail.lines.clone();
+
+        tokio::spawn(async move {
+            let mut partial = String::new();
+            while let Some(Ok(chunk)) = stream.next().await {
+                partial.push_str(&chunk);
+                while let Some(end) = partial.find('\n') {
+                    let line: String = partial.drain(..=end).collect();
+                    push_bounded(&lines, line.trim_end().to_string());
+                }
+            }
+            if !partial.trim().is_empty() {
+                push_bounded(&lines, partial.trim_end().to_string());
+            }
+            let _ = closed_tx.send(true);
+        });
+
+        tail
+    }
+
+    /// Give the reader a moment to catch up after the server has exited.
+    async fn wait_closed(&self) {
+        let mut closed = self.closed.clone();
+        let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, closed.wait_for(|closed| *closed)).await;
+    }
+
+    /// Error message suffix with the captured lines, or an empty string if there are none.
+    fn format(&self) -> String {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.is_empty() {
+            return String::new();
+        }
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        format!("\nServer stderr tail:\n{}", lines.join("\n"))
+    }
+}
+
+fn push_bounded(lines: &std::sync::Mutex<VecDeque<String>>, line: String) {
+    let mut lines = lines.lock().unwrap_or_else(|e| e.into_inner());
+    if lines.len() == STDERR_TAIL_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
 fn format_tail(captured: Vec<String>) -> String {
     captured
         .into_iter()
@@ -242,25 +453,30 @@ fn format_tail(captured: Vec<String>) -> String {
 async fn wait_for_server_url(
     stdout: tokio::process::ChildStdout,
     log_writer: Option<LogWriter>,
+    stderr_tail: &StderrTail,
+    timeout: Duration,
 ) -> Result<String, ExecutorError> {
     let mut lines = tokio::io::BufReader::new(stdout).lines();
-    let deadline = tokio::time::Instant::now() + Duration::from_secs(180);
+    let deadline = tokio::time::Instant::now() + timeout;
     let mut captured: Vec<String> = Vec::new();
 
     loop {
         if tokio::time::Instant::now() > deadline {
             return Err(ExecutorError::Io(std::io::Error::other(format!(
-                "Timed out waiting for OpenCode server to print listening URL.\nServer output tail:\n{}",
-                format_tail(captured)
+                "Timed out waiting for OpenCode server to print listening URL.\nServer output tail:\n{}{}",
+                format_tail(captured),
+                stderr_tail.format()
             ))));
         }
 
         let line = match tokio::time::timeout_at(deadline, lines.next_line()).await {
             Ok(Ok(Some(line))) => line,
             Ok(Ok(None)) => {
+                stderr_tail.wait_closed().await;
                 return Err(ExecutorError::Io(std::io::Error::other(format!(
-                    "OpenCode server exited before printing listening URL.\nServer output tail:\n{}",
-                    format_tail(captured)
+                    "OpenCode server exited before printing listening URL.\nServer output tail:\n{}{}",
+                    format_tail(captured),
+                    stderr_tail.format()
                 ))));
             }
             Ok(Err(err)) => return Err(ExecutorError::Io(err)),
@@ -375,6 +591,10 @@ impl StandardCodingAgentExecutor for Opencode {
     }
 
     fn get_availability_info(&self) -> AvailabilityInfo {
+        if matches!(self.check_availability(), AvailabilityInfo::NpxNotFound) {
+            return AvailabilityInfo::NpxNotFound;
+        }
+
         let mcp_config_found = self
             .default_mcp_config_path()
             .map(|p| p.exists())
@@ -473,3 +693,53 @@ fn merge_compaction_config(existing_json: Option<&str>) -> String {
 
     serde_json::to_string(&config).unwrap_or_else(|_| r#"{"compaction":{"auto":true}}"#.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    #[test]
+    fn push_bounded_drops_the_oldest_line() {
+        let lines = std::sync::Mutex::new(VecDeque::new());
+        for i in 0..STDERR_TAIL_LINES + 2 {
+            push_bounded(&lines, format!("line {i}"));
+        }
+
+        let lines = lines.into_inner().unwrap();
+        assert_eq!(lines.len(), STDERR_TAIL_LINES);
+        assert_eq!(lines.front().map(String::as_str), Some("line 2"));
+        assert_eq!(
+            lines.back().cloned(),
+            Some(format!("line {}", STDERR_TAIL_LINES + 1))
+        );
+    }
+
+    #[tokio::test]
+    async fn stderr_tail_joins_chunks_into_lines() {
+        let chunks = vec![
+            Ok("Error: port ".to_string()),
+            Ok("in use\r\nretrying\n".to_string()),
+            Ok("giving up".to_string()),
+        ];
+        let tail = StderrTail::collect(stream::iter(chunks).boxed());
+        tail.wait_closed().await;
+
+        assert_eq!(
+            tail.format(),
+            "\nServer stderr tail:\nError: port in use\nretrying\ngiving up"
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_stderr_tail_formats_to_nothing() {
+        let tail = StderrTail::default();
+        tail.wait_closed().await;
+        assert_eq!(tail.format(), "");
+
+        let tail = StderrTail::collect(stream::empty().boxed());
+        tail.wait_closed().await;
+        assert_eq!(tail.format(), "");
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Event types from the OpenCode event stream that the executor dispatches on. Anything
+/// else deserializes to [`SdkEventType::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(super) enum SdkEventType {
+    #[serde(rename = "message.updated")]
+    MessageUpdated,
+    #[serde(rename = "message.part.updated")]
+    MessagePartUpdated,
+    #[serde(rename = "permission.asked")]
+    PermissionAsked,
+    #[serde(rename = "permission.replied")]
+    PermissionReplied,
+    #[serde(rename = "session.idle")]
+    SessionIdle,
+    #[serde(rename = "session.error")]
+    SessionError,
+    #[serde(other)]
+    Other,
+}
+
+const SESSION_ID_POINTER: &str = "/properties/sessionID";
+const MESSAGE_SESSION_ID_POINTER: &str = "/properties/info/sessionID";
+const PART_SESSION_ID_POINTER: &str = "/properties/part/sessionID";
+
+impl SdkEventType {
+    /// Reads the `type` field of a raw event. Returns `None` when it is missing or not a
+    /// string.
+    pub(super) fn of(event: &Value) -> Option<Self> {
+        Self::deserialize(event.get("type")?).ok()
+    }
+
+    /// The session the event belongs to, looked up where this event type carries it.
+    /// Untyped events are checked at every known location.
+    pub(super) fn session_id(self, event: &Value) -> Option<&str> {
+        let pointers: &[&str] = match self {
+            Self::MessageUpdated => &[MESSAGE_SESSION_ID_POINTER],
+            Self::MessagePartUpdated => &[PART_SESSION_ID_POINTER],
+            Self::PermissionAsked
+            | Self::PermissionReplied
+            | Self::SessionIdle
+            | Self::SessionError => &[SESSION_ID_POINTER],
+            Self::Other => &[
+                SESSION_ID_POINTER,
+                MESSAGE_SESSION_ID_POINTER,
+                PART_SESSION_ID_POINTER,
+            ],
+        };
+        pointers
+            .iter()
+            .find_map(|pointer| event.pointer(pointer).and_then(Value::as_str))
+    }
+
+    pub(super) fn matches_session(self, event: &Value, session_id: &str) -> bool {
+        self.session_id(event) == Some(session_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn known_types_deserialize_and_others_fall_back() {
+        assert_eq!(
+            SdkEventType::of(&json!({ "type": "session.idle" })),
+            Some(SdkEventType::SessionIdle)
+        );
+        assert_eq!(
+            SdkEventType::of(&json!({ "type": "session.idl" })),
+            Some(SdkEventType::Other)
+        );
+        assert_eq!(SdkEventType::of(&json!({ "type": 1 })), None);
+        assert_eq!(SdkEventType::of(&json!({})), None);
+    }
+
+    #[test]
+    fn session_id_is_read_from_the_type_specific_location() {
+        let message = json!({
+            "type": "message.updated",
+            "properties": { "sessionID": "ses_wrong", "info": { "sessionID": "ses_1" } }
+        });
+        assert!(SdkEventType::MessageUpdated.matches_session(&message, "ses_1"));
+
+        let idle =
+            json!({ "type": "session.idle", "properties": { "info": { "sessionID": "ses_1" } } });
+        assert!(!SdkEventType::SessionIdle.matches_session(&idle, "ses_1"));
+
+        let other =
+            json!({ "type": "todo.updated", "properties": { "part": { "sessionID": "ses_1" } } });
+        assert!(SdkEventType::Other.matches_session(&other, "ses_1"));
+    }
+}
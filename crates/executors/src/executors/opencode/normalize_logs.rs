@@ -7,7 +7,7 @@ use workspace_utils::{approvals::ApprovalStatus, msg_store::MsgStore, path::make
 
 use super::types::{
     MessageInfo, MessageRole, OpencodeExecutorEvent, Part, PermissionAskedEvent, SdkEvent, SdkTodo,
-    SessionStatus, ToolPart, ToolStateUpdate,
+    SessionStatus, StreamPartType, ToolPart, ToolStateUpdate,
 };
 use crate::{
     approvals::ToolCallMetadata,
@@ -58,13 +58,60 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
             };
 
             match event {
-                OpencodeExecutorEvent::StartupLog { .. } => {}
+                OpencodeExecutorEvent::StartupLog { .. }
+                | OpencodeExecutorEvent::StreamSummary { .. } => {}
                 OpencodeExecutorEvent::SessionStart { session_id } => {
                     if !stored_session_id {
                         msg_store.push_session_id(session_id);
                         stored_session_id = true;
                     }
                 }
+                OpencodeExecutorEvent::ResumeFallback {
+                    previous_session_id,
+                    ..
+                } => {
+                    add_normalized_entry(
+                        &msg_store,
+                        &entry_index,
+                        system_message(format!(
+                            "Session {previous_session_id} is no longer available on the OpenCode server; started a new session."
+                        )),
+                    );
+                }
+                OpencodeExecutorEvent::MessagePartUpdated {
+                    part_type: StreamPartType::Tool,
+                    delta,
+                    tool_part,
+                    ..
+                } => {
+                    if let Some(part) =
+                        tool_part.and_then(|part| serde_json::from_value::<Part>(part).ok())
+                    {
+                        state.handle_part_update(
+                            part,
+                            delta.as_deref(),
+                            &worktree_path,
+                            &msg_store,
+                        );
+                    }
+                }
+                OpencodeExecutorEvent::MessagePartUpdated {
+                    message_id,
+                    part_type,
+                    text,
+                    delta,
+                    ..
+                } => {
+                    state.handle_stream_part(
+                        part_type,
+                        &message_id,
+                        &text,
+                        delta.as_deref(),
+                        &msg_store,
+                    );
+                }
+                OpencodeExecutorEvent::MessageCompleted { .. }
+                | OpencodeExecutorEvent::StepStarted { .. } => {}
                 OpencodeExecutorEvent::SdkEvent { event } => {
                     state.handle_sdk_event(&event, &worktree_path, &msg_store);
                 }
@@ -339,6 +386,47 @@ impl LogState {
         self.model_system_message_emitted = true;
     }
 
+    fn handle_stream_part(
+        &mut self,
+        part_type: StreamPartType,
+        message_id: &str,
+        text: &str,
+        delta: Option<&str>,
+        msg_store: &Arc<MsgStore>,
+    ) {
+        let (entry_type, map) = match part_type {
+            StreamPartType::Text => {
+                if self.message_roles.get(message_id) != Some(&MessageRole::Assistant) {
+                    tracing::debug!("Skipping text part for non-assistant message_id {message_id}");
+                    return;
+                }
+                (
+                    NormalizedEntryType::AssistantMessage,
+                    &mut self.assistant_text,
+                )
+            }
+            StreamPartType::Reasoning => (NormalizedEntryType::Thinking, &mut self.thinking_text),
+            // Tool parts are rendered from the full part by `handle_part_update`
+            StreamPartType::Tool => return,
+        };
+
+        let (text, mode) = if let Some(delta) = delta {
+            (delta, UpdateMode::Append)
+        } else {
+            (text, UpdateMode::Set)
+        };
+
+        update_streaming_text(
+            &self.entry_index,
+            text,
+            entry_type,
+            message_id,
+            map,
+            msg_store,
+            mode,
+        );
+    }
+
     fn handle_part_update(
         &mut self,
         part: Part,
@@ -348,47 +436,21 @@ impl LogState {
     ) {
         match part {
             Part::Text(part) => {
-                if self.message_roles.get(&part.message_id) != Some(&MessageRole::Assistant) {
-                    tracing::debug!(
-                        "Skipping text part for non-assistant message_id {}",
-                        part.message_id
-                    );
-                    return;
-                }
-
-                let (text, mode) = if let Some(delta) = delta {
-                    (delta, UpdateMode::Append)
-                } else {
-                    (part.text.as_str(), UpdateMode::Set)
-                };
-
-                let entry_index = self.entry_index.clone();
-                update_streaming_text(
-                    &entry_index,
-                    text,
-                    NormalizedEntryType::AssistantMessage,
+                self.handle_stream_part(
+                    StreamPartType::Text,
                     &part.message_id,
-                    &mut self.assistant_text,
+                    &part.text,
+                    delta,
                     msg_store,
-                    mode,
                 );
             }
             Part::Reasoning(part) => {
-                let (text, mode) = if let Some(delta) = delta {
-                    (delta, UpdateMode::Append)
-                } else {
-                    (part.text.as_str(), UpdateMode::Set)
-                };
-
-                let entry_index = self.entry_index.clone();
-                update_streaming_text(
-                    &entry_index,
-                    text,
-                    NormalizedEntryType::Thinking,
+                self.handle_stream_part(
+                    StreamPartType::Reasoning,
                     &part.message_id,
-                    &mut self.thinking_text,
+                    &part.text,
+                    delta,
                     msg_store,
-                    mode,
                 );
             }
             Part::Tool(part) => {
@@ -3,7 +3,10 @@ use std::{
     future::Future,
     io,
     path::Path,
-    sync::Arc,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Duration,
 };
 
@@ -11,7 +14,7 @@ use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use eventsource_stream::Eventsource;
 use futures::{FutureExt, StreamExt};
 use rand::{Rng, distributions::Alphanumeric};
-use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::{
@@ -21,7 +24,11 @@ use tokio::{
 use tokio_util::sync::CancellationToken;
 use workspace_utils::approvals::ApprovalStatus;
 
-use super::{slash_commands, types::OpencodeExecutorEvent};
+use super::{
+    event_types::SdkEventType,
+    slash_commands,
+    types::{EventStreamOutcome, OpencodeExecutorEvent},
+};
 use crate::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService},
     executors::{
@@ -30,22 +37,87 @@ use crate::{
     },
 };
 
+/// Flush buffered log output at least this often.
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+/// Buffered log output is written through once it grows past this size.
+const LOG_BUFFER_CAPACITY: usize = 64 * 1024;
+/// Control events are low-volume, so a small bound is enough to absorb bursts.
+pub const DEFAULT_CONTROL_CHANNEL_CAPACITY: usize = 32;
+/// Consecutive event stream reconnect failures tolerated before the session is abandoned.
+pub const DEFAULT_MAX_RECONNECT_ATTEMPTS: u32 = 20;
+
+type BoxedLogSink = BufWriter<Box<dyn AsyncWrite + Send + Unpin>>;
+
+struct LogSink {
+    writer: AsyncMutex<BoxedLogSink>,
+    /// Set when buffered output is waiting for the periodic flush.
+    dirty: AtomicBool,
+    /// Set once the periodic flush task has been spawned.
+    flusher_started: AtomicBool,
+}
+
+impl Drop for LogSink {
+    fn drop(&mut self) {
+        if self.writer.get_mut().buffer().is_empty() {
+            return;
+        }
+        let mut writer = std::mem::replace(
+            self.writer.get_mut(),
+            BufWriter::new(Box::new(tokio::io::sink())),
+        );
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move {
+                let _ = writer.flush().await;
+            });
+        }
+    }
+}
+
+/// Batches executor log lines: output is flushed on a short interval or when the buffer
+/// fills, while errors and `Done` are flushed immediately.
 #[derive(Clone)]
 pub struct LogWriter {
-    writer: Arc<AsyncMutex<BufWriter<Box<dyn AsyncWrite + Send + Unpin>>>>,
+    sink: Arc<LogSink>,
+    /// Short ID embedded in every emitted event so entries from concurrent executors
+    /// can be told apart in the stored logs.
+    correlation_id: Option<String>,
 }
 
 impl LogWriter {
     pub fn new(writer: impl AsyncWrite + Send + Unpin + 'static) -> Self {
+        let mut correlation_id = uuid::Uuid::new_v4().simple().to_string();
+        correlation_id.truncate(8);
+        let sink = Arc::new(LogSink {
+            writer: AsyncMutex::new(BufWriter::with_capacity(
+                LOG_BUFFER_CAPACITY,
+                Box::new(writer),
+            )),
+            dirty: AtomicBool::new(false),
+            flusher_started: AtomicBool::new(false),
+        });
         Self {
-            writer: Arc::new(AsyncMutex::new(BufWriter::new(Box::new(writer)))),
+            sink,
+            correlation_id: Some(correlation_id),
         }
     }
 
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
     pub async fn log_event(&self, event: &OpencodeExecutorEvent) -> Result<(), ExecutorError> {
-        let raw =
-            serde_json::to_string(event).map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
-        self.log_raw(&raw).await
+        let flush_now = matches!(
+            event,
+            OpencodeExecutorEvent::Error { .. } | OpencodeExecutorEvent::Done
+        );
+        let raw = self.serialize(event)?;
+        self.log_raw(&raw, flush_now).await
+    }
+
+    /// Serialize a typed event, tagging it with this writer's correlation ID.
+    pub async fn log_structured<T: Serialize>(&self, event: &T) -> Result<(), ExecutorError> {
+        let raw = self.serialize(event)?;
+        self.log_raw(&raw, false).await
     }
 
     pub async fn log_error(&self, message: String) -> Result<(), ExecutorError> {
@@ -58,18 +130,65 @@ impl LogWriter {
             .await
     }
 
-    async fn log_raw(&self, raw: &str) -> Result<(), ExecutorError> {
-        let mut guard = self.writer.lock().await;
+    /// Write out any buffered log lines. Call before the session exits.
+    pub async fn flush(&self) -> Result<(), ExecutorError> {
+        let mut guard = self.sink.writer.lock().await;
+        self.sink.dirty.store(false, Ordering::Release);
+        guard.flush().await.map_err(ExecutorError::Io)
+    }
+
+    fn serialize<T: Serialize>(&self, event: &T) -> Result<String, ExecutorError> {
+        let mut value =
+            serde_json::to_value(event).map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
+        if let (Some(correlation_id), Value::Object(map)) = (&self.correlation_id, &mut value) {
+            map.insert(
+                "correlation_id".to_string(),
+                Value::String(correlation_id.clone()),
+            );
+        }
+        serde_json::to_string(&value).map_err(|err| ExecutorError::Io(io::Error::other(err)))
+    }
+
+    async fn log_raw(&self, raw: &str, flush_now: bool) -> Result<(), ExecutorError> {
+        let mut guard = self.sink.writer.lock().await;
         guard
             .write_all(raw.as_bytes())
             .await
             .map_err(ExecutorError::Io)?;
         guard.write_all(b"\n").await.map_err(ExecutorError::Io)?;
-        guard.flush().await.map_err(ExecutorError::Io)?;
+        if flush_now {
+            self.sink.dirty.store(false, Ordering::Release);
+            guard.flush().await.map_err(ExecutorError::Io)?;
+        } else {
+            self.sink.dirty.store(true, Ordering::Release);
+            // Started on first buffered write so the writer can be built outside a runtime.
+            if !self.sink.flusher_started.swap(true, Ordering::AcqRel) {
+                spawn_periodic_flush(Arc::downgrade(&self.sink));
+            }
+        }
         Ok(())
     }
 }
 
+fn spawn_periodic_flush(sink: Weak<LogSink>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(LOG_FLUSH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let Some(sink) = sink.upgrade() else {
+                return;
+            };
+            if sink.dirty.swap(false, Ordering::AcqRel) {
+                let mut guard = sink.writer.lock().await;
+                if let Err(err) = guard.flush().await {
+                    tracing::debug!("Failed to flush OpenCode log output: {err}");
+                }
+            }
+        }
+    });
+}
+
 #[derive(Clone)]
 pub struct RunConfig {
     pub base_url: String,
@@ -81,10 +200,26 @@ pub struct RunConfig {
     pub agent: Option<String>,
     pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
     pub auto_approve: bool,
+    /// Reply "always" instead of "once" to permission requests approved for the session.
+    pub remember_approvals: bool,
     pub server_password: String,
     /// Cache key for model context windows. Should be derived from configuration
     /// that affects available models (e.g., env vars, base command).
     pub models_cache_key: String,
+    /// Capacity of the control channel between the event listener and the request loop.
+    pub control_channel_capacity: usize,
+    /// Consecutive event stream reconnect failures tolerated before giving up.
+    pub max_reconnect_attempts: u32,
+    /// Invoked once when the event listener gives up reconnecting.
+    pub on_max_attempts_exceeded: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl RunConfig {
+    pub(super) fn max_attempts_callback(&self) -> Option<Box<dyn Fn() + Send>> {
+        self.on_max_attempts_exceeded
+            .clone()
+            .map(|callback| Box::new(move || callback()) as Box<dyn Fn() + Send>)
+    }
 }
 
 /// Generate a cryptographically secure random password for OpenCode server auth.
@@ -203,11 +338,50 @@ struct TextPartInput {
 #[derive(Debug, Clone)]
 pub enum ControlEvent {
     Idle,
-    AuthRequired { message: String },
-    SessionError { message: String },
+    PermissionDenied {
+        provider: Option<String>,
+        reason: String,
+    },
+    SessionError {
+        message: String,
+    },
     Disconnected,
 }
 
+/// Sending half of the bounded control channel.
+///
+/// When the channel is full the request loop has stalled. Session errors are dropped in that
+/// case (they are still recorded in the raw event log); idle, auth and disconnect events wait
+/// for capacity so the request loop never misses a terminal signal.
+#[derive(Debug, Clone)]
+pub struct ControlSender {
+    tx: mpsc::Sender<ControlEvent>,
+}
+
+impl ControlSender {
+    pub async fn send(&self, event: ControlEvent) {
+        let event = match self.tx.try_send(event) {
+            Ok(()) | Err(mpsc::error::TrySendError::Closed(_)) => return,
+            Err(mpsc::error::TrySendError::Full(event)) => event,
+        };
+
+        tracing::warn!(
+            "OpenCode control channel is full ({} events); request loop may be stalled",
+            self.tx.max_capacity()
+        );
+        if let ControlEvent::SessionError { message } = &event {
+            tracing::warn!("Dropping OpenCode session error control event: {message}");
+            return;
+        }
+        let _ = self.tx.send(event).await;
+    }
+}
+
+pub fn control_channel(capacity: usize) -> (ControlSender, mpsc::Receiver<ControlEvent>) {
+    let (tx, rx) = mpsc::channel(capacity.max(1));
+    (ControlSender { tx }, rx)
+}
+
 pub async fn run_session(
     config: RunConfig,
     log_writer: LogWriter,
@@ -215,10 +389,13 @@ pub async fn run_session(
 ) -> Result<(), ExecutorError> {
     let cancel = CancellationToken::new();
 
+    let request_id = uuid::Uuid::new_v4().to_string();
+    tracing::debug!(%request_id, "Starting OpenCode session");
     let client = reqwest::Client::builder()
         .default_headers(build_default_headers(
             &config.directory,
             &config.server_password,
+            &request_id,
         ))
         .build()
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
@@ -253,7 +430,11 @@ pub(super) async fn discover_commands(
 ) -> Result<Vec<CommandInfo>, ExecutorError> {
     let directory = directory.to_string_lossy();
     let client = reqwest::Client::builder()
-        .default_headers(build_default_headers(&directory, &server.server_password))
+        .default_headers(build_default_headers(
+            &directory,
+            &server.server_password,
+            &uuid::Uuid::new_v4().to_string(),
+        ))
         .build()
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
 
@@ -269,10 +450,13 @@ pub async fn run_slash_command(
 ) -> Result<(), ExecutorError> {
     let cancel = CancellationToken::new();
 
+    let request_id = uuid::Uuid::new_v4().to_string();
+    tracing::debug!(%request_id, "Starting OpenCode slash command");
     let client = reqwest::Client::builder()
         .default_headers(build_default_headers(
             &config.directory,
             &config.server_password,
+            &request_id,
         ))
         .build()
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
@@ -313,11 +497,37 @@ async fn run_session_inner(
         res = wait_for_health(&client, &config.base_url) => res?,
     }
 
+    if let Some(agent) = config.agent.as_deref() {
+        let agents = tokio::select! {
+            _ = cancel.cancelled() => return Ok(()),
+            res = list_agents(&client, &config.base_url, &config.directory) => res,
+        };
+        // If the listing fails the prompt still goes out; the server rejects unknown agents
+        // itself, just less clearly.
+        match agents {
+            Ok(agents) => {
+                if let Some(message) = unknown_agent_message(agent, &agents) {
+                    log_writer.log_error(message.clone()).await?;
+                    return Err(ExecutorError::InvalidConfig(message));
+                }
+            }
+            Err(err) => {
+                tracing::warn!("Could not list OpenCode agents to validate `{agent}`: {err}")
+            }
+        }
+    }
+
     let session_id = match config.resume_session_id.as_deref() {
         Some(existing) => {
             tokio::select! {
                 _ = cancel.cancelled() => return Ok(()),
-                res = fork_session(&client, &config.base_url, &config.directory, existing) => res?,
+                res = fork_or_create_session(
+                    &client,
+                    &config.base_url,
+                    &config.directory,
+                    existing,
+                    &log_writer,
+                ) => res?,
             }
         }
         None => tokio::select! {
@@ -332,9 +542,16 @@ async fn run_session_inner(
         })
         .await?;
 
-    let model = config.model.as_deref().and_then(parse_model);
+    let model = match config.model.as_deref() {
+        Some(model) => Some(parse_model(model).ok_or_else(|| {
+            ExecutorError::InvalidConfig(format!(
+                "OpenCode model `{model}` must be in `provider/model` format"
+            ))
+        })?),
+        None => None,
+    };
 
-    let (control_tx, mut control_rx) = mpsc::unbounded_channel::<ControlEvent>();
+    let (control_tx, mut control_rx) = control_channel(config.control_channel_capacity);
 
     let event_resp = tokio::select! {
         _ = cancel.cancelled() => return Ok(()),
@@ -349,8 +566,11 @@ async fn run_session_inner(
             log_writer: log_writer.clone(),
             approvals: config.approvals.clone(),
             auto_approve: config.auto_approve,
+            remember_approvals: config.remember_approvals,
             control_tx,
             models_cache_key: config.models_cache_key.clone(),
+            max_attempts: config.max_reconnect_attempts,
+            on_max_attempts_exceeded: config.max_attempts_callback(),
         },
         event_resp,
     ));
@@ -368,7 +588,13 @@ async fn run_session_inner(
     let prompt_result = run_request_with_control(prompt_fut, &mut control_rx, cancel.clone()).await;
 
     if cancel.is_cancelled() {
-        send_abort(&client, &config.base_url, &config.directory, &session_id).await;
+        // Interrupting a session that already finished would only produce a spurious abort error.
+        if !matches!(prompt_result, Ok(RequestOutcome::Completed))
+            && let Err(err) =
+                send_abort(&client, &config.base_url, &config.directory, &session_id).await
+        {
+            tracing::warn!("Failed to abort OpenCode session {session_id}: {err}");
+        }
         event_handle.abort();
         return Ok(());
     }
@@ -381,8 +607,17 @@ async fn run_session_inner(
     Ok(())
 }
 
-fn build_default_headers(directory: &str, password: &str) -> HeaderMap {
+const CLIENT_USER_AGENT: &str = concat!("vibe-kanban/", env!("CARGO_PKG_VERSION"));
+const REQUEST_ID_HEADER: &str = "x-vibe-kanban-request-id";
+
+/// Headers sent with every request of one client. `request_id` should be fresh per
+/// session so its requests can be picked out of the OpenCode server logs.
+fn build_default_headers(directory: &str, password: &str, request_id: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static(CLIENT_USER_AGENT));
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
     if let Ok(value) = HeaderValue::from_str(directory) {
         headers.insert("x-opencode-directory", value);
     }
@@ -403,28 +638,44 @@ fn append_session_error(session_error: &mut Option<String>, message: String) {
     }
 }
 
+/// How a request driven by [`run_request_with_control`] finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// The request returned or the session went idle, so there is nothing left to abort.
+    Completed,
+    /// Cancellation arrived while the session was still running.
+    Cancelled,
+}
+
 pub async fn run_request_with_control<F>(
     mut request_fut: F,
-    control_rx: &mut mpsc::UnboundedReceiver<ControlEvent>,
+    control_rx: &mut mpsc::Receiver<ControlEvent>,
     cancel: CancellationToken,
-) -> Result<(), ExecutorError>
+) -> Result<RequestOutcome, ExecutorError>
 where
     F: Future<Output = Result<(), ExecutorError>> + Unpin,
 {
     let mut idle_seen = false;
     let mut session_error: Option<String> = None;
+    let cancelled = |idle_seen: bool| {
+        if idle_seen {
+            RequestOutcome::Completed
+        } else {
+            RequestOutcome::Cancelled
+        }
+    };
 
     let request_result = loop {
         tokio::select! {
-            _ = cancel.cancelled() => return Ok(()),
+            _ = cancel.cancelled() => return Ok(cancelled(idle_seen)),
             res = &mut request_fut => break res,
             event = control_rx.recv() => match event {
-                Some(ControlEvent::AuthRequired { message }) => return Err(ExecutorError::AuthRequired(message)),
+                Some(ControlEvent::PermissionDenied { provider, reason }) => return Err(ExecutorError::PermissionDenied { provider, reason }),
                 Some(ControlEvent::SessionError { message }) => append_session_error(&mut session_error, message),
                 Some(ControlEvent::Disconnected) if !cancel.is_cancelled() => {
                     return Err(ExecutorError::Io(io::Error::other("OpenCode event stream disconnected while request was running")));
                 }
-                Some(ControlEvent::Disconnected) => return Ok(()),
+                Some(ControlEvent::Disconnected) => return Ok(cancelled(idle_seen)),
                 Some(ControlEvent::Idle) => idle_seen = true,
                 None => {}
             }
@@ -433,27 +684,28 @@ where
 
     if let Err(err) = request_result {
         if cancel.is_cancelled() {
-            return Ok(());
+            return Ok(cancelled(idle_seen));
         }
         return Err(err);
     }
 
+    // From here on the prompt has returned, so the session has nothing left to abort.
     if !idle_seen {
         // The OpenCode server streams events independently; wait for `session.idle` so we capture
         // tail updates reliably (e.g. final tool completion events).
         loop {
             tokio::select! {
-                _ = cancel.cancelled() => return Ok(()),
+                _ = cancel.cancelled() => return Ok(RequestOutcome::Completed),
                 event = control_rx.recv() => match event {
                     Some(ControlEvent::Idle) | None => break,
-                    Some(ControlEvent::AuthRequired { message }) => return Err(ExecutorError::AuthRequired(message)),
+                    Some(ControlEvent::PermissionDenied { provider, reason }) => return Err(ExecutorError::PermissionDenied { provider, reason }),
                     Some(ControlEvent::SessionError { message }) => append_session_error(&mut session_error, message),
                     Some(ControlEvent::Disconnected) if !cancel.is_cancelled() => {
                         return Err(ExecutorError::Io(io::Error::other(
                             "OpenCode event stream disconnected while waiting for session to go idle",
                         )));
                     }
-                    Some(ControlEvent::Disconnected) => return Ok(()),
+                    Some(ControlEvent::Disconnected) => return Ok(RequestOutcome::Completed),
                 }
             }
         }
@@ -461,12 +713,12 @@ where
 
     if let Some(message) = session_error {
         if cancel.is_cancelled() {
-            return Ok(());
+            return Ok(RequestOutcome::Completed);
         }
         return Err(ExecutorError::Io(io::Error::other(message)));
     }
 
-    Ok(())
+    Ok(RequestOutcome::Completed)
 }
 
 pub async fn wait_for_health(
@@ -534,12 +786,13 @@ pub async fn create_session(
     Ok(session.id)
 }
 
+/// Fork `session_id`. Returns `Ok(None)` if the server doesn't know the session.
 pub async fn fork_session(
     client: &reqwest::Client,
     base_url: &str,
     directory: &str,
     session_id: &str,
-) -> Result<String, ExecutorError> {
+) -> Result<Option<String>, ExecutorError> {
     let resp = client
         .post(format!("{base_url}/session/{session_id}/fork"))
         .query(&[("directory", directory)])
@@ -548,6 +801,9 @@ pub async fn fork_session(
         .await
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
 
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
     if !resp.status().is_success() {
         return Err(ExecutorError::Io(io::Error::other(format!(
             "OpenCode session.fork failed: HTTP {}",
@@ -559,7 +815,31 @@ pub async fn fork_session(
         .json::<SessionResponse>()
         .await
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
-    Ok(session.id)
+    Ok(Some(session.id))
+}
+
+/// Fork `session_id`, falling back to a fresh session when the server no longer knows it,
+/// e.g. because the OpenCode server was restarted since the session was created.
+pub async fn fork_or_create_session(
+    client: &reqwest::Client,
+    base_url: &str,
+    directory: &str,
+    session_id: &str,
+    log_writer: &LogWriter,
+) -> Result<String, ExecutorError> {
+    if let Some(forked) = fork_session(client, base_url, directory, session_id).await? {
+        return Ok(forked);
+    }
+
+    tracing::warn!("OpenCode session {session_id} not found; starting a new session");
+    let new_session_id = create_session(client, base_url, directory).await?;
+    log_writer
+        .log_event(&OpencodeExecutorEvent::ResumeFallback {
+            previous_session_id: session_id.to_string(),
+            session_id: new_session_id.clone(),
+        })
+        .await?;
+    Ok(new_session_id)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -622,13 +902,12 @@ async fn prompt(
 
     // Error response: { name, data }
     if let Some(name) = parsed.get("name").and_then(Value::as_str) {
-        let message = parsed
-            .pointer("/data/message")
-            .and_then(Value::as_str)
-            .unwrap_or(trimmed);
-        return Err(ExecutorError::Io(io::Error::other(format!(
-            "OpenCode session.prompt failed: {name}: {message}"
-        ))));
+        return Err(named_error_response(
+            "session.prompt",
+            name,
+            &parsed,
+            trimmed,
+        ));
     }
 
     Err(ExecutorError::Io(io::Error::other(format!(
@@ -636,6 +915,27 @@ async fn prompt(
     ))))
 }
 
+/// Converts an OpenCode `{ name, data }` error body into an executor error, surfacing provider
+/// auth failures as [`ExecutorError::PermissionDenied`].
+fn named_error_response(operation: &str, name: &str, parsed: &Value, raw: &str) -> ExecutorError {
+    let message = parsed
+        .pointer("/data/message")
+        .and_then(Value::as_str)
+        .unwrap_or(raw);
+    if name == "ProviderAuthError" {
+        return ExecutorError::PermissionDenied {
+            provider: parsed
+                .pointer("/data/providerID")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            reason: message.to_string(),
+        };
+    }
+    ExecutorError::Io(io::Error::other(format!(
+        "OpenCode {operation} failed: {name}: {message}"
+    )))
+}
+
 #[derive(Debug, Serialize)]
 struct SessionCommandRequest {
     command: String,
@@ -703,13 +1003,12 @@ pub async fn session_command(
     }
 
     if let Some(name) = parsed.get("name").and_then(Value::as_str) {
-        let message = parsed
-            .pointer("/data/message")
-            .and_then(Value::as_str)
-            .unwrap_or(trimmed);
-        return Err(ExecutorError::Io(io::Error::other(format!(
-            "OpenCode session.command failed: {name}: {message}"
-        ))));
+        return Err(named_error_response(
+            "session.command",
+            name,
+            &parsed,
+            trimmed,
+        ));
     }
 
     Err(ExecutorError::Io(io::Error::other(format!(
@@ -937,42 +1236,58 @@ async fn build_response_error(resp: reqwest::Response, context: &str) -> Executo
     )))
 }
 
+const ABORT_MAX_ATTEMPTS: u32 = 3;
+const ABORT_RETRY_DELAY: Duration = Duration::from_millis(200);
+const ABORT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Ask the server to abort `session_id`. Connection errors and 5xx responses are retried
+/// up to [`ABORT_MAX_ATTEMPTS`] times; the whole loop is bounded by [`ABORT_TIMEOUT`].
 pub async fn send_abort(
     client: &reqwest::Client,
     base_url: &str,
     directory: &str,
     session_id: &str,
-) {
-    let request = client
-        .post(format!("{base_url}/session/{session_id}/abort"))
-        .query(&[("directory", directory)]);
-
-    let _ = tokio::time::timeout(Duration::from_millis(800), async move {
-        let resp = request.send().await;
-        if let Ok(resp) = resp {
-            // Drain body
-            let _ = resp.bytes().await;
+) -> Result<(), String> {
+    let url = format!("{base_url}/session/{session_id}/abort");
+
+    let attempts = async {
+        let mut last_error = String::new();
+        for attempt in 1..=ABORT_MAX_ATTEMPTS {
+            match client
+                .post(&url)
+                .query(&[("directory", directory)])
+                .send()
+                .await
+            {
+                Ok(resp) if resp.status().is_success() => {
+                    // Drain body
+                    let _ = resp.bytes().await;
+                    return Ok(());
+                }
+                Ok(resp) if !resp.status().is_server_error() => {
+                    return Err(format!("abort rejected with status {}", resp.status()));
+                }
+                Ok(resp) => last_error = format!("abort failed with status {}", resp.status()),
+                Err(err) => last_error = format!("abort request failed: {err}"),
+            }
+            if attempt < ABORT_MAX_ATTEMPTS {
+                tokio::time::sleep(ABORT_RETRY_DELAY).await;
+            }
         }
-    })
-    .await;
-}
-
-fn parse_model(model: &str) -> Option<ModelSpec> {
-    let (provider_id, model_id) = match model.split_once('/') {
-        Some((provider, rest)) => (provider.to_string(), rest.to_string()),
-        None => (model.to_string(), String::new()),
+        Err(last_error)
     };
 
-    Some(ModelSpec {
-        provider_id,
-        model_id,
-    })
+    tokio::time::timeout(ABORT_TIMEOUT, attempts)
+        .await
+        .map_err(|_| format!("abort timed out after {}ms", ABORT_TIMEOUT.as_millis()))?
 }
 
-fn parse_model_strict(model: &str) -> Option<ModelSpec> {
+/// Parse a `provider/model` string. Everything after the first `/` is the model id, so
+/// `a/b/c` yields provider `a` and model `b/c`. Returns `None` if either part is empty.
+fn parse_model(model: &str) -> Option<ModelSpec> {
     let (provider_id, model_id) = model.split_once('/')?;
-    let model_id = model_id.trim();
-    if model_id.is_empty() {
+    let (provider_id, model_id) = (provider_id.trim(), model_id.trim());
+    if provider_id.is_empty() || model_id.is_empty() {
         return None;
     }
     Some(ModelSpec {
@@ -981,18 +1296,33 @@ fn parse_model_strict(model: &str) -> Option<ModelSpec> {
     })
 }
 
+/// Error message for a configured agent the server doesn't know, naming the ones it does.
+fn unknown_agent_message(agent: &str, agents: &[AgentInfo]) -> Option<String> {
+    if agents.iter().any(|info| info.name == agent) {
+        return None;
+    }
+    let available = agents
+        .iter()
+        .map(|info| info.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "OpenCode agent `{agent}` does not exist. Available agents: {available}"
+    ))
+}
+
 pub async fn resolve_compaction_model(
     client: &reqwest::Client,
     base_url: &str,
     directory: &str,
     configured_model: Option<&str>,
 ) -> Result<ModelSpec, ExecutorError> {
-    if let Some(model) = configured_model.and_then(parse_model_strict) {
+    if let Some(model) = configured_model.and_then(parse_model) {
         return Ok(model);
     }
 
     let config = config_get(client, base_url, directory).await?;
-    if let Some(model) = config.model.as_deref().and_then(parse_model_strict) {
+    if let Some(model) = config.model.as_deref().and_then(parse_model) {
         return Ok(model);
     }
 
@@ -1065,8 +1395,12 @@ pub struct EventListenerConfig {
     pub log_writer: LogWriter,
     pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
     pub auto_approve: bool,
-    pub control_tx: mpsc::UnboundedSender<ControlEvent>,
+    pub remember_approvals: bool,
+    pub control_tx: ControlSender,
     pub models_cache_key: String,
+    pub max_attempts: u32,
+    /// Called before `ControlEvent::Disconnected` is sent once `max_attempts` is reached.
+    pub on_max_attempts_exceeded: Option<Box<dyn Fn() + Send>>,
 }
 
 pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: reqwest::Response) {
@@ -1078,15 +1412,18 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
         log_writer,
         approvals,
         auto_approve,
+        remember_approvals,
         control_tx,
         models_cache_key,
+        max_attempts,
+        on_max_attempts_exceeded,
     } = config;
 
     let mut seen_permissions: HashSet<String> = HashSet::new();
+    let always_approved = AlwaysApproved::default();
     let mut last_event_id: Option<String> = None;
     let mut base_retry_delay = Duration::from_millis(3000);
     let mut attempt: u32 = 0;
-    let max_attempts: u32 = 20;
     let mut resp: Option<reqwest::Response> = Some(initial_resp);
 
     loop {
@@ -1109,7 +1446,12 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
                             .await;
                         attempt += 1;
                         if attempt >= max_attempts {
-                            let _ = control_tx.send(ControlEvent::Disconnected);
+                            report_reconnect_exhausted(
+                                &session_id,
+                                attempt,
+                                on_max_attempts_exceeded.as_deref(),
+                            );
+                            control_tx.send(ControlEvent::Disconnected).await;
                             return;
                         }
 
@@ -1130,6 +1472,8 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
                 log_writer: &log_writer,
                 approvals: approvals.clone(),
                 auto_approve,
+                remember_approvals,
+                always_approved: &always_approved,
                 control_tx: &control_tx,
                 base_retry_delay: &mut base_retry_delay,
                 last_event_id: &mut last_event_id,
@@ -1144,7 +1488,12 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
             Ok(EventStreamOutcome::Disconnected) | Err(_) => {
                 attempt += 1;
                 if attempt >= max_attempts {
-                    let _ = control_tx.send(ControlEvent::Disconnected);
+                    report_reconnect_exhausted(
+                        &session_id,
+                        attempt,
+                        on_max_attempts_exceeded.as_deref(),
+                    );
+                    control_tx.send(ControlEvent::Disconnected).await;
                     return;
                 }
             }
@@ -1155,19 +1504,33 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
     }
 }
 
+fn report_reconnect_exhausted(
+    session_id: &str,
+    attempts: u32,
+    on_max_attempts_exceeded: Option<&(dyn Fn() + Send)>,
+) {
+    tracing::warn!(
+        session_id,
+        attempts,
+        "OpenCode event stream reconnect attempts exhausted"
+    );
+    if let Some(callback) = on_max_attempts_exceeded {
+        callback();
+    }
+}
+
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Exponential backoff with ±25% jitter so concurrent sessions don't reconnect in lockstep.
 fn exponential_backoff(base: Duration, attempt: u32) -> Duration {
     let exp = attempt.saturating_sub(1).min(10);
     let mult = 1u32 << exp;
-    base.checked_mul(mult)
-        .unwrap_or(Duration::from_secs(30))
-        .min(Duration::from_secs(30))
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum EventStreamOutcome {
-    Idle,
-    Terminal,
-    Disconnected,
+    let capped = base
+        .checked_mul(mult)
+        .unwrap_or(MAX_RETRY_DELAY)
+        .min(MAX_RETRY_DELAY);
+    let jitter: f64 = rand::thread_rng().gen_range(0.75..=1.25);
+    capped.mul_f64(jitter).min(MAX_RETRY_DELAY)
 }
 
 pub(super) struct EventStreamContext<'a> {
@@ -1179,21 +1542,63 @@ pub(super) struct EventStreamContext<'a> {
     pub log_writer: &'a LogWriter,
     approvals: Option<Arc<dyn ExecutorApprovalService>>,
     auto_approve: bool,
-    control_tx: &'a mpsc::UnboundedSender<ControlEvent>,
+    remember_approvals: bool,
+    always_approved: &'a AlwaysApproved,
+    control_tx: &'a ControlSender,
     base_retry_delay: &'a mut Duration,
     last_event_id: &'a mut Option<String>,
     /// Cache key for model context windows, derived from config that affects available models.
     pub models_cache_key: &'a str,
 }
 
+/// Permission/pattern pairs answered with "always", so repeat asks skip the approval service.
+#[derive(Clone, Default)]
+struct AlwaysApproved(Arc<Mutex<HashSet<(String, Vec<String>)>>>);
+
+impl AlwaysApproved {
+    fn contains(&self, key: &(String, Vec<String>)) -> bool {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(key)
+    }
+
+    fn insert(&self, key: (String, Vec<String>)) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).insert(key);
+    }
+}
+
+fn permission_patterns(data: &Value) -> Vec<String> {
+    data.pointer("/properties/patterns")
+        .and_then(Value::as_array)
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 async fn process_event_stream(
     ctx: EventStreamContext<'_>,
     resp: reqwest::Response,
 ) -> Result<EventStreamOutcome, ExecutorError> {
     let mut stream = resp.bytes_stream().eventsource();
+    let mut events_seen: u32 = 0;
+    let mut session_events: u32 = 0;
+    let mut permission_requests: u32 = 0;
 
-    while let Some(evt) = stream.next().await {
-        let evt = evt.map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
+    let result = loop {
+        let Some(evt) = stream.next().await else {
+            break Ok(EventStreamOutcome::Disconnected);
+        };
+        let evt = match evt {
+            Ok(evt) => evt,
+            Err(err) => break Err(ExecutorError::Io(io::Error::other(err))),
+        };
+        events_seen = events_seen.saturating_add(1);
 
         if !evt.id.trim().is_empty() {
             *ctx.last_event_id = Some(evt.id.trim().to_string());
@@ -1221,26 +1626,35 @@ async fn process_event_stream(
             continue;
         };
 
-        if !event_matches_session(event_type, &data, ctx.session_id) {
+        let Some(sdk_event_type) = SdkEventType::of(&data) else {
+            continue;
+        };
+        if !sdk_event_type.matches_session(&data, ctx.session_id) {
             continue;
         }
+        session_events = session_events.saturating_add(1);
 
-        let _ = ctx
-            .log_writer
-            .log_event(&OpencodeExecutorEvent::SdkEvent {
-                event: data.clone(),
-            })
-            .await;
+        let properties = data.get("properties").unwrap_or(&Value::Null);
+        let logged =
+            OpencodeExecutorEvent::from_sdk_event(event_type, properties).unwrap_or_else(|| {
+                OpencodeExecutorEvent::SdkEvent {
+                    event: data.clone(),
+                }
+            });
+        let _ = ctx.log_writer.log_event(&logged).await;
 
-        match event_type {
-            "message.updated" => {
+        match sdk_event_type {
+            SdkEventType::MessageUpdated => {
                 maybe_emit_token_usage(&ctx, &data).await;
+                if let Some(completed) = OpencodeExecutorEvent::message_completed(properties) {
+                    let _ = ctx.log_writer.log_event(&completed).await;
+                }
             }
-            "session.idle" => {
-                let _ = ctx.control_tx.send(ControlEvent::Idle);
-                return Ok(EventStreamOutcome::Idle);
+            SdkEventType::SessionIdle => {
+                ctx.control_tx.send(ControlEvent::Idle).await;
+                break Ok(EventStreamOutcome::Idle);
             }
-            "session.error" => {
+            SdkEventType::SessionError => {
                 let error_type = data
                     .pointer("/properties/error/name")
                     .or_else(|| data.pointer("/properties/error/type"))
@@ -1254,13 +1668,24 @@ async fn process_event_stream(
                     .to_string();
 
                 if error_type == "ProviderAuthError" {
-                    let _ = ctx.control_tx.send(ControlEvent::AuthRequired { message });
-                    return Ok(EventStreamOutcome::Terminal);
+                    let provider = data
+                        .pointer("/properties/error/data/providerID")
+                        .and_then(Value::as_str)
+                        .map(str::to_string);
+                    ctx.control_tx
+                        .send(ControlEvent::PermissionDenied {
+                            provider,
+                            reason: message,
+                        })
+                        .await;
+                    break Ok(EventStreamOutcome::Terminal);
                 }
 
-                let _ = ctx.control_tx.send(ControlEvent::SessionError { message });
+                ctx.control_tx
+                    .send(ControlEvent::SessionError { message })
+                    .await;
             }
-            "permission.asked" => {
+            SdkEventType::PermissionAsked => {
                 let request_id = data
                     .pointer("/properties/id")
                     .and_then(Value::as_str)
@@ -1270,6 +1695,7 @@ async fn process_event_stream(
                 if request_id.is_empty() || !ctx.seen_permissions.insert(request_id.clone()) {
                     continue;
                 }
+                permission_requests = permission_requests.saturating_add(1);
 
                 let tool_call_id = data
                     .pointer("/properties/tool/callID")
@@ -1288,6 +1714,12 @@ async fn process_event_stream(
                     .cloned()
                     .unwrap_or_else(|| serde_json::json!({}));
 
+                let approval_key = (permission.clone(), permission_patterns(&data));
+                let remember_approvals = ctx.remember_approvals;
+                let already_approved =
+                    remember_approvals && ctx.always_approved.contains(&approval_key);
+                let always_approved = ctx.always_approved.clone();
+
                 let approvals = ctx.approvals.clone();
                 let client = ctx.client.clone();
                 let base_url = ctx.base_url.to_string();
@@ -1295,14 +1727,20 @@ async fn process_event_stream(
                 let log_writer = ctx.log_writer.clone();
                 let auto_approve = ctx.auto_approve;
                 tokio::spawn(async move {
-                    let status = request_permission_approval(
-                        auto_approve,
-                        approvals,
-                        &permission,
-                        tool_input,
-                        &tool_call_id,
-                    )
-                    .await;
+                    // OpenCode may still ask after an "always" reply (e.g. after a reconnect);
+                    // answer it again without prompting the user.
+                    let status = if already_approved {
+                        ApprovalStatus::ApprovedForSession
+                    } else {
+                        request_permission_approval(
+                            auto_approve,
+                            approvals,
+                            &permission,
+                            tool_input,
+                            &tool_call_id,
+                        )
+                        .await
+                    };
 
                     let _ = log_writer
                         .log_event(&OpencodeExecutorEvent::ApprovalResponse {
@@ -1312,7 +1750,20 @@ async fn process_event_stream(
                         .await;
 
                     let (reply, message) = match status {
-                        ApprovalStatus::Approved => ("once", None),
+                        // Only an explicit session approval is remembered
+                        ApprovalStatus::ApprovedForSession if remember_approvals => {
+                            always_approved.insert(approval_key);
+                            ("always", None)
+                        }
+                        ApprovalStatus::Approved
+                        | ApprovalStatus::ApprovedForSession
+                        | ApprovalStatus::AutoAllowlisted => ("once", None),
+                        ApprovalStatus::ApprovedWithEdits { .. } => {
+                            tracing::warn!(
+                                "OpenCode cannot apply edited tool input; approving the original call"
+                            );
+                            ("once", None)
+                        }
                         ApprovalStatus::Denied { reason } => {
                             let msg = reason
                                 .unwrap_or_else(|| "User denied this tool use request".to_string())
@@ -1359,38 +1810,19 @@ async fn process_event_stream(
             }
             _ => {}
         }
-    }
-
-    Ok(EventStreamOutcome::Disconnected)
-}
-
-fn event_matches_session(event_type: &str, event: &Value, session_id: &str) -> bool {
-    let extracted = match event_type {
-        "message.updated" => event
-            .pointer("/properties/info/sessionID")
-            .and_then(Value::as_str),
-        "message.part.updated" => event
-            .pointer("/properties/part/sessionID")
-            .and_then(Value::as_str),
-        "permission.asked" | "permission.replied" | "session.idle" | "session.error" => event
-            .pointer("/properties/sessionID")
-            .and_then(Value::as_str),
-        _ => event
-            .pointer("/properties/sessionID")
-            .and_then(Value::as_str)
-            .or_else(|| {
-                event
-                    .pointer("/properties/info/sessionID")
-                    .and_then(Value::as_str)
-            })
-            .or_else(|| {
-                event
-                    .pointer("/properties/part/sessionID")
-                    .and_then(Value::as_str)
-            }),
     };
 
-    extracted == Some(session_id)
+    let _ = ctx
+        .log_writer
+        .log_event(&OpencodeExecutorEvent::StreamSummary {
+            events_seen,
+            session_events,
+            permission_requests,
+            outcome: *result.as_ref().unwrap_or(&EventStreamOutcome::Disconnected),
+        })
+        .await;
+
+    result
 }
 
 async fn request_permission_approval(
@@ -1421,3 +1853,420 @@ async fn request_permission_approval(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+        task::{Context, Poll},
+    };
+
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl CaptureWriter {
+        fn lines(&self) -> Vec<Value> {
+            let bytes = self.0.lock().unwrap().clone();
+            String::from_utf8(bytes)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    impl AsyncWrite for CaptureWriter {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn batched_writes_preserve_order() {
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+
+        for i in 0..2000 {
+            log_writer
+                .log_event(&OpencodeExecutorEvent::StartupLog {
+                    message: format!("line {i}"),
+                })
+                .await
+                .unwrap();
+        }
+        log_writer.flush().await.unwrap();
+
+        let lines = capture.lines();
+        assert_eq!(lines.len(), 2000);
+        for (i, line) in lines.iter().enumerate() {
+            assert_eq!(line["message"], format!("line {i}"));
+            assert_eq!(line["correlation_id"].as_str(), log_writer.correlation_id());
+        }
+    }
+
+    #[tokio::test]
+    async fn terminal_events_flush_immediately() {
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+
+        log_writer
+            .log_event(&OpencodeExecutorEvent::StartupLog {
+                message: "starting".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(capture.lines().is_empty(), "regular events are buffered");
+
+        log_writer.log_error("boom".to_string()).await.unwrap();
+        let lines = capture.lines();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "startup_log");
+        assert_eq!(lines[1]["type"], "error");
+
+        log_writer
+            .log_event(&OpencodeExecutorEvent::Done)
+            .await
+            .unwrap();
+        assert_eq!(capture.lines().last().unwrap()["type"], "done");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn buffered_events_flush_on_interval() {
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+
+        log_writer
+            .log_event(&OpencodeExecutorEvent::StartupLog {
+                message: "pending".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(capture.lines().is_empty());
+
+        tokio::time::sleep(LOG_FLUSH_INTERVAL * 3).await;
+        assert_eq!(capture.lines().len(), 1);
+    }
+
+    #[test]
+    fn log_writer_can_be_created_outside_a_runtime() {
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            log_writer
+                .log_event(&OpencodeExecutorEvent::StartupLog {
+                    message: "late".to_string(),
+                })
+                .await
+                .unwrap();
+            tokio::time::sleep(LOG_FLUSH_INTERVAL * 2).await;
+        });
+        assert_eq!(capture.lines().len(), 1);
+    }
+
+    /// Serve one response per `(status, body)` pair, closing the connection after each.
+    async fn serve_responses(responses: Vec<(u16, &'static str)>) -> (String, Arc<AtomicUsize>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (format!("http://{addr}"), hits)
+    }
+
+    async fn serve_statuses(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        serve_responses(statuses.into_iter().map(|status| (status, "")).collect()).await
+    }
+
+    #[test]
+    fn always_approved_matches_permission_and_patterns() {
+        let data = serde_json::json!({
+            "properties": { "permission": "bash", "patterns": ["git status", "ls *"] }
+        });
+        let always_approved = AlwaysApproved::default();
+        always_approved.insert(("bash".to_string(), permission_patterns(&data)));
+
+        assert!(always_approved.contains(&(
+            "bash".to_string(),
+            vec!["git status".to_string(), "ls *".to_string()]
+        )));
+        assert!(!always_approved.contains(&("bash".to_string(), vec!["rm -rf *".to_string()])));
+        assert!(!always_approved.contains(&(
+            "edit".to_string(),
+            vec!["git status".to_string(), "ls *".to_string()]
+        )));
+    }
+
+    #[tokio::test]
+    async fn fork_falls_back_to_new_session_on_not_found() {
+        let (base_url, hits) = serve_responses(vec![(404, ""), (200, r#"{"id":"ses_new"}"#)]).await;
+        let client = reqwest::Client::new();
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+
+        let session_id = fork_or_create_session(&client, &base_url, "/tmp", "ses_old", &log_writer)
+            .await
+            .unwrap();
+        log_writer.flush().await.unwrap();
+
+        assert_eq!(session_id, "ses_new");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        let lines = capture.lines();
+        assert_eq!(lines[0]["type"], "resume_fallback");
+        assert_eq!(lines[0]["previous_session_id"], "ses_old");
+        assert_eq!(lines[0]["session_id"], "ses_new");
+    }
+
+    #[tokio::test]
+    async fn fork_propagates_other_errors() {
+        let (base_url, hits) = serve_responses(vec![(500, ""), (200, r#"{"id":"ses_new"}"#)]).await;
+        let client = reqwest::Client::new();
+        let log_writer = LogWriter::new(CaptureWriter::default());
+
+        assert!(
+            fork_or_create_session(&client, &base_url, "/tmp", "ses_old", &log_writer)
+                .await
+                .is_err()
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn event_stream_emits_summary_when_it_ends() {
+        let body = concat!(
+            "data: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+            "data: {\"type\":\"session.updated\",\"properties\":{\"info\":{\"id\":\"ses_other\"}}}\n\n",
+            "data: {\"type\":\"session.idle\",\"properties\":{\"sessionID\":\"ses_1\"}}\n\n",
+        );
+        let (base_url, _) = serve_responses(vec![(200, body)]).await;
+        let client = reqwest::Client::new();
+        let resp = client.get(&base_url).send().await.unwrap();
+        let capture = CaptureWriter::default();
+        let log_writer = LogWriter::new(capture.clone());
+        let (control_tx, _control_rx) = control_channel(4);
+        let mut seen_permissions = HashSet::new();
+        let mut base_retry_delay = Duration::from_millis(100);
+        let mut last_event_id = None;
+
+        let outcome = process_event_stream(
+            EventStreamContext {
+                seen_permissions: &mut seen_permissions,
+                client: &client,
+                base_url: &base_url,
+                directory: "/tmp",
+                session_id: "ses_1",
+                log_writer: &log_writer,
+                approvals: None,
+                auto_approve: true,
+                remember_approvals: false,
+                always_approved: &AlwaysApproved::default(),
+                control_tx: &control_tx,
+                base_retry_delay: &mut base_retry_delay,
+                last_event_id: &mut last_event_id,
+                models_cache_key: "",
+            },
+            resp,
+        )
+        .await
+        .unwrap();
+        log_writer.flush().await.unwrap();
+
+        assert_eq!(outcome, EventStreamOutcome::Idle);
+        let summary = capture
+            .lines()
+            .into_iter()
+            .find(|line| line["type"] == "stream_summary")
+            .expect("stream summary logged");
+        assert_eq!(summary["events_seen"], 3);
+        assert_eq!(summary["session_events"], 1);
+        assert_eq!(summary["permission_requests"], 0);
+        assert_eq!(summary["outcome"], "idle");
+    }
+
+    #[tokio::test]
+    async fn send_abort_retries_server_errors() {
+        let (base_url, hits) = serve_statuses(vec![503, 502, 200]).await;
+        let client = reqwest::Client::new();
+
+        send_abort(&client, &base_url, "/tmp", "ses_1")
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn send_abort_gives_up_after_max_attempts() {
+        let (base_url, hits) = serve_statuses(vec![503; 5]).await;
+        let client = reqwest::Client::new();
+
+        let err = send_abort(&client, &base_url, "/tmp", "ses_1")
+            .await
+            .unwrap_err();
+        assert!(err.contains("503"), "unexpected error: {err}");
+        assert_eq!(hits.load(Ordering::SeqCst), ABORT_MAX_ATTEMPTS as usize);
+    }
+
+    #[tokio::test]
+    async fn send_abort_does_not_retry_client_errors() {
+        let (base_url, hits) = serve_statuses(vec![404, 200]).await;
+        let client = reqwest::Client::new();
+
+        assert!(
+            send_abort(&client, &base_url, "/tmp", "ses_1")
+                .await
+                .is_err()
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn default_headers_identify_client_and_request() {
+        let headers = build_default_headers("/repo", "secret", "req-123");
+        assert_eq!(
+            headers.get(USER_AGENT).unwrap(),
+            &format!("vibe-kanban/{}", env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(headers.get(REQUEST_ID_HEADER).unwrap(), "req-123");
+        assert_eq!(headers.get("x-opencode-directory").unwrap(), "/repo");
+        assert!(headers.contains_key(AUTHORIZATION));
+    }
+
+    #[test]
+    fn parse_model_splits_provider_and_model() {
+        let spec = parse_model("openai/gpt-5").unwrap();
+        assert_eq!(spec.provider_id, "openai");
+        assert_eq!(spec.model_id, "gpt-5");
+    }
+
+    #[test]
+    fn parse_model_keeps_extra_segments_in_model_id() {
+        let spec = parse_model("a/b/c").unwrap();
+        assert_eq!(spec.provider_id, "a");
+        assert_eq!(spec.model_id, "b/c");
+    }
+
+    #[test]
+    fn parse_model_rejects_missing_model_id() {
+        assert!(parse_model("openai").is_none());
+        assert!(parse_model("openai/").is_none());
+        assert!(parse_model("openai/  ").is_none());
+        assert!(parse_model("/gpt-5").is_none());
+        assert!(parse_model("").is_none());
+    }
+
+    #[test]
+    fn unknown_agent_names_available_agents() {
+        let agents = ["build", "plan"].map(|name| AgentInfo {
+            name: name.to_string(),
+            description: None,
+        });
+        assert!(unknown_agent_message("build", &agents).is_none());
+        let message = unknown_agent_message("buidl", &agents).unwrap();
+        assert!(message.contains("`buidl`"));
+        assert!(message.contains("build, plan"));
+    }
+
+    #[test]
+    fn provider_auth_error_response_is_permission_denied() {
+        let body = serde_json::json!({
+            "name": "ProviderAuthError",
+            "data": { "providerID": "anthropic", "message": "Invalid API key" }
+        });
+        match named_error_response("session.prompt", "ProviderAuthError", &body, "") {
+            ExecutorError::PermissionDenied { provider, reason } => {
+                assert_eq!(provider.as_deref(), Some("anthropic"));
+                assert_eq!(reason, "Invalid API key");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let body = serde_json::json!({ "name": "UnknownError", "data": { "message": "boom" } });
+        assert!(matches!(
+            named_error_response("session.prompt", "UnknownError", &body, ""),
+            ExecutorError::Io(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn full_control_channel_drops_session_errors() {
+        let (control_tx, mut control_rx) = control_channel(1);
+
+        control_tx.send(ControlEvent::Idle).await;
+        control_tx
+            .send(ControlEvent::SessionError {
+                message: "overflow".to_string(),
+            })
+            .await;
+
+        assert!(matches!(control_rx.recv().await, Some(ControlEvent::Idle)));
+        assert!(control_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn full_control_channel_waits_for_terminal_events() {
+        let (control_tx, mut control_rx) = control_channel(1);
+
+        control_tx
+            .send(ControlEvent::SessionError {
+                message: "first".to_string(),
+            })
+            .await;
+
+        let sender = control_tx.clone();
+        let mut pending =
+            tokio::spawn(async move { sender.send(ControlEvent::Disconnected).await });
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), &mut pending)
+                .await
+                .is_err(),
+            "send should wait for capacity"
+        );
+
+        assert!(matches!(
+            control_rx.recv().await,
+            Some(ControlEvent::SessionError { .. })
+        ));
+        pending.await.unwrap();
+        assert!(matches!(
+            control_rx.recv().await,
+            Some(ControlEvent::Disconnected)
+        ));
+    }
+}
@@ -13,14 +13,12 @@ use std::{
 };
 
 use serde_json::Value;
-use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use super::{
     sdk::{
-        self, AgentInfo, CommandInfo, ConfigProvidersResponse, ConfigResponse, ControlEvent,
-        EventListenerConfig, FormatterStatus, LogWriter, LspStatus, ProviderListResponse,
-        RunConfig,
+        self, AgentInfo, CommandInfo, ConfigProvidersResponse, ConfigResponse, EventListenerConfig,
+        FormatterStatus, LogWriter, LspStatus, ProviderListResponse, RunConfig,
     },
     types::OpencodeExecutorEvent,
 };
@@ -469,7 +467,13 @@ pub async fn execute(
         Some(existing) if command.should_fork_session() => {
             tokio::select! {
                 _ = cancel.cancelled() => return Ok(()),
-                res = sdk::fork_session(&client, &config.base_url, &config.directory, existing) => res?,
+                res = sdk::fork_or_create_session(
+                    &client,
+                    &config.base_url,
+                    &config.directory,
+                    existing,
+                    &log_writer,
+                ) => res?,
             }
         }
         Some(existing) => existing.to_string(),
@@ -500,7 +504,7 @@ pub async fn execute(
         None
     };
 
-    let (control_tx, mut control_rx) = mpsc::unbounded_channel::<ControlEvent>();
+    let (control_tx, mut control_rx) = sdk::control_channel(config.control_channel_capacity);
     let event_resp = tokio::select! {
         _ = cancel.cancelled() => return Ok(()),
         res = sdk::connect_event_stream(&client, &config.base_url, &config.directory, None) => res?,
@@ -516,6 +520,8 @@ pub async fn execute(
             auto_approve: config.auto_approve,
             control_tx,
             models_cache_key: config.models_cache_key.clone(),
+            max_attempts: config.max_reconnect_attempts,
+            on_max_attempts_exceeded: config.max_attempts_callback(),
         },
         event_resp,
     ));
@@ -566,7 +572,12 @@ pub async fn execute(
         sdk::run_request_with_control(request_fut, &mut control_rx, cancel.clone()).await;
 
     if cancel.is_cancelled() {
-        sdk::send_abort(&client, &config.base_url, &config.directory, &session_id).await;
+        if !matches!(request_result, Ok(sdk::RequestOutcome::Completed))
+            && let Err(err) =
+                sdk::send_abort(&client, &config.base_url, &config.directory, &session_id).await
+        {
+            tracing::warn!("Failed to abort OpenCode session {session_id}: {err}");
+        }
         event_handle.abort();
         return Ok(());
     }
@@ -12,9 +12,40 @@ pub enum OpencodeExecutorEvent {
     SessionStart {
         session_id: String,
     },
+    /// The session to resume no longer exists on the server (e.g. it was restarted), so a
+    /// fresh session was created instead of a fork.
+    ResumeFallback {
+        previous_session_id: String,
+        session_id: String,
+    },
     SlashCommandResult {
         message: String,
     },
+    /// A text, reasoning or tool part update decoded from `message.part.updated`.
+    MessagePartUpdated {
+        message_id: String,
+        part_type: StreamPartType,
+        #[serde(default)]
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delta: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_status: Option<String>,
+        /// The raw tool part, which carries the call's input and output for rendering.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_part: Option<Value>,
+    },
+    /// An assistant message finished streaming.
+    MessageCompleted {
+        message_id: String,
+    },
+    /// A new agent step started within a message.
+    StepStarted {
+        message_id: String,
+    },
+    /// Any SDK event without a typed variant above, forwarded as raw JSON.
     SdkEvent {
         event: serde_json::Value,
     },
@@ -29,9 +60,128 @@ pub enum OpencodeExecutorEvent {
     Error {
         message: String,
     },
+    /// Counters for one connection of the event stream, emitted when it ends.
+    StreamSummary {
+        events_seen: u32,
+        session_events: u32,
+        permission_requests: u32,
+        outcome: EventStreamOutcome,
+    },
     Done,
 }
 
+/// How a single connection of the OpenCode event stream ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStreamOutcome {
+    Idle,
+    Terminal,
+    Disconnected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamPartType {
+    Text,
+    Reasoning,
+    Tool,
+}
+
+impl OpencodeExecutorEvent {
+    /// Decodes the event families the log normalizer renders directly. Returns `None` for
+    /// everything else so the caller can fall back to [`OpencodeExecutorEvent::SdkEvent`].
+    pub(super) fn from_sdk_event(event_type: &str, properties: &Value) -> Option<Self> {
+        match event_type {
+            "message.part.updated" => {
+                let update = serde_json::from_value::<StreamPartUpdate>(properties.clone()).ok()?;
+                let part = update.part;
+                let part_type = match part.type_.as_str() {
+                    "text" => StreamPartType::Text,
+                    "reasoning" => StreamPartType::Reasoning,
+                    "tool" => StreamPartType::Tool,
+                    "step-start" => {
+                        return Some(Self::StepStarted {
+                            message_id: part.message_id,
+                        });
+                    }
+                    _ => return None,
+                };
+                let tool_part = (part_type == StreamPartType::Tool)
+                    .then(|| properties.get("part").cloned())
+                    .flatten();
+                Some(Self::MessagePartUpdated {
+                    message_id: part.message_id,
+                    part_type,
+                    text: part.text,
+                    delta: update.delta,
+                    tool_name: part.tool,
+                    tool_status: part.state.map(|state| state.status),
+                    tool_part,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a `MessageCompleted` event when a `message.updated` payload marks an
+    /// assistant message as finished.
+    pub(super) fn message_completed(properties: &Value) -> Option<Self> {
+        let update = serde_json::from_value::<CompletedMessageUpdate>(properties.clone()).ok()?;
+        let info = update.info;
+        if info.role != MessageRole::Assistant || info.time.completed.is_none() {
+            return None;
+        }
+        Some(Self::MessageCompleted {
+            message_id: info.id,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamPartUpdate {
+    part: StreamPart,
+    #[serde(default)]
+    delta: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamPart {
+    #[serde(rename = "type")]
+    type_: String,
+    #[serde(rename = "messageID")]
+    message_id: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tool: Option<String>,
+    #[serde(default)]
+    state: Option<StreamToolState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamToolState {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletedMessageUpdate {
+    info: CompletedMessageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletedMessageInfo {
+    id: String,
+    role: MessageRole,
+    #[serde(default)]
+    time: MessageTime,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessageTime {
+    #[serde(default)]
+    completed: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct SdkEventEnvelope {
     #[serde(rename = "type")]
@@ -368,3 +518,153 @@ pub(super) struct ProviderModelLimit {
     #[serde(default, deserialize_with = "deserialize_f64_as_u32")]
     pub(super) context: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Captured from an OpenCode `/event` stream; each line is one SSE `data` payload.
+    const EVENT_STREAM_FIXTURE: &str = include_str!("fixtures/event_stream.jsonl");
+
+    fn fixture_events() -> Vec<OpencodeExecutorEvent> {
+        EVENT_STREAM_FIXTURE
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                let data: Value = serde_json::from_str(line).unwrap();
+                let event_type = data["type"].as_str().unwrap().to_string();
+                let properties = data["properties"].clone();
+                OpencodeExecutorEvent::from_sdk_event(&event_type, &properties).or_else(|| {
+                    (event_type == "message.updated")
+                        .then(|| OpencodeExecutorEvent::message_completed(&properties))
+                        .flatten()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn captured_stream_decodes_typed_events() {
+        let events = fixture_events();
+        let kinds = events
+            .iter()
+            .map(|event| match event {
+                OpencodeExecutorEvent::StepStarted { .. } => "step_started",
+                OpencodeExecutorEvent::MessagePartUpdated {
+                    part_type: StreamPartType::Reasoning,
+                    ..
+                } => "reasoning",
+                OpencodeExecutorEvent::MessagePartUpdated {
+                    part_type: StreamPartType::Text,
+                    ..
+                } => "text",
+                OpencodeExecutorEvent::MessagePartUpdated {
+                    part_type: StreamPartType::Tool,
+                    ..
+                } => "tool",
+                OpencodeExecutorEvent::MessageCompleted { .. } => "completed",
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            [
+                "step_started",
+                "reasoning",
+                "reasoning",
+                "tool",
+                "text",
+                "text",
+                "completed"
+            ]
+        );
+
+        match &events[2] {
+            OpencodeExecutorEvent::MessagePartUpdated {
+                message_id,
+                text,
+                delta,
+                ..
+            } => {
+                assert_eq!(message_id, "msg_assistant_1");
+                assert_eq!(text, "Looking at the repo layout");
+                assert_eq!(delta.as_deref(), Some(" layout"));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        match &events[3] {
+            OpencodeExecutorEvent::MessagePartUpdated {
+                tool_name,
+                tool_status,
+                tool_part,
+                ..
+            } => {
+                assert_eq!(tool_name.as_deref(), Some("bash"));
+                assert_eq!(tool_status.as_deref(), Some("completed"));
+                assert_eq!(tool_part.as_ref().unwrap()["callID"], "toolu_1");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tool_parts_carry_name_and_status() {
+        let tool_part = serde_json::json!({
+            "part": {
+                "type": "tool",
+                "messageID": "msg_1",
+                "callID": "call_1",
+                "tool": "bash",
+                "state": { "status": "running", "input": { "command": "ls" } }
+            }
+        });
+        match OpencodeExecutorEvent::from_sdk_event("message.part.updated", &tool_part) {
+            Some(OpencodeExecutorEvent::MessagePartUpdated {
+                part_type: StreamPartType::Tool,
+                tool_name,
+                tool_status,
+                ..
+            }) => {
+                assert_eq!(tool_name.as_deref(), Some("bash"));
+                assert_eq!(tool_status.as_deref(), Some("running"));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_types_fall_back_to_raw_events() {
+        assert!(
+            OpencodeExecutorEvent::from_sdk_event("session.diff", &serde_json::json!({})).is_none()
+        );
+    }
+
+    #[test]
+    fn typed_events_round_trip_through_logs() {
+        let event = OpencodeExecutorEvent::MessagePartUpdated {
+            message_id: "msg_1".to_string(),
+            part_type: StreamPartType::Reasoning,
+            text: "thinking".to_string(),
+            delta: None,
+            tool_name: None,
+            tool_status: None,
+            tool_part: None,
+        };
+        let line = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap(),
+            serde_json::json!({
+                "type": "message_part_updated",
+                "message_id": "msg_1",
+                "part_type": "reasoning",
+                "text": "thinking"
+            })
+        );
+        assert!(matches!(
+            serde_json::from_str::<OpencodeExecutorEvent>(&line).unwrap(),
+            OpencodeExecutorEvent::MessagePartUpdated { .. }
+        ));
+    }
+}
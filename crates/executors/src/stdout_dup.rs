@@ -1,6 +1,6 @@
 //! Cross-platform stdout duplication utility for child processes
 //!
-//! Provides a single function to duplicate a child process's stdout stream.
+//! Provides helpers to duplicate a child process's stdout and stderr streams.
 //! Supports Unix and Windows platforms.
 
 #[cfg(unix)]
@@ -10,7 +10,7 @@ use std::os::windows::io::{FromRawHandle, IntoRawHandle, OwnedHandle};
 
 use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use futures::{StreamExt, stream::BoxStream};
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio_util::io::ReaderStream;
 
@@ -25,11 +25,6 @@ use crate::executors::{ExecutorError, SpawnedChild};
 pub fn duplicate_stdout(
     child: &mut AsyncGroupChild,
 ) -> Result<BoxStream<'static, std::io::Result<String>>, ExecutorError> {
-    // The implementation strategy is:
-    // 1. create a new file descriptor.
-    // 2. read the original stdout file descriptor.
-    // 3. write the data to both the new file descriptor and a duplicate stream.
-
     // Take the original stdout
     let original_stdout = child.inner().stdout.take().ok_or_else(|| {
         ExecutorError::Io(std::io::Error::new(
@@ -38,25 +33,68 @@ pub fn duplicate_stdout(
         ))
     })?;
 
+    let (pipe_reader, stream) = tee_to_pipe(original_stdout, "stdout")?;
+    // Use fd as new child stdout
+    child.inner().stdout = Some(wrap_fd_as_child_stdout(pipe_reader)?);
+    Ok(stream)
+}
+
+/// Duplicate stderr from AsyncGroupChild.
+///
+/// Works like [`duplicate_stdout`]: the child's stderr is replaced with a pipe that still
+/// receives everything, so existing stderr consumers keep working.
+pub fn duplicate_stderr(
+    child: &mut AsyncGroupChild,
+) -> Result<BoxStream<'static, std::io::Result<String>>, ExecutorError> {
+    let original_stderr = child.inner().stderr.take().ok_or_else(|| {
+        ExecutorError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Child process has no stderr",
+        ))
+    })?;
+
+    let (pipe_reader, stream) = tee_to_pipe(original_stderr, "stderr")?;
+    child.inner().stderr = Some(wrap_fd_as_child_stderr(pipe_reader)?);
+    Ok(stream)
+}
+
+/// Copy everything read from `original` into a new pipe and a duplicate stream. Returns
+/// the pipe's read end, which replaces the child's original output handle.
+fn tee_to_pipe<R>(
+    original: R,
+    stream_name: &'static str,
+) -> Result<
+    (
+        os_pipe::PipeReader,
+        BoxStream<'static, std::io::Result<String>>,
+    ),
+    ExecutorError,
+>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    // The implementation strategy is:
+    // 1. create a new file descriptor.
+    // 2. read the original file descriptor.
+    // 3. write the data to both the new file descriptor and a duplicate stream.
+
     // Create a new file descriptor in a cross-platform way (using os_pipe crate)
     let (pipe_reader, pipe_writer) = os_pipe::pipe().map_err(|e| {
         ExecutorError::Io(std::io::Error::other(format!("Failed to create pipe: {e}")))
     })?;
-    // Use fd as new child stdout
-    child.inner().stdout = Some(wrap_fd_as_child_stdout(pipe_reader)?);
 
     // Obtain writer from fd
     let mut fd_writer = wrap_fd_as_tokio_writer(pipe_writer)?;
 
-    // Create the duplicate stdout stream
+    // Create the duplicate stream
     let (dup_writer, dup_reader) =
         tokio::sync::mpsc::unbounded_channel::<std::io::Result<String>>();
 
-    // Read original stdout and write to both new ChildStdout and duplicate stream
+    // Read the original output and write to both the new pipe and the duplicate stream
     tokio::spawn(async move {
-        let mut stdout_stream = ReaderStream::new(original_stdout);
+        let mut original_stream = ReaderStream::new(original);
 
-        while let Some(res) = stdout_stream.next().await {
+        while let Some(res) = original_stream.next().await {
             match res {
                 Ok(data) => {
                     let _ = fd_writer.write_all(&data).await;
@@ -65,7 +103,7 @@ pub fn duplicate_stdout(
                     let _ = dup_writer.send(Ok(string_chunk));
                 }
                 Err(err) => {
-                    tracing::error!("Error reading from child stdout: {}", err);
+                    tracing::error!("Error reading from child {}: {}", stream_name, err);
                     let _ = dup_writer.send(Err(err));
                 }
             }
@@ -73,7 +111,10 @@ pub fn duplicate_stdout(
     });
 
     // Return the channel receiver as a boxed stream
-    Ok(Box::pin(UnboundedReceiverStream::new(dup_reader)))
+    Ok((
+        pipe_reader,
+        Box::pin(UnboundedReceiverStream::new(dup_reader)),
+    ))
 }
 
 /// Handle to append additional lines into the child's stdout stream.
@@ -230,6 +271,7 @@ pub fn spawn_local_output_process()
         child,
         exit_signal: None,
         interrupt_sender: None,
+        executor: None,
     };
 
     Ok((spawned, writer))
@@ -262,6 +304,27 @@ fn wrap_fd_as_child_stdout(
     }
 }
 
+/// Convert os_pipe::PipeReader to tokio::process::ChildStderr
+fn wrap_fd_as_child_stderr(
+    pipe_reader: os_pipe::PipeReader,
+) -> Result<tokio::process::ChildStderr, ExecutorError> {
+    #[cfg(unix)]
+    {
+        let raw_fd = pipe_reader.into_raw_fd();
+        let owned_fd = unsafe { OwnedFd::from_raw_fd(raw_fd) };
+        let std_stderr = std::process::ChildStderr::from(owned_fd);
+        tokio::process::ChildStderr::from_std(std_stderr).map_err(ExecutorError::Io)
+    }
+
+    #[cfg(windows)]
+    {
+        let raw_handle = pipe_reader.into_raw_handle();
+        let owned_handle = unsafe { OwnedHandle::from_raw_handle(raw_handle) };
+        let std_stderr = std::process::ChildStderr::from(owned_handle);
+        tokio::process::ChildStderr::from_std(std_stderr).map_err(ExecutorError::Io)
+    }
+}
+
 /// Convert os_pipe::PipeWriter to a tokio file for async writing
 fn wrap_fd_as_tokio_writer(
     pipe_writer: os_pipe::PipeWriter,
@@ -15,9 +15,11 @@ use db::{
         coding_agent_turn::CodingAgentTurn,
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+            ExecutionProcessTermination,
         },
+        execution_process_env::{EnvVarSource, ExecutionEnvVar, ExecutionProcessEnv},
         execution_process_repo_state::ExecutionProcessRepoState,
-        repo::Repo,
+        repo::{CommitCheck, Repo},
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         session::{Session, SessionError},
         task::{Task, TaskStatus},
@@ -34,7 +36,10 @@ use executors::{
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::{ExecutionEnv, RepoContext},
-    executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
+    executors::{
+        BaseCodingAgent, CodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender,
+        StandardCodingAgentExecutor,
+    },
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
@@ -191,9 +196,24 @@ impl LocalContainerService {
 
     pub fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
+        let config = self.config.clone();
         let cleanup_expired = Self::cleanup_expired_workspaces;
         tokio::spawn(async move {
-            WorkspaceManager::cleanup_orphan_workspaces(&db.pool).await;
+            if std::env::var("DISABLE_WORKTREE_ORPHAN_CLEANUP").is_ok() {
+                tracing::debug!(
+                    "Orphan workspace cleanup is disabled via DISABLE_WORKTREE_ORPHAN_CLEANUP environment variable"
+                );
+            } else if config.read().await.cleanup_orphans_on_startup {
+                match WorkspaceManager::cleanup_orphan_workspaces(&db.pool, false).await {
+                    Ok(report) => tracing::info!(
+                        "Orphan cleanup removed {} workspace dirs, cleared {} container refs, {} failures",
+                        report.removed.len(),
+                        report.cleared_container_refs.len(),
+                        report.failures.len()
+                    ),
+                    Err(e) => tracing::error!("Failed to clean up orphan workspaces: {}", e),
+                }
+            }
 
             let mut cleanup_interval =
                 tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
@@ -369,6 +389,7 @@ impl LocalContainerService {
         &self,
         exec_id: &Uuid,
         exit_signal: Option<ExecutorExitSignal>,
+        executor: Option<CodingAgent>,
     ) -> JoinHandle<()> {
         let exec_id = *exec_id;
         let child_store = self.child_store.clone();
@@ -426,6 +447,11 @@ impl LocalContainerService {
                 }
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
+            let exit_result = if matches!(status, ExecutionProcessStatus::Completed) {
+                ExecutorExitResult::Success
+            } else {
+                ExecutorExitResult::Failure
+            };
 
             if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
                 && let Err(e) =
@@ -434,6 +460,10 @@ impl LocalContainerService {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
 
+            if let Some(executor) = executor {
+                executor.post_session(&exit_result).await;
+            }
+
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
@@ -890,6 +920,44 @@ impl LocalContainerService {
     }
 }
 
+/// Merge each repo's configured env vars into `env` and return a redacted snapshot of the
+/// result, recording which repo each var came from.
+fn merge_repo_env(env: &mut ExecutionEnv, repos: &[Repo]) -> Vec<ExecutionEnvVar> {
+    let mut repo_sources: HashMap<&str, &str> = HashMap::new();
+    for repo in repos {
+        for key in repo.env_vars.keys() {
+            if let Some(previous) = repo_sources.insert(key, &repo.name) {
+                tracing::warn!(
+                    "Env var {} is set by both repo {} and repo {}; using the value from {}",
+                    key,
+                    previous,
+                    repo.name,
+                    repo.name
+                );
+            }
+        }
+        env.merge_repo_vars(&repo.env_vars);
+    }
+
+    env.redacted_vars()
+        .into_iter()
+        .map(|(key, value)| {
+            let repo_name = repo_sources.get(key.as_str()).map(|name| name.to_string());
+            let source = if repo_name.is_some() {
+                EnvVarSource::Repo
+            } else {
+                EnvVarSource::Runtime
+            };
+            ExecutionEnvVar {
+                key,
+                value,
+                source,
+                repo_name,
+            }
+        })
+        .collect()
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -1093,6 +1161,7 @@ impl ContainerService for LocalContainerService {
                     self.approvals.clone(),
                     self.db.clone(),
                     self.notification_service.clone(),
+                    self.config.clone(),
                     execution_process.id,
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
@@ -1100,7 +1169,19 @@ impl ContainerService for LocalContainerService {
 
         let repos = WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
         let repo_names: Vec<String> = repos.iter().map(|r| r.name.clone()).collect();
-        let repo_context = RepoContext::new(current_dir.clone(), repo_names);
+        let mut repo_context = RepoContext::new(current_dir.clone(), repo_names);
+        for repo in &repos {
+            let repo_path = current_dir.join(&repo.name);
+            match repo.commit_check {
+                CommitCheck::All => {}
+                CommitCheck::TrackedOnly => {
+                    repo_context.tracked_only_repo_paths.insert(repo_path);
+                }
+                CommitCheck::Off => {
+                    repo_context.ignored_repo_paths.insert(repo_path);
+                }
+            }
+        }
 
         let commit_reminder = self.config.read().await.commit_reminder;
         let mut env = ExecutionEnv::new(repo_context, commit_reminder);
@@ -1123,6 +1204,17 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        let env_snapshot = merge_repo_env(&mut env, &repos);
+        if let Err(e) =
+            ExecutionProcessEnv::create(&self.db.pool, execution_process.id, env_snapshot).await
+        {
+            tracing::warn!(
+                "Failed to record environment for execution process {}: {}",
+                execution_process.id,
+                e
+            );
+        }
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
@@ -1148,7 +1240,8 @@ impl ContainerService for LocalContainerService {
         }
 
         // Spawn unified exit monitor: watches OS exit and optional executor signal
-        let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
+        let _hn =
+            self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal, spawned.executor);
 
         Ok(())
     }
@@ -1174,6 +1267,9 @@ impl ContainerService for LocalContainerService {
             .await?;
 
         // Try graceful interrupt first, then force kill
+        let grace_period =
+            Duration::from_secs(self.config.read().await.stop_grace_period_secs.into());
+        let mut termination = ExecutionProcessTermination::ForceKill;
         if let Some(interrupt_sender) = self.take_interrupt_sender(&execution_process.id).await {
             // Send interrupt signal (ignore error if receiver dropped)
             let _ = interrupt_sender.send(());
@@ -1181,7 +1277,7 @@ impl ContainerService for LocalContainerService {
             // Wait for graceful exit with timeout
             let graceful_exit = {
                 let mut child_guard = child.write().await;
-                tokio::time::timeout(Duration::from_secs(5), child_guard.wait()).await
+                tokio::time::timeout(grace_period, child_guard.wait()).await
             };
 
             match graceful_exit {
@@ -1190,6 +1286,7 @@ impl ContainerService for LocalContainerService {
                         "Process {} exited gracefully after interrupt",
                         execution_process.id
                     );
+                    termination = ExecutionProcessTermination::GracefulInterrupt;
                 }
                 Ok(Err(e)) => {
                     tracing::info!("Error waiting for process {}: {}", execution_process.id, e);
@@ -1215,6 +1312,16 @@ impl ContainerService for LocalContainerService {
                 return Err(e);
             }
         }
+        if let Err(e) =
+            ExecutionProcess::set_termination(&self.db.pool, execution_process.id, termination)
+                .await
+        {
+            tracing::warn!(
+                "Failed to record termination for execution process {}: {}",
+                execution_process.id,
+                e
+            );
+        }
         self.remove_child_from_store(&execution_process.id).await;
 
         // Mark the process finished in the MsgStore
@@ -0,0 +1,27 @@
+-- Tag names must be unique within a project regardless of case and surrounding
+-- whitespace ("Bug" vs " bug"). Existing duplicates are merged into the tag with
+-- the lowest id before the index is created.
+CREATE TEMP TABLE tag_merges ON COMMIT DROP AS
+SELECT id AS duplicate_id, keeper_id
+FROM (
+    SELECT
+        id,
+        FIRST_VALUE(id) OVER (
+            PARTITION BY project_id, LOWER(BTRIM(name))
+            ORDER BY id
+        ) AS keeper_id
+    FROM tags
+) ranked
+WHERE id <> keeper_id;
+
+-- Link every issue tagged with a duplicate to the surviving tag
+INSERT INTO issue_tags (issue_id, tag_id)
+SELECT DISTINCT it.issue_id, m.keeper_id
+FROM issue_tags it
+JOIN tag_merges m ON m.duplicate_id = it.tag_id
+ON CONFLICT (issue_id, tag_id) DO NOTHING;
+
+-- The old links cascade away with the duplicate tags
+DELETE FROM tags WHERE id IN (SELECT duplicate_id FROM tag_merges);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_project_id_lower_name ON tags (project_id, LOWER(BTRIM(name)));
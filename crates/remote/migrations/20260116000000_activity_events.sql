@@ -0,0 +1,20 @@
+-- Organization-level activity feed
+CREATE TYPE activity_entity_type AS ENUM ('project', 'issue', 'issue_comment');
+CREATE TYPE activity_action AS ENUM ('created', 'updated', 'status_changed', 'deleted');
+
+CREATE TABLE activity_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    project_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    entity_type activity_entity_type NOT NULL,
+    entity_id UUID NOT NULL,
+    action activity_action NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_activity_events_org_created
+    ON activity_events (organization_id, created_at DESC, id DESC);
@@ -0,0 +1,6 @@
+-- Soft-delete support for projects, issues and comments
+ALTER TABLE projects ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE issues ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE issue_comments ADD COLUMN deleted_at TIMESTAMPTZ;
+
+ALTER TYPE activity_action ADD VALUE 'restored';
@@ -0,0 +1,3 @@
+-- Full-text search over issue titles and descriptions
+CREATE INDEX idx_issues_search ON issues
+    USING GIN (to_tsvector('english', title || ' ' || COALESCE(description, '')));
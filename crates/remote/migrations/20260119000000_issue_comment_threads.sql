@@ -0,0 +1,5 @@
+-- Threaded replies: a comment may answer another comment on the same issue
+ALTER TABLE issue_comments
+    ADD COLUMN parent_comment_id UUID REFERENCES issue_comments(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_issue_comments_parent_comment_id ON issue_comments(parent_comment_id);
@@ -0,0 +1,3 @@
+-- Invitations expire two weeks after they are created unless a caller sets expires_at
+ALTER TABLE organization_invitations
+    ALTER COLUMN expires_at SET DEFAULT NOW() + INTERVAL '14 days';
@@ -0,0 +1,4 @@
+-- Comments are soft-deleted and kept as tombstones; most reads only want live rows
+CREATE INDEX idx_issue_comments_issue_id_live
+    ON issue_comments (issue_id, created_at)
+    WHERE deleted_at IS NULL;
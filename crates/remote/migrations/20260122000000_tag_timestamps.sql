@@ -0,0 +1,4 @@
+-- Audit timestamps for tags, backfilled with the migration time for existing rows
+ALTER TABLE tags
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,6 @@
+-- Per-project notification mode: 'watch' opts into everything, 'mute' silences
+-- everything except direct @mentions
+CREATE TYPE notification_mode AS ENUM ('default', 'watch', 'mute');
+
+ALTER TABLE project_notification_preferences
+    ADD COLUMN mode notification_mode NOT NULL DEFAULT 'default';
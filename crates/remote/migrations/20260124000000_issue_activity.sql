@@ -0,0 +1,18 @@
+-- Per-field history of issue edits, streamed to clients to render an issue's timeline
+CREATE TABLE issue_activity (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+
+    field TEXT NOT NULL,
+    old_value JSONB,
+    new_value JSONB,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_activity_project ON issue_activity (project_id);
+CREATE INDEX idx_issue_activity_issue_created ON issue_activity (issue_id, created_at);
+
+SELECT electric_sync_table('public', 'issue_activity');
@@ -0,0 +1,17 @@
+-- Teammate reviews of a workspace pushed to the remote: approve, request changes, or comment
+CREATE TYPE workspace_review_state AS ENUM ('approved', 'changes_requested', 'commented');
+
+CREATE TABLE workspace_reviews (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id UUID NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    reviewer_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    state workspace_review_state NOT NULL,
+    body TEXT NOT NULL DEFAULT '',
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_workspace_reviews_workspace_created ON workspace_reviews (workspace_id, created_at);
+
+ALTER TYPE notification_type ADD VALUE 'workspace_review_submitted';
@@ -0,0 +1,15 @@
+-- Editable profile fields shown for comment authors, assignees and mentions
+ALTER TABLE users
+    ADD COLUMN display_name TEXT,
+    ADD COLUMN avatar_url   TEXT;
+
+-- Seed existing users from their most recently updated OAuth account
+UPDATE users u
+SET display_name = oa.display_name,
+    avatar_url   = oa.avatar_url
+FROM (
+    SELECT DISTINCT ON (user_id) user_id, display_name, avatar_url
+    FROM oauth_accounts
+    ORDER BY user_id, updated_at DESC
+) oa
+WHERE oa.user_id = u.id;
@@ -27,7 +27,7 @@ impl Server {
         fields(listen_addr = %config.listen_addr)
     )]
     pub async fn run(config: RemoteServerConfig) -> anyhow::Result<()> {
-        let pool = db::create_pool(&config.database_url)
+        let pool = db::create_pool(&config.database_url, &config.database)
             .await
             .context("failed to create postgres pool")?;
 
@@ -41,6 +41,8 @@ impl Server {
                 .context("failed to set electric role password")?;
         }
 
+        db::invitations::spawn_expired_invitation_cleanup(pool.clone());
+
         let auth_config = config.auth.clone();
         let jwt = Arc::new(JwtService::new(auth_config.jwt_secret().clone()));
 
@@ -1,24 +1,34 @@
-use std::{env, fs, path::Path};
+use std::{collections::HashSet, env, fs, path::Path};
 
 use remote::{
     db::{
+        issue_activity::IssueActivity,
         issue_assignees::IssueAssignee,
         issue_comment_reactions::IssueCommentReaction,
-        issue_comments::IssueComment,
+        issue_comments::{IssueComment, IssueCommentEntry},
         issue_followers::IssueFollower,
         issue_relationships::IssueRelationship,
         issue_tags::IssueTag,
-        issues::Issue,
+        issues::{Issue, IssueDetail},
         notifications::{Notification, NotificationType},
+        oauth_accounts::{LinkedOAuthAccount, ListOAuthAccountsResponse},
         organization_members::{MemberRole, OrganizationMember},
+        project_notification_preferences::{NotificationMode, ProjectNotificationPreference},
         project_statuses::ProjectStatus,
+        project_transfer::{
+            ExportedAssignee, ExportedComment, ExportedIssue, ExportedIssueTag, ExportedProject,
+            ExportedRelationship, ExportedStatus, ExportedTag, ExportedUser, ProjectExport,
+        },
         projects::Project,
         pull_requests::PullRequest,
         tags::Tag,
         types::{IssuePriority, IssueRelationshipType, PullRequestStatus},
-        users::User,
-        users::UserData,
-        workspaces::Workspace,
+        users::{MemberProfile, MemberProfilesResponse, UpdateUserProfileRequest, User, UserData},
+        workspace_reviews::{
+            AggregateReviewState, CreateWorkspaceReviewRequest, ListWorkspaceReviewsResponse,
+            WorkspaceReview, WorkspaceReviewState,
+        },
+        workspaces::{Workspace, WorkspaceWithReviewState},
     },
     // Import from new unified entities module
     entities::{
@@ -31,6 +41,7 @@ use remote::{
         UpdateNotificationRequest, UpdateProjectRequest, UpdateProjectStatusRequest,
         UpdateTagRequest, all_entities, all_shapes,
     },
+    routes::{ErrorBody, ErrorCode},
 };
 use ts_rs::TS;
 
@@ -84,7 +95,15 @@ fn export_shapes() -> String {
         Project::decl(),
         Notification::decl(),
         NotificationType::decl(),
+        NotificationMode::decl(),
+        ProjectNotificationPreference::decl(),
         Workspace::decl(),
+        WorkspaceWithReviewState::decl(),
+        WorkspaceReview::decl(),
+        WorkspaceReviewState::decl(),
+        AggregateReviewState::decl(),
+        CreateWorkspaceReviewRequest::decl(),
+        ListWorkspaceReviewsResponse::decl(),
         ProjectStatus::decl(),
         Tag::decl(),
         Issue::decl(),
@@ -94,14 +113,32 @@ fn export_shapes() -> String {
         IssueRelationship::decl(),
         IssueRelationshipType::decl(),
         IssueComment::decl(),
+        IssueCommentEntry::decl(),
         IssueCommentReaction::decl(),
+        IssueDetail::decl(),
+        IssueActivity::decl(),
         IssuePriority::decl(),
         PullRequestStatus::decl(),
         PullRequest::decl(),
         UserData::decl(),
         User::decl(),
+        UpdateUserProfileRequest::decl(),
+        MemberProfile::decl(),
+        MemberProfilesResponse::decl(),
+        LinkedOAuthAccount::decl(),
+        ListOAuthAccountsResponse::decl(),
         MemberRole::decl(),
         OrganizationMember::decl(),
+        ProjectExport::decl(),
+        ExportedProject::decl(),
+        ExportedStatus::decl(),
+        ExportedTag::decl(),
+        ExportedUser::decl(),
+        ExportedIssue::decl(),
+        ExportedIssueTag::decl(),
+        ExportedAssignee::decl(),
+        ExportedComment::decl(),
+        ExportedRelationship::decl(),
         // Mutation request types
         CreateProjectRequest::decl(),
         UpdateProjectRequest::decl(),
@@ -125,6 +162,8 @@ fn export_shapes() -> String {
         UpdateIssueCommentRequest::decl(),
         CreateIssueCommentReactionRequest::decl(),
         UpdateIssueCommentReactionRequest::decl(),
+        ErrorCode::decl(),
+        ErrorBody::decl(),
     ];
 
     for decl in type_decls {
@@ -161,8 +200,16 @@ fn export_shapes() -> String {
 
     // Generate individual shape definitions
     output.push_str("// Individual shape definitions with embedded types\n");
+    let mut exported_names = HashSet::new();
     for shape in &shapes {
-        let const_name = shape.table().to_uppercase();
+        // Shapes are named after their table; extra shapes over an already exported table
+        // fall back to their Rust constant name.
+        let const_name = format!("{}_SHAPE", shape.table().to_uppercase());
+        let const_name = if exported_names.insert(const_name.clone()) {
+            const_name
+        } else {
+            shape.name().to_string()
+        };
         let params_str = shape
             .params()
             .iter()
@@ -171,7 +218,7 @@ fn export_shapes() -> String {
             .join(", ");
 
         output.push_str(&format!(
-            "export const {}_SHAPE = defineShape<{}>(\n  '{}',\n  [{}] as const,\n  '/v1{}'\n);\n\n",
+            "export const {} = defineShape<{}>(\n  '{}',\n  [{}] as const,\n  '/v1{}'\n);\n\n",
             const_name,
             shape.ts_type_name(),
             shape.table(),
@@ -7,6 +7,7 @@ use thiserror::Error;
 #[derive(Debug, Clone)]
 pub struct RemoteServerConfig {
     pub database_url: String,
+    pub database: DatabaseConfig,
     pub listen_addr: String,
     pub server_public_base_url: Option<String>,
     pub auth: AuthConfig,
@@ -18,6 +19,52 @@ pub struct RemoteServerConfig {
     pub github_app: Option<GitHubAppConfig>,
 }
 
+/// Postgres connection pool sizing.
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub max_connections: u32,
+    /// Connections kept open while idle so requests after a quiet period skip connection setup
+    pub min_connections: u32,
+}
+
+impl DatabaseConfig {
+    const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+    const DEFAULT_MIN_CONNECTIONS: u32 = 2;
+
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let max_connections = parse_u32_var(
+            "SERVER_DATABASE_MAX_CONNECTIONS",
+            Self::DEFAULT_MAX_CONNECTIONS,
+        )?;
+        let min_connections = parse_u32_var(
+            "SERVER_DATABASE_MIN_CONNECTIONS",
+            Self::DEFAULT_MIN_CONNECTIONS.min(max_connections),
+        )?;
+
+        if max_connections == 0 {
+            return Err(ConfigError::InvalidVar("SERVER_DATABASE_MAX_CONNECTIONS"));
+        }
+        if min_connections > max_connections {
+            return Err(ConfigError::InvalidVar("SERVER_DATABASE_MIN_CONNECTIONS"));
+        }
+
+        Ok(Self {
+            max_connections,
+            min_connections,
+        })
+    }
+}
+
+fn parse_u32_var(name: &'static str, default: u32) -> Result<u32, ConfigError> {
+    match env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| ConfigError::InvalidVar(name)),
+        Err(_) => Ok(default),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct R2Config {
     pub access_key_id: String,
@@ -130,6 +177,8 @@ impl RemoteServerConfig {
             .or_else(|_| env::var("DATABASE_URL"))
             .map_err(|_| ConfigError::MissingVar("SERVER_DATABASE_URL"))?;
 
+        let database = DatabaseConfig::from_env()?;
+
         let listen_addr =
             env::var("SERVER_LISTEN_ADDR").unwrap_or_else(|_| "0.0.0.0:8081".to_string());
 
@@ -156,6 +205,7 @@ impl RemoteServerConfig {
 
         Ok(Self {
             database_url,
+            database,
             listen_addr,
             server_public_base_url,
             auth,
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, PgPool, Postgres, Type};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "activity_entity_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEntityType {
+    Project,
+    Issue,
+    IssueComment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "activity_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityAction {
+    Created,
+    Updated,
+    StatusChanged,
+    Deleted,
+    Restored,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEvent {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub entity_type: ActivityEntityType,
+    pub entity_id: Uuid,
+    pub action: ActivityAction,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An activity event to record. The organization is derived from `project_id`.
+#[derive(Debug, Clone)]
+pub struct NewActivityEvent {
+    pub project_id: Uuid,
+    pub actor_id: Uuid,
+    pub entity_type: ActivityEntityType,
+    pub entity_id: Uuid,
+    pub action: ActivityAction,
+    pub payload: Value,
+}
+
+#[derive(Debug, Error)]
+pub enum ActivityEventError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct ActivityEventRepository;
+
+impl ActivityEventRepository {
+    /// Record an event. Call this inside the mutation's transaction so a failed insert
+    /// rolls the mutation back with it.
+    pub async fn record<'e, E>(executor: E, event: NewActivityEvent) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO activity_events (
+                organization_id, project_id, actor_id, entity_type, entity_id, action, payload
+            )
+            SELECT p.organization_id, p.id, $2, $3, $4, $5, $6
+            FROM projects p
+            WHERE p.id = $1
+            "#,
+            event.project_id,
+            event.actor_id,
+            event.entity_type as ActivityEntityType,
+            event.entity_id,
+            event.action as ActivityAction,
+            event.payload
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List an organization's events newest first. `before` is the ID of the last event
+    /// from the previous page.
+    pub async fn list_by_organization(
+        pool: &PgPool,
+        organization_id: Uuid,
+        since: Option<DateTime<Utc>>,
+        before: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<ActivityEvent>, ActivityEventError> {
+        let records = sqlx::query_as!(
+            ActivityEvent,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                project_id      AS "project_id?: Uuid",
+                actor_id        AS "actor_id?: Uuid",
+                entity_type     AS "entity_type!: ActivityEntityType",
+                entity_id       AS "entity_id!: Uuid",
+                action          AS "action!: ActivityAction",
+                payload         AS "payload!: Value",
+                created_at      AS "created_at!: DateTime<Utc>"
+            FROM activity_events
+            WHERE organization_id = $1
+              AND ($2::timestamptz IS NULL OR created_at >= $2)
+              AND (
+                  $3::uuid IS NULL
+                  OR (created_at, id) < (
+                      SELECT created_at, id FROM activity_events WHERE id = $3
+                  )
+              )
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            organization_id,
+            since,
+            before,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+}
@@ -1,4 +1,6 @@
-use chrono::{DateTime, Utc};
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 pub use utils::api::organizations::InvitationStatus;
@@ -11,6 +13,13 @@ use super::{
 };
 use crate::db::organization_members::is_member;
 
+/// How long a newly created or re-sent invitation stays valid.
+pub const INVITATION_TTL: Duration = Duration::days(14);
+/// Expired invitations are kept this long (so admins can still resend them) before
+/// the cleanup task deletes them.
+pub const EXPIRED_INVITATION_RETENTION: Duration = Duration::days(30);
+const INVITATION_CLEANUP_INTERVAL: StdDuration = StdDuration::from_secs(60 * 60);
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Invitation {
     pub id: Uuid,
@@ -25,6 +34,22 @@ pub struct Invitation {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Invitation {
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == InvitationStatus::Expired
+            || (self.status == InvitationStatus::Pending && self.expires_at < now)
+    }
+}
+
+/// Invitations whose expiry is older than this instant are removed by the cleanup task.
+fn expired_invitation_cutoff(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - EXPIRED_INVITATION_RETENTION
+}
+
 pub struct InvitationRepository<'a> {
     pool: &'a PgPool,
 }
@@ -189,6 +214,94 @@ impl<'a> InvitationRepository<'a> {
         Ok(())
     }
 
+    pub async fn find_by_id(&self, invitation_id: Uuid) -> Result<Invitation, IdentityError> {
+        sqlx::query_as!(
+            Invitation,
+            r#"
+            SELECT
+                id AS "id!",
+                organization_id AS "organization_id!: Uuid",
+                invited_by_user_id AS "invited_by_user_id?: Uuid",
+                email AS "email!",
+                role AS "role!: MemberRole",
+                status AS "status!: InvitationStatus",
+                token AS "token!",
+                expires_at AS "expires_at!",
+                created_at AS "created_at!",
+                updated_at AS "updated_at!"
+            FROM organization_invitations
+            WHERE id = $1
+            "#,
+            invitation_id
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(IdentityError::NotFound)
+    }
+
+    /// Issue a fresh token and expiry for a pending or expired invitation so it can be
+    /// sent again. Accepted and declined invitations cannot be resent. Callers are
+    /// responsible for checking that the requester is an admin of the organization.
+    pub async fn resend_invitation(
+        &self,
+        invitation_id: Uuid,
+        token: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Invitation, IdentityError> {
+        sqlx::query_as!(
+            Invitation,
+            r#"
+            UPDATE organization_invitations
+            SET token = $2, expires_at = $3, status = 'pending'
+            WHERE id = $1 AND status IN ('pending', 'expired')
+            RETURNING
+                id AS "id!",
+                organization_id AS "organization_id!: Uuid",
+                invited_by_user_id AS "invited_by_user_id?: Uuid",
+                email AS "email!",
+                role AS "role!: MemberRole",
+                status AS "status!: InvitationStatus",
+                token AS "token!",
+                expires_at AS "expires_at!",
+                created_at AS "created_at!",
+                updated_at AS "updated_at!"
+            "#,
+            invitation_id,
+            token,
+            expires_at
+        )
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| {
+            if let Some(db_err) = e.as_database_error()
+                && db_err.is_unique_violation()
+            {
+                return IdentityError::InvitationError(
+                    "A pending invitation already exists for this email".to_string(),
+                );
+            }
+            IdentityError::from(e)
+        })?
+        .ok_or_else(|| {
+            IdentityError::InvitationError("Invitation has already been used".to_string())
+        })
+    }
+
+    /// Delete invitations whose expiry passed before `cutoff`. Returns the number removed.
+    pub async fn delete_expired_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM organization_invitations
+            WHERE status IN ('pending', 'expired') AND expires_at < $1
+            "#,
+            cutoff
+        )
+        .execute(self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn accept_invitation(
         &self,
         token: &str,
@@ -211,7 +324,7 @@ impl<'a> InvitationRepository<'a> {
                 created_at AS "created_at!",
                 updated_at AS "updated_at!"
             FROM organization_invitations
-            WHERE token = $1 AND status = 'pending'
+            WHERE token = $1 AND status IN ('pending', 'expired')
             FOR UPDATE
             "#,
             token
@@ -222,6 +335,11 @@ impl<'a> InvitationRepository<'a> {
             IdentityError::InvitationError("Invitation not found or already used".to_string())
         })?;
 
+        if invitation.status == InvitationStatus::Expired {
+            tx.rollback().await?;
+            return Err(IdentityError::InvitationExpired);
+        }
+
         if OrganizationRepository::new(self.pool)
             .is_personal(invitation.organization_id)
             .await?
@@ -245,9 +363,7 @@ impl<'a> InvitationRepository<'a> {
             .await?;
 
             tx.commit().await?;
-            return Err(IdentityError::InvitationError(
-                "Invitation has expired".to_string(),
-            ));
+            return Err(IdentityError::InvitationExpired);
         }
 
         if is_member(&mut *tx, invitation.organization_id, user_id).await? {
@@ -285,3 +401,132 @@ impl<'a> InvitationRepository<'a> {
         Ok((organization, invitation.role))
     }
 }
+
+/// Periodically delete invitations that expired more than [`EXPIRED_INVITATION_RETENTION`] ago.
+pub fn spawn_expired_invitation_cleanup(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(INVITATION_CLEANUP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let cutoff = expired_invitation_cutoff(Utc::now());
+            match InvitationRepository::new(&pool)
+                .delete_expired_before(cutoff)
+                .await
+            {
+                Ok(0) => {}
+                Ok(deleted) => tracing::info!(deleted, "deleted expired invitations"),
+                Err(error) => tracing::error!(?error, "failed to delete expired invitations"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use uuid::Uuid;
+
+    use super::{
+        EXPIRED_INVITATION_RETENTION, Invitation, InvitationRepository, InvitationStatus,
+        MemberRole, expired_invitation_cutoff,
+    };
+
+    fn invitation(status: InvitationStatus, expires_at: DateTime<Utc>) -> Invitation {
+        Invitation {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            invited_by_user_id: None,
+            email: "invitee@example.com".to_string(),
+            role: MemberRole::Member,
+            status,
+            token: "token".to_string(),
+            expires_at,
+            created_at: expires_at,
+            updated_at: expires_at,
+        }
+    }
+
+    #[test]
+    fn pending_invitation_expires_once_its_deadline_passes() {
+        let now = Utc::now();
+        let pending = invitation(InvitationStatus::Pending, now + Duration::seconds(1));
+        assert!(!pending.is_expired_at(now));
+        assert!(pending.is_expired_at(now + Duration::seconds(2)));
+    }
+
+    #[test]
+    fn expired_status_is_always_expired() {
+        let now = Utc::now();
+        let expired = invitation(InvitationStatus::Expired, now + Duration::days(1));
+        assert!(expired.is_expired_at(now));
+    }
+
+    #[test]
+    fn answered_invitations_never_report_expired() {
+        let now = Utc::now();
+        let past = now - Duration::days(1);
+        assert!(!invitation(InvitationStatus::Accepted, past).is_expired_at(now));
+        assert!(!invitation(InvitationStatus::Declined, past).is_expired_at(now));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL pointing at the remote Postgres database"]
+    async fn cleanup_keeps_invitations_within_the_retention_window() {
+        let pool = crate::db::test_pool().await;
+        let organization_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (id, name, slug) VALUES ($1, 'Cleanup', $2)")
+            .bind(organization_id)
+            .bind(format!("cleanup-{organization_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+        let recently_expired = Uuid::new_v4();
+        let long_expired = Uuid::new_v4();
+        for (id, email, expires_at) in [
+            (
+                recently_expired,
+                "recent@example.com",
+                now - EXPIRED_INVITATION_RETENTION + Duration::hours(1),
+            ),
+            (
+                long_expired,
+                "old@example.com",
+                now - EXPIRED_INVITATION_RETENTION - Duration::hours(1),
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO organization_invitations (id, organization_id, email, token, expires_at) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(id)
+            .bind(organization_id)
+            .bind(email)
+            .bind(id.to_string())
+            .bind(expires_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        InvitationRepository::new(&pool)
+            .delete_expired_before(expired_invitation_cutoff(now))
+            .await
+            .unwrap();
+
+        let remaining: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM organization_invitations WHERE organization_id = $1",
+        )
+        .bind(organization_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, vec![recently_expired]);
+
+        sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(organization_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
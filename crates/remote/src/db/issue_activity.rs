@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, Postgres};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::issues::Issue;
+
+/// One field of an issue changing value, e.g. `priority` going from `"high"` to `"urgent"`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueActivity {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub project_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub field: String,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A field change to record; values are the JSON form of the field (`None` for NULL).
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueFieldChange {
+    pub field: &'static str,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+impl IssueFieldChange {
+    fn new<T: Serialize>(field: &'static str, old: &T, new: &T) -> Self {
+        let to_json = |v: &T| match serde_json::to_value(v) {
+            Ok(Value::Null) | Err(_) => None,
+            Ok(value) => Some(value),
+        };
+        Self {
+            field,
+            old_value: to_json(old),
+            new_value: to_json(new),
+        }
+    }
+}
+
+/// Fields that differ between two snapshots of the same issue.
+///
+/// `sort_order` is left out: reordering within a column is not part of an issue's history.
+pub fn diff_issues(before: &Issue, after: &Issue) -> Vec<IssueFieldChange> {
+    let mut changes = Vec::new();
+    macro_rules! compare {
+        ($($field:ident),* $(,)?) => {
+            $(
+                if before.$field != after.$field {
+                    changes.push(IssueFieldChange::new(
+                        stringify!($field),
+                        &before.$field,
+                        &after.$field,
+                    ));
+                }
+            )*
+        };
+    }
+    compare!(
+        status_id,
+        title,
+        description,
+        priority,
+        start_date,
+        target_date,
+        completed_at,
+        parent_issue_id,
+        extension_metadata,
+    );
+    changes
+}
+
+pub struct IssueActivityRepository;
+
+impl IssueActivityRepository {
+    /// Record field changes for an issue. Call this inside the mutation's transaction so the
+    /// history can't drift from the issue row. Does nothing when `changes` is empty.
+    pub async fn record<'e, E>(
+        executor: E,
+        issue: &Issue,
+        actor_id: Uuid,
+        changes: &[IssueFieldChange],
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let fields: Vec<String> = changes.iter().map(|c| c.field.to_string()).collect();
+        let old_values: Vec<Option<Value>> = changes.iter().map(|c| c.old_value.clone()).collect();
+        let new_values: Vec<Option<Value>> = changes.iter().map(|c| c.new_value.clone()).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_activity (issue_id, project_id, actor_id, field, old_value, new_value)
+            SELECT $1, $2, $3, t.field, t.old_value, t.new_value
+            FROM UNNEST($4::text[], $5::jsonb[], $6::jsonb[]) AS t(field, old_value, new_value)
+            "#,
+            issue.id,
+            issue.project_id,
+            actor_id,
+            &fields,
+            &old_values,
+            &new_values
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::types::IssuePriority;
+
+    fn issue() -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            issue_number: 7,
+            simple_id: "VK-7".to_string(),
+            status_id: Uuid::new_v4(),
+            title: "Fix login".to_string(),
+            description: None,
+            priority: IssuePriority::High,
+            start_date: None,
+            target_date: None,
+            completed_at: None,
+            sort_order: 1024.0,
+            parent_issue_id: None,
+            extension_metadata: json!({}),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn unchanged_issue_has_no_changes() {
+        let before = issue();
+        let mut after = before.clone();
+        after.updated_at = Utc::now();
+        assert!(diff_issues(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn sort_order_only_change_is_ignored() {
+        let before = issue();
+        let mut after = before.clone();
+        after.sort_order = 2048.0;
+        assert!(diff_issues(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn changed_fields_are_snapshotted() {
+        let before = issue();
+        let mut after = before.clone();
+        after.priority = IssuePriority::Urgent;
+        after.description = Some("Steps to reproduce".to_string());
+
+        let changes = diff_issues(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                IssueFieldChange {
+                    field: "description",
+                    old_value: None,
+                    new_value: Some(json!("Steps to reproduce")),
+                },
+                IssueFieldChange {
+                    field: "priority",
+                    old_value: Some(json!("high")),
+                    new_value: Some(json!("urgent")),
+                },
+            ]
+        );
+    }
+}
@@ -18,12 +18,36 @@ pub struct IssueCommentReaction {
     pub created_at: DateTime<Utc>,
 }
 
+/// Result of toggling a reaction: the row that was inserted, or the one that was removed.
+#[derive(Debug, Clone)]
+pub struct ToggledReaction {
+    pub added: bool,
+    pub reaction: IssueCommentReaction,
+}
+
 #[derive(Debug, Error)]
 pub enum IssueCommentReactionError {
+    #[error("you already reacted with this emoji")]
+    Duplicate,
     #[error(transparent)]
     Database(#[from] sqlx::Error),
 }
 
+/// Unique constraint allowing one reaction per user, comment and emoji.
+const UNIQUE_REACTION_CONSTRAINT: &str = "issue_comment_reactions_comment_id_user_id_emoji_key";
+
+/// Convert a unique violation on the reaction constraint into
+/// [`IssueCommentReactionError::Duplicate`].
+fn map_duplicate(error: sqlx::Error) -> IssueCommentReactionError {
+    if let Some(db_error) = error.as_database_error()
+        && db_error.is_unique_violation()
+        && db_error.constraint() == Some(UNIQUE_REACTION_CONSTRAINT)
+    {
+        return IssueCommentReactionError::Duplicate;
+    }
+    IssueCommentReactionError::Database(error)
+}
+
 pub struct IssueCommentReactionRepository;
 
 impl IssueCommentReactionRepository {
@@ -80,7 +104,8 @@ impl IssueCommentReactionRepository {
             created_at
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(map_duplicate)?;
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
@@ -113,7 +138,8 @@ impl IssueCommentReactionRepository {
             id
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(map_duplicate)?;
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
@@ -133,6 +159,82 @@ impl IssueCommentReactionRepository {
         Ok(DeleteResponse { txid })
     }
 
+    /// Remove the user's `emoji` reaction on the comment if present, otherwise add it.
+    pub async fn toggle(
+        pool: &PgPool,
+        comment_id: Uuid,
+        user_id: Uuid,
+        emoji: String,
+    ) -> Result<MutationResponse<ToggledReaction>, IssueCommentReactionError> {
+        let mut tx = pool.begin().await?;
+        let removed = sqlx::query_as!(
+            IssueCommentReaction,
+            r#"
+            DELETE FROM issue_comment_reactions
+            WHERE comment_id = $1 AND user_id = $2 AND emoji = $3
+            RETURNING
+                id          AS "id!: Uuid",
+                comment_id  AS "comment_id!: Uuid",
+                user_id     AS "user_id!: Uuid",
+                emoji       AS "emoji!",
+                created_at  AS "created_at!: DateTime<Utc>"
+            "#,
+            comment_id,
+            user_id,
+            emoji
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let data = match removed {
+            Some(reaction) => ToggledReaction {
+                added: false,
+                reaction,
+            },
+            None => {
+                // A concurrent toggle may have inserted the same reaction; treat that as added.
+                let reaction = sqlx::query_as!(
+                    IssueCommentReaction,
+                    r#"
+                    WITH inserted AS (
+                        INSERT INTO issue_comment_reactions (id, comment_id, user_id, emoji, created_at)
+                        VALUES ($1, $2, $3, $4, $5)
+                        ON CONFLICT (comment_id, user_id, emoji) DO NOTHING
+                        RETURNING id, comment_id, user_id, emoji, created_at
+                    )
+                    SELECT
+                        id          AS "id!: Uuid",
+                        comment_id  AS "comment_id!: Uuid",
+                        user_id     AS "user_id!: Uuid",
+                        emoji       AS "emoji!",
+                        created_at  AS "created_at!: DateTime<Utc>"
+                    FROM inserted
+                    UNION ALL
+                    SELECT id, comment_id, user_id, emoji, created_at
+                    FROM issue_comment_reactions
+                    WHERE comment_id = $2 AND user_id = $3 AND emoji = $4
+                      AND NOT EXISTS (SELECT 1 FROM inserted)
+                    "#,
+                    Uuid::new_v4(),
+                    comment_id,
+                    user_id,
+                    emoji,
+                    Utc::now()
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                ToggledReaction {
+                    added: true,
+                    reaction,
+                }
+            }
+        };
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
     pub async fn list_by_comment(
         pool: &PgPool,
         comment_id: Uuid,
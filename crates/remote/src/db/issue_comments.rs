@@ -1,11 +1,19 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use serde_json::json;
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::get_txid;
+use super::{
+    Tx,
+    activity_events::{
+        ActivityAction, ActivityEntityType, ActivityEventRepository, NewActivityEvent,
+    },
+    get_txid,
+    soft_delete::{self, SoftDelete},
+};
 use crate::mutation_types::{DeleteResponse, MutationResponse};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -14,17 +22,50 @@ pub struct IssueComment {
     pub id: Uuid,
     pub issue_id: Uuid,
     pub author_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
     pub message: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Message shown in place of a soft-deleted comment's body.
+pub const TOMBSTONE_MESSAGE: &str = "[deleted]";
+
+/// A comment as rendered in a thread. Soft-deleted comments come back as tombstones
+/// (blanked message, `deleted_at` set) so their replies still have a parent to hang off.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueCommentEntry {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub comment: IssueComment,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl IssueCommentEntry {
+    fn new(mut comment: IssueComment, deleted_at: Option<DateTime<Utc>>) -> Self {
+        if deleted_at.is_some() {
+            comment.message = TOMBSTONE_MESSAGE.to_string();
+        }
+        Self {
+            comment,
+            deleted_at,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum IssueCommentError {
+    #[error("parent comment must belong to the same issue")]
+    InvalidParent,
     #[error(transparent)]
     Database(#[from] sqlx::Error),
 }
 
+impl SoftDelete for IssueComment {
+    const TABLE: &'static str = "issue_comments";
+}
+
 pub struct IssueCommentRepository;
 
 impl IssueCommentRepository {
@@ -39,11 +80,12 @@ impl IssueCommentRepository {
                 id          AS "id!: Uuid",
                 issue_id    AS "issue_id!: Uuid",
                 author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
                 message     AS "message!",
                 created_at  AS "created_at!: DateTime<Utc>",
                 updated_at  AS "updated_at!: DateTime<Utc>"
             FROM issue_comments
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -53,25 +95,70 @@ impl IssueCommentRepository {
         Ok(record)
     }
 
+    /// Like [`Self::find_by_id`], but also returns soft-deleted comments.
+    pub async fn find_by_id_including_deleted<'e, E>(
+        executor: E,
+        id: Uuid,
+    ) -> Result<Option<IssueComment>, IssueCommentError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            IssueComment,
+            r#"
+            SELECT
+                id          AS "id!: Uuid",
+                issue_id    AS "issue_id!: Uuid",
+                author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
+                message     AS "message!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
+            FROM issue_comments
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
     pub async fn create(
         pool: &PgPool,
         id: Option<Uuid>,
         issue_id: Uuid,
         author_id: Uuid,
+        parent_comment_id: Option<Uuid>,
         message: String,
     ) -> Result<MutationResponse<IssueComment>, IssueCommentError> {
         let id = id.unwrap_or_else(Uuid::new_v4);
         let now = Utc::now();
         let mut tx = pool.begin().await?;
+        if let Some(parent_comment_id) = parent_comment_id {
+            let parent_issue_id = sqlx::query_scalar!(
+                r#"
+                SELECT issue_id AS "issue_id!: Uuid"
+                FROM issue_comments
+                WHERE id = $1 AND deleted_at IS NULL
+                "#,
+                parent_comment_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            ensure_same_issue(parent_issue_id, issue_id)?;
+        }
         let data = sqlx::query_as!(
             IssueComment,
             r#"
-            INSERT INTO issue_comments (id, issue_id, author_id, message, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO issue_comments (id, issue_id, author_id, parent_comment_id, message, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING
                 id          AS "id!: Uuid",
                 issue_id    AS "issue_id!: Uuid",
                 author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
                 message     AS "message!",
                 created_at  AS "created_at!: DateTime<Utc>",
                 updated_at  AS "updated_at!: DateTime<Utc>"
@@ -79,12 +166,14 @@ impl IssueCommentRepository {
             id,
             issue_id,
             author_id,
+            parent_comment_id,
             message,
             now,
             now
         )
         .fetch_one(&mut *tx)
         .await?;
+        record_activity(&mut tx, author_id, &data, ActivityAction::Created).await?;
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
@@ -95,6 +184,7 @@ impl IssueCommentRepository {
     /// when None is provided.
     pub async fn update(
         pool: &PgPool,
+        actor_id: Uuid,
         id: Uuid,
         message: Option<String>,
     ) -> Result<MutationResponse<IssueComment>, IssueCommentError> {
@@ -112,6 +202,7 @@ impl IssueCommentRepository {
                 id          AS "id!: Uuid",
                 issue_id    AS "issue_id!: Uuid",
                 author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
                 message     AS "message!",
                 created_at  AS "created_at!: DateTime<Utc>",
                 updated_at  AS "updated_at!: DateTime<Utc>"
@@ -122,22 +213,86 @@ impl IssueCommentRepository {
         )
         .fetch_one(&mut *tx)
         .await?;
+        record_activity(&mut tx, actor_id, &data, ActivityAction::Updated).await?;
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueCommentError> {
+    pub async fn delete(
+        pool: &PgPool,
+        actor_id: Uuid,
+        id: Uuid,
+    ) -> Result<DeleteResponse, IssueCommentError> {
         let mut tx = pool.begin().await?;
-        sqlx::query!("DELETE FROM issue_comments WHERE id = $1", id)
-            .execute(&mut *tx)
-            .await?;
+        if soft_delete::soft_delete::<IssueComment, _>(&mut *tx, id).await?
+            && let Some(data) = Self::find_by_id_including_deleted(&mut *tx, id).await?
+        {
+            record_activity(&mut tx, actor_id, &data, ActivityAction::Deleted).await?;
+        }
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
         Ok(DeleteResponse { txid })
     }
 
+    /// Undo a soft delete. Returns `None` if the comment does not exist or is not deleted.
+    pub async fn restore(
+        pool: &PgPool,
+        actor_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<MutationResponse<IssueComment>>, IssueCommentError> {
+        let mut tx = pool.begin().await?;
+        if !soft_delete::restore::<IssueComment, _>(&mut *tx, id).await? {
+            return Ok(None);
+        }
+        let Some(data) = Self::find_by_id_including_deleted(&mut *tx, id).await? else {
+            return Ok(None);
+        };
+        record_activity(&mut tx, actor_id, &data, ActivityAction::Restored).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(Some(MutationResponse { data, txid }))
+    }
+
+    /// Case-insensitive substring search over an issue's live comments, oldest first.
+    pub async fn search(
+        pool: &PgPool,
+        issue_id: Uuid,
+        query: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<IssueComment>, IssueCommentError> {
+        let pattern = format!("%{}%", escape_like(query));
+        let records = sqlx::query_as!(
+            IssueComment,
+            r#"
+            SELECT
+                id          AS "id!: Uuid",
+                issue_id    AS "issue_id!: Uuid",
+                author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
+                message     AS "message!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
+            FROM issue_comments
+            WHERE issue_id = $1 AND deleted_at IS NULL AND message ILIKE $2 ESCAPE '\'
+            ORDER BY created_at ASC, id ASC
+            LIMIT $3 OFFSET $4
+            "#,
+            issue_id,
+            pattern,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// All live comments on an issue as a flat list, oldest first. Replies carry
+    /// `parent_comment_id` so clients can assemble threads.
     pub async fn list_by_issue(
         pool: &PgPool,
         issue_id: Uuid,
@@ -149,11 +304,13 @@ impl IssueCommentRepository {
                 id          AS "id!: Uuid",
                 issue_id    AS "issue_id!: Uuid",
                 author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
                 message     AS "message!",
                 created_at  AS "created_at!: DateTime<Utc>",
                 updated_at  AS "updated_at!: DateTime<Utc>"
             FROM issue_comments
-            WHERE issue_id = $1
+            WHERE issue_id = $1 AND deleted_at IS NULL
+            ORDER BY created_at ASC, id ASC
             "#,
             issue_id
         )
@@ -162,4 +319,173 @@ impl IssueCommentRepository {
 
         Ok(records)
     }
+
+    /// Like [`Self::list_by_issue`], but soft-deleted comments are included as tombstones.
+    pub async fn list_by_issue_with_tombstones(
+        pool: &PgPool,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueCommentEntry>, IssueCommentError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id          AS "id!: Uuid",
+                issue_id    AS "issue_id!: Uuid",
+                author_id   AS "author_id!: Uuid",
+                parent_comment_id AS "parent_comment_id?: Uuid",
+                message     AS "message!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>",
+                deleted_at  AS "deleted_at?: DateTime<Utc>"
+            FROM issue_comments
+            WHERE issue_id = $1
+            ORDER BY created_at ASC, id ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                IssueCommentEntry::new(
+                    IssueComment {
+                        id: row.id,
+                        issue_id: row.issue_id,
+                        author_id: row.author_id,
+                        parent_comment_id: row.parent_comment_id,
+                        message: row.message,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                    },
+                    row.deleted_at,
+                )
+            })
+            .collect())
+    }
+}
+
+/// A reply's parent must be a live comment on the same issue.
+fn ensure_same_issue(
+    parent_issue_id: Option<Uuid>,
+    issue_id: Uuid,
+) -> Result<(), IssueCommentError> {
+    match parent_issue_id {
+        Some(parent_issue_id) if parent_issue_id == issue_id => Ok(()),
+        _ => Err(IssueCommentError::InvalidParent),
+    }
+}
+
+/// Escape `ILIKE` wildcards so user input only matches literally.
+fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for ch in input.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+async fn record_activity(
+    tx: &mut Tx<'_>,
+    actor_id: Uuid,
+    comment: &IssueComment,
+    action: ActivityAction,
+) -> Result<(), sqlx::Error> {
+    let project_id = sqlx::query_scalar!(
+        r#"SELECT project_id AS "project_id!: Uuid" FROM issues WHERE id = $1"#,
+        comment.issue_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    ActivityEventRepository::record(
+        &mut **tx,
+        NewActivityEvent {
+            project_id,
+            actor_id,
+            entity_type: ActivityEntityType::IssueComment,
+            entity_id: comment.id,
+            action,
+            payload: json!({ "issue_id": comment.issue_id }),
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{
+        IssueComment, IssueCommentEntry, IssueCommentError, TOMBSTONE_MESSAGE, ensure_same_issue,
+        escape_like,
+    };
+
+    fn comment(message: &str) -> IssueComment {
+        let now = Utc::now();
+        IssueComment {
+            id: Uuid::new_v4(),
+            issue_id: Uuid::new_v4(),
+            author_id: Uuid::new_v4(),
+            parent_comment_id: None,
+            message: message.to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn escape_like_escapes_wildcards() {
+        assert_eq!(escape_like("50% off_now"), "50\\% off\\_now");
+        assert_eq!(escape_like("a\\b"), "a\\\\b");
+        assert_eq!(escape_like("plain"), "plain");
+    }
+
+    #[test]
+    fn reply_to_comment_on_same_issue_is_allowed() {
+        let issue_id = Uuid::new_v4();
+        assert!(ensure_same_issue(Some(issue_id), issue_id).is_ok());
+    }
+
+    #[test]
+    fn reply_to_comment_on_other_issue_is_rejected() {
+        assert!(matches!(
+            ensure_same_issue(Some(Uuid::new_v4()), Uuid::new_v4()),
+            Err(IssueCommentError::InvalidParent)
+        ));
+    }
+
+    #[test]
+    fn reply_to_missing_comment_is_rejected() {
+        assert!(matches!(
+            ensure_same_issue(None, Uuid::new_v4()),
+            Err(IssueCommentError::InvalidParent)
+        ));
+    }
+
+    #[test]
+    fn deleted_comment_is_rendered_as_tombstone() {
+        let original = comment("secret");
+        let deleted_at = Utc::now();
+        let entry = IssueCommentEntry::new(original.clone(), Some(deleted_at));
+
+        assert_eq!(entry.comment.message, TOMBSTONE_MESSAGE);
+        assert_eq!(entry.comment.id, original.id);
+        assert_eq!(entry.deleted_at, Some(deleted_at));
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["id"], original.id.to_string());
+        assert_eq!(json["message"], TOMBSTONE_MESSAGE);
+    }
+
+    #[test]
+    fn live_comment_keeps_its_message() {
+        let entry = IssueCommentEntry::new(comment("hello"), None);
+        assert_eq!(entry.comment.message, "hello");
+        assert!(entry.deleted_at.is_none());
+    }
 }
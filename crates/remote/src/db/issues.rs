@@ -1,20 +1,59 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use sqlx::PgPool;
+use serde_json::{Value, json};
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
 use super::{
+    activity_events::{
+        ActivityAction, ActivityEntityType, ActivityEventRepository, NewActivityEvent,
+    },
     get_txid,
+    issue_activity::{IssueActivityRepository, IssueFieldChange, diff_issues},
+    issue_assignees::IssueAssignee,
+    issue_comments::IssueComment,
+    issue_followers::IssueFollower,
+    issue_relationships::IssueRelationship,
+    issue_tags::IssueTag,
     project_statuses::ProjectStatusRepository,
     pull_requests::PullRequestRepository,
+    soft_delete::{self, SoftDelete},
     types::{IssuePriority, PullRequestStatus},
     workspaces::WorkspaceRepository,
 };
 use crate::mutation_types::{DeleteResponse, MutationResponse};
 
+/// Gap between neighbouring issues when appending to a status or rebalancing it.
+const SORT_ORDER_STEP: f64 = 1024.0;
+
+/// Page size used by [`IssueRepository::list_by_status`] when the caller doesn't pick one.
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+/// Upper bound on [`IssueRepository::list_by_status`] page sizes.
+pub const MAX_PAGE_LIMIT: i64 = 200;
+
+/// Direction in which a status column is paged through by `sort_order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// One page of issues plus the total number of issues matching the query.
+#[derive(Debug, Clone)]
+pub struct IssuePage {
+    pub issues: Vec<Issue>,
+    pub total: i64,
+}
+
+/// Clamp a requested page size into `1..=MAX_PAGE_LIMIT`.
+pub fn clamp_page_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct Issue {
@@ -36,6 +75,17 @@ pub struct Issue {
     pub updated_at: DateTime<Utc>,
 }
 
+/// An issue together with everything the issue detail view needs.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct IssueDetail {
+    pub issue: Issue,
+    pub comments: Vec<IssueComment>,
+    pub assignees: Vec<IssueAssignee>,
+    pub tags: Vec<IssueTag>,
+    pub followers: Vec<IssueFollower>,
+    pub relationships: Vec<IssueRelationship>,
+}
+
 #[derive(Debug, Error)]
 pub enum IssueError {
     #[error(transparent)]
@@ -46,6 +96,14 @@ pub enum IssueError {
     ProjectStatus(#[from] super::project_statuses::ProjectStatusError),
     #[error("workspace error: {0}")]
     Workspace(#[from] super::workspaces::WorkspaceError),
+    #[error("status {0} does not belong to the issue's project")]
+    InvalidStatus(Uuid),
+    #[error("issue {0} is not in the target status")]
+    InvalidMoveTarget(Uuid),
+}
+
+impl SoftDelete for Issue {
+    const TABLE: &'static str = "issues";
 }
 
 pub struct IssueRepository;
@@ -73,7 +131,7 @@ impl IssueRepository {
                 created_at          AS "created_at!: DateTime<Utc>",
                 updated_at          AS "updated_at!: DateTime<Utc>"
             FROM issues
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -83,6 +141,45 @@ impl IssueRepository {
         Ok(record)
     }
 
+    /// Like [`Self::find_by_id`], but also returns soft-deleted issues.
+    pub async fn find_by_id_including_deleted<'e, E>(
+        executor: E,
+        id: Uuid,
+    ) -> Result<Option<Issue>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
     pub async fn organization_id(
         pool: &PgPool,
         issue_id: Uuid,
@@ -92,7 +189,7 @@ impl IssueRepository {
             SELECT p.organization_id
             FROM issues i
             INNER JOIN projects p ON p.id = i.project_id
-            WHERE i.id = $1
+            WHERE i.id = $1 AND i.deleted_at IS NULL AND p.deleted_at IS NULL
             "#,
             issue_id
         )
@@ -102,9 +199,11 @@ impl IssueRepository {
         Ok(record)
     }
 
+    /// List a project's issues, optionally restricted to a single status.
     pub async fn list_by_project(
         pool: &PgPool,
         project_id: Uuid,
+        status_id: Option<Uuid>,
     ) -> Result<Vec<Issue>, IssueError> {
         let records = sqlx::query_as!(
             Issue,
@@ -128,8 +227,127 @@ impl IssueRepository {
                 updated_at          AS "updated_at!: DateTime<Utc>"
             FROM issues
             WHERE project_id = $1
+              AND ($2::uuid IS NULL OR status_id = $2)
+              AND deleted_at IS NULL
+            ORDER BY sort_order ASC, id ASC
+            "#,
+            project_id,
+            status_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// One page of a status column ordered by `sort_order`. Ties are broken by `id` so that
+    /// pages never overlap or skip issues while the column is unchanged.
+    pub async fn list_by_status(
+        pool: &PgPool,
+        project_id: Uuid,
+        status_id: Uuid,
+        limit: i64,
+        offset: i64,
+        direction: SortDirection,
+    ) -> Result<IssuePage, IssueError> {
+        let descending = direction == SortDirection::Desc;
+        let issues = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE project_id = $1
+              AND status_id = $2
+              AND deleted_at IS NULL
+            ORDER BY
+                CASE WHEN $5 THEN sort_order END DESC,
+                CASE WHEN $5 THEN id END DESC,
+                sort_order ASC,
+                id ASC
+            LIMIT $3 OFFSET $4
+            "#,
+            project_id,
+            status_id,
+            limit,
+            offset,
+            descending
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM issues
+            WHERE project_id = $1
+              AND status_id = $2
+              AND deleted_at IS NULL
+            "#,
+            project_id,
+            status_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(IssuePage { issues, total })
+    }
+
+    /// Full-text search over a project's issue titles and descriptions, best match first.
+    pub async fn search(
+        pool: &PgPool,
+        project_id: Uuid,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<Issue>, IssueError> {
+        let records = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues,
+                plainto_tsquery('english', $2) AS query
+            WHERE project_id = $1
+              AND deleted_at IS NULL
+              AND to_tsvector('english', title || ' ' || COALESCE(description, '')) @@ query
+            ORDER BY
+                ts_rank(to_tsvector('english', title || ' ' || COALESCE(description, '')), query) DESC,
+                created_at DESC
+            LIMIT $3
             "#,
-            project_id
+            project_id,
+            query,
+            limit
         )
         .fetch_all(pool)
         .await?;
@@ -140,6 +358,7 @@ impl IssueRepository {
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &PgPool,
+        actor_id: Uuid,
         id: Option<Uuid>,
         project_id: Uuid,
         status_id: Uuid,
@@ -200,6 +419,19 @@ impl IssueRepository {
         .fetch_one(&mut *tx)
         .await?;
 
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: data.project_id,
+                actor_id,
+                entity_type: ActivityEntityType::Issue,
+                entity_id: data.id,
+                action: ActivityAction::Created,
+                payload: json!({ "simple_id": data.simple_id, "title": data.title }),
+            },
+        )
+        .await?;
+
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
@@ -216,6 +448,7 @@ impl IssueRepository {
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &PgPool,
+        actor_id: Uuid,
         id: Uuid,
         status_id: Option<Uuid>,
         title: Option<String>,
@@ -243,6 +476,35 @@ impl IssueRepository {
         let update_parent_issue_id = parent_issue_id.is_some();
         let parent_issue_id_value = parent_issue_id.flatten();
 
+        let previous = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
         let data = sqlx::query_as!(
             Issue,
             r#"
@@ -298,18 +560,67 @@ impl IssueRepository {
         .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(previous) = &previous {
+            let changes = diff_issues(previous, &data);
+            IssueActivityRepository::record(&mut *tx, &data, actor_id, &changes).await?;
+        }
+
+        let (action, payload) = match previous.map(|issue| issue.status_id) {
+            Some(from) if from != data.status_id => (
+                ActivityAction::StatusChanged,
+                json!({
+                    "simple_id": data.simple_id,
+                    "from_status_id": from,
+                    "to_status_id": data.status_id,
+                }),
+            ),
+            _ => (
+                ActivityAction::Updated,
+                json!({ "simple_id": data.simple_id, "title": data.title }),
+            ),
+        };
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: data.project_id,
+                actor_id,
+                entity_type: ActivityEntityType::Issue,
+                entity_id: data.id,
+                action,
+                payload,
+            },
+        )
+        .await?;
+
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
 
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueError> {
+    pub async fn delete(
+        pool: &PgPool,
+        actor_id: Uuid,
+        id: Uuid,
+    ) -> Result<DeleteResponse, IssueError> {
         let mut tx = pool.begin().await?;
 
-        sqlx::query!("DELETE FROM issues WHERE id = $1", id)
-            .execute(&mut *tx)
+        if soft_delete::soft_delete::<Issue, _>(&mut *tx, id).await?
+            && let Some(issue) = Self::find_by_id_including_deleted(&mut *tx, id).await?
+        {
+            ActivityEventRepository::record(
+                &mut *tx,
+                NewActivityEvent {
+                    project_id: issue.project_id,
+                    actor_id,
+                    entity_type: ActivityEntityType::Issue,
+                    entity_id: id,
+                    action: ActivityAction::Deleted,
+                    payload: json!({ "simple_id": issue.simple_id }),
+                },
+            )
             .await?;
+        }
 
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
@@ -317,11 +628,213 @@ impl IssueRepository {
         Ok(DeleteResponse { txid })
     }
 
+    /// Undo a soft delete. Returns `None` if the issue does not exist or is not deleted.
+    pub async fn restore(
+        pool: &PgPool,
+        actor_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<MutationResponse<Issue>>, IssueError> {
+        let mut tx = pool.begin().await?;
+
+        if !soft_delete::restore::<Issue, _>(&mut *tx, id).await? {
+            return Ok(None);
+        }
+        let Some(data) = Self::find_by_id_including_deleted(&mut *tx, id).await? else {
+            return Ok(None);
+        };
+
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: data.project_id,
+                actor_id,
+                entity_type: ActivityEntityType::Issue,
+                entity_id: id,
+                action: ActivityAction::Restored,
+                payload: json!({ "simple_id": data.simple_id }),
+            },
+        )
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(Some(MutationResponse { data, txid }))
+    }
+
+    /// Sort order that places a new issue at the bottom of `status_id`.
+    pub async fn next_sort_order<'e, E>(executor: E, status_id: Uuid) -> Result<f64, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let max = sqlx::query_scalar!(
+            r#"
+            SELECT MAX(sort_order) AS "max_sort_order?"
+            FROM issues
+            WHERE status_id = $1 AND deleted_at IS NULL
+            "#,
+            status_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(max.map_or(SORT_ORDER_STEP, |max| max + SORT_ORDER_STEP))
+    }
+
+    /// Move an issue into `status_id`, directly below `after_issue_id` (or to the top when
+    /// `None`). The new `sort_order` is the midpoint between its neighbours; if they have
+    /// converged so that no f64 fits between them, the whole status is rebalanced first.
+    ///
+    /// Returns `None` if the issue does not exist.
+    pub async fn move_to_status(
+        pool: &PgPool,
+        actor_id: Uuid,
+        issue_id: Uuid,
+        status_id: Uuid,
+        after_issue_id: Option<Uuid>,
+    ) -> Result<Option<MutationResponse<Issue>>, IssueError> {
+        let mut tx = pool.begin().await?;
+
+        let Some(current) = sqlx::query!(
+            r#"
+            SELECT project_id AS "project_id!: Uuid", status_id AS "status_id!: Uuid"
+            FROM issues
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            issue_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let status_project_id = sqlx::query_scalar!(
+            r#"SELECT project_id AS "project_id!: Uuid" FROM project_statuses WHERE id = $1"#,
+            status_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        if status_project_id != Some(current.project_id) {
+            return Err(IssueError::InvalidStatus(status_id));
+        }
+
+        let siblings: Vec<(Uuid, f64)> = sqlx::query!(
+            r#"
+            SELECT id AS "id!: Uuid", sort_order AS "sort_order!"
+            FROM issues
+            WHERE status_id = $1 AND id <> $2 AND deleted_at IS NULL
+            ORDER BY sort_order ASC, id ASC
+            FOR UPDATE
+            "#,
+            status_id,
+            issue_id
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|row| (row.id, row.sort_order))
+        .collect();
+
+        let plan = plan_sort_order(&siblings, after_issue_id)?;
+
+        if let Some(rebalanced) = &plan.rebalanced {
+            let (ids, sort_orders): (Vec<Uuid>, Vec<f64>) = rebalanced.iter().copied().unzip();
+            sqlx::query!(
+                r#"
+                UPDATE issues AS i
+                SET sort_order = t.sort_order, updated_at = NOW()
+                FROM UNNEST($1::uuid[], $2::float8[]) AS t(id, sort_order)
+                WHERE i.id = t.id
+                "#,
+                &ids,
+                &sort_orders
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let data = sqlx::query_as!(
+            Issue,
+            r#"
+            UPDATE issues
+            SET status_id = $1, sort_order = $2, updated_at = NOW()
+            WHERE id = $3
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority!: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                extension_metadata  AS "extension_metadata!: Value",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            status_id,
+            plan.sort_order,
+            issue_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if current.status_id != data.status_id {
+            let change = IssueFieldChange {
+                field: "status_id",
+                old_value: Some(json!(current.status_id)),
+                new_value: Some(json!(data.status_id)),
+            };
+            IssueActivityRepository::record(&mut *tx, &data, actor_id, &[change]).await?;
+        }
+
+        let (action, payload) = if current.status_id != data.status_id {
+            (
+                ActivityAction::StatusChanged,
+                json!({
+                    "simple_id": data.simple_id,
+                    "from_status_id": current.status_id,
+                    "to_status_id": data.status_id,
+                }),
+            )
+        } else {
+            (
+                ActivityAction::Updated,
+                json!({ "simple_id": data.simple_id, "title": data.title }),
+            )
+        };
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: data.project_id,
+                actor_id,
+                entity_type: ActivityEntityType::Issue,
+                entity_id: data.id,
+                action,
+                payload,
+            },
+        )
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(Some(MutationResponse { data, txid }))
+    }
+
     /// Syncs issue status based on the current PR state.
     /// - If PR is open → move issue to "In review" (no need to fetch other PRs)
     /// - If PR is merged/closed → check if ALL PRs are merged → move to "Done"
     pub async fn sync_status_from_pull_request(
         pool: &PgPool,
+        actor_id: Uuid,
         issue_id: Uuid,
         pr_status: PullRequestStatus,
     ) -> Result<(), IssueError> {
@@ -354,6 +867,7 @@ impl IssueRepository {
 
         Self::update(
             pool,
+            actor_id,
             issue_id,
             Some(target_status.id),
             None,
@@ -376,6 +890,7 @@ impl IssueRepository {
     /// moves the issue to "In progress".
     pub async fn sync_status_from_workspace_created(
         pool: &PgPool,
+        actor_id: Uuid,
         issue_id: Uuid,
     ) -> Result<(), IssueError> {
         let workspace_count = WorkspaceRepository::count_by_issue_id(pool, issue_id).await?;
@@ -406,6 +921,7 @@ impl IssueRepository {
 
         Self::update(
             pool,
+            actor_id,
             issue_id,
             Some(in_progress_status.id),
             None,
@@ -423,3 +939,234 @@ impl IssueRepository {
         Ok(())
     }
 }
+
+#[derive(Debug, PartialEq)]
+struct SortOrderPlan {
+    sort_order: f64,
+    /// New sort orders for the siblings when they had to be respread to make room.
+    rebalanced: Option<Vec<(Uuid, f64)>>,
+}
+
+/// Work out the sort order for an issue placed below `after_issue_id` (or at the top) among
+/// `siblings`, which must be sorted ascending by sort order.
+fn plan_sort_order(
+    siblings: &[(Uuid, f64)],
+    after_issue_id: Option<Uuid>,
+) -> Result<SortOrderPlan, IssueError> {
+    let position = match after_issue_id {
+        Some(after) => {
+            siblings
+                .iter()
+                .position(|(id, _)| *id == after)
+                .ok_or(IssueError::InvalidMoveTarget(after))?
+                + 1
+        }
+        None => 0,
+    };
+    let neighbours = |orders: &[(Uuid, f64)]| {
+        (
+            position.checked_sub(1).map(|i| orders[i].1),
+            orders.get(position).map(|(_, sort_order)| *sort_order),
+        )
+    };
+
+    let (above, below) = neighbours(siblings);
+    if let Some(sort_order) = sort_order_between(above, below) {
+        return Ok(SortOrderPlan {
+            sort_order,
+            rebalanced: None,
+        });
+    }
+
+    let rebalanced: Vec<(Uuid, f64)> = siblings
+        .iter()
+        .zip(1..)
+        .map(|((id, _), slot)| (*id, f64::from(slot) * SORT_ORDER_STEP))
+        .collect();
+    let (above, below) = neighbours(&rebalanced);
+    let sort_order = sort_order_between(above, below)
+        .expect("rebalanced sort orders leave room between neighbours");
+
+    Ok(SortOrderPlan {
+        sort_order,
+        rebalanced: Some(rebalanced),
+    })
+}
+
+/// A sort order strictly between `above` and `below`, or `None` if the neighbours have
+/// converged and there is no representable f64 between them.
+fn sort_order_between(above: Option<f64>, below: Option<f64>) -> Option<f64> {
+    let candidate = match (above, below) {
+        (None, None) => SORT_ORDER_STEP,
+        (Some(above), None) => above + SORT_ORDER_STEP,
+        (None, Some(below)) => below - SORT_ORDER_STEP,
+        (Some(above), Some(below)) => above + (below - above) / 2.0,
+    };
+    let fits_above = above.is_none_or(|above| candidate > above);
+    let fits_below = below.is_none_or(|below| candidate < below);
+    (fits_above && fits_below).then_some(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn siblings(sort_orders: &[f64]) -> Vec<(Uuid, f64)> {
+        sort_orders.iter().map(|s| (Uuid::new_v4(), *s)).collect()
+    }
+
+    #[test]
+    fn first_issue_in_empty_status() {
+        let plan = plan_sort_order(&[], None).unwrap();
+        assert_eq!(plan.sort_order, SORT_ORDER_STEP);
+        assert!(plan.rebalanced.is_none());
+    }
+
+    #[test]
+    fn insert_at_top() {
+        let siblings = siblings(&[1.0, 2.0]);
+        let plan = plan_sort_order(&siblings, None).unwrap();
+        assert!(plan.sort_order < 1.0);
+        assert!(plan.rebalanced.is_none());
+    }
+
+    #[test]
+    fn insert_at_bottom() {
+        let siblings = siblings(&[1.0, 2.0]);
+        let plan = plan_sort_order(&siblings, Some(siblings[1].0)).unwrap();
+        assert!(plan.sort_order > 2.0);
+        assert!(plan.rebalanced.is_none());
+    }
+
+    #[test]
+    fn insert_between_neighbours_uses_midpoint() {
+        let siblings = siblings(&[1.0, 2.0]);
+        let plan = plan_sort_order(&siblings, Some(siblings[0].0)).unwrap();
+        assert_eq!(plan.sort_order, 1.5);
+        assert!(plan.rebalanced.is_none());
+    }
+
+    #[test]
+    fn converged_neighbours_trigger_rebalance() {
+        let low = 1.0_f64;
+        let high = f64::from_bits(low.to_bits() + 1);
+        let siblings = siblings(&[0.5, low, high]);
+
+        let plan = plan_sort_order(&siblings, Some(siblings[1].0)).unwrap();
+        let rebalanced = plan.rebalanced.expect("neighbours had no room");
+
+        let ids: Vec<Uuid> = rebalanced.iter().map(|(id, _)| *id).collect();
+        let original_ids: Vec<Uuid> = siblings.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, original_ids, "rebalance keeps the existing order");
+        assert!(rebalanced.windows(2).all(|w| w[0].1 < w[1].1));
+        assert!(plan.sort_order > rebalanced[1].1 && plan.sort_order < rebalanced[2].1);
+    }
+
+    #[test]
+    fn converged_top_triggers_rebalance() {
+        let siblings = siblings(&[f64::MIN, f64::MIN / 2.0]);
+        let plan = plan_sort_order(&siblings, None).unwrap();
+        let rebalanced = plan.rebalanced.expect("no room above the first issue");
+        assert!(plan.sort_order < rebalanced[0].1);
+    }
+
+    #[test]
+    fn unknown_after_issue_is_rejected() {
+        let siblings = siblings(&[1.0]);
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            plan_sort_order(&siblings, Some(missing)),
+            Err(IssueError::InvalidMoveTarget(id)) if id == missing
+        ));
+    }
+
+    #[test]
+    fn page_limit_is_defaulted_and_capped() {
+        assert_eq!(clamp_page_limit(None), DEFAULT_PAGE_LIMIT);
+        assert_eq!(clamp_page_limit(Some(0)), 1);
+        assert_eq!(clamp_page_limit(Some(-5)), 1);
+        assert_eq!(clamp_page_limit(Some(10)), 10);
+        assert_eq!(clamp_page_limit(Some(MAX_PAGE_LIMIT + 1)), MAX_PAGE_LIMIT);
+    }
+
+    #[test]
+    fn sort_direction_parses_lowercase() {
+        let asc: SortDirection = serde_json::from_str("\"asc\"").unwrap();
+        let desc: SortDirection = serde_json::from_str("\"desc\"").unwrap();
+        assert_eq!(asc, SortDirection::Asc);
+        assert_eq!(desc, SortDirection::Desc);
+        assert_eq!(SortDirection::default(), SortDirection::Asc);
+        assert!(serde_json::from_str::<SortDirection>("\"sideways\"").is_err());
+    }
+
+    /// Pages through a status column whose issues share one `sort_order`.
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL pointing at the remote Postgres database"]
+    async fn equal_sort_orders_page_by_id_without_overlap() {
+        let pool = crate::db::test_pool().await;
+
+        let organization_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let status_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (id, name, slug) VALUES ($1, 'Paging', $2)")
+            .bind(organization_id)
+            .bind(format!("paging-{organization_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO projects (id, organization_id, name) VALUES ($1, $2, 'Paging')")
+            .bind(project_id)
+            .bind(organization_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO project_statuses (id, project_id, name, color) VALUES ($1, $2, 'Todo', '0 0% 0%')",
+        )
+        .bind(status_id)
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        for id in &ids {
+            sqlx::query(
+                "INSERT INTO issues (id, project_id, status_id, title, sort_order) VALUES ($1, $2, $3, 'Issue', $4)",
+            )
+            .bind(id)
+            .bind(project_id)
+            .bind(status_id)
+            .bind(SORT_ORDER_STEP)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        ids.sort();
+        let mut descending_ids = ids.clone();
+        descending_ids.reverse();
+
+        for (direction, expected) in [
+            (SortDirection::Asc, ids),
+            (SortDirection::Desc, descending_ids),
+        ] {
+            let mut seen = Vec::new();
+            for offset in [0, 2, 4] {
+                let page = IssueRepository::list_by_status(
+                    &pool, project_id, status_id, 2, offset, direction,
+                )
+                .await
+                .unwrap();
+                assert_eq!(page.total, 5);
+                seen.extend(page.issues.into_iter().map(|issue| issue.id));
+            }
+            assert_eq!(seen, expected, "{direction:?} pages overlap or skip issues");
+        }
+
+        sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(organization_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
@@ -1,7 +1,9 @@
+pub mod activity_events;
 pub mod auth;
 pub mod github_app;
 pub mod identity_errors;
 pub mod invitations;
+pub mod issue_activity;
 pub mod issue_assignees;
 pub mod issue_comment_reactions;
 pub mod issue_comments;
@@ -16,17 +18,27 @@ pub mod organization_members;
 pub mod organizations;
 pub mod project_notification_preferences;
 pub mod project_statuses;
+pub mod project_transfer;
 pub mod projects;
 pub mod pull_requests;
 pub mod reviews;
+pub mod soft_delete;
 pub mod tags;
 pub mod types;
 pub mod users;
+pub mod workspace_reviews;
 pub mod workspaces;
 
+use std::collections::HashSet;
+
 use sqlx::{
-    Executor, PgPool, Postgres, Transaction, migrate::MigrateError, postgres::PgPoolOptions,
+    Executor, PgPool, Postgres, Transaction,
+    migrate::{Migrate, MigrateError, Migrator},
+    postgres::PgPoolOptions,
 };
+use thiserror::Error;
+
+use crate::config::DatabaseConfig;
 
 pub(crate) type Tx<'a> = Transaction<'a, Postgres>;
 
@@ -43,25 +55,84 @@ where
     Ok(row.0)
 }
 
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
 pub(crate) async fn migrate(pool: &PgPool) -> Result<(), MigrateError> {
-    sqlx::migrate!("./migrations").run(pool).await
+    MIGRATOR.run(pool).await
 }
 
-pub(crate) async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+/// How many migrations embedded in this build have not been applied to the database yet.
+pub(crate) async fn pending_migration_count(pool: &PgPool) -> Result<usize, MigrateError> {
+    let mut conn = pool.acquire().await?;
+    let applied: HashSet<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .count())
+}
+
+pub(crate) async fn create_pool(
+    database_url: &str,
+    config: &DatabaseConfig,
+) -> Result<PgPool, sqlx::Error> {
     PgPoolOptions::new()
-        .max_connections(10)
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
         .connect(database_url)
         .await
 }
 
+/// Pool for tests that need the real schema, migrated on first use. `DATABASE_URL` must name
+/// the `remote` database: the electric migrations create a cluster-wide role and grant on
+/// that database by name, so they can't be replayed into per-test databases.
+#[cfg(test)]
+pub(crate) async fn test_pool() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url).await.unwrap();
+    migrate(&pool).await.unwrap();
+    pool
+}
+
+/// Longest role password accepted from config
+const MAX_ROLE_PASSWORD_LEN: usize = 128;
+
+#[derive(Debug, Error)]
+pub enum PasswordError {
+    #[error("password contains a null byte")]
+    ContainsNullByte,
+    #[error("password is longer than {MAX_ROLE_PASSWORD_LEN} characters")]
+    TooLong,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Reject passwords that can't be embedded safely in an `ALTER ROLE` literal.
+fn validate_role_password(password: &str) -> Result<(), PasswordError> {
+    if password.contains('\0') {
+        return Err(PasswordError::ContainsNullByte);
+    }
+    if password.chars().count() > MAX_ROLE_PASSWORD_LEN {
+        return Err(PasswordError::TooLong);
+    }
+    Ok(())
+}
+
 pub(crate) async fn ensure_electric_role_password(
     pool: &PgPool,
     password: &str,
-) -> Result<(), sqlx::Error> {
+) -> Result<(), PasswordError> {
     if password.is_empty() {
         return Ok(());
     }
 
+    validate_role_password(password)?;
+
     // PostgreSQL doesn't support parameter binding for ALTER ROLE PASSWORD
     // We need to escape the password properly and embed it directly in the SQL
     let escaped_password = password.replace("'", "''");
@@ -71,3 +142,30 @@ pub(crate) async fn ensure_electric_role_password(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_quotes_within_length_limit() {
+        assert!(validate_role_password("it's-a-secret").is_ok());
+        assert!(validate_role_password(&"x".repeat(MAX_ROLE_PASSWORD_LEN)).is_ok());
+    }
+
+    #[test]
+    fn rejects_null_bytes() {
+        assert!(matches!(
+            validate_role_password("abc\0def"),
+            Err(PasswordError::ContainsNullByte)
+        ));
+    }
+
+    #[test]
+    fn rejects_overlong_passwords() {
+        assert!(matches!(
+            validate_role_password(&"x".repeat(MAX_ROLE_PASSWORD_LEN + 1)),
+            Err(PasswordError::TooLong)
+        ));
+    }
+}
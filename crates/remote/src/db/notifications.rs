@@ -14,6 +14,7 @@ pub enum NotificationType {
     IssueStatusChanged,
     IssueAssigneeChanged,
     IssueDeleted,
+    WorkspaceReviewSubmitted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -207,13 +208,26 @@ impl NotificationRepository {
         Ok(record)
     }
 
-    pub async fn mark_all_seen<'e, E>(executor: E, user_id: Uuid) -> Result<u64, NotificationError>
+    /// Mark the user's unseen notifications as seen, limited to one organization when given.
+    /// Returns how many notifications changed.
+    pub async fn mark_all_seen<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<u64, NotificationError>
     where
         E: Executor<'e, Database = Postgres>,
     {
         let result = sqlx::query!(
-            "UPDATE notifications SET seen = TRUE WHERE user_id = $1 AND seen = FALSE",
-            user_id
+            r#"
+            UPDATE notifications
+            SET seen = TRUE
+            WHERE user_id = $1
+              AND seen = FALSE
+              AND ($2::uuid IS NULL OR organization_id = $2)
+            "#,
+            user_id,
+            organization_id
         )
         .execute(executor)
         .await?;
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use thiserror::Error;
+use ts_rs::TS;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -9,6 +11,17 @@ pub enum OAuthAccountError {
     Database(#[from] sqlx::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum UnlinkOAuthAccountError {
+    #[error("oauth account not found")]
+    NotFound,
+    /// Users sign in only through OAuth, so removing their last provider would lock them out.
+    #[error("cannot unlink the only sign-in provider on this account")]
+    LastAccount,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct OAuthAccount {
     pub id: Uuid,
@@ -23,6 +36,39 @@ pub struct OAuthAccount {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A sign-in provider linked to the current user, as shown in account settings.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LinkedOAuthAccount {
+    pub id: Uuid,
+    pub provider: String,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OAuthAccount> for LinkedOAuthAccount {
+    fn from(account: OAuthAccount) -> Self {
+        Self {
+            id: account.id,
+            provider: account.provider,
+            username: account.username,
+            display_name: account.display_name,
+            email: account.email,
+            avatar_url: account.avatar_url,
+            created_at: account.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ListOAuthAccountsResponse {
+    pub accounts: Vec<LinkedOAuthAccount>,
+}
+
 #[derive(Debug, Clone)]
 pub struct OAuthAccountInsert<'a> {
     pub user_id: Uuid,
@@ -150,4 +196,76 @@ impl<'a> OAuthAccountRepository<'a> {
         .await
         .map_err(OAuthAccountError::from)
     }
+
+    /// Unlink one of the user's accounts. The user's accounts are locked for the check so
+    /// two concurrent unlinks can't remove the last provider between them.
+    pub async fn delete_for_user(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<(), UnlinkOAuthAccountError> {
+        let mut tx = self.pool.begin().await?;
+
+        let account_ids = sqlx::query_scalar!(
+            r#"
+            SELECT id AS "id!: Uuid"
+            FROM oauth_accounts
+            WHERE user_id = $1
+            FOR UPDATE
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        ensure_unlinkable(&account_ids, account_id)?;
+
+        sqlx::query!(
+            "DELETE FROM oauth_accounts WHERE id = $1 AND user_id = $2",
+            account_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+fn ensure_unlinkable(
+    account_ids: &[Uuid],
+    account_id: Uuid,
+) -> Result<(), UnlinkOAuthAccountError> {
+    if !account_ids.contains(&account_id) {
+        return Err(UnlinkOAuthAccountError::NotFound);
+    }
+    if account_ids.len() == 1 {
+        return Err(UnlinkOAuthAccountError::LastAccount);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_account_cannot_be_unlinked() {
+        let only = Uuid::new_v4();
+        assert!(matches!(
+            ensure_unlinkable(&[only], only),
+            Err(UnlinkOAuthAccountError::LastAccount)
+        ));
+    }
+
+    #[test]
+    fn other_users_account_is_not_found() {
+        let (github, google) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(matches!(
+            ensure_unlinkable(&[github, google], Uuid::new_v4()),
+            Err(UnlinkOAuthAccountError::NotFound)
+        ));
+        assert!(ensure_unlinkable(&[github, google], github).is_ok());
+    }
 }
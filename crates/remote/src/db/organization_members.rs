@@ -43,11 +43,15 @@ where
     Ok(())
 }
 
-pub(super) async fn check_user_role(
-    pool: &PgPool,
+/// The user's role in the organization, or `None` if they are not a member.
+pub async fn find_role<'a, E>(
+    executor: E,
     organization_id: Uuid,
     user_id: Uuid,
-) -> Result<Option<MemberRole>, IdentityError> {
+) -> Result<Option<MemberRole>, IdentityError>
+where
+    E: Executor<'a, Database = Postgres>,
+{
     let result = sqlx::query!(
         r#"
         SELECT role AS "role!: MemberRole"
@@ -57,7 +61,7 @@ pub(super) async fn check_user_role(
         organization_id,
         user_id
     )
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await?;
 
     Ok(result.map(|r| r.role))
@@ -88,6 +92,30 @@ where
     Ok(exists)
 }
 
+/// Resolve `@username` handles (compared case-insensitively) to members of the organization.
+pub async fn find_member_ids_by_usernames<'a, E>(
+    executor: E,
+    organization_id: Uuid,
+    usernames: &[String],
+) -> Result<Vec<Uuid>, sqlx::Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    let usernames: Vec<String> = usernames.iter().map(|u| u.to_lowercase()).collect();
+    sqlx::query_scalar!(
+        r#"
+        SELECT u.id AS "id!: Uuid"
+        FROM users u
+        JOIN organization_member_metadata m ON m.user_id = u.id
+        WHERE m.organization_id = $1 AND LOWER(u.username) = ANY($2)
+        "#,
+        organization_id,
+        &usernames
+    )
+    .fetch_all(executor)
+    .await
+}
+
 pub(crate) async fn assert_membership(
     pool: &PgPool,
     organization_id: Uuid,
@@ -139,14 +167,61 @@ pub(crate) async fn assert_project_access(
     assert_membership(pool, org_id, user_id).await
 }
 
+/// Require the user to hold `min_role` or a higher role in the organization.
+pub(crate) async fn assert_role(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+    min_role: MemberRole,
+) -> Result<(), IdentityError> {
+    let role = find_role(pool, organization_id, user_id).await?;
+    ensure_role(role, min_role)
+}
+
+fn ensure_role(role: Option<MemberRole>, min_role: MemberRole) -> Result<(), IdentityError> {
+    match role {
+        Some(role) if role.is_at_least(min_role) => Ok(()),
+        Some(_) => Err(IdentityError::PermissionDenied),
+        None => Err(IdentityError::NotFound),
+    }
+}
+
 pub(super) async fn assert_admin(
     pool: &PgPool,
     organization_id: Uuid,
     user_id: Uuid,
 ) -> Result<(), IdentityError> {
-    let role = check_user_role(pool, organization_id, user_id).await?;
+    let role = find_role(pool, organization_id, user_id).await?;
     match role {
         Some(MemberRole::Admin) => Ok(()),
         _ => Err(IdentityError::PermissionDenied),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{IdentityError, MemberRole, ensure_role};
+
+    #[test]
+    fn member_is_denied_admin_operations() {
+        assert!(matches!(
+            ensure_role(Some(MemberRole::Member), MemberRole::Admin),
+            Err(IdentityError::PermissionDenied)
+        ));
+    }
+
+    #[test]
+    fn admin_passes_admin_and_member_checks() {
+        assert!(ensure_role(Some(MemberRole::Admin), MemberRole::Admin).is_ok());
+        assert!(ensure_role(Some(MemberRole::Admin), MemberRole::Member).is_ok());
+        assert!(ensure_role(Some(MemberRole::Member), MemberRole::Member).is_ok());
+    }
+
+    #[test]
+    fn non_member_is_not_found() {
+        assert!(matches!(
+            ensure_role(None, MemberRole::Member),
+            Err(IdentityError::NotFound)
+        ));
+    }
+}
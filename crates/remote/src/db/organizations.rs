@@ -5,8 +5,7 @@ use uuid::Uuid;
 use super::{
     identity_errors::IdentityError,
     organization_members::{
-        add_member, assert_admin as check_admin, assert_membership as check_membership,
-        check_user_role as get_user_role,
+        add_member, assert_admin as check_admin, assert_membership as check_membership, find_role,
     },
     projects::ProjectRepository,
 };
@@ -110,7 +109,7 @@ impl<'a> OrganizationRepository<'a> {
         organization_id: Uuid,
         user_id: Uuid,
     ) -> Result<Option<MemberRole>, IdentityError> {
-        get_user_role(self.pool, organization_id, user_id).await
+        find_role(self.pool, organization_id, user_id).await
     }
 
     pub async fn assert_admin(
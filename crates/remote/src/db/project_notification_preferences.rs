@@ -1,14 +1,107 @@
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, Postgres};
 use thiserror::Error;
+use ts_rs::TS;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::notifications::NotificationType;
+
+/// Project-wide override on top of the per-event toggles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
+#[sqlx(type_name = "notification_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum NotificationMode {
+    /// Use the per-event toggles
+    #[default]
+    Default,
+    /// Notify about every event in the project
+    Watch,
+    /// Only direct @mentions get through
+    Mute,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ProjectNotificationPreference {
     pub project_id: Uuid,
     pub user_id: Uuid,
     pub notify_on_issue_created: bool,
     pub notify_on_issue_assigned: bool,
+    pub mode: NotificationMode,
+}
+
+/// A user's notification preferences for a project after filling in defaults.
+///
+/// When no `project_notification_preferences` row exists the user is notified about new
+/// issues, assignments and comments, but not about every status change. A stored row
+/// overrides the issue-created and issue-assigned settings; everything else keeps its default.
+/// A `watch` mode turns every event on, and `mute` turns every event off except mentions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectivePrefs {
+    pub notify_on_issue_created: bool,
+    pub notify_on_issue_assigned: bool,
+    pub notify_on_comment: bool,
+    pub notify_on_status_change: bool,
+    pub muted: bool,
+}
+
+impl Default for EffectivePrefs {
+    fn default() -> Self {
+        Self {
+            notify_on_issue_created: true,
+            notify_on_issue_assigned: true,
+            notify_on_comment: true,
+            notify_on_status_change: false,
+            muted: false,
+        }
+    }
+}
+
+impl EffectivePrefs {
+    pub fn merge(preference: Option<&ProjectNotificationPreference>) -> Self {
+        let defaults = Self::default();
+        match preference {
+            Some(preference) => match preference.mode {
+                NotificationMode::Default => Self {
+                    notify_on_issue_created: preference.notify_on_issue_created,
+                    notify_on_issue_assigned: preference.notify_on_issue_assigned,
+                    ..defaults
+                },
+                NotificationMode::Watch => Self {
+                    notify_on_issue_created: true,
+                    notify_on_issue_assigned: true,
+                    notify_on_comment: true,
+                    notify_on_status_change: true,
+                    muted: false,
+                },
+                NotificationMode::Mute => Self {
+                    muted: true,
+                    ..defaults
+                },
+            },
+            None => defaults,
+        }
+    }
+
+    /// Whether a notification should be delivered. A direct @mention always gets through,
+    /// even when the project is muted.
+    pub fn allows_delivery(&self, notification_type: NotificationType, mentioned: bool) -> bool {
+        mentioned || self.allows(notification_type)
+    }
+
+    pub fn allows(&self, notification_type: NotificationType) -> bool {
+        if self.muted {
+            return false;
+        }
+        match notification_type {
+            NotificationType::IssueCommentAdded => self.notify_on_comment,
+            NotificationType::IssueStatusChanged => self.notify_on_status_change,
+            NotificationType::IssueAssigneeChanged => self.notify_on_issue_assigned,
+            NotificationType::IssueDeleted => true,
+            NotificationType::WorkspaceReviewSubmitted => true,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -35,7 +128,8 @@ impl ProjectNotificationPreferenceRepository {
                 project_id               AS "project_id!: Uuid",
                 user_id                  AS "user_id!: Uuid",
                 notify_on_issue_created  AS "notify_on_issue_created!",
-                notify_on_issue_assigned AS "notify_on_issue_assigned!"
+                notify_on_issue_assigned AS "notify_on_issue_assigned!",
+                mode                     AS "mode!: NotificationMode"
             FROM project_notification_preferences
             WHERE project_id = $1 AND user_id = $2
             "#,
@@ -47,4 +141,119 @@ impl ProjectNotificationPreferenceRepository {
 
         Ok(record)
     }
+
+    /// Set the notification mode for a user in a project, keeping any per-event toggles.
+    pub async fn set_mode<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+        mode: NotificationMode,
+    ) -> Result<ProjectNotificationPreference, ProjectNotificationPreferenceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ProjectNotificationPreference,
+            r#"
+            INSERT INTO project_notification_preferences (project_id, user_id, mode)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (project_id, user_id) DO UPDATE
+            SET mode = EXCLUDED.mode
+            RETURNING
+                project_id               AS "project_id!: Uuid",
+                user_id                  AS "user_id!: Uuid",
+                notify_on_issue_created  AS "notify_on_issue_created!",
+                notify_on_issue_assigned AS "notify_on_issue_assigned!",
+                mode                     AS "mode!: NotificationMode"
+            "#,
+            project_id,
+            user_id,
+            mode as NotificationMode
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Resolve whether `user_id` should be notified about events in `project_id`, merging any
+    /// stored preference row with the defaults documented on [`EffectivePrefs`].
+    pub async fn resolve<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<EffectivePrefs, ProjectNotificationPreferenceError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let preference = Self::find(executor, project_id, user_id).await?;
+        Ok(EffectivePrefs::merge(preference.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_row_uses_defaults() {
+        let prefs = EffectivePrefs::merge(None);
+
+        assert!(prefs.allows(NotificationType::IssueAssigneeChanged));
+        assert!(prefs.allows(NotificationType::IssueCommentAdded));
+        assert!(!prefs.allows(NotificationType::IssueStatusChanged));
+    }
+
+    #[test]
+    fn stored_row_overrides_only_the_fields_it_covers() {
+        let preference = ProjectNotificationPreference {
+            project_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            notify_on_issue_created: true,
+            notify_on_issue_assigned: false,
+            mode: NotificationMode::Default,
+        };
+
+        let prefs = EffectivePrefs::merge(Some(&preference));
+
+        assert_eq!(
+            prefs,
+            EffectivePrefs {
+                notify_on_issue_created: true,
+                notify_on_issue_assigned: false,
+                notify_on_comment: true,
+                notify_on_status_change: false,
+                muted: false,
+            }
+        );
+        assert!(!prefs.allows(NotificationType::IssueAssigneeChanged));
+        assert!(prefs.allows(NotificationType::IssueCommentAdded));
+    }
+
+    fn preference(mode: NotificationMode) -> ProjectNotificationPreference {
+        ProjectNotificationPreference {
+            project_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            notify_on_issue_created: true,
+            notify_on_issue_assigned: true,
+            mode,
+        }
+    }
+
+    #[test]
+    fn muted_project_blocks_everything_but_mentions() {
+        let prefs = EffectivePrefs::merge(Some(&preference(NotificationMode::Mute)));
+
+        assert!(!prefs.allows_delivery(NotificationType::IssueCommentAdded, false));
+        assert!(!prefs.allows_delivery(NotificationType::IssueAssigneeChanged, false));
+        assert!(prefs.allows_delivery(NotificationType::IssueCommentAdded, true));
+    }
+
+    #[test]
+    fn watched_project_includes_status_changes() {
+        let prefs = EffectivePrefs::merge(Some(&preference(NotificationMode::Watch)));
+
+        assert!(prefs.allows(NotificationType::IssueStatusChanged));
+        assert!(prefs.allows(NotificationType::IssueCommentAdded));
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, PgPool, Postgres};
@@ -33,6 +35,14 @@ pub struct ProjectStatus {
 
 #[derive(Debug, Error)]
 pub enum ProjectStatusError {
+    #[error("{0}")]
+    InvalidReorder(String),
+    #[error("status is still used by {issue_count} issue(s)")]
+    InUse { issue_count: i64 },
+    #[error("cannot delete the last status in a project")]
+    LastStatus,
+    #[error("reassign target {0} is not another status in this project")]
+    InvalidReassignTarget(Uuid),
     #[error(transparent)]
     Database(#[from] sqlx::Error),
 }
@@ -186,8 +196,49 @@ impl ProjectStatusRepository {
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, ProjectStatusError> {
+    /// Delete a status. Issues still using it are moved to `reassign_to` in the same
+    /// transaction; without a target the delete is refused. The last status in a project
+    /// can never be deleted.
+    pub async fn delete(
+        pool: &PgPool,
+        id: Uuid,
+        reassign_to: Option<Uuid>,
+    ) -> Result<DeleteResponse, ProjectStatusError> {
         let mut tx = pool.begin().await?;
+
+        let project_status_ids = sqlx::query_scalar!(
+            r#"
+            SELECT id AS "id!: Uuid"
+            FROM project_statuses
+            WHERE project_id = (SELECT project_id FROM project_statuses WHERE id = $1)
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let issue_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM issues WHERE status_id = $1"#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        validate_delete(id, &project_status_ids, issue_count, reassign_to)?;
+
+        if let Some(target) = reassign_to
+            && issue_count > 0
+        {
+            sqlx::query!(
+                "UPDATE issues SET status_id = $1, updated_at = NOW() WHERE status_id = $2",
+                target,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
         sqlx::query!("DELETE FROM project_statuses WHERE id = $1", id)
             .execute(&mut *tx)
             .await?;
@@ -196,6 +247,72 @@ impl ProjectStatusRepository {
         Ok(DeleteResponse { txid })
     }
 
+    /// Reassign `sort_order` 0..n following `ordered_ids` in a single transaction.
+    /// `ordered_ids` must list every status of the project exactly once.
+    pub async fn reorder(
+        pool: &PgPool,
+        project_id: Uuid,
+        ordered_ids: Vec<Uuid>,
+    ) -> Result<MutationResponse<Vec<ProjectStatus>>, ProjectStatusError> {
+        let mut tx = pool.begin().await?;
+
+        let current_ids = sqlx::query_scalar!(
+            r#"
+            SELECT id AS "id!: Uuid"
+            FROM project_statuses
+            WHERE project_id = $1
+            FOR UPDATE
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        validate_reorder(&current_ids, &ordered_ids)?;
+
+        // (project_id, sort_order) is unique and checked per row, so move every status to a
+        // temporary negative slot before assigning the final positions.
+        sqlx::query!(
+            r#"
+            UPDATE project_statuses ps
+            SET sort_order = -t.position::int
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS t(id, position)
+            WHERE ps.id = t.id AND ps.project_id = $1
+            "#,
+            project_id,
+            &ordered_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let mut data = sqlx::query_as!(
+            ProjectStatus,
+            r#"
+            UPDATE project_statuses ps
+            SET sort_order = (t.position - 1)::int
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS t(id, position)
+            WHERE ps.id = t.id AND ps.project_id = $1
+            RETURNING
+                ps.id           AS "id!: Uuid",
+                ps.project_id   AS "project_id!: Uuid",
+                ps.name         AS "name!",
+                ps.color        AS "color!",
+                ps.sort_order   AS "sort_order!",
+                ps.hidden       AS "hidden!",
+                ps.created_at   AS "created_at!: DateTime<Utc>"
+            "#,
+            project_id,
+            &ordered_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        data.sort_by_key(|status| status.sort_order);
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(MutationResponse { data, txid })
+    }
+
     pub async fn list_by_project<'e, E>(
         executor: E,
         project_id: Uuid,
@@ -270,3 +387,131 @@ impl ProjectStatusRepository {
         Ok(statuses)
     }
 }
+
+fn validate_reorder(current_ids: &[Uuid], ordered_ids: &[Uuid]) -> Result<(), ProjectStatusError> {
+    let mut seen = HashSet::with_capacity(ordered_ids.len());
+    if let Some(duplicate) = ordered_ids.iter().find(|id| !seen.insert(**id)) {
+        return Err(ProjectStatusError::InvalidReorder(format!(
+            "status {duplicate} is listed more than once"
+        )));
+    }
+
+    let current: HashSet<Uuid> = current_ids.iter().copied().collect();
+    if let Some(unknown) = ordered_ids.iter().find(|id| !current.contains(id)) {
+        return Err(ProjectStatusError::InvalidReorder(format!(
+            "status {unknown} does not belong to this project"
+        )));
+    }
+    if seen.len() != current.len() {
+        return Err(ProjectStatusError::InvalidReorder(
+            "ordered_ids must include every status in the project".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_delete(
+    id: Uuid,
+    project_status_ids: &[Uuid],
+    issue_count: i64,
+    reassign_to: Option<Uuid>,
+) -> Result<(), ProjectStatusError> {
+    // Unknown status: nothing to guard, the delete is a no-op.
+    if project_status_ids.is_empty() {
+        return Ok(());
+    }
+    if project_status_ids.len() == 1 {
+        return Err(ProjectStatusError::LastStatus);
+    }
+    match reassign_to {
+        Some(target) if target == id || !project_status_ids.contains(&target) => {
+            Err(ProjectStatusError::InvalidReassignTarget(target))
+        }
+        None if issue_count > 0 => Err(ProjectStatusError::InUse { issue_count }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_reorder_accepts_permutation() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let ordered = vec![ids[2], ids[0], ids[1]];
+        assert!(validate_reorder(&ids, &ordered).is_ok());
+    }
+
+    #[test]
+    fn validate_reorder_rejects_missing_status() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        assert!(matches!(
+            validate_reorder(&ids, &[ids[0]]),
+            Err(ProjectStatusError::InvalidReorder(_))
+        ));
+    }
+
+    #[test]
+    fn validate_reorder_rejects_foreign_status() {
+        let ids = [Uuid::new_v4()];
+        assert!(matches!(
+            validate_reorder(&ids, &[ids[0], Uuid::new_v4()]),
+            Err(ProjectStatusError::InvalidReorder(_))
+        ));
+    }
+
+    #[test]
+    fn validate_reorder_rejects_duplicates() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        assert!(matches!(
+            validate_reorder(&ids, &[ids[0], ids[0]]),
+            Err(ProjectStatusError::InvalidReorder(_))
+        ));
+    }
+
+    #[test]
+    fn validate_delete_refuses_last_status() {
+        let id = Uuid::new_v4();
+        assert!(matches!(
+            validate_delete(id, &[id], 0, None),
+            Err(ProjectStatusError::LastStatus)
+        ));
+    }
+
+    #[test]
+    fn validate_delete_refuses_status_in_use_without_target() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        assert!(matches!(
+            validate_delete(ids[0], &ids, 3, None),
+            Err(ProjectStatusError::InUse { issue_count: 3 })
+        ));
+    }
+
+    #[test]
+    fn validate_delete_allows_status_in_use_with_target() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        assert!(validate_delete(ids[0], &ids, 3, Some(ids[1])).is_ok());
+    }
+
+    #[test]
+    fn validate_delete_allows_unused_status() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        assert!(validate_delete(ids[0], &ids, 0, None).is_ok());
+    }
+
+    #[test]
+    fn validate_delete_rejects_invalid_target() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let foreign = Uuid::new_v4();
+        assert!(matches!(
+            validate_delete(ids[0], &ids, 1, Some(foreign)),
+            Err(ProjectStatusError::InvalidReassignTarget(target)) if target == foreign
+        ));
+        assert!(matches!(
+            validate_delete(ids[0], &ids, 1, Some(ids[0])),
+            Err(ProjectStatusError::InvalidReassignTarget(_))
+        ));
+    }
+}
@@ -0,0 +1,1027 @@
+//! Portable JSON export/import of a project and everything in it.
+//!
+//! An export references its rows by their original ids. Importing gives every row a fresh id
+//! and rewrites the references, so the same export can be imported any number of times.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use sqlx::PgPool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    Tx, get_txid,
+    projects::{Project, ProjectError, ProjectRepository},
+    types::{IssuePriority, IssueRelationshipType},
+};
+use crate::mutation_types::MutationResponse;
+
+/// Format version written by [`ProjectTransferRepository::export`]. Bump it when the shape of
+/// [`ProjectExport`] changes in a way older importers can't read.
+pub const PROJECT_EXPORT_VERSION: u32 = 1;
+
+/// Key in an imported issue's `extension_metadata` holding identities that couldn't be
+/// matched to members of the target organization.
+pub const IMPORTED_USERS_KEY: &str = "imported_users";
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProjectExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ExportedProject,
+    pub statuses: Vec<ExportedStatus>,
+    pub tags: Vec<ExportedTag>,
+    /// Everyone referenced as an assignee or comment author.
+    pub users: Vec<ExportedUser>,
+    /// Ordered by issue number, so re-importing keeps the numbering order.
+    pub issues: Vec<ExportedIssue>,
+    pub issue_tags: Vec<ExportedIssueTag>,
+    pub assignees: Vec<ExportedAssignee>,
+    pub comments: Vec<ExportedComment>,
+    pub relationships: Vec<ExportedRelationship>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedProject {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedStatus {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub sort_order: i32,
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedTag {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedUser {
+    pub id: Uuid,
+    pub email: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedIssue {
+    pub id: Uuid,
+    pub status_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: IssuePriority,
+    pub start_date: Option<DateTime<Utc>>,
+    pub target_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub sort_order: f64,
+    pub parent_issue_id: Option<Uuid>,
+    pub extension_metadata: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedIssueTag {
+    pub issue_id: Uuid,
+    pub tag_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedAssignee {
+    pub issue_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedComment {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub author_id: Uuid,
+    pub parent_comment_id: Option<Uuid>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ExportedRelationship {
+    pub issue_id: Uuid,
+    pub related_issue_id: Uuid,
+    pub relationship_type: IssueRelationshipType,
+}
+
+#[derive(Debug, Error)]
+pub enum ProjectTransferError {
+    #[error("unsupported export version {0} (expected {PROJECT_EXPORT_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error("export references unknown {kind} {id}")]
+    InvalidReference { kind: &'static str, id: Uuid },
+    #[error("export contains duplicate {kind} id {id}")]
+    DuplicateId { kind: &'static str, id: Uuid },
+    #[error("project not found")]
+    ProjectNotFound,
+    #[error(transparent)]
+    Project(#[from] ProjectError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct ProjectTransferRepository;
+
+impl ProjectTransferRepository {
+    /// Assemble a portable snapshot of a project. Soft-deleted issues and comments are left out.
+    ///
+    /// Everything is read in one `REPEATABLE READ` transaction so concurrent edits can't leave
+    /// the export referring to rows it doesn't contain.
+    pub async fn export(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<ProjectExport, ProjectTransferError> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let project = ProjectRepository::find_by_id(&mut *tx, project_id)
+            .await?
+            .ok_or(ProjectTransferError::ProjectNotFound)?;
+
+        let statuses = sqlx::query_as!(
+            ExportedStatus,
+            r#"
+            SELECT
+                id          AS "id!: Uuid",
+                name        AS "name!",
+                color       AS "color!",
+                sort_order  AS "sort_order!",
+                hidden      AS "hidden!"
+            FROM project_statuses
+            WHERE project_id = $1
+            ORDER BY sort_order ASC, id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let tags = sqlx::query_as!(
+            ExportedTag,
+            r#"
+            SELECT
+                id     AS "id!: Uuid",
+                name   AS "name!",
+                color  AS "color!"
+            FROM tags
+            WHERE project_id = $1
+            ORDER BY name ASC, id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Parents outside the export (soft-deleted) are dropped so it stays self-contained.
+        let issues = sqlx::query_as!(
+            ExportedIssue,
+            r#"
+            SELECT
+                i.id                  AS "id!: Uuid",
+                i.status_id           AS "status_id!: Uuid",
+                i.title               AS "title!",
+                i.description         AS "description?",
+                i.priority            AS "priority!: IssuePriority",
+                i.start_date          AS "start_date?: DateTime<Utc>",
+                i.target_date         AS "target_date?: DateTime<Utc>",
+                i.completed_at        AS "completed_at?: DateTime<Utc>",
+                i.sort_order          AS "sort_order!",
+                p.id                  AS "parent_issue_id?: Uuid",
+                i.extension_metadata  AS "extension_metadata!: Value",
+                i.created_at          AS "created_at!: DateTime<Utc>"
+            FROM issues i
+            LEFT JOIN issues p
+                ON p.id = i.parent_issue_id AND p.project_id = $1 AND p.deleted_at IS NULL
+            WHERE i.project_id = $1 AND i.deleted_at IS NULL
+            ORDER BY i.issue_number ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let issue_tags = sqlx::query_as!(
+            ExportedIssueTag,
+            r#"
+            SELECT
+                it.issue_id  AS "issue_id!: Uuid",
+                it.tag_id    AS "tag_id!: Uuid"
+            FROM issue_tags it
+            JOIN issues i ON i.id = it.issue_id
+            WHERE i.project_id = $1 AND i.deleted_at IS NULL
+            ORDER BY i.issue_number ASC, it.tag_id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let assignees = sqlx::query_as!(
+            ExportedAssignee,
+            r#"
+            SELECT
+                ia.issue_id  AS "issue_id!: Uuid",
+                ia.user_id   AS "user_id!: Uuid"
+            FROM issue_assignees ia
+            JOIN issues i ON i.id = ia.issue_id
+            WHERE i.project_id = $1 AND i.deleted_at IS NULL
+            ORDER BY i.issue_number ASC, ia.assigned_at ASC, ia.user_id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Replies to soft-deleted comments become top-level comments.
+        let comments = sqlx::query_as!(
+            ExportedComment,
+            r#"
+            SELECT
+                c.id          AS "id!: Uuid",
+                c.issue_id    AS "issue_id!: Uuid",
+                c.author_id   AS "author_id!: Uuid",
+                p.id          AS "parent_comment_id?: Uuid",
+                c.message     AS "message!",
+                c.created_at  AS "created_at!: DateTime<Utc>"
+            FROM issue_comments c
+            JOIN issues i ON i.id = c.issue_id
+            LEFT JOIN issue_comments p ON p.id = c.parent_comment_id AND p.deleted_at IS NULL
+            WHERE i.project_id = $1 AND i.deleted_at IS NULL AND c.deleted_at IS NULL
+            ORDER BY i.issue_number ASC, c.created_at ASC, c.id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Links to issues in other projects aren't part of the export.
+        let relationships = sqlx::query_as!(
+            ExportedRelationship,
+            r#"
+            SELECT
+                ir.issue_id           AS "issue_id!: Uuid",
+                ir.related_issue_id   AS "related_issue_id!: Uuid",
+                ir.relationship_type  AS "relationship_type!: IssueRelationshipType"
+            FROM issue_relationships ir
+            JOIN issues i ON i.id = ir.issue_id
+            JOIN issues r ON r.id = ir.related_issue_id
+            WHERE i.project_id = $1 AND i.deleted_at IS NULL
+              AND r.project_id = $1 AND r.deleted_at IS NULL
+            ORDER BY i.issue_number ASC, r.issue_number ASC, ir.relationship_type ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // The importer falls back to the bare id for users that no longer exist.
+        let user_ids: Vec<Uuid> = assignees
+            .iter()
+            .map(|a| a.user_id)
+            .chain(comments.iter().map(|c| c.author_id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let users = sqlx::query_as!(
+            ExportedUser,
+            r#"
+            SELECT
+                id          AS "id!: Uuid",
+                email       AS "email!",
+                first_name  AS "first_name?",
+                last_name   AS "last_name?",
+                username    AS "username?"
+            FROM users
+            WHERE id = ANY($1)
+            ORDER BY email ASC
+            "#,
+            &user_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(ProjectExport {
+            version: PROJECT_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            project: ExportedProject {
+                id: project.id,
+                name: project.name,
+                color: project.color,
+            },
+            statuses,
+            tags,
+            users,
+            issues,
+            issue_tags,
+            assignees,
+            comments,
+            relationships,
+        })
+    }
+
+    /// Recreate an exported project inside `organization_id` in a single transaction.
+    ///
+    /// Users are matched to members of the target organization by id, then by email. Assignees
+    /// that don't match are dropped, and comments by authors that don't match are attributed to
+    /// `actor_id`; either way the original identity is kept in the issue's `extension_metadata`
+    /// under [`IMPORTED_USERS_KEY`].
+    pub async fn import(
+        pool: &PgPool,
+        organization_id: Uuid,
+        actor_id: Uuid,
+        export: &ProjectExport,
+    ) -> Result<MutationResponse<Project>, ProjectTransferError> {
+        let members = Self::match_members(pool, organization_id, &export.users).await?;
+        let plan = plan_import(export, &members, actor_id)?;
+
+        let mut tx = pool.begin().await?;
+        let project = ProjectRepository::create(
+            &mut *tx,
+            Some(plan.project.id),
+            organization_id,
+            plan.project.name.clone(),
+            plan.project.color.clone(),
+        )
+        .await?;
+        insert_rows(&mut tx, &plan).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse {
+            data: project,
+            txid,
+        })
+    }
+
+    /// Map exported user ids to members of the target organization.
+    async fn match_members(
+        pool: &PgPool,
+        organization_id: Uuid,
+        users: &[ExportedUser],
+    ) -> Result<HashMap<Uuid, Uuid>, ProjectTransferError> {
+        let ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+        let emails: Vec<String> = users.iter().map(|u| u.email.to_lowercase()).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.id AS "id!: Uuid", LOWER(u.email) AS "email!"
+            FROM users u
+            JOIN organization_member_metadata m ON m.user_id = u.id
+            WHERE m.organization_id = $1 AND (u.id = ANY($2) OR LOWER(u.email) = ANY($3))
+            "#,
+            organization_id,
+            &ids,
+            &emails
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let member_ids: HashSet<Uuid> = rows.iter().map(|r| r.id).collect();
+        let by_email: HashMap<&str, Uuid> = rows.iter().map(|r| (r.email.as_str(), r.id)).collect();
+
+        Ok(users
+            .iter()
+            .filter_map(|user| {
+                if member_ids.contains(&user.id) {
+                    Some((user.id, user.id))
+                } else {
+                    by_email
+                        .get(user.email.to_lowercase().as_str())
+                        .map(|member_id| (user.id, *member_id))
+                }
+            })
+            .collect())
+    }
+}
+
+/// Insert the planned rows into the project `plan.project.id`, which must already exist.
+///
+/// This writes straight to the tables instead of going through the repositories' `create`
+/// functions: no activity is recorded for the imported rows, and issues keep their exported
+/// `sort_order` rather than being appended via `next_sort_order`.
+async fn insert_rows(tx: &mut Tx<'_>, plan: &ProjectExport) -> Result<(), sqlx::Error> {
+    let project_id = plan.project.id;
+
+    for status in &plan.statuses {
+        sqlx::query!(
+            r#"
+            INSERT INTO project_statuses (id, project_id, name, color, sort_order, hidden)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            status.id,
+            project_id,
+            status.name,
+            status.color,
+            status.sort_order,
+            status.hidden
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    for tag in &plan.tags {
+        sqlx::query!(
+            "INSERT INTO tags (id, project_id, name, color) VALUES ($1, $2, $3, $4)",
+            tag.id,
+            project_id,
+            tag.name,
+            tag.color
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    // Issue numbers are assigned by a trigger in insertion order. Parents are linked once
+    // every issue exists, so the export order doesn't have to put parents first.
+    for issue in &plan.issues {
+        sqlx::query!(
+            r#"
+            INSERT INTO issues (
+                id, project_id, status_id, title, description, priority,
+                start_date, target_date, completed_at, sort_order,
+                extension_metadata, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+            issue.id,
+            project_id,
+            issue.status_id,
+            issue.title,
+            issue.description,
+            issue.priority as IssuePriority,
+            issue.start_date,
+            issue.target_date,
+            issue.completed_at,
+            issue.sort_order,
+            issue.extension_metadata,
+            issue.created_at
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+    for issue in &plan.issues {
+        if let Some(parent_issue_id) = issue.parent_issue_id {
+            sqlx::query!(
+                "UPDATE issues SET parent_issue_id = $2 WHERE id = $1",
+                issue.id,
+                parent_issue_id
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    for issue_tag in &plan.issue_tags {
+        sqlx::query!(
+            "INSERT INTO issue_tags (issue_id, tag_id) VALUES ($1, $2)",
+            issue_tag.issue_id,
+            issue_tag.tag_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    for assignee in &plan.assignees {
+        sqlx::query!(
+            "INSERT INTO issue_assignees (issue_id, user_id) VALUES ($1, $2)",
+            assignee.issue_id,
+            assignee.user_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    for comment in &plan.comments {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_comments (id, issue_id, author_id, message, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $5)
+            "#,
+            comment.id,
+            comment.issue_id,
+            comment.author_id,
+            comment.message,
+            comment.created_at
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+    for comment in &plan.comments {
+        if let Some(parent_comment_id) = comment.parent_comment_id {
+            sqlx::query!(
+                "UPDATE issue_comments SET parent_comment_id = $2 WHERE id = $1",
+                comment.id,
+                parent_comment_id
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    for relationship in &plan.relationships {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_relationships (issue_id, related_issue_id, relationship_type)
+            VALUES ($1, $2, $3)
+            "#,
+            relationship.issue_id,
+            relationship.related_issue_id,
+            relationship.relationship_type as IssueRelationshipType
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Fresh ids for one kind of row, keyed by the id used in the export.
+struct IdMap {
+    kind: &'static str,
+    ids: HashMap<Uuid, Uuid>,
+}
+
+impl IdMap {
+    fn new(
+        kind: &'static str,
+        old_ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<Self, ProjectTransferError> {
+        let mut ids = HashMap::new();
+        for id in old_ids {
+            if ids.insert(id, Uuid::new_v4()).is_some() {
+                return Err(ProjectTransferError::DuplicateId { kind, id });
+            }
+        }
+        Ok(Self { kind, ids })
+    }
+
+    fn get(&self, id: Uuid) -> Result<Uuid, ProjectTransferError> {
+        self.ids
+            .get(&id)
+            .copied()
+            .ok_or(ProjectTransferError::InvalidReference {
+                kind: self.kind,
+                id,
+            })
+    }
+}
+
+/// Rewrite an export into the rows to insert: every id is replaced with a fresh one and
+/// user references are resolved through `members` (export user id to target user id).
+fn plan_import(
+    export: &ProjectExport,
+    members: &HashMap<Uuid, Uuid>,
+    actor_id: Uuid,
+) -> Result<ProjectExport, ProjectTransferError> {
+    if export.version != PROJECT_EXPORT_VERSION {
+        return Err(ProjectTransferError::UnsupportedVersion(export.version));
+    }
+
+    let statuses = IdMap::new("status", export.statuses.iter().map(|s| s.id))?;
+    let tags = IdMap::new("tag", export.tags.iter().map(|t| t.id))?;
+    let issues = IdMap::new("issue", export.issues.iter().map(|i| i.id))?;
+    let comments = IdMap::new("comment", export.comments.iter().map(|c| c.id))?;
+
+    let identity = |user_id: Uuid| -> Value {
+        match export.users.iter().find(|u| u.id == user_id) {
+            Some(user) => json!({
+                "id": user.id,
+                "email": user.email,
+                "first_name": user.first_name,
+                "last_name": user.last_name,
+                "username": user.username,
+            }),
+            None => json!({ "id": user_id }),
+        }
+    };
+    // New issue id -> identities stashed in that issue's extension_metadata
+    let mut unmatched: HashMap<Uuid, Vec<Value>> = HashMap::new();
+
+    let mut assignees = Vec::with_capacity(export.assignees.len());
+    for assignee in &export.assignees {
+        let issue_id = issues.get(assignee.issue_id)?;
+        match members.get(&assignee.user_id) {
+            Some(user_id) => assignees.push(ExportedAssignee {
+                issue_id,
+                user_id: *user_id,
+            }),
+            None => {
+                let mut stashed = identity(assignee.user_id);
+                stashed["role"] = json!("assignee");
+                unmatched.entry(issue_id).or_default().push(stashed);
+            }
+        }
+    }
+
+    let mut imported_comments = Vec::with_capacity(export.comments.len());
+    for comment in &export.comments {
+        let id = comments.get(comment.id)?;
+        let issue_id = issues.get(comment.issue_id)?;
+        let author_id = match members.get(&comment.author_id) {
+            Some(user_id) => *user_id,
+            None => {
+                let mut stashed = identity(comment.author_id);
+                stashed["role"] = json!("comment_author");
+                stashed["comment_id"] = json!(id);
+                unmatched.entry(issue_id).or_default().push(stashed);
+                actor_id
+            }
+        };
+        imported_comments.push(ExportedComment {
+            id,
+            issue_id,
+            author_id,
+            parent_comment_id: comment
+                .parent_comment_id
+                .map(|parent| comments.get(parent))
+                .transpose()?,
+            message: comment.message.clone(),
+            created_at: comment.created_at,
+        });
+    }
+
+    let mut imported_issues = Vec::with_capacity(export.issues.len());
+    for issue in &export.issues {
+        let id = issues.get(issue.id)?;
+        let mut extension_metadata = issue.extension_metadata.clone();
+        if let Some(stashed) = unmatched.remove(&id) {
+            if !extension_metadata.is_object() {
+                extension_metadata = Value::Object(Map::new());
+            }
+            extension_metadata[IMPORTED_USERS_KEY] = Value::Array(stashed);
+        }
+        imported_issues.push(ExportedIssue {
+            id,
+            status_id: statuses.get(issue.status_id)?,
+            parent_issue_id: issue
+                .parent_issue_id
+                .map(|parent| issues.get(parent))
+                .transpose()?,
+            extension_metadata,
+            ..issue.clone()
+        });
+    }
+
+    Ok(ProjectExport {
+        version: export.version,
+        exported_at: export.exported_at,
+        project: ExportedProject {
+            id: Uuid::new_v4(),
+            ..export.project.clone()
+        },
+        statuses: export
+            .statuses
+            .iter()
+            .map(|status| {
+                Ok(ExportedStatus {
+                    id: statuses.get(status.id)?,
+                    ..status.clone()
+                })
+            })
+            .collect::<Result<_, ProjectTransferError>>()?,
+        tags: export
+            .tags
+            .iter()
+            .map(|tag| {
+                Ok(ExportedTag {
+                    id: tags.get(tag.id)?,
+                    ..tag.clone()
+                })
+            })
+            .collect::<Result<_, ProjectTransferError>>()?,
+        users: export
+            .users
+            .iter()
+            .filter_map(|user| {
+                members.get(&user.id).map(|member_id| ExportedUser {
+                    id: *member_id,
+                    ..user.clone()
+                })
+            })
+            .collect(),
+        issues: imported_issues,
+        issue_tags: export
+            .issue_tags
+            .iter()
+            .map(|row| {
+                Ok(ExportedIssueTag {
+                    issue_id: issues.get(row.issue_id)?,
+                    tag_id: tags.get(row.tag_id)?,
+                })
+            })
+            .collect::<Result<_, ProjectTransferError>>()?,
+        assignees,
+        comments: imported_comments,
+        relationships: export
+            .relationships
+            .iter()
+            .map(|row| {
+                Ok(ExportedRelationship {
+                    issue_id: issues.get(row.issue_id)?,
+                    related_issue_id: issues.get(row.related_issue_id)?,
+                    relationship_type: row.relationship_type,
+                })
+            })
+            .collect::<Result<_, ProjectTransferError>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whole seconds, so values survive a round trip through Postgres unchanged.
+    fn timestamp(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_767_225_600 + minutes * 60, 0).unwrap()
+    }
+
+    fn user(email: &str) -> ExportedUser {
+        ExportedUser {
+            id: Uuid::new_v4(),
+            email: email.to_string(),
+            first_name: Some("Sam".to_string()),
+            last_name: None,
+            username: None,
+        }
+    }
+
+    fn issue(status_id: Uuid, title: &str, parent_issue_id: Option<Uuid>) -> ExportedIssue {
+        ExportedIssue {
+            id: Uuid::new_v4(),
+            status_id,
+            title: title.to_string(),
+            description: None,
+            priority: IssuePriority::Medium,
+            start_date: None,
+            target_date: None,
+            completed_at: None,
+            sort_order: 1024.0,
+            parent_issue_id,
+            extension_metadata: json!({}),
+            created_at: timestamp(0),
+        }
+    }
+
+    fn sample_export(author: &ExportedUser, assignee: &ExportedUser) -> ProjectExport {
+        let status = ExportedStatus {
+            id: Uuid::new_v4(),
+            name: "To do".to_string(),
+            color: "217 91% 60%".to_string(),
+            sort_order: 0,
+            hidden: false,
+        };
+        let tag = ExportedTag {
+            id: Uuid::new_v4(),
+            name: "bug".to_string(),
+            color: "355 65% 53%".to_string(),
+        };
+        let parent = issue(status.id, "Epic", None);
+        let child = issue(status.id, "Task", Some(parent.id));
+        let comment = ExportedComment {
+            id: Uuid::new_v4(),
+            issue_id: child.id,
+            author_id: author.id,
+            parent_comment_id: None,
+            message: "First".to_string(),
+            created_at: timestamp(1),
+        };
+        let reply = ExportedComment {
+            id: Uuid::new_v4(),
+            parent_comment_id: Some(comment.id),
+            message: "Reply".to_string(),
+            created_at: timestamp(2),
+            ..comment.clone()
+        };
+
+        ProjectExport {
+            version: PROJECT_EXPORT_VERSION,
+            exported_at: timestamp(0),
+            project: ExportedProject {
+                id: Uuid::new_v4(),
+                name: "Backend".to_string(),
+                color: "217 91% 60%".to_string(),
+            },
+            statuses: vec![status],
+            tags: vec![tag.clone()],
+            users: vec![author.clone(), assignee.clone()],
+            issue_tags: vec![ExportedIssueTag {
+                issue_id: child.id,
+                tag_id: tag.id,
+            }],
+            assignees: vec![ExportedAssignee {
+                issue_id: child.id,
+                user_id: assignee.id,
+            }],
+            relationships: vec![ExportedRelationship {
+                issue_id: child.id,
+                related_issue_id: parent.id,
+                relationship_type: IssueRelationshipType::Blocking,
+            }],
+            issues: vec![parent, child],
+            comments: vec![comment, reply],
+        }
+    }
+
+    /// Replace every uuid with its order of first appearance, so two exports can be compared
+    /// structurally regardless of which ids they were given.
+    fn normalize(export: &ProjectExport) -> Value {
+        fn walk(value: &mut Value, seen: &mut HashMap<String, usize>) {
+            match value {
+                Value::String(s) if Uuid::parse_str(s).is_ok() => {
+                    let next = seen.len();
+                    *s = format!("id-{}", seen.entry(s.clone()).or_insert(next));
+                }
+                Value::Array(items) => items.iter_mut().for_each(|v| walk(v, seen)),
+                Value::Object(map) => map.values_mut().for_each(|v| walk(v, seen)),
+                _ => {}
+            }
+        }
+        let mut value = serde_json::to_value(export).unwrap();
+        walk(&mut value, &mut HashMap::new());
+        value
+    }
+
+    #[test]
+    fn round_trip_preserves_structure_with_fresh_ids() {
+        let author = user("author@example.com");
+        let assignee = user("assignee@example.com");
+        let export = sample_export(&author, &assignee);
+        // Both users exist in the target org under different ids
+        let members = HashMap::from([(author.id, Uuid::new_v4()), (assignee.id, Uuid::new_v4())]);
+
+        let imported = plan_import(&export, &members, Uuid::new_v4()).unwrap();
+
+        assert_eq!(normalize(&imported), normalize(&export));
+        assert_ne!(imported.project.id, export.project.id);
+        assert!(
+            imported
+                .issues
+                .iter()
+                .all(|i| export.issues.iter().all(|e| e.id != i.id))
+        );
+        // Internal references point at the new rows
+        assert_eq!(
+            imported.issues[1].parent_issue_id,
+            Some(imported.issues[0].id)
+        );
+        assert_eq!(
+            imported.comments[1].parent_comment_id,
+            Some(imported.comments[0].id)
+        );
+        assert_eq!(imported.comments[0].issue_id, imported.issues[1].id);
+        assert_eq!(
+            imported.relationships[0].related_issue_id,
+            imported.issues[0].id
+        );
+        assert_eq!(imported.assignees[0].user_id, members[&assignee.id]);
+    }
+
+    #[test]
+    fn unknown_users_are_unassigned_and_stashed() {
+        let author = user("author@example.com");
+        let assignee = user("assignee@example.com");
+        let export = sample_export(&author, &assignee);
+        let actor_id = Uuid::new_v4();
+
+        let imported = plan_import(&export, &HashMap::new(), actor_id).unwrap();
+
+        assert!(imported.assignees.is_empty());
+        assert!(imported.users.is_empty());
+        assert!(imported.comments.iter().all(|c| c.author_id == actor_id));
+
+        let stashed = imported.issues[1].extension_metadata[IMPORTED_USERS_KEY]
+            .as_array()
+            .unwrap();
+        assert_eq!(stashed.len(), 3);
+        assert_eq!(stashed[0]["role"], "assignee");
+        assert_eq!(stashed[0]["email"], "assignee@example.com");
+        assert_eq!(stashed[1]["role"], "comment_author");
+        assert_eq!(stashed[1]["comment_id"], json!(imported.comments[0].id));
+        assert!(
+            imported.issues[0]
+                .extension_metadata
+                .get(IMPORTED_USERS_KEY)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn dangling_references_are_rejected() {
+        let author = user("author@example.com");
+        let mut export = sample_export(&author, &author);
+        let missing = Uuid::new_v4();
+        export.issues[1].parent_issue_id = Some(missing);
+
+        assert!(matches!(
+            plan_import(&export, &HashMap::new(), Uuid::new_v4()),
+            Err(ProjectTransferError::InvalidReference { kind: "issue", id }) if id == missing
+        ));
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let author = user("author@example.com");
+        let mut export = sample_export(&author, &author);
+        export.version = PROJECT_EXPORT_VERSION + 1;
+
+        assert!(matches!(
+            plan_import(&export, &HashMap::new(), Uuid::new_v4()),
+            Err(ProjectTransferError::UnsupportedVersion(_))
+        ));
+    }
+
+    /// Writes an import plan with `insert_rows` and reads it back with `export`.
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL pointing at the remote Postgres database"]
+    async fn insert_rows_round_trips_through_export() {
+        let pool = crate::db::test_pool().await;
+
+        let organization_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO organizations (id, name, slug) VALUES ($1, 'Transfer', $2)")
+            .bind(organization_id)
+            .bind(format!("transfer-{organization_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        let author = user(&format!("author-{organization_id}@example.com"));
+        let assignee = user(&format!("assignee-{organization_id}@example.com"));
+        let mut members = HashMap::new();
+        for exported in [&author, &assignee] {
+            let id = Uuid::new_v4();
+            sqlx::query("INSERT INTO users (id, email, first_name) VALUES ($1, $2, $3)")
+                .bind(id)
+                .bind(&exported.email)
+                .bind(&exported.first_name)
+                .execute(&pool)
+                .await
+                .unwrap();
+            members.insert(exported.id, id);
+        }
+
+        let export = sample_export(&author, &assignee);
+        let mut plan = plan_import(&export, &members, Uuid::new_v4()).unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        ProjectRepository::create(
+            &mut *tx,
+            Some(plan.project.id),
+            organization_id,
+            plan.project.name.clone(),
+            plan.project.color.clone(),
+        )
+        .await
+        .unwrap();
+        insert_rows(&mut tx, &plan).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut exported = ProjectTransferRepository::export(&pool, plan.project.id)
+            .await
+            .unwrap();
+        exported.exported_at = plan.exported_at;
+        plan.users.sort_by(|a, b| a.email.cmp(&b.email));
+
+        assert_eq!(normalize(&exported), normalize(&plan));
+        assert_eq!(exported.issues[1].parent_issue_id, Some(plan.issues[0].id));
+
+        sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(organization_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(members.values().copied().collect::<Vec<_>>())
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+}
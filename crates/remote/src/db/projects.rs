@@ -1,11 +1,20 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{get_txid, project_statuses::ProjectStatusRepository, tags::TagRepository};
+use super::{
+    activity_events::{
+        ActivityAction, ActivityEntityType, ActivityEventRepository, NewActivityEvent,
+    },
+    get_txid,
+    project_statuses::ProjectStatusRepository,
+    soft_delete::{self, SoftDelete},
+    tags::TagRepository,
+};
 use crate::mutation_types::{DeleteResponse, MutationResponse};
 
 /// Default color for the initial project created with personal organizations
@@ -38,6 +47,10 @@ pub enum ProjectError {
     Database(#[from] sqlx::Error),
 }
 
+impl SoftDelete for Project {
+    const TABLE: &'static str = "projects";
+}
+
 pub struct ProjectRepository;
 
 impl ProjectRepository {
@@ -56,7 +69,7 @@ impl ProjectRepository {
                 created_at       AS "created_at!: DateTime<Utc>",
                 updated_at       AS "updated_at!: DateTime<Utc>"
             FROM projects
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -125,7 +138,7 @@ impl ProjectRepository {
                 created_at       AS "created_at!: DateTime<Utc>",
                 updated_at       AS "updated_at!: DateTime<Utc>"
             FROM projects
-            WHERE organization_id = $1
+            WHERE organization_id = $1 AND deleted_at IS NULL
             ORDER BY created_at DESC
             "#,
             organization_id
@@ -140,6 +153,7 @@ impl ProjectRepository {
     /// when None is provided.
     pub async fn update(
         pool: &PgPool,
+        actor_id: Uuid,
         id: Uuid,
         name: Option<String>,
         color: Option<String>,
@@ -171,16 +185,44 @@ impl ProjectRepository {
         .fetch_one(&mut *tx)
         .await?;
 
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: data.id,
+                actor_id,
+                entity_type: ActivityEntityType::Project,
+                entity_id: data.id,
+                action: ActivityAction::Updated,
+                payload: json!({ "name": data.name }),
+            },
+        )
+        .await?;
+
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
         Ok(MutationResponse { data, txid })
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, ProjectError> {
+    pub async fn delete(
+        pool: &PgPool,
+        actor_id: Uuid,
+        id: Uuid,
+    ) -> Result<DeleteResponse, ProjectError> {
         let mut tx = pool.begin().await?;
-        sqlx::query!("DELETE FROM projects WHERE id = $1", id)
-            .execute(&mut *tx)
+        if soft_delete::soft_delete::<Project, _>(&mut *tx, id).await? {
+            ActivityEventRepository::record(
+                &mut *tx,
+                NewActivityEvent {
+                    project_id: id,
+                    actor_id,
+                    entity_type: ActivityEntityType::Project,
+                    entity_id: id,
+                    action: ActivityAction::Deleted,
+                    payload: json!({}),
+                },
+            )
             .await?;
+        }
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
         Ok(DeleteResponse { txid })
@@ -197,7 +239,7 @@ impl ProjectRepository {
             r#"
             SELECT organization_id
             FROM projects
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             project_id
         )
@@ -235,6 +277,7 @@ impl ProjectRepository {
     /// Creates a project along with default tags and statuses in a single transaction.
     pub async fn create_with_defaults(
         pool: &PgPool,
+        actor_id: Uuid,
         id: Option<Uuid>,
         organization_id: Uuid,
         name: String,
@@ -252,6 +295,19 @@ impl ProjectRepository {
             .await
             .map_err(|e| ProjectError::DefaultStatusesFailed(e.to_string()))?;
 
+        ActivityEventRepository::record(
+            &mut *tx,
+            NewActivityEvent {
+                project_id: project.id,
+                actor_id,
+                entity_type: ActivityEntityType::Project,
+                entity_id: project.id,
+                action: ActivityAction::Created,
+                payload: json!({ "name": project.name }),
+            },
+        )
+        .await?;
+
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
         Ok(MutationResponse {
@@ -0,0 +1,68 @@
+//! Shared `deleted_at` handling for soft-deletable tables.
+//!
+//! Soft-deleted rows stay in place with `deleted_at` set, so they can be restored later.
+//! Reads should exclude them by default; `sqlx` query macros need literal SQL, so static
+//! queries inline [`NOT_DELETED`] rather than building it at runtime.
+
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+/// Predicate that keeps only rows which have not been soft-deleted.
+pub const NOT_DELETED: &str = "deleted_at IS NULL";
+
+/// A table with a nullable `deleted_at` column and a UUID `id` primary key.
+pub trait SoftDelete {
+    const TABLE: &'static str;
+}
+
+/// Append the [`NOT_DELETED`] filter to a `WHERE` clause.
+pub fn exclude_deleted(where_clause: &str) -> String {
+    if where_clause.trim().is_empty() {
+        NOT_DELETED.to_string()
+    } else {
+        format!("({where_clause}) AND {NOT_DELETED}")
+    }
+}
+
+/// Mark a row as deleted. Returns `false` if it does not exist or is already deleted.
+pub async fn soft_delete<'e, T, E>(executor: E, id: Uuid) -> Result<bool, sqlx::Error>
+where
+    T: SoftDelete,
+    E: Executor<'e, Database = Postgres>,
+{
+    let sql = format!(
+        "UPDATE {} SET deleted_at = NOW() WHERE {}",
+        T::TABLE,
+        exclude_deleted("id = $1")
+    );
+    let result = sqlx::query(&sql).bind(id).execute(executor).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Clear a row's deletion mark. Returns `false` if it does not exist or is not deleted.
+pub async fn restore<'e, T, E>(executor: E, id: Uuid) -> Result<bool, sqlx::Error>
+where
+    T: SoftDelete,
+    E: Executor<'e, Database = Postgres>,
+{
+    let sql = format!(
+        "UPDATE {} SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+        T::TABLE
+    );
+    let result = sqlx::query(&sql).bind(id).execute(executor).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::exclude_deleted;
+
+    #[test]
+    fn exclude_deleted_wraps_existing_clause() {
+        assert_eq!(
+            exclude_deleted("a = $1 OR b = $2"),
+            "(a = $1 OR b = $2) AND deleted_at IS NULL"
+        );
+        assert_eq!(exclude_deleted(""), "deleted_at IS NULL");
+    }
+}
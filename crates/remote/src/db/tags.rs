@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
@@ -14,14 +15,43 @@ pub struct Tag {
     pub project_id: Uuid,
     pub name: String,
     pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Error)]
 pub enum TagError {
+    #[error("a tag named '{0}' already exists in this project")]
+    DuplicateName(String),
     #[error(transparent)]
     Database(#[from] sqlx::Error),
 }
 
+/// Unique index enforcing case-insensitive tag names per project.
+const LOWER_NAME_INDEX: &str = "idx_tags_project_id_lower_name";
+
+/// Whether `name` collides with any of `existing` names, ignoring case and surrounding whitespace.
+fn name_conflicts<'a>(existing: impl IntoIterator<Item = &'a str>, name: &str) -> bool {
+    let normalized = name.trim().to_lowercase();
+    existing
+        .into_iter()
+        .any(|existing| existing.trim().to_lowercase() == normalized)
+}
+
+/// Convert a unique violation on the tag name index into [`TagError::DuplicateName`].
+fn map_duplicate_name(error: sqlx::Error, name: &str) -> TagError {
+    if let Some(db_error) = error.as_database_error()
+        && db_error.is_unique_violation()
+        && matches!(
+            db_error.constraint(),
+            Some(LOWER_NAME_INDEX) | Some("tags_project_id_name_key")
+        )
+    {
+        return TagError::DuplicateName(name.to_string());
+    }
+    TagError::Database(error)
+}
+
 /// Default tags that are created for each new project
 /// Colors are in HSL format: "H S% L%"
 pub const DEFAULT_TAGS: &[(&str, &str)] = &[
@@ -42,7 +72,9 @@ impl TagRepository {
                 id          AS "id!: Uuid",
                 project_id  AS "project_id!: Uuid",
                 name        AS "name!",
-                color       AS "color!"
+                color       AS "color!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
             FROM tags
             WHERE id = $1
             "#,
@@ -61,6 +93,8 @@ impl TagRepository {
         name: String,
         color: String,
     ) -> Result<MutationResponse<Tag>, TagError> {
+        Self::ensure_unique_name(pool, project_id, None, &name).await?;
+
         let mut tx = pool.begin().await?;
 
         let id = id.unwrap_or_else(Uuid::new_v4);
@@ -73,7 +107,9 @@ impl TagRepository {
                 id          AS "id!: Uuid",
                 project_id  AS "project_id!: Uuid",
                 name        AS "name!",
-                color       AS "color!"
+                color       AS "color!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
             "#,
             id,
             project_id,
@@ -81,7 +117,8 @@ impl TagRepository {
             color
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(|error| map_duplicate_name(error, &name))?;
 
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
@@ -97,6 +134,12 @@ impl TagRepository {
         name: Option<String>,
         color: Option<String>,
     ) -> Result<MutationResponse<Tag>, TagError> {
+        if let Some(name) = name.as_deref()
+            && let Some(tag) = Self::find_by_id(pool, id).await?
+        {
+            Self::ensure_unique_name(pool, tag.project_id, Some(id), name).await?;
+        }
+
         let mut tx = pool.begin().await?;
 
         let data = sqlx::query_as!(
@@ -105,20 +148,24 @@ impl TagRepository {
             UPDATE tags
             SET
                 name = COALESCE($1, name),
-                color = COALESCE($2, color)
+                color = COALESCE($2, color),
+                updated_at = NOW()
             WHERE id = $3
             RETURNING
                 id          AS "id!: Uuid",
                 project_id  AS "project_id!: Uuid",
                 name        AS "name!",
-                color       AS "color!"
+                color       AS "color!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
             "#,
             name,
             color,
             id
         )
         .fetch_one(&mut *tx)
-        .await?;
+        .await
+        .map_err(|error| map_duplicate_name(error, name.as_deref().unwrap_or_default()))?;
 
         let txid = get_txid(&mut *tx).await?;
         tx.commit().await?;
@@ -147,7 +194,9 @@ impl TagRepository {
                 id          AS "id!: Uuid",
                 project_id  AS "project_id!: Uuid",
                 name        AS "name!",
-                color       AS "color!"
+                color       AS "color!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
             FROM tags
             WHERE project_id = $1
             "#,
@@ -159,6 +208,25 @@ impl TagRepository {
         Ok(records)
     }
 
+    /// Reject `name` if another tag in the project already uses it, ignoring case and surrounding whitespace.
+    /// The unique index on `LOWER(BTRIM(name))` backs this up against concurrent writers.
+    async fn ensure_unique_name(
+        pool: &PgPool,
+        project_id: Uuid,
+        exclude_id: Option<Uuid>,
+        name: &str,
+    ) -> Result<(), TagError> {
+        let tags = Self::list_by_project(pool, project_id).await?;
+        let existing = tags
+            .iter()
+            .filter(|tag| Some(tag.id) != exclude_id)
+            .map(|tag| tag.name.as_str());
+        if name_conflicts(existing, name) {
+            return Err(TagError::DuplicateName(name.to_string()));
+        }
+        Ok(())
+    }
+
     pub async fn create_default_tags<'e, E>(
         executor: E,
         project_id: Uuid,
@@ -179,7 +247,9 @@ impl TagRepository {
                 id          AS "id!: Uuid",
                 project_id  AS "project_id!: Uuid",
                 name        AS "name!",
-                color       AS "color!"
+                color       AS "color!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
             "#,
             project_id,
             &names,
@@ -191,3 +261,20 @@ impl TagRepository {
         Ok(tags)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::name_conflicts;
+
+    #[test]
+    fn differing_case_conflicts() {
+        assert!(name_conflicts(["bug", "feature"], "Bug"));
+        assert!(name_conflicts(["Feature"], " feature "));
+    }
+
+    #[test]
+    fn distinct_names_do_not_conflict() {
+        assert!(!name_conflicts(["bug", "feature"], "bugfix"));
+        assert!(!name_conflicts(std::iter::empty(), "bug"));
+    }
+}
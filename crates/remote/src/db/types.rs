@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use ts_rs::TS;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
@@ -73,6 +74,44 @@ pub fn is_valid_hsl_color(color: &str) -> bool {
     true
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum HexColorError {
+    #[error("hex color must start with '#'")]
+    MissingHash,
+    #[error("hex color must have 3 or 6 digits")]
+    InvalidLength,
+    #[error("hex color may only contain 0-9 and a-f")]
+    InvalidCharacter,
+}
+
+/// Validates a `#rgb` or `#rrggbb` hex color and returns it in lowercase `#rrggbb` form.
+pub fn validate_hex_color(color: &str) -> Result<String, HexColorError> {
+    let digits = color.strip_prefix('#').ok_or(HexColorError::MissingHash)?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(HexColorError::InvalidCharacter);
+    }
+
+    let digits = digits.to_ascii_lowercase();
+    match digits.len() {
+        3 => Ok(digits.chars().fold(String::from("#"), |mut out, c| {
+            out.push(c);
+            out.push(c);
+            out
+        })),
+        6 => Ok(format!("#{digits}")),
+        _ => Err(HexColorError::InvalidLength),
+    }
+}
+
+/// Normalizes a tag or status color before it is persisted. HSL values are kept as-is;
+/// hex values are expanded to lowercase `#rrggbb`.
+pub fn normalize_color(color: &str) -> Result<String, HexColorError> {
+    if is_valid_hsl_color(color) {
+        return Ok(color.to_string());
+    }
+    validate_hex_color(color)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +137,49 @@ mod tests {
         assert!(!is_valid_hsl_color("180, 50%, 50%")); // Wrong separator
         assert!(!is_valid_hsl_color("")); // Empty
     }
+
+    #[test]
+    fn test_valid_hex_colors() {
+        assert_eq!(validate_hex_color("#3b82f6").unwrap(), "#3b82f6");
+        assert_eq!(validate_hex_color("#3B82F6").unwrap(), "#3b82f6");
+    }
+
+    #[test]
+    fn test_short_hex_colors_expand() {
+        assert_eq!(validate_hex_color("#fff").unwrap(), "#ffffff");
+        assert_eq!(validate_hex_color("#A1c").unwrap(), "#aa11cc");
+    }
+
+    #[test]
+    fn test_hex_colors_require_hash() {
+        assert_eq!(
+            validate_hex_color("3b82f6"),
+            Err(HexColorError::MissingHash)
+        );
+        assert_eq!(validate_hex_color(""), Err(HexColorError::MissingHash));
+    }
+
+    #[test]
+    fn test_invalid_hex_colors() {
+        assert_eq!(
+            validate_hex_color("#gg0000"),
+            Err(HexColorError::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_hex_color("#ff 000"),
+            Err(HexColorError::InvalidCharacter)
+        );
+        assert_eq!(
+            validate_hex_color("#ff00"),
+            Err(HexColorError::InvalidLength)
+        );
+        assert_eq!(validate_hex_color("#"), Err(HexColorError::InvalidLength));
+    }
+
+    #[test]
+    fn test_normalize_color_accepts_hsl_and_hex() {
+        assert_eq!(normalize_color("217 91% 60%").unwrap(), "217 91% 60%");
+        assert_eq!(normalize_color("#ABC").unwrap(), "#aabbcc");
+        assert!(normalize_color("hsl(180, 50%, 50%)").is_err());
+    }
 }
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, query_as};
+use thiserror::Error;
 use ts_rs::TS;
+use url::Url;
 use uuid::Uuid;
 
 use super::{Tx, identity_errors::IdentityError};
@@ -14,6 +18,8 @@ pub struct User {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -27,6 +33,57 @@ pub struct UserData {
     pub username: Option<String>,
 }
 
+/// Partial profile update; omitted fields are left unchanged.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdateUserProfileRequest {
+    #[ts(optional)]
+    pub display_name: Option<String>,
+    #[ts(optional)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MemberProfile {
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MemberProfilesResponse {
+    pub profiles: HashMap<Uuid, MemberProfile>,
+}
+
+pub const MAX_DISPLAY_NAME_CHARS: usize = 80;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProfileValidationError {
+    #[error("display_name must be between 1 and {MAX_DISPLAY_NAME_CHARS} characters")]
+    DisplayNameLength,
+    #[error("avatar_url must be an http or https URL")]
+    InvalidAvatarUrl,
+}
+
+/// Trim the display name and check it is 1–80 characters long.
+pub fn validate_display_name(display_name: &str) -> Result<&str, ProfileValidationError> {
+    let trimmed = display_name.trim();
+    let len = trimmed.chars().count();
+    if len == 0 || len > MAX_DISPLAY_NAME_CHARS {
+        return Err(ProfileValidationError::DisplayNameLength);
+    }
+    Ok(trimmed)
+}
+
+pub fn validate_avatar_url(avatar_url: &str) -> Result<&str, ProfileValidationError> {
+    let trimmed = avatar_url.trim();
+    match Url::parse(trimmed) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(trimmed),
+        _ => Err(ProfileValidationError::InvalidAvatarUrl),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UpsertUser<'a> {
     pub id: Uuid,
@@ -61,6 +118,8 @@ impl<'a> UserRepository<'a> {
                 first_name   AS "first_name?",
                 last_name    AS "last_name?",
                 username     AS "username?",
+                display_name AS "display_name?",
+                avatar_url   AS "avatar_url?",
                 created_at   AS "created_at!",
                 updated_at   AS "updated_at!"
             FROM users
@@ -72,6 +131,75 @@ impl<'a> UserRepository<'a> {
         .await?
         .ok_or(IdentityError::NotFound)
     }
+
+    /// Update the given profile fields, leaving `None` ones as they are. The
+    /// `updated_at` trigger bumps the timestamp so clients can revalidate cached profiles.
+    pub async fn update_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> Result<User, IdentityError> {
+        query_as!(
+            User,
+            r#"
+            UPDATE users
+            SET display_name = COALESCE($2, display_name),
+                avatar_url   = COALESCE($3, avatar_url)
+            WHERE id = $1
+            RETURNING
+                id           AS "id!: Uuid",
+                email        AS "email!",
+                first_name   AS "first_name?",
+                last_name    AS "last_name?",
+                username     AS "username?",
+                display_name AS "display_name?",
+                avatar_url   AS "avatar_url?",
+                created_at   AS "created_at!",
+                updated_at   AS "updated_at!"
+            "#,
+            user_id,
+            display_name,
+            avatar_url
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(IdentityError::NotFound)
+    }
+
+    /// Profiles of every member of the organization, keyed by user id.
+    pub async fn member_profiles(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<HashMap<Uuid, MemberProfile>, IdentityError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                u.id           AS "id!: Uuid",
+                u.display_name AS "display_name?",
+                u.avatar_url   AS "avatar_url?"
+            FROM users u
+            JOIN organization_member_metadata m ON m.user_id = u.id
+            WHERE m.organization_id = $1
+            "#,
+            organization_id
+        )
+        .fetch_all(self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.id,
+                    MemberProfile {
+                        display_name: row.display_name,
+                        avatar_url: row.avatar_url,
+                    },
+                )
+            })
+            .collect())
+    }
 }
 
 async fn upsert_user(pool: &PgPool, user: &UpsertUser<'_>) -> Result<User, sqlx::Error> {
@@ -91,6 +219,8 @@ async fn upsert_user(pool: &PgPool, user: &UpsertUser<'_>) -> Result<User, sqlx:
             first_name   AS "first_name?",
             last_name    AS "last_name?",
             username     AS "username?",
+            display_name AS "display_name?",
+            avatar_url   AS "avatar_url?",
             created_at   AS "created_at!",
             updated_at   AS "updated_at!"
         "#,
@@ -129,3 +259,39 @@ pub async fn fetch_user(tx: &mut Tx<'_>, user_id: Uuid) -> Result<Option<UserDat
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_name_is_trimmed_and_length_checked() {
+        assert_eq!(validate_display_name("  Ada Lovelace "), Ok("Ada Lovelace"));
+        assert_eq!(
+            validate_display_name("   "),
+            Err(ProfileValidationError::DisplayNameLength)
+        );
+        assert!(validate_display_name(&"é".repeat(MAX_DISPLAY_NAME_CHARS)).is_ok());
+        assert_eq!(
+            validate_display_name(&"a".repeat(MAX_DISPLAY_NAME_CHARS + 1)),
+            Err(ProfileValidationError::DisplayNameLength)
+        );
+    }
+
+    #[test]
+    fn avatar_url_must_be_http_or_https() {
+        assert!(validate_avatar_url("https://example.com/a.png").is_ok());
+        assert!(validate_avatar_url("http://example.com/a.png").is_ok());
+        for url in [
+            "javascript:alert(1)",
+            "data:image/png;base64,AA",
+            "/a.png",
+            "",
+        ] {
+            assert_eq!(
+                validate_avatar_url(url),
+                Err(ProfileValidationError::InvalidAvatarUrl)
+            );
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Postgres, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "workspace_review_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum WorkspaceReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WorkspaceReview {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub reviewer_user_id: Uuid,
+    pub state: WorkspaceReviewState,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Overall review outcome of a workspace, from each reviewer's latest approval or change
+/// request. Comments don't change it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum AggregateReviewState {
+    Pending,
+    Approved,
+    ChangesRequested,
+}
+
+impl AggregateReviewState {
+    /// Any outstanding change request wins; otherwise a single approval is enough.
+    pub fn from_latest(latest: &[WorkspaceReview]) -> Self {
+        if latest
+            .iter()
+            .any(|r| r.state == WorkspaceReviewState::ChangesRequested)
+        {
+            Self::ChangesRequested
+        } else if latest
+            .iter()
+            .any(|r| r.state == WorkspaceReviewState::Approved)
+        {
+            Self::Approved
+        } else {
+            Self::Pending
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(export)]
+pub struct CreateWorkspaceReviewRequest {
+    pub state: WorkspaceReviewState,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct ListWorkspaceReviewsResponse {
+    pub reviews: Vec<WorkspaceReview>,
+    pub review_state: AggregateReviewState,
+}
+
+#[derive(Debug, Error)]
+pub enum WorkspaceReviewError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct WorkspaceReviewRepository;
+
+impl WorkspaceReviewRepository {
+    pub async fn create<'e, E>(
+        executor: E,
+        workspace_id: Uuid,
+        reviewer_user_id: Uuid,
+        state: WorkspaceReviewState,
+        body: &str,
+    ) -> Result<WorkspaceReview, WorkspaceReviewError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            WorkspaceReview,
+            r#"
+            INSERT INTO workspace_reviews (workspace_id, reviewer_user_id, state, body)
+            VALUES ($1, $2, $3, $4)
+            RETURNING
+                id               AS "id!: Uuid",
+                workspace_id     AS "workspace_id!: Uuid",
+                reviewer_user_id AS "reviewer_user_id!: Uuid",
+                state            AS "state!: WorkspaceReviewState",
+                body             AS "body!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            "#,
+            workspace_id,
+            reviewer_user_id,
+            state as WorkspaceReviewState,
+            body
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_by_workspace<'e, E>(
+        executor: E,
+        workspace_id: Uuid,
+    ) -> Result<Vec<WorkspaceReview>, WorkspaceReviewError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            WorkspaceReview,
+            r#"
+            SELECT
+                id               AS "id!: Uuid",
+                workspace_id     AS "workspace_id!: Uuid",
+                reviewer_user_id AS "reviewer_user_id!: Uuid",
+                state            AS "state!: WorkspaceReviewState",
+                body             AS "body!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            FROM workspace_reviews
+            WHERE workspace_id = $1
+            ORDER BY created_at ASC
+            "#,
+            workspace_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Each reviewer's most recent approval or change request on each of `workspace_ids`.
+    /// Plain comments are skipped so they don't mask an earlier decision.
+    pub async fn latest_per_reviewer<'e, E>(
+        executor: E,
+        workspace_ids: &[Uuid],
+    ) -> Result<Vec<WorkspaceReview>, WorkspaceReviewError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            WorkspaceReview,
+            r#"
+            SELECT DISTINCT ON (workspace_id, reviewer_user_id)
+                id               AS "id!: Uuid",
+                workspace_id     AS "workspace_id!: Uuid",
+                reviewer_user_id AS "reviewer_user_id!: Uuid",
+                state            AS "state!: WorkspaceReviewState",
+                body             AS "body!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            FROM workspace_reviews
+            WHERE workspace_id = ANY($1)
+              AND state <> 'commented'
+            ORDER BY workspace_id, reviewer_user_id, created_at DESC
+            "#,
+            workspace_ids
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Aggregate review state for each of `workspace_ids`; workspaces without decisive
+    /// reviews map to [`AggregateReviewState::Pending`].
+    pub async fn aggregate_states<'e, E>(
+        executor: E,
+        workspace_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, AggregateReviewState>, WorkspaceReviewError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let mut latest_by_workspace: HashMap<Uuid, Vec<WorkspaceReview>> = HashMap::new();
+        for review in Self::latest_per_reviewer(executor, workspace_ids).await? {
+            latest_by_workspace
+                .entry(review.workspace_id)
+                .or_default()
+                .push(review);
+        }
+
+        Ok(workspace_ids
+            .iter()
+            .map(|id| {
+                let latest = latest_by_workspace
+                    .get(id)
+                    .map(Vec::as_slice)
+                    .unwrap_or(&[]);
+                (*id, AggregateReviewState::from_latest(latest))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn review(reviewer: Uuid, state: WorkspaceReviewState) -> WorkspaceReview {
+        WorkspaceReview {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::nil(),
+            reviewer_user_id: reviewer,
+            state,
+            body: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn no_decisions_is_pending() {
+        assert_eq!(
+            AggregateReviewState::from_latest(&[]),
+            AggregateReviewState::Pending
+        );
+    }
+
+    #[test]
+    fn approval_without_change_requests_is_approved() {
+        let latest = [
+            review(Uuid::new_v4(), WorkspaceReviewState::Approved),
+            review(Uuid::new_v4(), WorkspaceReviewState::Approved),
+        ];
+        assert_eq!(
+            AggregateReviewState::from_latest(&latest),
+            AggregateReviewState::Approved
+        );
+    }
+
+    #[test]
+    fn outstanding_change_request_blocks_approval() {
+        let latest = [
+            review(Uuid::new_v4(), WorkspaceReviewState::Approved),
+            review(Uuid::new_v4(), WorkspaceReviewState::ChangesRequested),
+        ];
+        assert_eq!(
+            AggregateReviewState::from_latest(&latest),
+            AggregateReviewState::ChangesRequested
+        );
+    }
+}
@@ -5,6 +5,9 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::{get_txid, workspace_reviews::AggregateReviewState};
+use crate::mutation_types::MutationResponse;
+
 /// Workspace metadata pushed from local clients
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, TS)]
 #[ts(export)]
@@ -22,6 +25,16 @@ pub struct Workspace {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A workspace together with the aggregate outcome of its teammate reviews
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct WorkspaceWithReviewState {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub workspace: Workspace,
+    pub review_state: AggregateReviewState,
+}
+
 #[derive(Debug, Error)]
 pub enum WorkspaceError {
     #[error(transparent)]
@@ -176,6 +189,76 @@ impl WorkspaceRepository {
         Ok(count)
     }
 
+    pub async fn list_by_issue(
+        pool: &PgPool,
+        issue_id: Uuid,
+    ) -> Result<Vec<Workspace>, WorkspaceError> {
+        let records = sqlx::query_as!(
+            Workspace,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                owner_user_id       AS "owner_user_id!: Uuid",
+                issue_id            AS "issue_id: Uuid",
+                local_workspace_id  AS "local_workspace_id: Uuid",
+                archived            AS "archived!: bool",
+                files_changed       AS "files_changed: i32",
+                lines_added         AS "lines_added: i32",
+                lines_removed       AS "lines_removed: i32",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM workspaces
+            WHERE issue_id = $1
+            ORDER BY created_at ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Link the workspace to `issue_id`, or clear the link when `None`.
+    pub async fn set_issue(
+        pool: &PgPool,
+        id: Uuid,
+        issue_id: Option<Uuid>,
+    ) -> Result<MutationResponse<Workspace>, WorkspaceError> {
+        let mut tx = pool.begin().await?;
+
+        let data = sqlx::query_as!(
+            Workspace,
+            r#"
+            UPDATE workspaces
+            SET issue_id = $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                owner_user_id       AS "owner_user_id!: Uuid",
+                issue_id            AS "issue_id: Uuid",
+                local_workspace_id  AS "local_workspace_id: Uuid",
+                archived            AS "archived!: bool",
+                files_changed       AS "files_changed: i32",
+                lines_added         AS "lines_added: i32",
+                lines_removed       AS "lines_removed: i32",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            "#,
+            issue_id,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
     pub async fn update(
         pool: &PgPool,
         id: Uuid,
@@ -10,6 +10,7 @@ use serde_json::Value;
 
 use crate::{
     db::{
+        issue_activity::IssueActivity,
         issue_assignees::IssueAssignee,
         issue_comment_reactions::IssueCommentReaction,
         issue_comments::IssueComment,
@@ -40,7 +41,7 @@ crate::define_entity!(
     table: "projects",
     mutation_scope: Organization,
     shape: {
-        where_clause: r#""organization_id" = $1"#,
+        where_clause: r#""organization_id" = $1 AND "deleted_at" IS NULL"#,
         params: ["organization_id"],
         url: "/shape/projects",
     },
@@ -102,11 +103,16 @@ crate::define_entity!(
     fields: [name: String, color: String, sort_order: i32, hidden: bool],
 );
 
-// Issue: simple project scope with many fields
+// Issue: project scope, soft-deleted issues are not streamed
 crate::define_entity!(
     Issue,
     table: "issues",
-    scope: Project,
+    mutation_scope: Project,
+    shape: {
+        where_clause: r#""project_id" = $1 AND "deleted_at" IS NULL"#,
+        params: ["project_id"],
+        url: "/shape/project/{project_id}/issues",
+    },
     fields: [
         status_id: uuid::Uuid,
         title: String,
@@ -187,6 +193,24 @@ crate::define_entity!(
     },
 );
 
+// Per-field issue history, written alongside issue updates; append-only, so shape-only
+crate::define_shape!(
+    ISSUE_ACTIVITY_SHAPE, IssueActivity,
+    table: "issue_activity",
+    where_clause: r#""project_id" = $1"#,
+    url: "/shape/project/{project_id}/issue_activity",
+    params: ["project_id"]
+);
+
+// Issues in a project assigned to the requesting user; user_id is filled in by the proxy
+crate::define_shape!(
+    MY_ASSIGNED_ISSUES_SHAPE, Issue,
+    table: "issues",
+    where_clause: r#""project_id" = $1 AND "deleted_at" IS NULL AND "id" IN (SELECT issue_id FROM issue_assignees WHERE "user_id" = $2)"#,
+    url: "/shape/project/{project_id}/my_assigned_issues",
+    params: ["project_id", "user_id"]
+);
+
 // =============================================================================
 // Issue-scoped entities (both mutations and streaming at issue level)
 // =============================================================================
@@ -197,11 +221,33 @@ crate::define_entity!(
     table: "issue_comments",
     mutation_scope: Issue,
     shape: {
-        where_clause: r#""issue_id" = $1"#,
+        where_clause: r#""issue_id" = $1 AND "deleted_at" IS NULL"#,
         params: ["issue_id"],
         url: "/shape/issue/{issue_id}/comments",
     },
-    fields: [message: String],
+    fields: [
+        #[ts(optional, type = "string | null")]
+        parent_comment_id: Option<uuid::Uuid>,
+        message: String,
+    ],
+);
+
+// A single issue, for clients that deep link to one issue without syncing its project
+crate::define_shape!(
+    SINGLE_ISSUE_SHAPE, Issue,
+    table: "issues",
+    where_clause: r#""id" = $1 AND "deleted_at" IS NULL"#,
+    url: "/shape/issue/{issue_id}",
+    params: ["issue_id"]
+);
+
+// Direct children of an issue
+crate::define_shape!(
+    SUB_ISSUES_SHAPE, Issue,
+    table: "issues",
+    where_clause: r#""parent_issue_id" = $1 AND "deleted_at" IS NULL"#,
+    url: "/shape/issue/{issue_id}/sub_issues",
+    params: ["issue_id"]
 );
 
 // =============================================================================
@@ -267,7 +313,40 @@ pub fn all_shapes() -> Vec<&'static dyn crate::shapes::ShapeExport> {
         &ISSUE_TAG_SHAPE,
         &ISSUE_RELATIONSHIP_SHAPE,
         &PULL_REQUEST_SHAPE,
+        &ISSUE_ACTIVITY_SHAPE,
         &ISSUE_COMMENT_SHAPE,
         &ISSUE_COMMENT_REACTION_SHAPE,
+        &MY_ASSIGNED_ISSUES_SHAPE,
+        &SINGLE_ISSUE_SHAPE,
+        &SUB_ISSUES_SHAPE,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn omitted_update_fields_are_left_unset() {
+        let request: UpdateTagRequest = serde_json::from_str(r##"{"color":"#ff0000"}"##).unwrap();
+        assert!(request.name.is_none());
+        assert_eq!(request.color.as_deref(), Some("#ff0000"));
+        assert!(!request.is_empty());
+
+        let request: UpdateProjectStatusRequest =
+            serde_json::from_str(r#"{"hidden":true}"#).unwrap();
+        assert!(request.name.is_none());
+        assert!(request.color.is_none());
+        assert!(request.sort_order.is_none());
+        assert_eq!(request.hidden, Some(true));
+    }
+
+    #[test]
+    fn update_with_no_fields_is_empty() {
+        let request: UpdateTagRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.is_empty());
+
+        let request: UpdateProjectStatusRequest = serde_json::from_str(r#"{"name":null}"#).unwrap();
+        assert!(request.is_empty());
+    }
+}
@@ -177,14 +177,14 @@ macro_rules! define_entity {
         $entity:ident,
         table: $table:literal,
         scope: $scope:ident,
-        fields: [$($field:ident : $ty:ty),* $(,)?] $(,)?
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?] $(,)?
     ) => {
         // Generate mutation types using existing macro
         $crate::define_mutation_types!(
             $entity,
             table: $table,
             scope: $scope,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
 
         // Generate shape using existing macro with auto-derived where clause
@@ -232,14 +232,14 @@ macro_rules! define_entity {
         mutation_scope: $mut_scope:ident,
         shape_scope: $shape_scope:ident,
         shape_where: $where_clause:literal,
-        fields: [$($field:ident : $ty:ty),* $(,)?] $(,)?
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?] $(,)?
     ) => {
         // Generate mutation types
         $crate::define_mutation_types!(
             $entity,
             table: $table,
             scope: $mut_scope,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
 
         // Generate shape with custom where clause
@@ -271,14 +271,14 @@ macro_rules! define_entity {
             params: [$($param:literal),* $(,)?],
             url: $url:literal $(,)?
         },
-        fields: [$($field:ident : $ty:ty),* $(,)?] $(,)?
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?] $(,)?
     ) => {
         // Generate mutation types
         $crate::define_mutation_types!(
             $entity,
             table: $table,
             scope: $mut_scope,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
 
         // Generate shape with fully custom config
@@ -2,7 +2,9 @@
 ///
 /// This macro generates:
 /// - `Create{Entity}Request` struct with parent_id (based on scope) and all fields required
-/// - `Update{Entity}Request` struct with all fields optional (for partial updates)
+/// - `Update{Entity}Request` struct with all fields optional (for partial updates), plus an
+///   `is_empty()` check for requests that set nothing. Attributes written before a field,
+///   e.g. `#[ts(optional)]`, are applied to its update field only
 /// - `List{Entity}sQuery` struct with parent_id for filtering
 /// - `List{Entity}sResponse` struct wrapping `Vec<Entity>`
 ///
@@ -36,14 +38,14 @@ macro_rules! define_mutation_types {
         $entity:ident,
         table: $table:literal,
         scope: Project,
-        fields: [$($field:ident : $ty:ty),* $(,)?]
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?]
         $(,)?
     ) => {
         $crate::define_mutation_types!(@impl
             $entity,
             table: $table,
             parent_field: project_id,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
     };
 
@@ -52,14 +54,14 @@ macro_rules! define_mutation_types {
         $entity:ident,
         table: $table:literal,
         scope: Issue,
-        fields: [$($field:ident : $ty:ty),* $(,)?]
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?]
         $(,)?
     ) => {
         $crate::define_mutation_types!(@impl
             $entity,
             table: $table,
             parent_field: issue_id,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
     };
 
@@ -68,14 +70,14 @@ macro_rules! define_mutation_types {
         $entity:ident,
         table: $table:literal,
         scope: Organization,
-        fields: [$($field:ident : $ty:ty),* $(,)?]
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?]
         $(,)?
     ) => {
         $crate::define_mutation_types!(@impl
             $entity,
             table: $table,
             parent_field: organization_id,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
     };
 
@@ -84,14 +86,14 @@ macro_rules! define_mutation_types {
         $entity:ident,
         table: $table:literal,
         scope: Comment,
-        fields: [$($field:ident : $ty:ty),* $(,)?]
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),* $(,)?]
         $(,)?
     ) => {
         $crate::define_mutation_types!(@impl
             $entity,
             table: $table,
             parent_field: comment_id,
-            fields: [$($field : $ty),*]
+            fields: [$($(#[$fattr])* $field : $ty),*]
         );
     };
 
@@ -100,7 +102,7 @@ macro_rules! define_mutation_types {
         $entity:ident,
         table: $table:literal,
         parent_field: $parent_field:ident,
-        fields: [$($field:ident : $ty:ty),*]
+        fields: [$($(#[$fattr:meta])* $field:ident : $ty:ty),*]
     ) => {
         paste::paste! {
             // Compile-time SQL validation - ensures table exists
@@ -131,7 +133,14 @@ macro_rules! define_mutation_types {
             #[derive(Debug, serde::Deserialize, ts_rs::TS)]
             #[ts(export)]
             pub struct [<Update $entity Request>] {
-                $(pub $field: Option<$ty>,)*
+                $($(#[$fattr])* pub $field: Option<$ty>,)*
+            }
+
+            impl [<Update $entity Request>] {
+                /// True when no field is set, i.e. the update would be a no-op.
+                pub fn is_empty(&self) -> bool {
+                    [$(self.$field.is_none()),*].into_iter().all(|unset| unset)
+                }
             }
 
             // List query params - for filtering by parent
@@ -0,0 +1,83 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_member_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::activity_events::{ActivityEvent, ActivityEventRepository},
+};
+
+const DEFAULT_ACTIVITY_LIMIT: i64 = 50;
+const MAX_ACTIVITY_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct ListActivityQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    /// ID of the last event from the previous page.
+    pub cursor: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListActivityResponse {
+    pub events: Vec<ActivityEvent>,
+    pub next_cursor: Option<Uuid>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/organizations/{org_id}/activity", get(list_activity))
+}
+
+#[instrument(
+    name = "activity.list",
+    skip(state, ctx, query),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn list_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<ListActivityQuery>,
+) -> Result<Json<ListActivityResponse>, ErrorResponse> {
+    ensure_member_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ACTIVITY_LIMIT)
+        .clamp(1, MAX_ACTIVITY_LIMIT);
+
+    // Fetch one extra row to tell whether another page exists.
+    let mut events = ActivityEventRepository::list_by_organization(
+        state.pool(),
+        org_id,
+        query.since,
+        query.cursor,
+        limit + 1,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to list activity");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list activity")
+    })?;
+
+    let next_cursor = if events.len() as i64 > limit {
+        events.truncate(limit as usize);
+        events.last().map(|event| event.id)
+    } else {
+        None
+    };
+
+    Ok(Json(ListActivityResponse {
+        events,
+        next_cursor,
+    }))
+}
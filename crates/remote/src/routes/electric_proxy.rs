@@ -47,6 +47,10 @@ pub fn router() -> Router<AppState> {
         .route(shapes::PROJECT_STATUSES.url, get(proxy_project_statuses))
         .route(shapes::TAGS.url, get(proxy_tags))
         .route(shapes::ISSUES.url, get(proxy_issues))
+        .route(
+            shapes::MY_ASSIGNED_ISSUES.url,
+            get(proxy_my_assigned_issues),
+        )
         .route(shapes::ISSUE_ASSIGNEES.url, get(proxy_issue_assignees))
         .route(shapes::ISSUE_FOLLOWERS.url, get(proxy_issue_followers))
         .route(shapes::ISSUE_TAGS.url, get(proxy_issue_tags))
@@ -55,7 +59,10 @@ pub fn router() -> Router<AppState> {
             get(proxy_issue_relationships),
         )
         .route(shapes::PULL_REQUESTS.url, get(proxy_pull_requests))
+        .route(shapes::ISSUE_ACTIVITY.url, get(proxy_issue_activity))
         // Issue-scoped
+        .route(shapes::SINGLE_ISSUE.url, get(proxy_single_issue))
+        .route(shapes::SUB_ISSUES.url, get(proxy_sub_issues))
         .route(shapes::ISSUE_COMMENTS.url, get(proxy_issue_comments))
         .route(
             shapes::ISSUE_COMMENT_REACTIONS.url,
@@ -211,6 +218,25 @@ async fn proxy_issues(
     .await
 }
 
+async fn proxy_my_assigned_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::MY_ASSIGNED_ISSUES,
+        &query.params,
+        &[project_id.to_string(), ctx.user.id.to_string()],
+    )
+    .await
+}
+
 async fn proxy_issue_assignees(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
@@ -268,6 +294,44 @@ async fn proxy_issue_tags(
     .await
 }
 
+async fn proxy_single_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Query(query): Query<ShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_issue_access(state.pool(), issue_id, ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::SINGLE_ISSUE,
+        &query.params,
+        &[issue_id.to_string()],
+    )
+    .await
+}
+
+async fn proxy_sub_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Query(query): Query<ShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_issue_access(state.pool(), issue_id, ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::SUB_ISSUES,
+        &query.params,
+        &[issue_id.to_string()],
+    )
+    .await
+}
+
 async fn proxy_issue_comments(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
@@ -325,6 +389,25 @@ async fn proxy_pull_requests(
     .await
 }
 
+async fn proxy_issue_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_project_access(state.pool(), project_id, ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::ISSUE_ACTIVITY,
+        &query.params,
+        &[project_id.to_string()],
+    )
+    .await
+}
+
 async fn proxy_issue_comment_reactions(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
@@ -3,13 +3,63 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+use serde::Serialize;
+use ts_rs::TS;
 
-use crate::db::identity_errors::IdentityError;
+use crate::db::{
+    identity_errors::IdentityError, issue_comment_reactions::IssueCommentReactionError,
+    issue_comments::IssueCommentError, project_statuses::ProjectStatusError,
+    projects::ProjectError, tags::TagError, types::HexColorError,
+};
+
+/// Machine-readable error category so clients can branch without parsing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    /// A record with the same unique values already exists
+    Duplicate,
+    /// The request references a record that does not exist or is still in use
+    InvalidReference,
+    Unprocessable,
+    Unavailable,
+    Internal,
+}
+
+impl ErrorCode {
+    fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::UNPROCESSABLE_ENTITY => Self::Unprocessable,
+            StatusCode::SERVICE_UNAVAILABLE => Self::Unavailable,
+            status if status.is_client_error() => Self::BadRequest,
+            _ => Self::Internal,
+        }
+    }
+}
+
+/// JSON body of every [`ErrorResponse`]. The message is serialized as `error` so clients
+/// that only read the message keep working.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    #[serde(rename = "error")]
+    pub message: String,
+}
 
 #[derive(Debug)]
 pub struct ErrorResponse {
     status: StatusCode,
+    code: ErrorCode,
     message: String,
 }
 
@@ -17,14 +67,25 @@ impl ErrorResponse {
     pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
         Self {
             status,
+            code: ErrorCode::from_status(status),
             message: message.into(),
         }
     }
+
+    /// Override the code derived from the status.
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
 }
 
 impl IntoResponse for ErrorResponse {
     fn into_response(self) -> Response {
-        (self.status, Json(json!({ "error": self.message }))).into_response()
+        let body = ErrorBody {
+            code: self.code,
+            message: self.message,
+        };
+        (self.status, Json(body)).into_response()
     }
 }
 
@@ -42,3 +103,147 @@ pub(crate) fn membership_error(error: IdentityError, forbidden_message: &str) ->
         }
     }
 }
+
+pub(crate) fn invalid_color_response(error: HexColorError) -> ErrorResponse {
+    ErrorResponse::new(
+        StatusCode::BAD_REQUEST,
+        format!("Invalid color format ({error}). Expected HSL 'H S% L%' or hex '#rrggbb'"),
+    )
+}
+
+/// Postgres SQLSTATE for `unique_violation`.
+const UNIQUE_VIOLATION: &str = "23505";
+/// Postgres SQLSTATE for `foreign_key_violation`.
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// Map a database error to a client-facing response. Unique violations become 409s,
+/// foreign key violations 422s and missing rows 404s; anything else is an opaque 500.
+pub(crate) fn map_db_error(error: sqlx::Error) -> ErrorResponse {
+    if matches!(error, sqlx::Error::RowNotFound) {
+        return ErrorResponse::new(StatusCode::NOT_FOUND, "record not found");
+    }
+
+    if let Some(db_error) = error.as_database_error() {
+        match db_error.code().as_deref() {
+            Some(UNIQUE_VIOLATION) => {
+                return ErrorResponse::new(
+                    StatusCode::CONFLICT,
+                    "a record with these values already exists",
+                )
+                .with_code(ErrorCode::Duplicate);
+            }
+            Some(FOREIGN_KEY_VIOLATION) => {
+                return ErrorResponse::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "referenced record does not exist or is still in use",
+                )
+                .with_code(ErrorCode::InvalidReference);
+            }
+            _ => {}
+        }
+    }
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+}
+
+impl From<sqlx::Error> for ErrorResponse {
+    fn from(error: sqlx::Error) -> Self {
+        map_db_error(error)
+    }
+}
+
+impl From<ProjectError> for ErrorResponse {
+    fn from(error: ProjectError) -> Self {
+        match error {
+            ProjectError::Database(error) => map_db_error(error),
+            ProjectError::Conflict(message) => ErrorResponse::new(StatusCode::CONFLICT, message),
+            ProjectError::DefaultTagsFailed(_) | ProjectError::DefaultStatusesFailed(_) => {
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        }
+    }
+}
+
+impl From<ProjectStatusError> for ErrorResponse {
+    fn from(error: ProjectStatusError) -> Self {
+        match error {
+            ProjectStatusError::InUse { .. } | ProjectStatusError::LastStatus => {
+                ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+            }
+            ProjectStatusError::InvalidReorder(_)
+            | ProjectStatusError::InvalidReassignTarget(_) => {
+                ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+            }
+            ProjectStatusError::Database(error) => map_db_error(error),
+        }
+    }
+}
+
+impl From<TagError> for ErrorResponse {
+    fn from(error: TagError) -> Self {
+        match error {
+            TagError::DuplicateName(_) => {
+                ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+                    .with_code(ErrorCode::Duplicate)
+            }
+            TagError::Database(error) => map_db_error(error),
+        }
+    }
+}
+
+impl From<IssueCommentError> for ErrorResponse {
+    fn from(error: IssueCommentError) -> Self {
+        match error {
+            IssueCommentError::InvalidParent => {
+                ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+            }
+            IssueCommentError::Database(error) => map_db_error(error),
+        }
+    }
+}
+
+impl From<IssueCommentReactionError> for ErrorResponse {
+    fn from(error: IssueCommentReactionError) -> Self {
+        match error {
+            IssueCommentReactionError::Duplicate => {
+                ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+                    .with_code(ErrorCode::Duplicate)
+            }
+            IssueCommentReactionError::Database(error) => map_db_error(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_row_maps_to_not_found() {
+        let response = map_db_error(sqlx::Error::RowNotFound);
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+        assert_eq!(response.code, ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn duplicate_tag_keeps_conflict_status_with_specific_code() {
+        let response = ErrorResponse::from(TagError::DuplicateName("bug".to_string()));
+        assert_eq!(response.status, StatusCode::CONFLICT);
+        assert_eq!(response.code, ErrorCode::Duplicate);
+    }
+
+    #[test]
+    fn error_body_serializes_code_and_message() {
+        let body = ErrorBody {
+            code: ErrorCode::InvalidReference,
+            message: "referenced record does not exist".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            serde_json::json!({
+                "code": "invalid_reference",
+                "error": "referenced record does not exist",
+            })
+        );
+    }
+}
@@ -1,16 +1,27 @@
+use std::collections::HashSet;
+
 use axum::{
     Json,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
 };
+use serde_json::json;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    notifications::{ProjectNotification, notify_users},
+    organization_members::ensure_issue_access,
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_assignees::{IssueAssignee, IssueAssigneeRepository},
+    db::{
+        issue_assignees::{IssueAssignee, IssueAssigneeError, IssueAssigneeRepository},
+        issues::IssueRepository,
+        notifications::NotificationType,
+    },
     define_mutation_router,
     entities::{
         CreateIssueAssigneeRequest, ListIssueAssigneesQuery, ListIssueAssigneesResponse,
@@ -83,7 +94,7 @@ async fn create_issue_assignee(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueAssigneeRequest>,
 ) -> Result<Json<MutationResponse<IssueAssignee>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
     let response = IssueAssigneeRepository::create(
         state.pool(),
@@ -94,9 +105,39 @@ async fn create_issue_assignee(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create issue assignee");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            IssueAssigneeError::Database(error) => map_db_error(error),
+        }
     })?;
 
+    match IssueRepository::find_by_id(state.pool(), payload.issue_id).await {
+        Ok(Some(issue)) => {
+            notify_users(
+                state.pool(),
+                ctx.user.id,
+                [payload.user_id],
+                ProjectNotification {
+                    organization_id,
+                    project_id: issue.project_id,
+                    issue_id: Some(issue.id),
+                    comment_id: None,
+                    notification_type: NotificationType::IssueAssigneeChanged,
+                    payload: json!({
+                        "simple_id": issue.simple_id,
+                        "title": issue.title,
+                        "assigned_by": ctx.user.id,
+                    }),
+                    mentioned_user_ids: HashSet::new(),
+                },
+            )
+            .await;
+        }
+        Ok(None) => {}
+        Err(error) => {
+            tracing::error!(?error, issue_id = %payload.issue_id, "failed to load issue for notification");
+        }
+    }
+
     Ok(Json(response))
 }
 
@@ -144,7 +185,9 @@ async fn delete_issue_assignee(
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue assignee");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            match error {
+                IssueAssigneeError::Database(error) => map_db_error(error),
+            }
         })?;
 
     Ok(Json(response))
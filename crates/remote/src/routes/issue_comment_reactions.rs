@@ -1,8 +1,10 @@
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::put,
 };
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -25,6 +27,26 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(IssueCommentReaction, table: "issue_comment_reactions");
 
+#[derive(Debug, Deserialize)]
+pub struct ToggleReactionRequest {
+    pub emoji: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToggleReactionResponse {
+    /// `true` if the reaction was added, `false` if it was removed
+    pub added: bool,
+    pub reaction: IssueCommentReaction,
+    pub txid: i64,
+}
+
+pub fn toggle_router() -> Router<AppState> {
+    Router::new().route(
+        "/comments/{comment_id}/reactions/toggle",
+        put(toggle_issue_comment_reaction),
+    )
+}
+
 #[instrument(
     name = "issue_comment_reactions.list_issue_comment_reactions",
     skip(state, ctx),
@@ -123,7 +145,7 @@ async fn create_issue_comment_reaction(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create reaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -174,7 +196,7 @@ async fn update_issue_comment_reaction(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to update reaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -220,8 +242,52 @@ async fn delete_issue_comment_reaction(
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete reaction");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            ErrorResponse::from(error)
         })?;
 
     Ok(Json(response))
 }
+
+#[instrument(
+    name = "issue_comment_reactions.toggle_issue_comment_reaction",
+    skip(state, ctx, payload),
+    fields(comment_id = %comment_id, user_id = %ctx.user.id)
+)]
+async fn toggle_issue_comment_reaction(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(comment_id): Path<Uuid>,
+    Json(payload): Json<ToggleReactionRequest>,
+) -> Result<Json<ToggleReactionResponse>, ErrorResponse> {
+    let emoji = payload.emoji.trim().to_string();
+    if emoji.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "emoji must not be empty",
+        ));
+    }
+
+    let comment = IssueCommentRepository::find_by_id(state.pool(), comment_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %comment_id, "failed to load comment");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load comment")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
+
+    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+
+    let MutationResponse { data, txid } =
+        IssueCommentReactionRepository::toggle(state.pool(), comment_id, ctx.user.id, emoji)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to toggle reaction");
+                ErrorResponse::from(error)
+            })?;
+
+    Ok(Json(ToggleReactionResponse {
+        added: data.added,
+        reaction: data.reaction,
+        txid,
+    }))
+}
@@ -1,16 +1,32 @@
+use std::collections::HashSet;
+
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::{get, post},
 };
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::ErrorResponse,
+    notifications::{ProjectNotification, extract_mentions, notify_users},
+    organization_members::{ensure_admin_access, ensure_issue_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_comments::{IssueComment, IssueCommentRepository},
+    db::{
+        issue_assignees::IssueAssigneeRepository,
+        issue_comments::{IssueComment, IssueCommentEntry, IssueCommentRepository},
+        issue_followers::IssueFollowerRepository,
+        issues::IssueRepository,
+        notifications::NotificationType,
+        organization_members::find_member_ids_by_usernames,
+    },
     define_mutation_router,
     entities::{
         CreateIssueCommentRequest, ListIssueCommentsQuery, ListIssueCommentsResponse,
@@ -22,6 +38,45 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(IssueComment, table: "issue_comments");
 
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIssueCommentsQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchIssueCommentsResponse {
+    pub issue_comments: Vec<IssueComment>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueCommentThreadResponse {
+    pub issue_comments: Vec<IssueCommentEntry>,
+}
+
+pub fn search_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/issues/{issue_id}/comments/search",
+            get(search_issue_comments),
+        )
+        .route(
+            "/issues/{issue_id}/comments/thread",
+            get(list_issue_comment_thread),
+        )
+}
+
+pub fn restore_router() -> Router<AppState> {
+    Router::new().route(
+        "/issue_comments/{issue_comment_id}/restore",
+        post(restore_issue_comment),
+    )
+}
+
 #[instrument(
     name = "issue_comments.list_issue_comments",
     skip(state, ctx),
@@ -47,6 +102,73 @@ async fn list_issue_comments(
     Ok(Json(ListIssueCommentsResponse { issue_comments }))
 }
 
+#[instrument(
+    name = "issue_comments.list_issue_comment_thread",
+    skip(state, ctx),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_issue_comment_thread(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<IssueCommentThreadResponse>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let issue_comments =
+        IssueCommentRepository::list_by_issue_with_tombstones(state.pool(), issue_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %issue_id, "failed to list issue comment thread");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to list issue comment thread",
+                )
+            })?;
+
+    Ok(Json(IssueCommentThreadResponse { issue_comments }))
+}
+
+#[instrument(
+    name = "issue_comments.search_issue_comments",
+    skip(state, ctx, query),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn search_issue_comments(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Query(query): Query<SearchIssueCommentsQuery>,
+) -> Result<Json<SearchIssueCommentsResponse>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let search = query.q.trim();
+    if search.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "search query must not be empty",
+        ));
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let issue_comments =
+        IssueCommentRepository::search(state.pool(), issue_id, search, limit, offset)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %issue_id, "failed to search issue comments");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to search issue comments",
+                )
+            })?;
+
+    Ok(Json(SearchIssueCommentsResponse { issue_comments }))
+}
+
 #[instrument(
     name = "issue_comments.get_issue_comment",
     skip(state, ctx),
@@ -83,21 +205,24 @@ async fn create_issue_comment(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueCommentRequest>,
 ) -> Result<Json<MutationResponse<IssueComment>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
     let response = IssueCommentRepository::create(
         state.pool(),
         payload.id,
         payload.issue_id,
         ctx.user.id,
+        payload.parent_comment_id,
         payload.message,
     )
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create issue comment");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
+    notify_comment_recipients(&state, organization_id, ctx.user.id, &response.data).await;
+
     Ok(Json(response))
 }
 
@@ -132,12 +257,26 @@ async fn update_issue_comment(
 
     ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
-    let response = IssueCommentRepository::update(state.pool(), issue_comment_id, payload.message)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to update issue comment");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-        })?;
+    if let Some(parent_comment_id) = payload.parent_comment_id
+        && parent_comment_id != comment.parent_comment_id
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "a comment cannot be moved to a different thread",
+        ));
+    }
+
+    let response = IssueCommentRepository::update(
+        state.pool(),
+        ctx.user.id,
+        issue_comment_id,
+        payload.message,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update issue comment");
+        ErrorResponse::from(error)
+    })?;
 
     Ok(Json(response))
 }
@@ -172,12 +311,114 @@ async fn delete_issue_comment(
 
     ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
-    let response = IssueCommentRepository::delete(state.pool(), issue_comment_id)
+    let response = IssueCommentRepository::delete(state.pool(), ctx.user.id, issue_comment_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue comment");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            ErrorResponse::from(error)
         })?;
 
     Ok(Json(response))
 }
+
+/// Notify the issue's assignees and followers, plus anyone @mentioned, about a new comment.
+async fn notify_comment_recipients(
+    state: &AppState,
+    organization_id: Uuid,
+    author_id: Uuid,
+    comment: &IssueComment,
+) {
+    let pool = state.pool();
+    let issue = match IssueRepository::find_by_id(pool, comment.issue_id).await {
+        Ok(Some(issue)) => issue,
+        Ok(None) => return,
+        Err(error) => {
+            tracing::error!(?error, issue_id = %comment.issue_id, "failed to load issue for notification");
+            return;
+        }
+    };
+
+    let mut recipients = Vec::new();
+    match IssueAssigneeRepository::list_by_issue(pool, issue.id).await {
+        Ok(assignees) => recipients.extend(assignees.into_iter().map(|a| a.user_id)),
+        Err(error) => {
+            tracing::error!(?error, issue_id = %issue.id, "failed to list issue assignees")
+        }
+    }
+    match IssueFollowerRepository::list_by_issue(pool, issue.id).await {
+        Ok(followers) => recipients.extend(followers.into_iter().map(|f| f.user_id)),
+        Err(error) => {
+            tracing::error!(?error, issue_id = %issue.id, "failed to list issue followers")
+        }
+    }
+
+    let mut mentioned_user_ids = HashSet::new();
+    let mentions = extract_mentions(&comment.message);
+    if !mentions.is_empty() {
+        match find_member_ids_by_usernames(pool, organization_id, &mentions).await {
+            Ok(user_ids) => mentioned_user_ids.extend(user_ids),
+            Err(error) => {
+                tracing::error!(?error, comment_id = %comment.id, "failed to resolve comment mentions")
+            }
+        }
+    }
+    recipients.extend(mentioned_user_ids.iter().copied());
+
+    notify_users(
+        pool,
+        author_id,
+        recipients,
+        ProjectNotification {
+            organization_id,
+            project_id: issue.project_id,
+            issue_id: Some(issue.id),
+            comment_id: Some(comment.id),
+            notification_type: NotificationType::IssueCommentAdded,
+            payload: json!({
+                "simple_id": issue.simple_id,
+                "title": issue.title,
+                "author_id": author_id,
+            }),
+            mentioned_user_ids,
+        },
+    )
+    .await;
+}
+
+#[instrument(
+    name = "issue_comments.restore_issue_comment",
+    skip(state, ctx),
+    fields(issue_comment_id = %issue_comment_id, user_id = %ctx.user.id)
+)]
+async fn restore_issue_comment(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_comment_id): Path<Uuid>,
+) -> Result<Json<MutationResponse<IssueComment>>, ErrorResponse> {
+    let comment =
+        IssueCommentRepository::find_by_id_including_deleted(state.pool(), issue_comment_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %issue_comment_id, "failed to load issue comment");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to load issue comment",
+                )
+            })?
+            .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue comment not found"))?;
+
+    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    if comment.author_id != ctx.user.id {
+        ensure_admin_access(state.pool(), organization_id, ctx.user.id).await?;
+    }
+
+    let response = IssueCommentRepository::restore(state.pool(), ctx.user.id, issue_comment_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to restore issue comment");
+            ErrorResponse::from(error)
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::CONFLICT, "issue comment is not deleted"))?;
+
+    Ok(Json(response))
+}
@@ -6,11 +6,14 @@ use axum::{
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::ensure_issue_access,
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_followers::{IssueFollower, IssueFollowerRepository},
+    db::issue_followers::{IssueFollower, IssueFollowerError, IssueFollowerRepository},
     define_mutation_router,
     entities::{
         CreateIssueFollowerRequest, ListIssueFollowersQuery, ListIssueFollowersResponse,
@@ -94,7 +97,9 @@ async fn create_issue_follower(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create issue follower");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            IssueFollowerError::Database(error) => map_db_error(error),
+        }
     })?;
 
     Ok(Json(response))
@@ -144,7 +149,9 @@ async fn delete_issue_follower(
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue follower");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            match error {
+                IssueFollowerError::Database(error) => map_db_error(error),
+            }
         })?;
 
     Ok(Json(response))
@@ -6,11 +6,16 @@ use axum::{
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::ensure_issue_access,
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_relationships::{IssueRelationship, IssueRelationshipRepository},
+    db::issue_relationships::{
+        IssueRelationship, IssueRelationshipError, IssueRelationshipRepository,
+    },
     define_mutation_router,
     entities::{
         CreateIssueRelationshipRequest, ListIssueRelationshipsQuery,
@@ -100,7 +105,9 @@ async fn create_issue_relationship(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create issue relationship");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            IssueRelationshipError::Database(error) => map_db_error(error),
+        }
     })?;
 
     Ok(Json(response))
@@ -150,7 +157,9 @@ async fn delete_issue_relationship(
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue relationship");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            match error {
+                IssueRelationshipError::Database(error) => map_db_error(error),
+            }
         })?;
 
     Ok(Json(response))
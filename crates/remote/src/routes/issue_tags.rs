@@ -6,11 +6,14 @@ use axum::{
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::ensure_issue_access,
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_tags::{IssueTag, IssueTagRepository},
+    db::issue_tags::{IssueTag, IssueTagError, IssueTagRepository},
     define_mutation_router,
     entities::{
         CreateIssueTagRequest, ListIssueTagsQuery, ListIssueTagsResponse, UpdateIssueTagRequest,
@@ -89,7 +92,9 @@ async fn create_issue_tag(
             .await
             .map_err(|error| {
                 tracing::error!(?error, "failed to create issue tag");
-                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                match error {
+                    IssueTagError::Database(error) => map_db_error(error),
+                }
             })?;
 
     Ok(Json(response))
@@ -139,7 +144,9 @@ async fn delete_issue_tag(
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue tag");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            match error {
+                IssueTagError::Database(error) => map_db_error(error),
+            }
         })?;
 
     Ok(Json(response))
@@ -1,16 +1,31 @@
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::{get, post},
 };
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::{ensure_admin_access, ensure_issue_access, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issues::{Issue, IssueRepository},
+    db::{
+        issue_assignees::IssueAssigneeRepository,
+        issue_comments::IssueCommentRepository,
+        issue_followers::IssueFollowerRepository,
+        issue_relationships::IssueRelationshipRepository,
+        issue_tags::IssueTagRepository,
+        issues::{
+            Issue, IssueDetail, IssueError, IssueRepository, SortDirection, clamp_page_limit,
+        },
+        project_statuses::ProjectStatusRepository,
+    },
     define_mutation_router,
     entities::{CreateIssueRequest, ListIssuesQuery, ListIssuesResponse, UpdateIssueRequest},
     mutation_types::{DeleteResponse, MutationResponse},
@@ -19,6 +34,236 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(Issue, table: "issues");
 
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+const MAX_SEARCH_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIssuesQuery {
+    #[serde(default)]
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchIssuesResponse {
+    pub issues: Vec<Issue>,
+}
+
+/// Extra filters accepted by `GET /issues` on top of [`ListIssuesQuery`].
+#[derive(Debug, Deserialize)]
+pub struct ListIssuesFilter {
+    pub status_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListStatusIssuesQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    #[serde(default)]
+    pub order: SortDirection,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListStatusIssuesResponse {
+    pub issues: Vec<Issue>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveIssueRequest {
+    pub status_id: Uuid,
+    /// Place the issue directly below this one; `None` moves it to the top of the status.
+    pub after_issue_id: Option<Uuid>,
+}
+
+pub fn restore_router() -> Router<AppState> {
+    Router::new().route("/issues/{issue_id}/restore", post(restore_issue))
+}
+
+pub fn search_router() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/issues/search", get(search_issues))
+        .route(
+            "/projects/{project_id}/statuses/{status_id}/issues",
+            get(list_status_issues),
+        )
+}
+
+pub fn detail_router() -> Router<AppState> {
+    Router::new().route("/issues/{issue_id}/detail", get(get_issue_detail))
+}
+
+pub fn move_router() -> Router<AppState> {
+    Router::new().route("/issues/{issue_id}/move", post(move_issue))
+}
+
+fn detail_load_error<E: std::fmt::Debug>(
+    issue_id: Uuid,
+    relation: &'static str,
+) -> impl FnOnce(E) -> ErrorResponse {
+    move |error| {
+        tracing::error!(?error, %issue_id, relation, "failed to load issue detail");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to load issue detail",
+        )
+    }
+}
+
+#[instrument(
+    name = "issues.get_issue_detail",
+    skip(state, ctx),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn get_issue_detail(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<IssueDetail>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let pool = state.pool();
+    let (issue, comments, assignees, tags, followers, relationships) = tokio::try_join!(
+        async {
+            IssueRepository::find_by_id(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "issue"))
+        },
+        async {
+            IssueCommentRepository::list_by_issue(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "comments"))
+        },
+        async {
+            IssueAssigneeRepository::list_by_issue(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "assignees"))
+        },
+        async {
+            IssueTagRepository::list_by_issue(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "tags"))
+        },
+        async {
+            IssueFollowerRepository::list_by_issue(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "followers"))
+        },
+        async {
+            IssueRelationshipRepository::list_by_issue(pool, issue_id)
+                .await
+                .map_err(detail_load_error(issue_id, "relationships"))
+        },
+    )?;
+
+    let issue =
+        issue.ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+    Ok(Json(IssueDetail {
+        issue,
+        comments,
+        assignees,
+        tags,
+        followers,
+        relationships,
+    }))
+}
+
+#[instrument(
+    name = "issues.search_issues",
+    skip(state, ctx, query),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn search_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<SearchIssuesQuery>,
+) -> Result<Json<SearchIssuesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let search = query.q.trim();
+    if search.is_empty() {
+        return Ok(Json(SearchIssuesResponse { issues: Vec::new() }));
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    let issues = IssueRepository::search(state.pool(), project_id, search, limit)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to search issues");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to search issues")
+        })?;
+
+    Ok(Json(SearchIssuesResponse { issues }))
+}
+
+#[instrument(
+    name = "issues.list_status_issues",
+    skip(state, ctx, query),
+    fields(project_id = %project_id, status_id = %status_id, user_id = %ctx.user.id)
+)]
+async fn list_status_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, status_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<ListStatusIssuesQuery>,
+) -> Result<Json<ListStatusIssuesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let offset = query.offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "offset must not be negative",
+        ));
+    }
+    let limit = clamp_page_limit(query.limit);
+
+    let status = ProjectStatusRepository::find_by_id(state.pool(), status_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %status_id, "failed to load project status");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load project status",
+            )
+        })?;
+    if status.is_none_or(|status| status.project_id != project_id) {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "project status not found",
+        ));
+    }
+
+    let page = IssueRepository::list_by_status(
+        state.pool(),
+        project_id,
+        status_id,
+        limit,
+        offset,
+        query.order,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, %status_id, "failed to list issues by status");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
+    })?;
+
+    Ok(Json(ListStatusIssuesResponse {
+        issues: page.issues,
+        total: page.total,
+        limit,
+        offset,
+    }))
+}
+
 #[instrument(
     name = "issues.list_issues",
     skip(state, ctx),
@@ -28,10 +273,11 @@ async fn list_issues(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ListIssuesQuery>,
+    Query(filter): Query<ListIssuesFilter>,
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
     ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
-    let issues = IssueRepository::list_by_project(state.pool(), query.project_id)
+    let issues = IssueRepository::list_by_project(state.pool(), query.project_id, filter.status_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, project_id = %query.project_id, "failed to list issues");
@@ -78,6 +324,7 @@ async fn create_issue(
 
     let response = IssueRepository::create(
         state.pool(),
+        ctx.user.id,
         payload.id,
         payload.project_id,
         payload.status_id,
@@ -94,7 +341,10 @@ async fn create_issue(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create issue");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            IssueError::Database(error) => map_db_error(error),
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error"),
+        }
     })?;
 
     Ok(Json(response))
@@ -123,6 +373,7 @@ async fn update_issue(
 
     let response = IssueRepository::update(
         state.pool(),
+        ctx.user.id,
         issue_id,
         payload.status_id,
         payload.title,
@@ -138,7 +389,10 @@ async fn update_issue(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to update issue");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            IssueError::Database(error) => map_db_error(error),
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error"),
+        }
     })?;
 
     Ok(Json(response))
@@ -164,12 +418,99 @@ async fn delete_issue(
 
     ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
 
-    let response = IssueRepository::delete(state.pool(), issue_id)
+    let response = IssueRepository::delete(state.pool(), ctx.user.id, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            match error {
+                IssueError::Database(error) => map_db_error(error),
+                _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error"),
+            }
         })?;
 
     Ok(Json(response))
 }
+
+#[instrument(
+    name = "issues.move_issue",
+    skip(state, ctx, payload),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id, status_id = %payload.status_id)
+)]
+async fn move_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Json(payload): Json<MoveIssueRequest>,
+) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
+    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_id, "failed to load issue");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+    ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
+
+    let response = IssueRepository::move_to_status(
+        state.pool(),
+        ctx.user.id,
+        issue_id,
+        payload.status_id,
+        payload.after_issue_id,
+    )
+    .await
+    .map_err(|error| match error {
+        IssueError::InvalidStatus(_) | IssueError::InvalidMoveTarget(_) => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+        }
+        IssueError::Database(error) => {
+            tracing::error!(?error, "failed to move issue");
+            map_db_error(error)
+        }
+        error => {
+            tracing::error!(?error, "failed to move issue");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        }
+    })?
+    .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+    Ok(Json(response))
+}
+
+/// Issues have no author, so restoring one is limited to organization admins.
+#[instrument(
+    name = "issues.restore_issue",
+    skip(state, ctx),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn restore_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
+    let issue = IssueRepository::find_by_id_including_deleted(state.pool(), issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_id, "failed to load issue");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
+    ensure_admin_access(state.pool(), organization_id, ctx.user.id).await?;
+
+    let response = IssueRepository::restore(state.pool(), ctx.user.id, issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to restore issue");
+            match error {
+                IssueError::Database(error) => map_db_error(error),
+                _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error"),
+            }
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::CONFLICT, "issue is not deleted"))?;
+
+    Ok(Json(response))
+}
@@ -1,9 +1,11 @@
 use axum::{
-    Router,
-    http::{Request, header::HeaderName},
+    Json, Router,
+    extract::State,
+    http::{Request, StatusCode, header::HeaderName},
     middleware,
     routing::get,
 };
+use serde::Serialize;
 use tower_http::{
     cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer},
     request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer},
@@ -12,8 +14,11 @@ use tower_http::{
 };
 use tracing::{Level, field};
 
-use crate::{AppState, auth::require_session};
+use self::error::ErrorResponse;
+pub use self::error::{ErrorBody, ErrorCode};
+use crate::{AppState, auth::require_session, db};
 
+mod activity;
 mod electric_proxy;
 mod error;
 mod github_app;
@@ -35,6 +40,7 @@ mod pull_requests;
 mod review;
 mod tags;
 mod tokens;
+mod users;
 mod workspaces;
 
 pub fn router(state: AppState) -> Router {
@@ -60,6 +66,7 @@ pub fn router(state: AppState) -> Router {
 
     let v1_public = Router::<AppState>::new()
         .route("/health", get(health))
+        .route("/health/migrations", get(migration_health))
         .merge(oauth::public_router())
         .merge(organization_members::public_router())
         .merge(tokens::public_router())
@@ -68,17 +75,27 @@ pub fn router(state: AppState) -> Router {
 
     let v1_protected = Router::<AppState>::new()
         .merge(identity::router())
+        .merge(users::router())
         .merge(projects::router())
+        .merge(projects::transfer_router())
         .merge(organizations::router())
         .merge(organization_members::protected_router())
         .merge(oauth::protected_router())
         .merge(electric_proxy::router())
         .merge(github_app::protected_router())
         .merge(project_statuses::router())
+        .merge(project_statuses::reorder_router())
         .merge(tags::router())
         .merge(issue_comments::router())
+        .merge(issue_comments::search_router())
+        .merge(issue_comments::restore_router())
         .merge(issue_comment_reactions::router())
+        .merge(issue_comment_reactions::toggle_router())
         .merge(issues::router())
+        .merge(issues::restore_router())
+        .merge(issues::search_router())
+        .merge(issues::detail_router())
+        .merge(issues::move_router())
         .merge(issue_assignees::router())
         .merge(issue_followers::router())
         .merge(issue_tags::router())
@@ -86,6 +103,8 @@ pub fn router(state: AppState) -> Router {
         .merge(pull_requests::router())
         .merge(notifications::router())
         .merge(workspaces::router())
+        .merge(workspaces::reviews_router())
+        .merge(activity::router())
         .layer(middleware::from_fn_with_state(
             state.clone(),
             require_session,
@@ -120,3 +139,49 @@ pub fn router(state: AppState) -> Router {
 async fn health() -> &'static str {
     "ok"
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum MigrationStatus {
+    Ok,
+    Pending,
+}
+
+#[derive(Debug, Serialize)]
+struct MigrationHealth {
+    status: MigrationStatus,
+    pending_count: usize,
+}
+
+/// Reports 503 while migrations are pending so load balancers hold traffic during a rollout.
+async fn migration_health(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<MigrationHealth>), ErrorResponse> {
+    let pending_count = db::pending_migration_count(state.pool())
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to check applied migrations");
+            ErrorResponse::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "failed to check applied migrations",
+            )
+        })?;
+
+    if pending_count == 0 {
+        Ok((
+            StatusCode::OK,
+            Json(MigrationHealth {
+                status: MigrationStatus::Ok,
+                pending_count,
+            }),
+        ))
+    } else {
+        Ok((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(MigrationHealth {
+                status: MigrationStatus::Pending,
+                pending_count,
+            }),
+        ))
+    }
+}
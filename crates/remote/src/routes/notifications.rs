@@ -1,18 +1,28 @@
+use std::collections::HashSet;
+
 use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::error::ErrorResponse;
+use super::{error::ErrorResponse, organization_members::ensure_project_access};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::notifications::{Notification, NotificationRepository},
+    db::{
+        notifications::{Notification, NotificationRepository, NotificationType},
+        project_notification_preferences::{
+            NotificationMode, ProjectNotificationPreference,
+            ProjectNotificationPreferenceRepository,
+        },
+    },
     entities::UpdateNotificationRequest,
 };
 
@@ -31,6 +41,16 @@ pub struct MarkAllSeenResponse {
     pub updated: u64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SetNotificationPreferenceRequest {
+    pub mode: NotificationMode,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkAllSeenQuery {
+    pub organization_id: Option<Uuid>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListNotificationsQuery {
     #[serde(default)]
@@ -42,6 +62,10 @@ pub fn router() -> Router<AppState> {
         .route("/notifications", get(list_notifications))
         .route("/notifications/unread-count", get(unread_count))
         .route("/notifications/mark-all-seen", post(mark_all_seen))
+        .route(
+            "/projects/{project_id}/notification_preferences",
+            put(set_project_notification_preference),
+        )
         .route(
             "/notifications/{notification_id}",
             get(get_notification)
@@ -190,13 +214,15 @@ async fn delete_notification(
 async fn mark_all_seen(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<MarkAllSeenQuery>,
 ) -> Result<Json<MarkAllSeenResponse>, ErrorResponse> {
-    let updated = NotificationRepository::mark_all_seen(state.pool(), ctx.user.id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to mark all notifications as seen");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-        })?;
+    let updated =
+        NotificationRepository::mark_all_seen(state.pool(), ctx.user.id, query.organization_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to mark all notifications as seen");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
 
     Ok(Json(MarkAllSeenResponse { updated }))
 }
@@ -219,3 +245,130 @@ async fn unread_count(
 
     Ok(Json(UnreadCountResponse { count }))
 }
+
+#[instrument(
+    name = "notifications.set_project_notification_preference",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn set_project_notification_preference(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<SetNotificationPreferenceRequest>,
+) -> Result<Json<ProjectNotificationPreference>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let preference = ProjectNotificationPreferenceRepository::set_mode(
+        state.pool(),
+        project_id,
+        ctx.user.id,
+        payload.mode,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to set notification preference");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(preference))
+}
+
+/// Collect `@username` handles from text, e.g. a comment body. A handle must start at
+/// the beginning of the text or after whitespace, so email addresses aren't picked up.
+pub(crate) fn extract_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(handle) = word.strip_prefix('@') else {
+            continue;
+        };
+        let handle: String = handle
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            .collect();
+        let handle = handle.trim_end_matches('.');
+        if !handle.is_empty() && !mentions.iter().any(|m| m.eq_ignore_ascii_case(handle)) {
+            mentions.push(handle.to_string());
+        }
+    }
+    mentions
+}
+
+/// An event in a project (on an issue, or on a workspace) to fan out to interested users.
+pub(crate) struct ProjectNotification {
+    pub organization_id: Uuid,
+    pub project_id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub comment_id: Option<Uuid>,
+    pub notification_type: NotificationType,
+    pub payload: Value,
+    /// Users @mentioned by the event; they are notified even if the project is muted
+    pub mentioned_user_ids: HashSet<Uuid>,
+}
+
+/// Create `notification` for each recipient whose project preferences allow it, skipping
+/// the actor. Failures are logged rather than returned.
+pub(crate) async fn notify_users(
+    pool: &PgPool,
+    actor_id: Uuid,
+    recipients: impl IntoIterator<Item = Uuid>,
+    notification: ProjectNotification,
+) {
+    let mut seen = HashSet::new();
+    for user_id in recipients {
+        if user_id == actor_id || !seen.insert(user_id) {
+            continue;
+        }
+
+        let prefs = match ProjectNotificationPreferenceRepository::resolve(
+            pool,
+            notification.project_id,
+            user_id,
+        )
+        .await
+        {
+            Ok(prefs) => prefs,
+            Err(error) => {
+                tracing::error!(?error, %user_id, "failed to resolve notification preferences");
+                continue;
+            }
+        };
+
+        let mentioned = notification.mentioned_user_ids.contains(&user_id);
+        if !prefs.allows_delivery(notification.notification_type, mentioned) {
+            continue;
+        }
+
+        if let Err(error) = NotificationRepository::create(
+            pool,
+            notification.organization_id,
+            user_id,
+            notification.notification_type,
+            notification.payload.clone(),
+            notification.issue_id,
+            notification.comment_id,
+        )
+        .await
+        {
+            tracing::error!(?error, %user_id, "failed to create notification");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_mentions;
+
+    #[test]
+    fn extracts_handles_at_word_start() {
+        assert_eq!(
+            extract_mentions("thanks @alice, can @bob.smith take a look? cc @Alice"),
+            vec!["alice".to_string(), "bob.smith".to_string()]
+        );
+    }
+
+    #[test]
+    fn ignores_emails_and_bare_at_signs() {
+        assert!(extract_mentions("mail alice@example.com or ping @ later").is_empty());
+    }
+}
@@ -5,7 +5,7 @@ use axum::{
     extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Redirect, Response},
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use serde::Deserialize;
 use tracing::warn;
@@ -16,10 +16,16 @@ use utils::api::oauth::{
 };
 use uuid::Uuid;
 
+use super::error::ErrorResponse;
 use crate::{
     AppState,
     auth::{CallbackResult, HandoffError, RequestContext},
-    db::{oauth::OAuthHandoffError, oauth_accounts::OAuthAccountRepository},
+    db::{
+        oauth::OAuthHandoffError,
+        oauth_accounts::{
+            ListOAuthAccountsResponse, OAuthAccountRepository, UnlinkOAuthAccountError,
+        },
+    },
 };
 
 pub fn public_router() -> Router<AppState> {
@@ -34,6 +40,8 @@ pub fn protected_router() -> Router<AppState> {
     Router::new()
         .route("/profile", get(profile))
         .route("/oauth/logout", post(logout))
+        .route("/me/oauth-accounts", get(list_oauth_accounts))
+        .route("/oauth-accounts/{account_id}", delete(unlink_oauth_account))
 }
 
 pub async fn web_init(
@@ -204,6 +212,47 @@ pub async fn profile(
     })
 }
 
+pub async fn list_oauth_accounts(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<ListOAuthAccountsResponse>, ErrorResponse> {
+    let accounts = OAuthAccountRepository::new(state.pool())
+        .list_by_user(ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, user_id = %ctx.user.id, "failed to list oauth accounts");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(ListOAuthAccountsResponse {
+        accounts: accounts.into_iter().map(Into::into).collect(),
+    }))
+}
+
+pub async fn unlink_oauth_account(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(account_id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    OAuthAccountRepository::new(state.pool())
+        .delete_for_user(ctx.user.id, account_id)
+        .await
+        .map_err(|error| match error {
+            UnlinkOAuthAccountError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, error.to_string())
+            }
+            UnlinkOAuthAccountError::LastAccount => {
+                ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+            }
+            UnlinkOAuthAccountError::Database(error) => {
+                tracing::error!(?error, %account_id, "failed to unlink oauth account");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 pub async fn logout(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
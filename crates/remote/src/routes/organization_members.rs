@@ -5,7 +5,7 @@ use axum::{
     response::IntoResponse,
     routing::{delete, get, patch, post},
 };
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::warn;
@@ -21,11 +21,12 @@ use crate::{
     auth::RequestContext,
     db::{
         identity_errors::IdentityError,
-        invitations::{Invitation, InvitationRepository},
+        invitations::{INVITATION_TTL, Invitation, InvitationRepository},
         issues::IssueRepository,
         organization_members::{self, MemberRole},
         organizations::OrganizationRepository,
         projects::ProjectRepository,
+        users::{MemberProfilesResponse, UserRepository},
     },
 };
 
@@ -44,8 +45,16 @@ pub fn protected_router() -> Router<AppState> {
             "/organizations/{org_id}/invitations/revoke",
             post(revoke_invitation),
         )
+        .route(
+            "/invitations/{invitation_id}/resend",
+            post(resend_invitation),
+        )
         .route("/invitations/{token}/accept", post(accept_invitation))
         .route("/organizations/{org_id}/members", get(list_members))
+        .route(
+            "/organizations/{org_id}/members/profiles",
+            get(list_member_profiles),
+        )
         .route(
             "/organizations/{org_id}/members/{user_id}",
             delete(remove_member),
@@ -67,9 +76,30 @@ pub struct CreateInvitationResponse {
     pub invitation: Invitation,
 }
 
+#[derive(Debug, Serialize)]
+pub struct InvitationWithExpiry {
+    #[serde(flatten)]
+    pub invitation: Invitation,
+    pub is_expired: bool,
+}
+
+impl From<Invitation> for InvitationWithExpiry {
+    fn from(invitation: Invitation) -> Self {
+        Self {
+            is_expired: invitation.is_expired(),
+            invitation,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct ListInvitationsResponse {
-    pub invitations: Vec<Invitation>,
+    pub invitations: Vec<InvitationWithExpiry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResendInvitationResponse {
+    pub invitation: Invitation,
 }
 
 #[derive(Debug, Serialize)]
@@ -101,7 +131,7 @@ pub async fn create_invitation(
     ensure_admin_access(&state.pool, org_id, user.id).await?;
 
     let token = Uuid::new_v4().to_string();
-    let expires_at = Utc::now() + Duration::days(7);
+    let expires_at = Utc::now() + INVITATION_TTL;
 
     let invitation = invitation_repo
         .create_invitation(
@@ -170,7 +200,9 @@ pub async fn list_invitations(
             _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
         })?;
 
-    Ok(Json(ListInvitationsResponse { invitations }))
+    Ok(Json(ListInvitationsResponse {
+        invitations: invitations.into_iter().map(Into::into).collect(),
+    }))
 }
 
 pub async fn get_invitation(
@@ -231,6 +263,65 @@ pub async fn revoke_invitation(
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn resend_invitation(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path(invitation_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user = ctx.user;
+    let invitation_repo = InvitationRepository::new(&state.pool);
+
+    let existing = invitation_repo
+        .find_by_id(invitation_id)
+        .await
+        .map_err(|e| match e {
+            IdentityError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "Invitation not found")
+            }
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        })?;
+
+    ensure_admin_access(&state.pool, existing.organization_id, user.id).await?;
+
+    let token = Uuid::new_v4().to_string();
+    let expires_at = Utc::now() + INVITATION_TTL;
+
+    let invitation = invitation_repo
+        .resend_invitation(invitation_id, &token, expires_at)
+        .await
+        .map_err(|e| match e {
+            IdentityError::InvitationError(msg) => ErrorResponse::new(StatusCode::CONFLICT, msg),
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        })?;
+
+    let organization = OrganizationRepository::new(&state.pool)
+        .fetch_organization(invitation.organization_id)
+        .await
+        .map_err(|_| {
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch organization",
+            )
+        })?;
+
+    let accept_url = format!(
+        "{}/invitations/{}/accept",
+        state.server_public_base_url, token
+    );
+    state
+        .mailer
+        .send_org_invitation(
+            &organization.name,
+            &invitation.email,
+            &accept_url,
+            invitation.role,
+            user.username.as_deref(),
+        )
+        .await;
+
+    Ok(Json(ResendInvitationResponse { invitation }))
+}
+
 pub async fn accept_invitation(
     State(state): State<AppState>,
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
@@ -244,6 +335,9 @@ pub async fn accept_invitation(
         .await
         .map_err(|e| match e {
             IdentityError::InvitationError(msg) => ErrorResponse::new(StatusCode::BAD_REQUEST, msg),
+            IdentityError::InvitationExpired => {
+                ErrorResponse::new(StatusCode::GONE, "Invitation has expired")
+            }
             IdentityError::NotFound => {
                 ErrorResponse::new(StatusCode::NOT_FOUND, "Invitation not found")
             }
@@ -276,7 +370,7 @@ pub async fn list_members(
             u.last_name AS "last_name?",
             u.username AS "username?",
             u.email AS "email?",
-            oa.avatar_url AS "avatar_url?"
+            COALESCE(u.avatar_url, oa.avatar_url) AS "avatar_url?"
         FROM organization_member_metadata omm
         INNER JOIN users u ON omm.user_id = u.id
         LEFT JOIN LATERAL (
@@ -489,6 +583,26 @@ pub async fn update_member_role(
     }))
 }
 
+/// Display names and avatars for every member, so clients can hydrate mentions,
+/// assignees and comment authors with a single request.
+pub async fn list_member_profiles(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<MemberProfilesResponse>, ErrorResponse> {
+    ensure_member_access(&state.pool, org_id, ctx.user.id).await?;
+
+    let profiles = UserRepository::new(&state.pool)
+        .member_profiles(org_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to load member profiles");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(MemberProfilesResponse { profiles }))
+}
+
 pub(crate) async fn ensure_member_access(
     pool: &PgPool,
     organization_id: Uuid,
@@ -510,6 +624,23 @@ pub(crate) async fn ensure_admin_access(
         .map_err(|err| membership_error(err, "Admin access required"))
 }
 
+/// Like [`ensure_member_access`], but also requires at least `min_role`.
+pub(crate) async fn ensure_member_role(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+    min_role: MemberRole,
+) -> Result<(), ErrorResponse> {
+    organization_members::assert_role(pool, organization_id, user_id, min_role)
+        .await
+        .map_err(|err| {
+            if matches!(err, IdentityError::PermissionDenied) {
+                warn!(%organization_id, %user_id, ?min_role, "member role too low");
+            }
+            membership_error(err, "Insufficient role for this operation")
+        })
+}
+
 pub(crate) async fn ensure_project_access(
     pool: &PgPool,
     user_id: Uuid,
@@ -1,18 +1,24 @@
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::put,
 };
+use serde::Deserialize;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::{ErrorResponse, invalid_color_response},
+    organization_members::{ensure_member_role, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
+        organization_members::MemberRole,
         project_statuses::{ProjectStatus, ProjectStatusRepository},
-        types::is_valid_hsl_color,
+        types::normalize_color,
     },
     define_mutation_router,
     entities::{
@@ -25,6 +31,24 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(ProjectStatus, table: "project_statuses");
 
+#[derive(Debug, Deserialize)]
+pub struct DeleteProjectStatusQuery {
+    /// Status to move issues to before deleting.
+    pub reassign_to: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReorderProjectStatusesRequest {
+    pub ordered_ids: Vec<Uuid>,
+}
+
+pub fn reorder_router() -> Router<AppState> {
+    Router::new().route(
+        "/projects/{project_id}/statuses/reorder",
+        put(reorder_project_statuses),
+    )
+}
+
 #[instrument(
     name = "project_statuses.list_project_statuss",
     skip(state, ctx),
@@ -88,26 +112,21 @@ async fn create_project_status(
 ) -> Result<Json<MutationResponse<ProjectStatus>>, ErrorResponse> {
     ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
-    if !is_valid_hsl_color(&payload.color) {
-        return Err(ErrorResponse::new(
-            StatusCode::BAD_REQUEST,
-            "Invalid color format. Expected HSL format: 'H S% L%'",
-        ));
-    }
+    let color = normalize_color(&payload.color).map_err(invalid_color_response)?;
 
     let response = ProjectStatusRepository::create(
         state.pool(),
         payload.id,
         payload.project_id,
         payload.name,
-        payload.color,
+        color,
         payload.sort_order,
         payload.hidden,
     )
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create project status");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -124,6 +143,13 @@ async fn update_project_status(
     Path(project_status_id): Path<Uuid>,
     Json(payload): Json<UpdateProjectStatusRequest>,
 ) -> Result<Json<MutationResponse<ProjectStatus>>, ErrorResponse> {
+    if payload.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "no fields to update",
+        ));
+    }
+
     let status = ProjectStatusRepository::find_by_id(state.pool(), project_status_id)
         .await
         .map_err(|error| {
@@ -137,27 +163,25 @@ async fn update_project_status(
 
     ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
 
-    if let Some(ref color) = payload.color
-        && !is_valid_hsl_color(color)
-    {
-        return Err(ErrorResponse::new(
-            StatusCode::BAD_REQUEST,
-            "Invalid color format. Expected HSL format: 'H S% L%'",
-        ));
-    }
+    let color = payload
+        .color
+        .as_deref()
+        .map(normalize_color)
+        .transpose()
+        .map_err(invalid_color_response)?;
 
     let response = ProjectStatusRepository::update(
         state.pool(),
         project_status_id,
         payload.name,
-        payload.color,
+        color,
         payload.sort_order,
         payload.hidden,
     )
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to update project status");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -172,6 +196,7 @@ async fn delete_project_status(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Path(project_status_id): Path<Uuid>,
+    Query(query): Query<DeleteProjectStatusQuery>,
 ) -> Result<Json<DeleteResponse>, ErrorResponse> {
     let status = ProjectStatusRepository::find_by_id(state.pool(), project_status_id)
         .await
@@ -184,13 +209,52 @@ async fn delete_project_status(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    ensure_member_role(
+        state.pool(),
+        organization_id,
+        ctx.user.id,
+        MemberRole::Admin,
+    )
+    .await?;
+
+    let response =
+        ProjectStatusRepository::delete(state.pool(), project_status_id, query.reassign_to)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to delete project status");
+                ErrorResponse::from(error)
+            })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "project_statuses.reorder_project_statuses",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn reorder_project_statuses(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ReorderProjectStatusesRequest>,
+) -> Result<Json<MutationResponse<Vec<ProjectStatus>>>, ErrorResponse> {
+    let organization_id = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_member_role(
+        state.pool(),
+        organization_id,
+        ctx.user.id,
+        MemberRole::Admin,
+    )
+    .await?;
 
-    let response = ProjectStatusRepository::delete(state.pool(), project_status_id)
+    let response = ProjectStatusRepository::reorder(state.pool(), project_id, payload.ordered_ids)
         .await
         .map_err(|error| {
-            tracing::error!(?error, "failed to delete project status");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            tracing::error!(?error, "failed to reorder project statuses");
+            ErrorResponse::from(error)
         })?;
 
     Ok(Json(response))
@@ -1,17 +1,23 @@
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::{get, post},
 };
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_member_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::{ensure_member_access, ensure_member_role, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        projects::{Project, ProjectRepository},
+        organization_members::MemberRole,
+        project_transfer::{ProjectExport, ProjectTransferError, ProjectTransferRepository},
+        projects::{Project, ProjectError, ProjectRepository},
         types::is_valid_hsl_color,
     },
     define_mutation_router,
@@ -24,6 +30,15 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(Project, table: "projects");
 
+pub fn transfer_router() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/export", get(export_project))
+        .route(
+            "/organizations/{organization_id}/projects/import",
+            post(import_project),
+        )
+}
+
 #[instrument(
     name = "projects.list_projects",
     skip(state, ctx),
@@ -90,6 +105,7 @@ async fn create_project(
 
     let response = ProjectRepository::create_with_defaults(
         state.pool(),
+        ctx.user.id,
         payload.id,
         payload.organization_id,
         payload.name,
@@ -98,7 +114,7 @@ async fn create_project(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create project");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -134,12 +150,18 @@ async fn update_project(
         ));
     }
 
-    let response = ProjectRepository::update(state.pool(), project_id, payload.name, payload.color)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to update project");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-        })?;
+    let response = ProjectRepository::update(
+        state.pool(),
+        ctx.user.id,
+        project_id,
+        payload.name,
+        payload.color,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update project");
+        ErrorResponse::from(error)
+    })?;
 
     Ok(Json(response))
 }
@@ -162,14 +184,89 @@ async fn delete_project(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
 
-    ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
+    ensure_member_role(
+        state.pool(),
+        project.organization_id,
+        ctx.user.id,
+        MemberRole::Admin,
+    )
+    .await?;
 
-    let response = ProjectRepository::delete(state.pool(), project_id)
+    let response = ProjectRepository::delete(state.pool(), ctx.user.id, project_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete project");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            ErrorResponse::from(error)
+        })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "projects.export_project",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn export_project(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectExport>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let export = ProjectTransferRepository::export(state.pool(), project_id)
+        .await
+        .map_err(|error| match error {
+            ProjectTransferError::ProjectNotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "project not found")
+            }
+            error => {
+                tracing::error!(?error, %project_id, "failed to export project");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to export project",
+                )
+            }
         })?;
 
+    Ok(Json(export))
+}
+
+#[instrument(
+    name = "projects.import_project",
+    skip(state, ctx, payload),
+    fields(organization_id = %organization_id, user_id = %ctx.user.id)
+)]
+async fn import_project(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<ProjectExport>,
+) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
+    ensure_member_access(state.pool(), organization_id, ctx.user.id).await?;
+
+    let response =
+        ProjectTransferRepository::import(state.pool(), organization_id, ctx.user.id, &payload)
+            .await
+            .map_err(|error| match error {
+                ProjectTransferError::UnsupportedVersion(_)
+                | ProjectTransferError::InvalidReference { .. }
+                | ProjectTransferError::DuplicateId { .. } => {
+                    ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+                }
+                ProjectTransferError::Database(error)
+                | ProjectTransferError::Project(ProjectError::Database(error)) => {
+                    tracing::error!(?error, "failed to import project");
+                    map_db_error(error)
+                }
+                error => {
+                    tracing::error!(?error, "failed to import project");
+                    ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "failed to import project",
+                    )
+                }
+            })?;
+
     Ok(Json(response))
 }
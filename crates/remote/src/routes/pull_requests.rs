@@ -9,13 +9,16 @@ use serde::Deserialize;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_issue_access};
+use super::{
+    error::{ErrorResponse, map_db_error},
+    organization_members::ensure_issue_access,
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
         issues::IssueRepository,
-        pull_requests::{PullRequest, PullRequestRepository},
+        pull_requests::{PullRequest, PullRequestError, PullRequestRepository},
         types::PullRequestStatus,
         workspaces::WorkspaceRepository,
     },
@@ -95,15 +98,22 @@ async fn create_pull_request(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create pull request");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            PullRequestError::Database(error) => map_db_error(error),
+        }
     })?;
 
-    IssueRepository::sync_status_from_pull_request(state.pool(), pr.issue_id, pr.status)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to sync issue status");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-        })?;
+    IssueRepository::sync_status_from_pull_request(
+        state.pool(),
+        ctx.user.id,
+        pr.issue_id,
+        pr.status,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to sync issue status");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
 
     Ok(Json(pr))
 }
@@ -141,15 +151,22 @@ async fn update_pull_request(
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to update pull request");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        match error {
+            PullRequestError::Database(error) => map_db_error(error),
+        }
     })?;
 
-    IssueRepository::sync_status_from_pull_request(state.pool(), pr.issue_id, pr.status)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to sync issue status");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-        })?;
+    IssueRepository::sync_status_from_pull_request(
+        state.pool(),
+        ctx.user.id,
+        pr.issue_id,
+        pr.status,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to sync issue status");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
 
     Ok(Json(pr))
 }
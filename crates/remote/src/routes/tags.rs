@@ -6,13 +6,17 @@ use axum::{
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::{ErrorResponse, invalid_color_response},
+    organization_members::{ensure_member_role, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
+        organization_members::MemberRole,
         tags::{Tag, TagRepository},
-        types::is_valid_hsl_color,
+        types::normalize_color,
     },
     define_mutation_router,
     entities::{CreateTagRequest, ListTagsQuery, ListTagsResponse, UpdateTagRequest},
@@ -79,24 +83,19 @@ async fn create_tag(
 ) -> Result<Json<MutationResponse<Tag>>, ErrorResponse> {
     ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
-    if !is_valid_hsl_color(&payload.color) {
-        return Err(ErrorResponse::new(
-            StatusCode::BAD_REQUEST,
-            "Invalid color format. Expected HSL format: 'H S% L%'",
-        ));
-    }
+    let color = normalize_color(&payload.color).map_err(invalid_color_response)?;
 
     let response = TagRepository::create(
         state.pool(),
         payload.id,
         payload.project_id,
         payload.name,
-        payload.color,
+        color,
     )
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to create tag");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        ErrorResponse::from(error)
     })?;
 
     Ok(Json(response))
@@ -113,6 +112,13 @@ async fn update_tag(
     Path(tag_id): Path<Uuid>,
     Json(payload): Json<UpdateTagRequest>,
 ) -> Result<Json<MutationResponse<Tag>>, ErrorResponse> {
+    if payload.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "no fields to update",
+        ));
+    }
+
     let tag = TagRepository::find_by_id(state.pool(), tag_id)
         .await
         .map_err(|error| {
@@ -123,21 +129,19 @@ async fn update_tag(
 
     ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
 
-    if let Some(ref color) = payload.color
-        && !is_valid_hsl_color(color)
-    {
-        return Err(ErrorResponse::new(
-            StatusCode::BAD_REQUEST,
-            "Invalid color format. Expected HSL format: 'H S% L%'",
-        ));
-    }
+    let color = payload
+        .color
+        .as_deref()
+        .map(normalize_color)
+        .transpose()
+        .map_err(invalid_color_response)?;
 
     // Partial update - use existing values if not provided
-    let response = TagRepository::update(state.pool(), tag_id, payload.name, payload.color)
+    let response = TagRepository::update(state.pool(), tag_id, payload.name, color)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to update tag");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            ErrorResponse::from(error)
         })?;
 
     Ok(Json(response))
@@ -161,13 +165,20 @@ async fn delete_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "tag not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    let organization_id = ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    ensure_member_role(
+        state.pool(),
+        organization_id,
+        ctx.user.id,
+        MemberRole::Admin,
+    )
+    .await?;
 
     let response = TagRepository::delete(state.pool(), tag_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete tag");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            ErrorResponse::from(error)
         })?;
 
     Ok(Json(response))
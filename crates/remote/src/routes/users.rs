@@ -0,0 +1,72 @@
+use axum::{Extension, Json, Router, extract::State, http::StatusCode, routing::get};
+use tracing::instrument;
+
+use super::error::ErrorResponse;
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        identity_errors::IdentityError,
+        users::{
+            ProfileValidationError, UpdateUserProfileRequest, User, UserRepository,
+            validate_avatar_url, validate_display_name,
+        },
+    },
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/users/me",
+        get(get_current_user).patch(update_current_user),
+    )
+}
+
+#[instrument(name = "users.get_current_user", skip(ctx), fields(user_id = %ctx.user.id))]
+async fn get_current_user(Extension(ctx): Extension<RequestContext>) -> Json<User> {
+    Json(ctx.user)
+}
+
+#[instrument(
+    name = "users.update_current_user",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn update_current_user(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<UpdateUserProfileRequest>,
+) -> Result<Json<User>, ErrorResponse> {
+    let display_name = payload
+        .display_name
+        .as_deref()
+        .map(validate_display_name)
+        .transpose()
+        .map_err(invalid_profile_response)?;
+    let avatar_url = payload
+        .avatar_url
+        .as_deref()
+        .map(validate_avatar_url)
+        .transpose()
+        .map_err(invalid_profile_response)?;
+
+    if display_name.is_none() && avatar_url.is_none() {
+        return Ok(Json(ctx.user));
+    }
+
+    let user = UserRepository::new(state.pool())
+        .update_profile(ctx.user.id, display_name, avatar_url)
+        .await
+        .map_err(|error| match error {
+            IdentityError::NotFound => ErrorResponse::new(StatusCode::NOT_FOUND, "user not found"),
+            error => {
+                tracing::error!(?error, "failed to update user profile");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(user))
+}
+
+fn invalid_profile_response(error: ProfileValidationError) -> ErrorResponse {
+    ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+}
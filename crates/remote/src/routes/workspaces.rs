@@ -1,21 +1,37 @@
+use std::collections::HashSet;
+
 use axum::{
     Json, Router,
     extract::{Extension, Path, State},
     http::StatusCode,
-    routing::{delete, post},
+    routing::{get, patch, post},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use super::{
+    error::ErrorResponse,
+    notifications::{ProjectNotification, notify_users},
+    organization_members::{ensure_issue_access, ensure_member_role, ensure_project_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
         issues::IssueRepository,
-        workspaces::{CreateWorkspaceParams, Workspace, WorkspaceRepository},
+        notifications::NotificationType,
+        organization_members::MemberRole,
+        workspace_reviews::{
+            AggregateReviewState, CreateWorkspaceReviewRequest, ListWorkspaceReviewsResponse,
+            WorkspaceReview, WorkspaceReviewRepository, WorkspaceReviewState,
+        },
+        workspaces::{
+            CreateWorkspaceParams, Workspace, WorkspaceRepository, WorkspaceWithReviewState,
+        },
     },
+    mutation_types::MutationResponse,
 };
 
 #[derive(Debug, Deserialize)]
@@ -43,6 +59,16 @@ pub struct DeleteWorkspaceRequest {
     pub local_workspace_id: Uuid,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct LinkWorkspaceIssueRequest {
+    pub issue_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListIssueWorkspacesResponse {
+    pub workspaces: Vec<WorkspaceWithReviewState>,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route(
@@ -51,7 +77,18 @@ pub fn router() -> Router<AppState> {
                 .patch(update_workspace)
                 .delete(delete_workspace),
         )
-        .route("/workspaces/{workspace_id}", delete(unlink_workspace))
+        .route(
+            "/workspaces/{workspace_id}",
+            patch(link_workspace_issue).delete(unlink_workspace),
+        )
+        .route("/issues/{issue_id}/workspaces", get(list_issue_workspaces))
+}
+
+pub fn reviews_router() -> Router<AppState> {
+    Router::new().route(
+        "/workspaces/{workspace_id}/reviews",
+        get(list_workspace_reviews).post(create_workspace_review),
+    )
 }
 
 #[instrument(
@@ -90,7 +127,8 @@ async fn create_workspace(
 
     if let Some(issue_id) = payload.issue_id
         && let Err(error) =
-            IssueRepository::sync_status_from_workspace_created(state.pool(), issue_id).await
+            IssueRepository::sync_status_from_workspace_created(state.pool(), ctx.user.id, issue_id)
+                .await
     {
         tracing::warn!(
             ?error,
@@ -212,3 +250,230 @@ async fn unlink_workspace(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[instrument(
+    name = "workspaces.link_workspace_issue",
+    skip(state, ctx, payload),
+    fields(workspace_id = %workspace_id, issue_id = ?payload.issue_id, user_id = %ctx.user.id)
+)]
+async fn link_workspace_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(workspace_id): Path<Uuid>,
+    Json(payload): Json<LinkWorkspaceIssueRequest>,
+) -> Result<Json<MutationResponse<Workspace>>, ErrorResponse> {
+    let workspace = WorkspaceRepository::find_by_id(state.pool(), workspace_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to find workspace");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to find workspace",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "workspace not found"))?;
+
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
+    if workspace.owner_user_id != ctx.user.id {
+        ensure_member_role(
+            state.pool(),
+            organization_id,
+            ctx.user.id,
+            MemberRole::Admin,
+        )
+        .await?;
+    }
+
+    if let Some(issue_id) = payload.issue_id {
+        let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %issue_id, "failed to load issue");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
+            })?
+            .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+        if issue.project_id != workspace.project_id {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "issue belongs to a different project than the workspace",
+            ));
+        }
+    }
+
+    let response = WorkspaceRepository::set_issue(state.pool(), workspace_id, payload.issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to link workspace to issue");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to update workspace",
+            )
+        })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "workspaces.list_issue_workspaces",
+    skip(state, ctx),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn list_issue_workspaces(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<Json<ListIssueWorkspacesResponse>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let workspaces = WorkspaceRepository::list_by_issue(state.pool(), issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to list workspaces for issue");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list workspaces",
+            )
+        })?;
+
+    let workspace_ids: Vec<Uuid> = workspaces.iter().map(|w| w.id).collect();
+    let mut review_states =
+        WorkspaceReviewRepository::aggregate_states(state.pool(), &workspace_ids)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to load workspace review states");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to list workspaces",
+                )
+            })?;
+
+    let workspaces = workspaces
+        .into_iter()
+        .map(|workspace| WorkspaceWithReviewState {
+            review_state: review_states
+                .remove(&workspace.id)
+                .unwrap_or(AggregateReviewState::Pending),
+            workspace,
+        })
+        .collect();
+
+    Ok(Json(ListIssueWorkspacesResponse { workspaces }))
+}
+
+async fn load_workspace(state: &AppState, workspace_id: Uuid) -> Result<Workspace, ErrorResponse> {
+    WorkspaceRepository::find_by_id(state.pool(), workspace_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to find workspace");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to find workspace",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "workspace not found"))
+}
+
+#[instrument(
+    name = "workspaces.list_workspace_reviews",
+    skip(state, ctx),
+    fields(workspace_id = %workspace_id, user_id = %ctx.user.id)
+)]
+async fn list_workspace_reviews(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<ListWorkspaceReviewsResponse>, ErrorResponse> {
+    let workspace = load_workspace(&state, workspace_id).await?;
+    ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
+
+    let reviews = WorkspaceReviewRepository::list_by_workspace(state.pool(), workspace_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to list workspace reviews");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list reviews")
+        })?;
+
+    let latest = WorkspaceReviewRepository::latest_per_reviewer(state.pool(), &[workspace_id])
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to load latest workspace reviews");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list reviews")
+        })?;
+
+    Ok(Json(ListWorkspaceReviewsResponse {
+        reviews,
+        review_state: AggregateReviewState::from_latest(&latest),
+    }))
+}
+
+#[instrument(
+    name = "workspaces.create_workspace_review",
+    skip(state, ctx, payload),
+    fields(workspace_id = %workspace_id, state = ?payload.state, user_id = %ctx.user.id)
+)]
+async fn create_workspace_review(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(workspace_id): Path<Uuid>,
+    Json(payload): Json<CreateWorkspaceReviewRequest>,
+) -> Result<Json<WorkspaceReview>, ErrorResponse> {
+    let workspace = load_workspace(&state, workspace_id).await?;
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
+
+    let body = payload.body.trim();
+    if payload.state == WorkspaceReviewState::Commented && body.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "a comment review needs a body",
+        ));
+    }
+
+    let review = WorkspaceReviewRepository::create(
+        state.pool(),
+        workspace_id,
+        ctx.user.id,
+        payload.state,
+        body,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to create workspace review");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to create review")
+    })?;
+
+    notify_workspace_owner(&state, organization_id, &workspace, &review).await;
+
+    Ok(Json(review))
+}
+
+/// Tell the workspace owner about a review from a teammate. Failures are logged so they
+/// never fail the review itself.
+async fn notify_workspace_owner(
+    state: &AppState,
+    organization_id: Uuid,
+    workspace: &Workspace,
+    review: &WorkspaceReview,
+) {
+    notify_users(
+        state.pool(),
+        review.reviewer_user_id,
+        [workspace.owner_user_id],
+        ProjectNotification {
+            organization_id,
+            project_id: workspace.project_id,
+            issue_id: workspace.issue_id,
+            comment_id: None,
+            notification_type: NotificationType::WorkspaceReviewSubmitted,
+            payload: json!({
+                "workspace_id": workspace.id,
+                "review_id": review.id,
+                "state": review.state,
+                "reviewer_id": review.reviewer_user_id,
+            }),
+            mentioned_user_ids: HashSet::new(),
+        },
+    )
+    .await;
+}
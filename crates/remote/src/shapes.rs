@@ -14,6 +14,8 @@ use ts_rs::TS;
 
 #[derive(Debug)]
 pub struct ShapeDefinition<T: TS> {
+    /// Name of the Rust constant holding this shape
+    pub name: &'static str,
     pub table: &'static str,
     pub where_clause: &'static str,
     pub params: &'static [&'static str],
@@ -23,6 +25,7 @@ pub struct ShapeDefinition<T: TS> {
 
 /// Trait to allow heterogeneous collection of shapes for export
 pub trait ShapeExport: Sync {
+    fn name(&self) -> &'static str;
     fn table(&self) -> &'static str;
     fn where_clause(&self) -> &'static str;
     fn params(&self) -> &'static [&'static str];
@@ -31,6 +34,9 @@ pub trait ShapeExport: Sync {
 }
 
 impl<T: TS + Sync> ShapeExport for ShapeDefinition<T> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
     fn table(&self) -> &'static str {
         self.table
     }
@@ -90,6 +96,7 @@ macro_rules! define_shape {
             }
 
             $crate::shapes::ShapeDefinition {
+                name: stringify!($name),
                 table: $table,
                 where_clause: $where,
                 params: &[$($param),*],
@@ -102,11 +109,13 @@ macro_rules! define_shape {
 
 // Re-export shape constants from entities module for backward compatibility
 pub use crate::entities::{
-    ISSUE_ASSIGNEE_SHAPE as ISSUE_ASSIGNEES,
+    ISSUE_ACTIVITY_SHAPE as ISSUE_ACTIVITY, ISSUE_ASSIGNEE_SHAPE as ISSUE_ASSIGNEES,
     ISSUE_COMMENT_REACTION_SHAPE as ISSUE_COMMENT_REACTIONS, ISSUE_COMMENT_SHAPE as ISSUE_COMMENTS,
     ISSUE_FOLLOWER_SHAPE as ISSUE_FOLLOWERS, ISSUE_RELATIONSHIP_SHAPE as ISSUE_RELATIONSHIPS,
-    ISSUE_SHAPE as ISSUES, ISSUE_TAG_SHAPE as ISSUE_TAGS, NOTIFICATION_SHAPE as NOTIFICATIONS,
+    ISSUE_SHAPE as ISSUES, ISSUE_TAG_SHAPE as ISSUE_TAGS,
+    MY_ASSIGNED_ISSUES_SHAPE as MY_ASSIGNED_ISSUES, NOTIFICATION_SHAPE as NOTIFICATIONS,
     ORGANIZATION_MEMBER_SHAPE as ORGANIZATION_MEMBERS, PROJECT_SHAPE as PROJECTS,
     PROJECT_STATUS_SHAPE as PROJECT_STATUSES, PULL_REQUEST_SHAPE as PULL_REQUESTS,
-    TAG_SHAPE as TAGS, USER_SHAPE as USERS, WORKSPACE_SHAPE as WORKSPACES, all_shapes,
+    SINGLE_ISSUE_SHAPE as SINGLE_ISSUE, SUB_ISSUES_SHAPE as SUB_ISSUES, TAG_SHAPE as TAGS,
+    USER_SHAPE as USERS, WORKSPACE_SHAPE as WORKSPACES, all_shapes,
 };
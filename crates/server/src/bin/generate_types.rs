@@ -16,6 +16,7 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::repo::CommitCheck::decl(),
         db::models::repo::Repo::decl(),
         db::models::repo::UpdateRepo::decl(),
         db::models::project_repo::ProjectRepo::decl(),
@@ -32,6 +33,9 @@ fn generate_types_content() -> String {
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_template::TaskTemplate::decl(),
+        db::models::task_template::CreateTaskTemplate::decl(),
+        db::models::task_template::UpdateTaskTemplate::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::DraftWorkspaceData::decl(),
         db::models::scratch::DraftWorkspaceRepo::decl(),
@@ -49,8 +53,12 @@ fn generate_types_content() -> String {
         db::models::session::Session::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
+        db::models::execution_process::ExecutionProcessTermination::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_env::EnvVarSource::decl(),
+        db::models::execution_process_env::ExecutionEnvVar::decl(),
+        db::models::execution_process_env::ExecutionProcessEnv::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -92,8 +100,14 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::ArchiveCompletedQuery::decl(),
+        server::routes::projects::ArchiveSkipReason::decl(),
+        server::routes::projects::SkippedWorkspace::decl(),
+        server::routes::projects::ArchiveCompletedResponse::decl(),
+        server::routes::admin::CleanupRequest::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
+        server::routes::repo::GitHostCapabilitiesResponse::decl(),
         server::routes::tags::TagSearchParams::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
@@ -126,18 +140,28 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RunAgentSetupResponse::decl(),
         server::routes::task_attempts::gh_cli_setup::GhCliSetupError::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
+        server::routes::task_attempts::RebaseTaskAttemptResponse::decl(),
         server::routes::task_attempts::AbortConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::PushError::decl(),
         server::routes::task_attempts::pr::PrError::decl(),
         server::routes::task_attempts::RunScriptError::decl(),
+        server::routes::task_attempts::pr::CreatePrResponse::decl(),
         server::routes::task_attempts::pr::AttachPrResponse::decl(),
         server::routes::task_attempts::pr::AttachExistingPrRequest::decl(),
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
-        server::routes::task_attempts::pr::GetPrCommentsError::decl(),
+        server::routes::task_attempts::pr::PrRouteError::decl(),
+        server::routes::task_attempts::pr::UpdatePrApiRequest::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
+        server::routes::task_attempts::pr::GetPrChecksQuery::decl(),
+        server::routes::task_attempts::pr::GetPrStatusQuery::decl(),
+        server::routes::task_attempts::pr::PrStatusResponse::decl(),
         services::services::git_host::UnifiedPrComment::decl(),
         services::services::git_host::ProviderKind::decl(),
+        services::services::git_host::ProviderCapabilities::decl(),
+        services::services::git_host::PrChecks::decl(),
+        services::services::git_host::ChecksStatus::decl(),
+        services::services::git_host::ReviewDecision::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
         server::routes::task_attempts::UpdateWorkspace::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryRequest::decl(),
@@ -146,7 +170,10 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
+        services::services::workspace_manager::OrphanCleanupReport::decl(),
+        services::services::workspace_manager::OrphanCleanupFailure::decl(),
         services::services::file_search::SearchMode::decl(),
+        services::services::file_search::GrepMatch::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
         services::services::config::ThemeMode::decl(),
@@ -124,10 +124,18 @@ impl IntoResponse for ApiError {
                 }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
-            ApiError::GitHost(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHostError"),
+            ApiError::GitHost(git_host_err) => match git_host_err {
+                GitHostError::TokenNotConfigured { .. } => {
+                    (StatusCode::UNAUTHORIZED, "GitHostError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitHostError"),
+            },
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
-            ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
+            ApiError::Executor(executor_err) => match executor_err {
+                ExecutorError::PermissionDenied { .. } => (StatusCode::FORBIDDEN, "ExecutorError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
+            },
             ApiError::CommandBuilder(_) => (StatusCode::INTERNAL_SERVER_ERROR, "CommandBuildError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
             ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
@@ -211,6 +219,7 @@ impl IntoResponse for ApiError {
                 }
                 _ => format!("{}: {}", error_type, self),
             },
+            ApiError::GitHost(err @ GitHostError::TokenNotConfigured { .. }) => err.to_string(),
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
             ApiError::RemoteClient(err) => match err {
                 RemoteClientError::Auth => "Unauthorized. Please sign in again.".to_string(),
@@ -287,6 +296,10 @@ impl From<ProjectServiceError> for ApiError {
             ProjectServiceError::NotGitRepository(path) => {
                 ApiError::BadRequest(format!("Path is not a git repository: {}", path.display()))
             }
+            ProjectServiceError::AccessDenied(path) => ApiError::Forbidden(format!(
+                "Permission denied reading path: {}",
+                path.display()
+            )),
             ProjectServiceError::DuplicateGitRepoPath => ApiError::Conflict(
                 "A project with this git repository path already exists".to_string(),
             ),
@@ -302,6 +315,16 @@ impl From<ProjectServiceError> for ApiError {
             ProjectServiceError::RemoteClient(msg) => {
                 ApiError::BadRequest(format!("Remote client error: {}", msg))
             }
+            ProjectServiceError::ContentSearchUnavailable => ApiError::BadRequest(
+                "Content search unavailable: ripgrep (rg) is not installed".to_string(),
+            ),
+            ProjectServiceError::InvalidRemoteUrl(url) => {
+                ApiError::BadRequest(format!("Invalid repository URL: {}", url))
+            }
+            ProjectServiceError::CloneDestinationExists(path) => ApiError::Conflict(format!(
+                "Clone destination already exists: {}",
+                path.display()
+            )),
         }
     }
 }
@@ -51,6 +51,15 @@ async fn main() -> Result<(), VibeKanbanError> {
     }
 
     let deployment = DeploymentImpl::new().await?;
+    if let Some(prompt) = deployment
+        .config()
+        .read()
+        .await
+        .pr_auto_description_prompt
+        .as_deref()
+    {
+        routes::task_attempts::pr::warn_unknown_pr_prompt_placeholders(prompt);
+    }
     deployment.update_sentry_scope().await?;
     deployment
         .container()
@@ -0,0 +1,38 @@
+use axum::{Router, extract::State, response::Json as ResponseJson, routing::post};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{container::ContainerService, workspace_manager::OrphanCleanupReport};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct CleanupRequest {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+pub async fn cleanup_orphans(
+    State(deployment): State<DeploymentImpl>,
+    ResponseJson(payload): ResponseJson<CleanupRequest>,
+) -> Result<ResponseJson<ApiResponse<OrphanCleanupReport>>, ApiError> {
+    let report = deployment
+        .container()
+        .cleanup_orphans(payload.dry_run)
+        .await?;
+
+    tracing::info!(
+        "Orphan cleanup (dry_run: {}) removed {} workspace dirs, cleared {} container refs, {} failures",
+        report.dry_run,
+        report.removed.len(),
+        report.cleared_container_refs.len(),
+        report.failures.len()
+    );
+
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/admin/cleanup", post(cleanup_orphans))
+}
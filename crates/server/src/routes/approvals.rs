@@ -18,16 +18,28 @@ pub async fn respond_to_approval(
     Path(id): Path<String>,
     ResponseJson(request): ResponseJson<ApprovalResponse>,
 ) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, StatusCode> {
+    // Tool inputs are always JSON objects; anything else can't be handed back to the agent
+    if let ApprovalStatus::ApprovedWithEdits { updated_input } = &request.status
+        && !updated_input.is_object()
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
     let service = deployment.approvals();
 
     match service.respond(&deployment.db().pool, &id, request).await {
         Ok((status, context)) => {
+            // Don't send the edited tool input to analytics
+            let status_label = match &status {
+                ApprovalStatus::ApprovedWithEdits { .. } => "ApprovedWithEdits".to_string(),
+                other => format!("{:?}", other),
+            };
             deployment
                 .track_if_analytics_allowed(
                     "approval_responded",
                     serde_json::json!({
                         "approval_id": &id,
-                        "status": format!("{:?}", status),
+                        "status": status_label,
                         "tool_name": context.tool_name,
                         "execution_process_id": context.execution_process_id.to_string(),
                     }),
@@ -13,15 +13,18 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
+    execution_process::ExecutionProcess,
+    merge::{Merge, MergeStatus},
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
+    workspace::Workspace,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::{
-    file_search::SearchQuery, project::ProjectServiceError,
+    container::ContainerService, file_search::SearchQuery, project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
 };
 use ts_rs::TS;
@@ -44,6 +47,39 @@ pub struct CreateRemoteProjectRequest {
     pub name: String,
 }
 
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct ArchiveCompletedQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveSkipReason {
+    Pinned,
+    Running,
+    MergePending,
+    /// Removing the workspace's worktrees failed, so it was left unarchived
+    CleanupFailed,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SkippedWorkspace {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub reason: ArchiveSkipReason,
+}
+
+/// Summary of archiving the workspaces of a project's done tasks
+#[derive(Debug, Default, Serialize, TS)]
+pub struct ArchiveCompletedResponse {
+    /// When true nothing was archived; the response lists what would have been
+    pub dry_run: bool,
+    /// Workspaces that were (or would be) archived and had their worktrees removed
+    pub archived: Vec<Uuid>,
+    pub skipped: Vec<SkippedWorkspace>,
+}
+
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
@@ -99,6 +135,70 @@ pub async fn get_project(
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+/// Archive the workspaces of done tasks and free their worktrees. Pinned workspaces, those
+/// with running processes and those with an open pull request are left alone.
+pub async fn archive_completed_workspaces(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ArchiveCompletedQuery>,
+) -> Result<ResponseJson<ApiResponse<ArchiveCompletedResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut response = ArchiveCompletedResponse {
+        dry_run: query.dry_run,
+        ..Default::default()
+    };
+
+    for workspace in Workspace::find_unarchived_for_done_tasks(pool, project.id).await? {
+        let skip_reason = if workspace.pinned {
+            Some(ArchiveSkipReason::Pinned)
+        } else if ExecutionProcess::has_running_processes_for_workspace(pool, workspace.id).await? {
+            Some(ArchiveSkipReason::Running)
+        } else if Merge::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .iter()
+            .any(|merge| matches!(merge, Merge::Pr(pr) if pr.pr_info.status == MergeStatus::Open))
+        {
+            Some(ArchiveSkipReason::MergePending)
+        } else {
+            None
+        };
+
+        if let Some(reason) = skip_reason {
+            response.skipped.push(SkippedWorkspace {
+                workspace_id: workspace.id,
+                task_id: workspace.task_id,
+                reason,
+            });
+            continue;
+        }
+
+        if !query.dry_run {
+            // Cleaned up first so a failure leaves the workspace unarchived and retryable
+            if let Err(e) = deployment.container().delete(&workspace).await {
+                tracing::warn!("Failed to clean up workspace {}: {}", workspace.id, e);
+                response.skipped.push(SkippedWorkspace {
+                    workspace_id: workspace.id,
+                    task_id: workspace.task_id,
+                    reason: ArchiveSkipReason::CleanupFailed,
+                });
+                continue;
+            }
+            Workspace::set_archived(pool, workspace.id, true).await?;
+        }
+        response.archived.push(workspace.id);
+    }
+
+    tracing::info!(
+        "Archive completed (dry_run: {}) for project {}: {} archived, {} skipped",
+        response.dry_run,
+        project.id,
+        response.archived.len(),
+        response.skipped.len()
+    );
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 pub async fn link_project_to_existing_remote(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -215,6 +315,33 @@ async fn apply_remote_project_link(
     Ok(updated_project)
 }
 
+/// Build the project's file search indexes in the background so the first search is fast.
+fn spawn_search_cache_warmup(deployment: &DeploymentImpl, project_id: Uuid) {
+    let deployment = deployment.clone();
+    tokio::spawn(async move {
+        let repositories = match deployment
+            .project()
+            .get_repositories(&deployment.db().pool, project_id)
+            .await
+        {
+            Ok(repos) => repos,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load repositories to warm search cache for project {}: {}",
+                    project_id,
+                    e
+                );
+                return;
+            }
+        };
+
+        deployment
+            .project()
+            .warm_search_cache(deployment.file_search_cache().as_ref(), &repositories)
+            .await;
+    });
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -228,6 +355,8 @@ pub async fn create_project(
         .await
     {
         Ok(project) => {
+            spawn_search_cache_warmup(&deployment, project.id);
+
             // Track project creation event
             deployment
                 .track_if_analytics_allowed(
@@ -271,7 +400,10 @@ pub async fn update_project(
         .update_project(&deployment.db().pool, &existing_project, payload)
         .await
     {
-        Ok(project) => Ok(ResponseJson(ApiResponse::success(project))),
+        Ok(project) => {
+            spawn_search_cache_warmup(&deployment, project.id);
+            Ok(ResponseJson(ApiResponse::success(project)))
+        }
         Err(e) => {
             tracing::error!("Failed to update project: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -385,10 +517,9 @@ pub async fn search_project_files(
     Extension(project): Extension<Project>,
     Query(search_query): Query<SearchQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, StatusCode> {
-    if search_query.q.trim().is_empty() {
-        return Ok(ResponseJson(ApiResponse::error(
-            "Query parameter 'q' is required and cannot be empty",
-        )));
+    let content_search_enabled = deployment.config().read().await.content_search_enabled;
+    if let Err(message) = search_query.validate(content_search_enabled) {
+        return Ok(ResponseJson(ApiResponse::error(message)));
     }
 
     let repositories = match deployment
@@ -493,6 +624,15 @@ pub async fn add_project_repository(
                 "The specified directory is not a git repository",
             )))
         }
+        Err(ProjectServiceError::AccessDenied(_)) => {
+            tracing::warn!(
+                "Failed to add repository to project {}: permission denied",
+                project.id
+            );
+            Ok(ResponseJson(ApiResponse::error(
+                "Permission denied reading the specified directory",
+            )))
+        }
         Err(ProjectServiceError::DuplicateRepositoryName) => {
             tracing::warn!(
                 "Failed to add repository to project {}: duplicate repository name",
@@ -577,6 +717,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
+        .route(
+            "/workspaces/archive_completed",
+            post(archive_completed_workspaces),
+        )
         .route(
             "/link",
             post(link_project_to_existing_remote).delete(unlink_project),
@@ -10,8 +10,12 @@ use db::models::{
     repo::{Repo, UpdateRepo},
 };
 use deployment::Deployment;
-use serde::Deserialize;
-use services::services::{file_search::SearchQuery, git::GitBranch};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    file_search::SearchQuery,
+    git::GitBranch,
+    git_host::{GitHostError, GitHostProvider, GitHostService, ProviderCapabilities, ProviderKind},
+};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -42,6 +46,13 @@ pub struct BatchRepoRequest {
     pub ids: Vec<Uuid>,
 }
 
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct GitHostCapabilitiesResponse {
+    pub provider: ProviderKind,
+    pub capabilities: ProviderCapabilities,
+}
+
 pub async fn register_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<RegisterRepoRequest>,
@@ -114,6 +125,42 @@ pub async fn get_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+/// Reports what the repo's git host supports, resolved from the remote of its default
+/// target branch (or the current branch when none is configured).
+pub async fn get_git_host_capabilities(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<GitHostCapabilitiesResponse>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+
+    let git = deployment.git();
+    let branch = match repo.default_target_branch {
+        Some(branch) => branch,
+        None => git.get_current_branch(&repo.path)?,
+    };
+    let remote_url = git.get_remote_url(
+        &repo.path,
+        &git.resolve_remote_name_for_branch(&repo.path, &branch)?,
+    )?;
+
+    let response = match GitHostService::from_url(&remote_url) {
+        Ok(host) => GitHostCapabilitiesResponse {
+            provider: host.provider_kind(),
+            capabilities: host.capabilities(),
+        },
+        Err(GitHostError::UnsupportedProvider) => GitHostCapabilitiesResponse {
+            provider: ProviderKind::Unknown,
+            capabilities: ProviderCapabilities::default(),
+        },
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 pub async fn update_repo(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -175,10 +222,9 @@ pub async fn search_repo(
     Path(repo_id): Path<Uuid>,
     Query(search_query): Query<SearchQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, StatusCode> {
-    if search_query.q.trim().is_empty() {
-        return Ok(ResponseJson(ApiResponse::error(
-            "Query parameter 'q' is required and cannot be empty",
-        )));
+    let content_search_enabled = deployment.config().read().await.content_search_enabled;
+    if let Err(message) = search_query.validate(content_search_enabled) {
+        return Ok(ResponseJson(ApiResponse::error(message)));
     }
 
     let repo = match deployment
@@ -195,7 +241,12 @@ pub async fn search_repo(
 
     match deployment
         .file_search_cache()
-        .search_repo(&repo.path, &search_query.q, search_query.mode)
+        .search_repo(
+            &repo.path,
+            &search_query.q,
+            search_query.mode,
+            search_query.limit,
+        )
         .await
     {
         Ok(results) => Ok(ResponseJson(ApiResponse::success(results))),
@@ -213,6 +264,10 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/repos/batch", post(get_repos_batch))
         .route("/repos/{repo_id}", get(get_repo).put(update_repo))
         .route("/repos/{repo_id}/branches", get(get_repo_branches))
+        .route(
+            "/repos/{repo_id}/git-host/capabilities",
+            get(get_git_host_capabilities),
+        )
         .route("/repos/{repo_id}/search", get(search_repo))
         .route("/repos/{repo_id}/open-editor", post(open_repo_in_editor))
 }
@@ -47,7 +47,7 @@ use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
     file_search::SearchQuery,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{ConflictOp, GitCliError, GitService, GitServiceError},
     workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
@@ -65,6 +65,19 @@ pub struct RebaseTaskAttemptRequest {
     pub repo_id: Uuid,
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
+    /// Abort the rebase on conflict instead of leaving it in progress for resolution
+    #[serde(default)]
+    pub abort_on_conflict: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RebaseTaskAttemptResponse {
+    pub head_sha: String,
+    /// Commits on the workspace branch that were replayed onto the new base
+    pub commits_replayed: usize,
+    /// Set when uncommitted changes couldn't be reapplied after the rebase. They are kept in
+    /// this stash and can be recovered with `git stash apply <sha>`.
+    pub kept_stash: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -81,8 +94,11 @@ pub enum GitOperationError {
         op: ConflictOp,
         conflicted_files: Vec<String>,
         target_branch: String,
+        // Stash holding the workspace's uncommitted changes while the conflict is resolved
+        kept_stash: Option<String>,
     },
     RebaseInProgress,
+    ExecutionProcessRunning,
 }
 
 #[derive(Debug, Deserialize)]
@@ -703,6 +719,21 @@ pub struct BranchStatus {
     pub is_target_remote: bool,
 }
 
+/// Commits `branch` is ahead of and behind `target_branch`, comparing against the
+/// remote-tracking ref when the target is a remote branch.
+fn target_branch_status(
+    git: &GitService,
+    repo_path: &Path,
+    branch: &str,
+    target_branch: &str,
+    target_branch_type: BranchType,
+) -> Result<(usize, usize), GitServiceError> {
+    match target_branch_type {
+        BranchType::Local => git.get_branch_status(repo_path, branch, target_branch),
+        BranchType::Remote => git.get_remote_branch_status(repo_path, branch, Some(target_branch)),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, TS)]
 pub struct RepoBranchStatus {
     pub repo_id: Uuid,
@@ -793,24 +824,13 @@ pub async fn get_task_attempt_branch_status(
             .git()
             .find_branch_type(&repo.path, &target_branch)?;
 
-        let (commits_ahead, commits_behind) = match target_branch_type {
-            BranchType::Local => {
-                let (a, b) = deployment.git().get_branch_status(
-                    &repo.path,
-                    &workspace.branch,
-                    &target_branch,
-                )?;
-                (Some(a), Some(b))
-            }
-            BranchType::Remote => {
-                let (ahead, behind) = deployment.git().get_remote_branch_status(
-                    &repo.path,
-                    &workspace.branch,
-                    Some(&target_branch),
-                )?;
-                (Some(ahead), Some(behind))
-            }
-        };
+        let (commits_ahead, commits_behind) = target_branch_status(
+            deployment.git(),
+            &repo.path,
+            &workspace.branch,
+            &target_branch,
+            target_branch_type,
+        )?;
 
         let (remote_ahead, remote_behind) = if let Some(Merge::Pr(PrMerge {
             pr_info:
@@ -836,8 +856,8 @@ pub async fn get_task_attempt_branch_status(
             repo_id: repo.id,
             repo_name: repo.name,
             status: BranchStatus {
-                commits_ahead,
-                commits_behind,
+                commits_ahead: Some(commits_ahead),
+                commits_behind: Some(commits_behind),
                 has_uncommitted_changes,
                 head_oid,
                 uncommitted_count,
@@ -1090,9 +1110,18 @@ pub async fn rebase_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<RebaseTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<RebaseTaskAttemptResponse, GitOperationError>>, ApiError> {
     let pool = &deployment.db().pool;
 
+    // Rewriting the branch under a running agent would race with its commits
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            GitOperationError::ExecutionProcessRunning,
+        )));
+    }
+
     let workspace_repo =
         WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
             .await?
@@ -1140,38 +1169,60 @@ pub async fn rebase_task_attempt(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
-    let result = deployment.git().rebase_branch(
-        &repo.path,
-        &worktree_path,
-        &new_base_branch,
-        &old_base_branch,
-        &workspace.branch.clone(),
-    );
-    if let Err(e) = result {
-        use services::services::git::GitServiceError;
-        return match e {
-            GitServiceError::MergeConflicts {
-                message,
-                conflicted_files,
-            } => Ok(ResponseJson(
-                ApiResponse::<(), GitOperationError>::error_with_data(
+    // Uncommitted agent changes are stashed around the rebase. Aborting on conflict happens
+    // inside the closure so the stash is reapplied onto the restored branch.
+    let git = deployment.git();
+    let old_head = git.get_branch_oid(&worktree_path, &workspace.branch)?;
+    let autostashed = git.with_autostash(&worktree_path, true, || {
+        let result = git.rebase_branch(
+            &repo.path,
+            &worktree_path,
+            &new_base_branch,
+            &old_base_branch,
+            &workspace.branch.clone(),
+        );
+        if payload.abort_on_conflict
+            && matches!(result, Err(GitServiceError::MergeConflicts { .. }))
+        {
+            git.abort_rebase(&worktree_path)?;
+        }
+        result
+    })?;
+    let kept_stash = autostashed.kept_stash;
+    let head_sha = match autostashed.result {
+        Ok(head_sha) => head_sha,
+        Err(e) => {
+            return match e {
+                GitServiceError::MergeConflicts {
+                    message,
+                    conflicted_files,
+                } => Ok(ResponseJson(ApiResponse::error_with_data(
                     GitOperationError::MergeConflicts {
                         message,
                         op: ConflictOp::Rebase,
                         conflicted_files,
                         target_branch: new_base_branch.clone(),
+                        kept_stash,
                     },
-                ),
-            )),
-            GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::RebaseInProgress,
-            ))),
-            other => Err(ApiError::GitService(other)),
-        };
-    }
+                ))),
+                GitServiceError::RebaseInProgress => Ok(ResponseJson(
+                    ApiResponse::error_with_data(GitOperationError::RebaseInProgress),
+                )),
+                other => Err(ApiError::GitService(other)),
+            };
+        }
+    };
+
+    let commits_replayed = git
+        .count_replayed_commits(&worktree_path, &old_head, &head_sha, &new_base_branch)
+        .unwrap_or_else(|e| {
+            tracing::warn!(
+                "Failed to count replayed commits for attempt {}: {}",
+                workspace.id,
+                e
+            );
+            0
+        });
 
     deployment
         .track_if_analytics_allowed(
@@ -1183,7 +1234,13 @@ pub async fn rebase_task_attempt(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(
+        RebaseTaskAttemptResponse {
+            head_sha,
+            commits_replayed,
+            kept_stash,
+        },
+    )))
 }
 
 #[axum::debug_handler]
@@ -1594,10 +1651,9 @@ pub async fn search_workspace_files(
     State(deployment): State<DeploymentImpl>,
     Query(search_query): Query<SearchQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, StatusCode> {
-    if search_query.q.trim().is_empty() {
-        return Ok(ResponseJson(ApiResponse::error(
-            "Query parameter 'q' is required and cannot be empty",
-        )));
+    let content_search_enabled = deployment.config().read().await.content_search_enabled;
+    if let Err(message) = search_query.validate(content_search_enabled) {
+        return Ok(ResponseJson(ApiResponse::error(message)));
     }
 
     let repos =
@@ -1773,9 +1829,11 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
-        .route("/pr", post(pr::create_pr))
+        .route("/pr", post(pr::create_pr).patch(pr::update_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route("/pr/checks", get(pr::get_pr_checks))
+        .route("/pr/status", get(pr::get_pr_status))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
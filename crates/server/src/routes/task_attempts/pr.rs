@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use axum::{
     Extension, Json,
@@ -7,7 +7,7 @@ use axum::{
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
-    merge::{Merge, MergeStatus},
+    merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
@@ -21,11 +21,14 @@ use executors::actions::{
 };
 use serde::{Deserialize, Serialize};
 use services::services::{
+    analytics::AnalyticsContext,
     container::ContainerService,
-    git::{GitCliError, GitServiceError},
+    git::{GitCliError, GitService, GitServiceError},
     git_host::{
-        self, CreatePrRequest, GitHostError, GitHostProvider, ProviderKind, UnifiedPrComment,
+        self, CreatePrRequest, GitHostError, GitHostProvider, PrChecks, ProviderKind,
+        UnifiedPrComment,
     },
+    pr_monitor,
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -42,6 +45,12 @@ pub struct CreatePrApiRequest {
     pub repo_id: Uuid,
     #[serde(default)]
     pub auto_generate_description: bool,
+    /// Remote to push the branch to (e.g. a fork) when it differs from the PR target.
+    pub push_remote: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Overwrite a diverged remote branch (e.g. after a rebase) using `--force-with-lease`
+    pub force_with_lease: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -53,9 +62,20 @@ pub enum PrError {
     GitCliNotLoggedIn,
     GitCliNotInstalled,
     TargetBranchNotFound { branch: String },
+    PushRemoteNotFound { remote: String },
+    PushRejectedNonFastForward,
     UnsupportedProvider,
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct CreatePrResponse {
+    pub url: String,
+    /// Commits the target branch has that the workspace branch lacks, when known
+    pub commits_behind: Option<usize>,
+    /// Non-fatal hint for the UI, e.g. suggesting a rebase before merging
+    pub warning: Option<String>,
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
@@ -74,13 +94,83 @@ pub struct PrCommentsResponse {
     pub comments: Vec<UnifiedPrComment>,
 }
 
+/// Failures shared by the routes that act on a workspace's attached PR.
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
-pub enum GetPrCommentsError {
+pub enum PrRouteError {
     NoPrAttached,
     CliNotInstalled { provider: ProviderKind },
     CliNotLoggedIn { provider: ProviderKind },
+    UnsupportedProvider,
+}
+
+impl PrRouteError {
+    /// Map a git host failure to the error the UI can act on, or pass it through as an
+    /// [`ApiError`] when there is nothing the user can fix.
+    fn from_git_host(error: GitHostError, provider: ProviderKind) -> Result<Self, ApiError> {
+        match error {
+            GitHostError::CliNotInstalled { provider } => Ok(Self::CliNotInstalled { provider }),
+            GitHostError::AuthFailed(_) => Ok(Self::CliNotLoggedIn { provider }),
+            GitHostError::UnsupportedProvider => Ok(Self::UnsupportedProvider),
+            e => Err(ApiError::GitHost(e)),
+        }
+    }
+}
+
+/// Everything the PR routes need to talk to the host about a workspace's attached PR.
+struct PrContext {
+    repo: Repo,
+    pr_merge: PrMerge,
+    remote_url: String,
+    git_host: git_host::GitHostService,
+}
+
+/// Load the repo, attached PR and git host for `repo_id` in `workspace`.
+async fn load_pr_context(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<Result<PrContext, PrRouteError>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, repo_id).await?;
+    let pr_merge = match merges.into_iter().next() {
+        Some(Merge::Pr(pr_merge)) => pr_merge,
+        _ => return Ok(Err(PrRouteError::NoPrAttached)),
+    };
+
+    let git = deployment.git();
+    let remote_url = git.get_remote_url(
+        &repo.path,
+        &git.resolve_remote_name_for_branch(&repo.path, &workspace_repo.target_branch)?,
+    )?;
+
+    let git_host = match git_host::GitHostService::from_url(&remote_url) {
+        Ok(host) => host,
+        Err(GitHostError::CliNotInstalled { provider }) => {
+            return Ok(Err(PrRouteError::CliNotInstalled { provider }));
+        }
+        Err(GitHostError::UnsupportedProvider) => {
+            return Ok(Err(PrRouteError::UnsupportedProvider));
+        }
+        Err(e) => return Err(ApiError::GitHost(e)),
+    };
+
+    Ok(Ok(PrContext {
+        repo,
+        pr_merge,
+        remote_url,
+        git_host,
+    }))
 }
 
 #[derive(Debug, Deserialize, TS)]
@@ -88,6 +178,37 @@ pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct GetPrChecksQuery {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct GetPrStatusQuery {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct PrStatusResponse {
+    pub status: MergeStatus,
+    pub merge_commit_sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS)]
+pub struct UpdatePrApiRequest {
+    pub repo_id: Uuid,
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+/// Placeholders substituted into the PR description prompt:
+/// - `{pr_number}`: number of the PR that was just opened
+/// - `{pr_url}`: URL of that PR
+/// - `{branch}`: the workspace branch the PR was opened from
+/// - `{task_title}`: title of the task the workspace belongs to
+pub const PR_DESCRIPTION_PROMPT_PLACEHOLDERS: &[&str] =
+    &["pr_number", "pr_url", "branch", "task_title"];
+
 pub const DEFAULT_PR_DESCRIPTION_PROMPT: &str = r#"Update the PR that was just created with a better title and description.
 The PR number is #{pr_number} and the URL is {pr_url}.
 
@@ -101,12 +222,54 @@ Analyze the changes in this branch and write:
 
 Use the appropriate CLI tool to update the PR (gh pr edit for GitHub, az repos pr update for Azure DevOps)."#;
 
+/// Return `{token}`s in a prompt template that aren't supported placeholders.
+/// Braces around anything other than a bare identifier (e.g. JSON) are ignored.
+pub fn unknown_pr_prompt_placeholders(template: &str) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let token = &rest[..end];
+        if !token.is_empty()
+            && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !PR_DESCRIPTION_PROMPT_PLACEHOLDERS.contains(&token)
+            && !unknown.iter().any(|t| t == token)
+        {
+            unknown.push(token.to_string());
+        }
+    }
+    unknown
+}
+
+/// Log a warning for each unsupported placeholder in a custom PR description prompt.
+pub fn warn_unknown_pr_prompt_placeholders(template: &str) {
+    for token in unknown_pr_prompt_placeholders(template) {
+        tracing::warn!(
+            "PR description prompt contains unknown placeholder {{{}}}; supported placeholders: {}",
+            token,
+            PR_DESCRIPTION_PROMPT_PLACEHOLDERS
+                .iter()
+                .map(|p| format!("{{{p}}}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
 async fn trigger_pr_description_follow_up(
     deployment: &DeploymentImpl,
     workspace: &Workspace,
     pr_number: i64,
     pr_url: &str,
 ) -> Result<(), ApiError> {
+    let task_title = Task::find_by_id(&deployment.db().pool, workspace.task_id)
+        .await?
+        .map(|task| task.title)
+        .unwrap_or_default();
+
     // Get the custom prompt from config, or use default
     let config = deployment.config().read().await;
     let prompt_template = config
@@ -117,7 +280,9 @@ async fn trigger_pr_description_follow_up(
     // Replace placeholders in prompt
     let prompt = prompt_template
         .replace("{pr_number}", &pr_number.to_string())
-        .replace("{pr_url}", pr_url);
+        .replace("{pr_url}", pr_url)
+        .replace("{branch}", &workspace.branch)
+        .replace("{task_title}", &task_title);
 
     drop(config); // Release the lock before async operations
 
@@ -192,11 +357,34 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
+/// Base branch to use when neither the request nor the workspace names one: the
+/// default branch of `remote`, qualified as a remote-tracking branch when one exists
+/// locally so the target remote is picked up below. `None` if the remote's default
+/// can't be found.
+fn default_target_branch(git: &GitService, repo_path: &Path, remote: &str) -> Option<String> {
+    let branch = match git.get_remote_default_branch(repo_path, remote) {
+        Ok(branch) => branch,
+        Err(e) => {
+            tracing::debug!("Could not detect default branch of remote '{remote}': {e}");
+            return None;
+        }
+    };
+    let tracking = format!("{remote}/{branch}");
+    if git
+        .get_remote_name_from_branch_name(repo_path, &tracking)
+        .is_ok()
+    {
+        Some(tracking)
+    } else {
+        Some(branch)
+    }
+}
+
 pub async fn create_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreatePrApiRequest>,
-) -> Result<ResponseJson<ApiResponse<String, PrError>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<CreatePrResponse, PrError>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -209,11 +397,6 @@ pub async fn create_pr(
         .ok_or(RepoError::NotFound)?;
 
     let repo_path = repo.path.clone();
-    let target_branch = if let Some(branch) = request.target_branch {
-        branch
-    } else {
-        workspace_repo.target_branch.clone()
-    };
 
     let container_ref = deployment
         .container()
@@ -223,7 +406,31 @@ pub async fn create_pr(
     let worktree_path = workspace_path.join(&repo.name);
 
     let git = deployment.git();
-    let push_remote = git.resolve_remote_name_for_branch(&repo_path, &workspace.branch)?;
+    let push_remote = match request.push_remote.as_deref() {
+        Some(remote) => {
+            if !git.remote_exists(&repo_path, remote)? {
+                return Ok(ResponseJson(ApiResponse::error_with_data(
+                    PrError::PushRemoteNotFound {
+                        remote: remote.to_string(),
+                    },
+                )));
+            }
+            remote.to_string()
+        }
+        None => git.resolve_remote_name_for_branch(&repo_path, &workspace.branch)?,
+    };
+
+    let target_branch = match request.target_branch {
+        Some(branch) => branch,
+        None if !workspace_repo.target_branch.is_empty() => workspace_repo.target_branch.clone(),
+        None => {
+            // The push remote may be a fork, so read the default branch from the
+            // remote the workspace branch would push to without an override.
+            let upstream_remote =
+                git.resolve_remote_name_for_branch(&repo_path, &workspace.branch)?;
+            default_target_branch(git, &repo_path, &upstream_remote).unwrap_or_default()
+        }
+    };
 
     // Try to get the remote from the branch name (works for remote-tracking branches like "upstream/main").
     // Fall back to push_remote if the branch doesn't exist locally or isn't a remote-tracking branch.
@@ -263,7 +470,40 @@ pub async fn create_pr(
         Ok(true) => {}
     }
 
-    if let Err(e) = git.push_to_remote(&worktree_path, &workspace.branch, false) {
+    // Not fatal: the PR can still be opened, but the UI should suggest a rebase.
+    let commits_behind = git
+        .find_branch_type(&repo_path, &target_branch)
+        .and_then(|branch_type| {
+            super::target_branch_status(
+                git,
+                &repo_path,
+                &workspace.branch,
+                &target_branch,
+                branch_type,
+            )
+        })
+        .map(|(_, behind)| behind)
+        .inspect_err(|e| {
+            tracing::warn!(
+                "Failed to compute ahead/behind for attempt {}: {}",
+                workspace.id,
+                e
+            )
+        })
+        .ok();
+
+    let force_with_lease = request.force_with_lease.unwrap_or(false);
+    let push_result = match (request.push_remote.is_some(), force_with_lease) {
+        (true, true) => {
+            git.push_to_named_remote_with_lease(&worktree_path, &push_remote, &workspace.branch)
+        }
+        (true, false) => {
+            git.push_to_named_remote(&worktree_path, &push_remote, &workspace.branch, false)
+        }
+        (false, true) => git.push_to_remote_with_lease(&worktree_path, &workspace.branch),
+        (false, false) => git.push_to_remote(&worktree_path, &workspace.branch, false),
+    };
+    if let Err(e) = push_result {
         tracing::error!("Failed to push branch to remote: {}", e);
         match e {
             GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
@@ -276,6 +516,11 @@ pub async fn create_pr(
                     PrError::GitCliNotInstalled,
                 )));
             }
+            GitServiceError::GitCLI(GitCliError::PushRejected(_)) if !force_with_lease => {
+                return Ok(ResponseJson(ApiResponse::error_with_data(
+                    PrError::PushRejectedNonFastForward,
+                )));
+            }
             _ => return Err(ApiError::GitService(e)),
         }
     }
@@ -297,6 +542,17 @@ pub async fn create_pr(
 
     let provider = git_host.provider_kind();
 
+    let labels = if !request.labels.is_empty() && !git_host.capabilities().supports_labels {
+        tracing::warn!(
+            "{} does not support PR labels, ignoring {:?}",
+            provider,
+            request.labels
+        );
+        Vec::new()
+    } else {
+        request.labels.clone()
+    };
+
     // Create the PR
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
@@ -305,6 +561,7 @@ pub async fn create_pr(
         base_branch: base_branch.clone(),
         draft: request.draft,
         head_repo_url: Some(push_remote_url),
+        labels,
     };
 
     match git_host
@@ -358,7 +615,17 @@ pub async fn create_pr(
                 );
             }
 
-            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
+            let warning = commits_behind.filter(|behind| *behind > 0).map(|behind| {
+                format!(
+                    "Branch is {behind} commit(s) behind {target_branch}; consider rebasing before merging."
+                )
+            });
+
+            Ok(ResponseJson(ApiResponse::success(CreatePrResponse {
+                url: pr_info.url,
+                commits_behind,
+                warning,
+            })))
         }
         Err(e) => {
             tracing::error!(
@@ -382,6 +649,18 @@ pub async fn create_pr(
     }
 }
 
+/// Pick the PR to attach from all PRs for a branch: open first, then merged, then
+/// closed. The host's order is kept within each status.
+fn select_pr_to_attach(mut prs: Vec<PullRequestInfo>) -> Option<PullRequestInfo> {
+    prs.sort_by_key(|pr| match pr.status {
+        MergeStatus::Open => 0,
+        MergeStatus::Merged => 1,
+        MergeStatus::Closed => 2,
+        MergeStatus::Unknown => 3,
+    });
+    prs.into_iter().next()
+}
+
 pub async fn attach_existing_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -456,8 +735,8 @@ pub async fn attach_existing_pr(
         Err(e) => return Err(ApiError::GitHost(e)),
     };
 
-    // Take the first PR (prefer open, but also accept merged/closed)
-    if let Some(pr_info) = prs.into_iter().next() {
+    // Prefer an open PR, but also accept merged/closed
+    if let Some(pr_info) = select_pr_to_attach(prs) {
         // Save PR info to database
         let merge = Merge::create_pr(
             pool,
@@ -508,75 +787,197 @@ pub async fn get_pr_comments(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<GetPrCommentsQuery>,
-) -> Result<ResponseJson<ApiResponse<PrCommentsResponse, GetPrCommentsError>>, ApiError> {
-    let pool = &deployment.db().pool;
-
-    // Look up the specific repo using the multi-repo pattern
-    let workspace_repo =
-        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id)
-            .await?
-            .ok_or(RepoError::NotFound)?;
+) -> Result<ResponseJson<ApiResponse<PrCommentsResponse, PrRouteError>>, ApiError> {
+    let ctx = match load_pr_context(&deployment, &workspace, query.repo_id).await? {
+        Ok(ctx) => ctx,
+        Err(e) => return Ok(ResponseJson(ApiResponse::error_with_data(e))),
+    };
+    let pr_number = ctx.pr_merge.pr_info.number;
 
-    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
-        .await?
-        .ok_or(RepoError::NotFound)?;
+    match ctx
+        .git_host
+        .get_pr_comments(&ctx.repo.path, &ctx.remote_url, pr_number)
+        .await
+    {
+        Ok(comments) => Ok(ResponseJson(ApiResponse::success(PrCommentsResponse {
+            comments,
+        }))),
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch PR comments for attempt {}, PR #{}: {}",
+                workspace.id,
+                pr_number,
+                e
+            );
+            let error = PrRouteError::from_git_host(e, ctx.git_host.provider_kind())?;
+            Ok(ResponseJson(ApiResponse::error_with_data(error)))
+        }
+    }
+}
 
-    // Find the merge/PR for this specific repo
-    let merges = Merge::find_by_workspace_and_repo_id(pool, workspace.id, query.repo_id).await?;
+pub async fn get_pr_checks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<GetPrChecksQuery>,
+) -> Result<ResponseJson<ApiResponse<PrChecks, PrRouteError>>, ApiError> {
+    let ctx = match load_pr_context(&deployment, &workspace, query.repo_id).await? {
+        Ok(ctx) => ctx,
+        Err(e) => return Ok(ResponseJson(ApiResponse::error_with_data(e))),
+    };
+    let pr_number = ctx.pr_merge.pr_info.number;
 
-    // Ensure there's an attached PR for this repo
-    let pr_info = match merges.into_iter().next() {
-        Some(Merge::Pr(pr_merge)) => pr_merge.pr_info,
-        _ => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                GetPrCommentsError::NoPrAttached,
-            )));
+    match ctx
+        .git_host
+        .get_pr_checks(&ctx.repo.path, &ctx.remote_url, pr_number)
+        .await
+    {
+        Ok(checks) => Ok(ResponseJson(ApiResponse::success(checks))),
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch PR checks for attempt {}, PR #{}: {}",
+                workspace.id,
+                pr_number,
+                e
+            );
+            let error = PrRouteError::from_git_host(e, ctx.git_host.provider_kind())?;
+            Ok(ResponseJson(ApiResponse::error_with_data(error)))
         }
-    };
+    }
+}
 
-    let git = deployment.git();
-    let remote_url = git.get_remote_url(
-        &repo.path,
-        &git.resolve_remote_name_for_branch(&repo.path, &workspace_repo.target_branch)?,
-    )?;
+pub async fn get_pr_status(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<GetPrStatusQuery>,
+) -> Result<ResponseJson<ApiResponse<PrStatusResponse, PrRouteError>>, ApiError> {
+    let ctx = match load_pr_context(&deployment, &workspace, query.repo_id).await? {
+        Ok(ctx) => ctx,
+        Err(e) => return Ok(ResponseJson(ApiResponse::error_with_data(e))),
+    };
+    let pr_merge = ctx.pr_merge;
 
-    let git_host = match git_host::GitHostService::from_url(&remote_url) {
-        Ok(host) => host,
-        Err(GitHostError::CliNotInstalled { provider }) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                GetPrCommentsError::CliNotInstalled { provider },
-            )));
+    let remote_info = match ctx.git_host.get_pr_status(&pr_merge.pr_info.url).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::error!(
+                "Failed to fetch PR status for attempt {}, PR #{}: {}",
+                workspace.id,
+                pr_merge.pr_info.number,
+                e
+            );
+            let error = PrRouteError::from_git_host(e, ctx.git_host.provider_kind())?;
+            return Ok(ResponseJson(ApiResponse::error_with_data(error)));
         }
-        Err(e) => return Err(ApiError::GitHost(e)),
     };
 
-    let provider = git_host.provider_kind();
+    // Lazily sync the stored row so list views reflect the remote state without
+    // waiting for the next PR monitor sweep, applying the same merge side effects.
+    if remote_info.status != pr_merge.pr_info.status
+        || remote_info.merge_commit_sha != pr_merge.pr_info.merge_commit_sha
+    {
+        let analytics = deployment
+            .analytics()
+            .as_ref()
+            .map(|analytics_service| AnalyticsContext {
+                user_id: deployment.user_id().to_string(),
+                analytics_service: analytics_service.clone(),
+            });
+        pr_monitor::record_pr_status(
+            &deployment.db().pool,
+            analytics.as_ref(),
+            &pr_merge,
+            remote_info.status.clone(),
+            remote_info.merge_commit_sha.clone(),
+        )
+        .await?;
+    }
 
-    match git_host
-        .get_pr_comments(&repo.path, &remote_url, pr_info.number)
+    Ok(ResponseJson(ApiResponse::success(PrStatusResponse {
+        status: remote_info.status,
+        merge_commit_sha: remote_info.merge_commit_sha,
+    })))
+}
+
+pub async fn update_pr(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<UpdatePrApiRequest>,
+) -> Result<ResponseJson<ApiResponse<(), PrRouteError>>, ApiError> {
+    if request.title.is_none() && request.body.is_none() {
+        return Err(ApiError::BadRequest(
+            "Provide a title and/or body to update".to_string(),
+        ));
+    }
+    if request
+        .title
+        .as_deref()
+        .is_some_and(|title| title.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest("PR title cannot be empty".to_string()));
+    }
+
+    let ctx = match load_pr_context(&deployment, &workspace, request.repo_id).await? {
+        Ok(ctx) => ctx,
+        Err(e) => return Ok(ResponseJson(ApiResponse::error_with_data(e))),
+    };
+    let pr_number = ctx.pr_merge.pr_info.number;
+
+    match ctx
+        .git_host
+        .update_pr(
+            &ctx.repo.path,
+            &ctx.remote_url,
+            pr_number,
+            request.title.as_deref(),
+            request.body.as_deref(),
+        )
         .await
     {
-        Ok(comments) => Ok(ResponseJson(ApiResponse::success(PrCommentsResponse {
-            comments,
-        }))),
+        Ok(()) => Ok(ResponseJson(ApiResponse::success(()))),
         Err(e) => {
             tracing::error!(
-                "Failed to fetch PR comments for attempt {}, PR #{}: {}",
+                "Failed to update PR for attempt {}, PR #{}: {}",
                 workspace.id,
-                pr_info.number,
+                pr_number,
                 e
             );
-            match &e {
-                GitHostError::CliNotInstalled { provider } => Ok(ResponseJson(
-                    ApiResponse::error_with_data(GetPrCommentsError::CliNotInstalled {
-                        provider: *provider,
-                    }),
-                )),
-                GitHostError::AuthFailed(_) => Ok(ResponseJson(ApiResponse::error_with_data(
-                    GetPrCommentsError::CliNotLoggedIn { provider },
-                ))),
-                _ => Err(ApiError::GitHost(e)),
-            }
+            let error = PrRouteError::from_git_host(e, ctx.git_host.provider_kind())?;
+            Ok(ResponseJson(ApiResponse::error_with_data(error)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: i64, status: MergeStatus) -> PullRequestInfo {
+        PullRequestInfo {
+            number,
+            url: format!("https://github.com/owner/repo/pull/{number}"),
+            status,
+            merged_at: None,
+            merge_commit_sha: None,
         }
     }
+
+    #[test]
+    fn attach_prefers_open_pr_over_earlier_closed_one() {
+        let prs = vec![
+            pr(1, MergeStatus::Closed),
+            pr(2, MergeStatus::Merged),
+            pr(3, MergeStatus::Open),
+        ];
+
+        let selected = select_pr_to_attach(prs).unwrap();
+        assert_eq!(selected.number, 3);
+    }
+
+    #[test]
+    fn attach_prefers_merged_over_closed() {
+        let prs = vec![pr(1, MergeStatus::Closed), pr(2, MergeStatus::Merged)];
+
+        assert_eq!(select_pr_to_attach(prs).unwrap().number, 2);
+        assert!(select_pr_to_attach(Vec::new()).is_none());
+    }
 }
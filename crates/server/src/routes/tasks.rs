@@ -16,6 +16,7 @@ use db::models::{
     image::TaskImage,
     repo::{Repo, RepoError},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task_template::TaskTemplate,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -24,7 +25,7 @@ use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{container::ContainerService, workspace_manager::WorkspaceManager};
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -104,11 +105,29 @@ pub async fn get_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+/// Merges the content of the requested task template into the task description.
+async fn apply_task_template(pool: &SqlitePool, task: &mut CreateTask) -> Result<(), ApiError> {
+    let Some(template_id) = task.template_id else {
+        return Ok(());
+    };
+    let template = TaskTemplate::find_by_id(pool, template_id)
+        .await?
+        .filter(|template| template.is_available_for(task.project_id))
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Task template {template_id} is not available for this project"
+            ))
+        })?;
+    task.description = template.apply(task.description.as_deref());
+    Ok(())
+}
+
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateTask>,
+    Json(mut payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     let id = Uuid::new_v4();
+    apply_task_template(&deployment.db().pool, &mut payload).await?;
 
     tracing::debug!(
         "Creating task '{}' in project {}",
@@ -146,7 +165,7 @@ pub struct CreateAndStartTaskRequest {
 
 pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateAndStartTaskRequest>,
+    Json(mut payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
@@ -156,6 +175,8 @@ pub async fn create_task_and_start(
 
     let pool = &deployment.db().pool;
 
+    apply_task_template(pool, &mut payload.task).await?;
+
     let task_id = Uuid::new_v4();
     let task = Task::create(pool, &payload.task, task_id).await?;
 
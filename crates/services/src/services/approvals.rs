@@ -40,6 +40,9 @@ struct PendingApproval {
 
 type ApprovalWaiter = Shared<BoxFuture<'static, ApprovalStatus>>;
 
+/// Runs once if an approval is still pending halfway to its timeout.
+pub type ApprovalReminder = BoxFuture<'static, ()>;
+
 #[derive(Debug)]
 pub struct ToolContext {
     pub tool_name: String,
@@ -81,6 +84,7 @@ impl Approvals {
     pub async fn create_with_waiter(
         &self,
         request: ApprovalRequest,
+        reminder: Option<ApprovalReminder>,
     ) -> Result<(ApprovalRequest, ApprovalWaiter), ApprovalError> {
         let (tx, rx) = oneshot::channel();
         let waiter: ApprovalWaiter = rx
@@ -133,7 +137,7 @@ impl Approvals {
             );
         }
 
-        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone());
+        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone(), reminder);
         Ok((request, waiter))
     }
 
@@ -173,7 +177,10 @@ impl Approvals {
             // If approved or denied, and task is still InReview, move back to InProgress
             if matches!(
                 req.status,
-                ApprovalStatus::Approved | ApprovalStatus::Denied { .. }
+                ApprovalStatus::Approved
+                    | ApprovalStatus::ApprovedForSession
+                    | ApprovalStatus::ApprovedWithEdits { .. }
+                    | ApprovalStatus::Denied { .. }
             ) && let Ok(ctx) =
                 ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await
                 && ctx.task.status == TaskStatus::InReview
@@ -193,12 +200,13 @@ impl Approvals {
         }
     }
 
-    #[tracing::instrument(skip(self, id, timeout_at, waiter))]
+    #[tracing::instrument(skip(self, id, timeout_at, waiter, reminder))]
     fn spawn_timeout_watcher(
         &self,
         id: String,
         timeout_at: chrono::DateTime<chrono::Utc>,
         waiter: ApprovalWaiter,
+        reminder: Option<ApprovalReminder>,
     ) {
         let pending = self.pending.clone();
         let completed = self.completed.clone();
@@ -208,15 +216,9 @@ impl Approvals {
         let to_wait = (timeout_at - now)
             .to_std()
             .unwrap_or_else(|_| StdDuration::from_secs(0));
-        let deadline = tokio::time::Instant::now() + to_wait;
 
         tokio::spawn(async move {
-            let status = tokio::select! {
-                biased;
-
-                resolved = waiter.clone() => resolved,
-                _ = tokio::time::sleep_until(deadline) => ApprovalStatus::TimedOut,
-            };
+            let status = wait_for_decision(waiter, to_wait, reminder).await;
 
             let is_timeout = matches!(&status, ApprovalStatus::TimedOut);
             completed.insert(id.clone(), status.clone());
@@ -282,6 +284,36 @@ impl Approvals {
     }
 }
 
+/// Wait for an approval decision, resolving to [`ApprovalStatus::TimedOut`] once `timeout`
+/// elapses. If the approval is still pending halfway through, `reminder` runs first.
+async fn wait_for_decision<W>(
+    waiter: W,
+    timeout: StdDuration,
+    reminder: Option<ApprovalReminder>,
+) -> ApprovalStatus
+where
+    W: Future<Output = ApprovalStatus>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    tokio::pin!(waiter);
+
+    if let Some(reminder) = reminder {
+        tokio::select! {
+            biased;
+
+            status = &mut waiter => return status,
+            _ = tokio::time::sleep(timeout / 2) => reminder.await,
+        }
+    }
+
+    tokio::select! {
+        biased;
+
+        status = &mut waiter => status,
+        _ = tokio::time::sleep_until(deadline) => ApprovalStatus::TimedOut,
+    }
+}
+
 pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_id: Uuid) {
     if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
         && ctx.task.status == TaskStatus::InProgress
@@ -334,7 +366,10 @@ fn find_matching_tool_use(
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
 
     use executors::logs::{ActionType, NormalizedEntry, NormalizedEntryType, ToolStatus};
     use utils::msg_store::MsgStore;
@@ -422,4 +457,77 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+
+    fn counting_reminder(count: &Arc<AtomicUsize>) -> Option<ApprovalReminder> {
+        let count = count.clone();
+        Some(
+            async move {
+                count.fetch_add(1, Ordering::SeqCst);
+            }
+            .boxed(),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pending_approval_sends_reminder_then_times_out() {
+        let reminders = Arc::new(AtomicUsize::new(0));
+        let started = tokio::time::Instant::now();
+
+        let status = wait_for_decision(
+            futures::future::pending(),
+            StdDuration::from_secs(10),
+            counting_reminder(&reminders),
+        )
+        .await;
+
+        assert!(matches!(status, ApprovalStatus::TimedOut));
+        assert_eq!(reminders.load(Ordering::SeqCst), 1);
+        assert_eq!(started.elapsed(), StdDuration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn early_decision_skips_reminder() {
+        let reminders = Arc::new(AtomicUsize::new(0));
+        let waiter = async {
+            tokio::time::sleep(StdDuration::from_secs(2)).await;
+            ApprovalStatus::Approved
+        };
+
+        let status = wait_for_decision(
+            waiter,
+            StdDuration::from_secs(10),
+            counting_reminder(&reminders),
+        )
+        .await;
+
+        assert!(matches!(status, ApprovalStatus::Approved));
+        assert_eq!(reminders.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn decision_after_reminder_is_returned() {
+        let reminders = Arc::new(AtomicUsize::new(0));
+        let waiter = async {
+            tokio::time::sleep(StdDuration::from_secs(7)).await;
+            ApprovalStatus::Denied { reason: None }
+        };
+
+        let status = wait_for_decision(
+            waiter,
+            StdDuration::from_secs(10),
+            counting_reminder(&reminders),
+        )
+        .await;
+
+        assert!(matches!(status, ApprovalStatus::Denied { .. }));
+        assert_eq!(reminders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn no_reminder_still_times_out() {
+        let status =
+            wait_for_decision(futures::future::pending(), StdDuration::from_secs(10), None).await;
+
+        assert!(matches!(status, ApprovalStatus::TimedOut));
+    }
 }
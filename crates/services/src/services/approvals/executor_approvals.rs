@@ -3,16 +3,19 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use db::{self, DBService, models::execution_process::ExecutionProcess};
 use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
+use futures::FutureExt;
 use serde_json::Value;
+use tokio::sync::RwLock;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::{approvals::Approvals, notification::NotificationService};
+use crate::services::{approvals::Approvals, config::Config, notification::NotificationService};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
     notification_service: NotificationService,
+    config: Arc<RwLock<Config>>,
     execution_process_id: Uuid,
 }
 
@@ -21,12 +24,14 @@ impl ExecutorApprovalBridge {
         approvals: Approvals,
         db: DBService,
         notification_service: NotificationService,
+        config: Arc<RwLock<Config>>,
         execution_process_id: Uuid,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
+            config,
             execution_process_id,
         })
     }
@@ -42,6 +47,7 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
+        let timeout_secs = self.config.read().await.approval_timeout_secs;
         let request = ApprovalRequest::from_create(
             CreateApprovalRequest {
                 tool_name: tool_name.to_string(),
@@ -49,19 +55,27 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
                 tool_call_id: tool_call_id.to_string(),
             },
             self.execution_process_id,
+            i64::from(timeout_secs),
         );
 
-        let (_, waiter) = self
-            .approvals
-            .create_with_waiter(request)
-            .await
-            .map_err(ExecutorApprovalError::request_failed)?;
-
         let task_name = ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
             .await
             .map(|ctx| ctx.task.title)
             .unwrap_or_else(|_| "Unknown task".to_string());
 
+        let reminder = {
+            let notification_service = self.notification_service.clone();
+            let title = format!("Approval Still Needed: {}", task_name);
+            let message = format!("Tool '{}' is still waiting for approval", tool_name);
+            async move { notification_service.notify(&title, &message).await }.boxed()
+        };
+
+        let (_, waiter) = self
+            .approvals
+            .create_with_waiter(request, Some(reminder))
+            .await
+            .map_err(ExecutorApprovalError::request_failed)?;
+
         self.notification_service
             .notify(
                 &format!("Approval Needed: {}", task_name),
@@ -69,7 +83,7 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             )
             .await;
 
-        let status = waiter.clone().await;
+        let status = waiter.await;
 
         if matches!(status, ApprovalStatus::Pending) {
             return Err(ExecutorApprovalError::request_failed(
@@ -2,6 +2,7 @@ use anyhow::Error;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utils::approvals::APPROVAL_TIMEOUT_SECONDS;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
     ThemeMode, UiLanguage,
@@ -17,6 +18,22 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+fn default_cleanup_orphans_on_startup() -> bool {
+    true
+}
+
+fn default_approval_timeout_secs() -> u32 {
+    APPROVAL_TIMEOUT_SECONDS as u32
+}
+
+fn default_stop_grace_period_secs() -> u32 {
+    5
+}
+
+fn default_pr_poll_interval_secs() -> u32 {
+    60
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
 pub enum SendMessageShortcut {
     #[default]
@@ -56,6 +73,22 @@ pub struct Config {
     pub commit_reminder: bool,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    #[serde(default = "default_cleanup_orphans_on_startup")]
+    pub cleanup_orphans_on_startup: bool,
+    /// How long a tool approval may stay pending before it times out. A reminder
+    /// notification is sent halfway through.
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u32,
+    /// How long a stopped execution gets to exit after being interrupted before its
+    /// process group is killed.
+    #[serde(default = "default_stop_grace_period_secs")]
+    pub stop_grace_period_secs: u32,
+    /// How often open PRs are checked for merges or closure.
+    #[serde(default = "default_pr_poll_interval_secs")]
+    pub pr_poll_interval_secs: u32,
+    /// Allow file searches that match on file contents (`mode=content`).
+    #[serde(default)]
+    pub content_search_enabled: bool,
 }
 
 impl Config {
@@ -85,6 +118,11 @@ impl Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            cleanup_orphans_on_startup: true,
+            approval_timeout_secs: default_approval_timeout_secs(),
+            stop_grace_period_secs: default_stop_grace_period_secs(),
+            pr_poll_interval_secs: default_pr_poll_interval_secs(),
+            content_search_enabled: false,
         }
     }
 
@@ -139,6 +177,11 @@ impl Default for Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            cleanup_orphans_on_startup: true,
+            approval_timeout_secs: default_approval_timeout_secs(),
+            stop_grace_period_secs: default_stop_grace_period_secs(),
+            pr_poll_interval_secs: default_pr_poll_interval_secs(),
+            content_search_enabled: false,
         }
     }
 }
@@ -54,7 +54,9 @@ use uuid::Uuid;
 use crate::services::{
     git::{GitService, GitServiceError},
     notification::NotificationService,
-    workspace_manager::WorkspaceError as WorkspaceManagerError,
+    workspace_manager::{
+        OrphanCleanupReport, WorkspaceError as WorkspaceManagerError, WorkspaceManager,
+    },
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
@@ -250,6 +252,12 @@ pub trait ContainerService {
         self.notification_service().notify(&title, &message).await;
     }
 
+    /// Remove workspace directories and stale container refs no longer backed by a workspace.
+    /// With `dry_run` set, only reports what would be removed.
+    async fn cleanup_orphans(&self, dry_run: bool) -> Result<OrphanCleanupReport, ContainerError> {
+        Ok(WorkspaceManager::cleanup_orphan_workspaces(&self.db().pool, dry_run).await?)
+    }
+
     /// Cleanup executions marked as running in the db, call at startup
     async fn cleanup_orphan_executions(&self) -> Result<(), ContainerError> {
         let running_processes = ExecutionProcess::find_running(&self.db().pool).await?;
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use chrono::{DateTime, Utc};
@@ -21,6 +22,11 @@ pub struct FileStat {
     pub commit_count: u32,
     /// Timestamp of the most recent change
     pub last_time: DateTime<Utc>,
+    /// Timestamps of every change within the scanned history, newest first
+    pub commit_times: Vec<DateTime<Utc>>,
+    /// Recency-decayed activity normalised to `0.0..=1.0`, where 1.0 is the
+    /// most recently active file in the repository
+    pub recency: f64,
 }
 
 /// File statistics for a repository
@@ -30,6 +36,7 @@ pub type FileStats = HashMap<String, FileStat>;
 #[derive(Clone)]
 struct RepoHistoryCache {
     head_sha: String,
+    config: FileRankerConfig,
     stats: Arc<FileStats>,
 }
 
@@ -38,16 +45,41 @@ static FILE_STATS_CACHE: Lazy<DashMap<PathBuf, RepoHistoryCache>> = Lazy::new(Da
 
 /// Configuration constants for ranking algorithm
 const DEFAULT_COMMIT_LIMIT: usize = 100;
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+const DEFAULT_HALF_LIFE: Duration = Duration::from_secs(7 * SECS_PER_DAY);
 const BASE_MATCH_SCORE_FILENAME: i64 = 100;
 const BASE_MATCH_SCORE_DIRNAME: i64 = 10;
 const BASE_MATCH_SCORE_FULLPATH: i64 = 1;
 const RECENCY_WEIGHT: i64 = 2;
 const FREQUENCY_WEIGHT: i64 = 1;
+/// Multipliers applied to the git-history bonus by age of a file's last commit
+const RECENCY_BOOST_LAST_DAY: u32 = 3;
+const RECENCY_BOOST_LAST_WEEK: u32 = 2;
+const RECENCY_BOOST_NONE: u32 = 1;
+
+/// Tunables for how much git history is scanned and how quickly it fades
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FileRankerConfig {
+    /// Number of commits walked back from HEAD
+    pub history_depth: usize,
+    /// Age at which a commit counts half as much as one made just now
+    pub half_life: Duration,
+}
+
+impl Default for FileRankerConfig {
+    fn default() -> Self {
+        Self {
+            history_depth: DEFAULT_COMMIT_LIMIT,
+            half_life: DEFAULT_HALF_LIFE,
+        }
+    }
+}
 
 /// Service for ranking files based on git history
 #[derive(Clone)]
 pub struct FileRanker {
     git_service: GitService,
+    config: FileRankerConfig,
 }
 
 impl Default for FileRanker {
@@ -58,11 +90,29 @@ impl Default for FileRanker {
 
 impl FileRanker {
     pub fn new() -> Self {
+        Self::with_config(FileRankerConfig::default())
+    }
+
+    pub fn with_config(config: FileRankerConfig) -> Self {
         Self {
             git_service: GitService::new(),
+            config,
         }
     }
 
+    /// Ranker whose recency bonus halves for every `days` since a file was changed
+    pub fn with_recency_halflife(days: u64) -> Self {
+        Self::with_config(FileRankerConfig {
+            half_life: Duration::from_secs(days * SECS_PER_DAY),
+            ..Default::default()
+        })
+    }
+
+    /// Drop cached statistics for a repository, e.g. when its HEAD moves
+    pub fn invalidate(&self, repo_path: &Path) {
+        FILE_STATS_CACHE.remove(repo_path);
+    }
+
     /// Get file statistics for a repository, using cache when possible
     pub async fn get_stats(&self, repo_path: &Path) -> Result<Arc<FileStats>, GitServiceError> {
         let repo_path = repo_path.to_path_buf();
@@ -70,7 +120,8 @@ impl FileRanker {
         // Check if we have a valid cache entry
         if let Some(cache_entry) = FILE_STATS_CACHE.get(&repo_path) {
             // Verify cache is still valid by checking HEAD
-            if let Ok(head_info) = self.git_service.get_head_info(&repo_path)
+            if cache_entry.config == self.config
+                && let Ok(head_info) = self.git_service.get_head_info(&repo_path)
                 && head_info.oid == cache_entry.head_sha
             {
                 return Ok(Arc::clone(&cache_entry.stats));
@@ -96,35 +147,45 @@ impl FileRanker {
         let base_score = match result.match_type {
             SearchMatchType::FileName => BASE_MATCH_SCORE_FILENAME,
             SearchMatchType::DirectoryName => BASE_MATCH_SCORE_DIRNAME,
-            SearchMatchType::FullPath => BASE_MATCH_SCORE_FULLPATH,
+            SearchMatchType::FullPath | SearchMatchType::Content => BASE_MATCH_SCORE_FULLPATH,
         };
 
         if let Some(stat) = stats.get(&result.path) {
-            let recency_bonus = (100 - stat.last_index.min(99) as i64) * RECENCY_WEIGHT;
+            let recency_bonus = (stat.recency * 100.0).round() as i64 * RECENCY_WEIGHT;
             let frequency_bonus = stat.commit_count as i64 * FREQUENCY_WEIGHT;
+            let boost = self.calculate_recency_boost(&result.path, stats) as i64;
 
             // Multiply base score to maintain hierarchy, add git-based bonuses
-            base_score * 1000 + recency_bonus * 10 + frequency_bonus
+            base_score * 1000 + (recency_bonus * 10 + frequency_bonus) * boost
         } else {
             // Files not in git history get base score only
             base_score * 1000
         }
     }
 
+    /// Multiplier for a file's git-history bonus: 3x if it was last committed within a
+    /// day, 2x within a week, otherwise (or if it has no history) 1x
+    pub fn calculate_recency_boost(&self, path: &str, stats: &FileStats) -> u32 {
+        stats.get(path).map_or(RECENCY_BOOST_NONE, |stat| {
+            recency_boost(stat.last_time, Utc::now())
+        })
+    }
+
     /// Compute file statistics from git history
     async fn compute_stats(&self, repo_path: &Path) -> Result<Arc<FileStats>, GitServiceError> {
         let repo_path = repo_path.to_path_buf();
         let repo_path_for_error = repo_path.clone();
         let git_service = self.git_service.clone();
+        let history_depth = self.config.history_depth;
 
         // Run git analysis in blocking task to avoid blocking async runtime
         let stats = task::spawn_blocking(move || {
-            git_service.collect_recent_file_stats(&repo_path, DEFAULT_COMMIT_LIMIT)
+            git_service.collect_recent_file_stats(&repo_path, history_depth)
         })
         .await
         .map_err(|e| GitServiceError::InvalidRepository(format!("Task join error: {e}")))?;
 
-        let stats = match stats {
+        let mut stats = match stats {
             Ok(s) => s,
             Err(e) => {
                 tracing::warn!(
@@ -137,6 +198,7 @@ impl FileRanker {
             }
         };
 
+        apply_recency_decay(&mut stats, self.config.half_life, Utc::now());
         let stats_arc = Arc::new(stats);
 
         // Update cache
@@ -145,6 +207,7 @@ impl FileRanker {
                 repo_path_for_error,
                 RepoHistoryCache {
                     head_sha: head_info.oid,
+                    config: self.config,
                     stats: Arc::clone(&stats_arc),
                 },
             );
@@ -153,3 +216,230 @@ impl FileRanker {
         Ok(stats_arc)
     }
 }
+
+fn recency_boost(last_time: DateTime<Utc>, now: DateTime<Utc>) -> u32 {
+    let age = now - last_time;
+    if age <= chrono::Duration::days(1) {
+        RECENCY_BOOST_LAST_DAY
+    } else if age <= chrono::Duration::days(7) {
+        RECENCY_BOOST_LAST_WEEK
+    } else {
+        RECENCY_BOOST_NONE
+    }
+}
+
+/// Score each file by summing its commits with exponential decay, so a commit
+/// `half_life` old counts half as much as one made at `now`, then normalise
+/// against the most active file.
+fn apply_recency_decay(stats: &mut FileStats, half_life: Duration, now: DateTime<Utc>) {
+    let half_life_secs = half_life.as_secs_f64().max(1.0);
+
+    let mut max_weight = 0.0_f64;
+    for stat in stats.values_mut() {
+        let weight: f64 = stat
+            .commit_times
+            .iter()
+            .map(|time| {
+                let age_secs = (now - *time).num_seconds().max(0) as f64;
+                0.5_f64.powf(age_secs / half_life_secs)
+            })
+            .sum();
+        stat.recency = weight;
+        max_weight = max_weight.max(weight);
+    }
+
+    if max_weight > 0.0 {
+        for stat in stats.values_mut() {
+            stat.recency /= max_weight;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use git2::{Repository, Signature, Time};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const DAY_SECS: i64 = 24 * 60 * 60;
+
+    fn commit_file(repo: &Repository, path: &str, contents: &str, time_secs: i64) {
+        let workdir = repo.workdir().unwrap();
+        std::fs::write(workdir.join(path), contents).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let sig = Signature::new("Test", "test@example.com", &Time::new(time_secs, 0)).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, "update", &tree, &parents)
+            .unwrap();
+    }
+
+    fn file_result(path: &str) -> SearchResult {
+        SearchResult {
+            path: path.to_string(),
+            is_file: true,
+            match_type: SearchMatchType::FileName,
+            score: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn recently_touched_file_outranks_stale_popular_file() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+
+        // Heavily edited two years ago, untouched since
+        let stale_start = now - 730 * DAY_SECS;
+        for i in 0..40 {
+            commit_file(&repo, "legacy.rs", &format!("v{i}"), stale_start + i * 60);
+        }
+        // Edited once, yesterday
+        commit_file(&repo, "fresh.rs", "new", now - DAY_SECS);
+
+        let ranker = FileRanker::new();
+        let stats = ranker.get_stats(dir.path()).await.unwrap();
+
+        let stale = &stats["legacy.rs"];
+        let fresh = &stats["fresh.rs"];
+        assert_eq!(stale.commit_count, 40);
+        assert_eq!(fresh.commit_count, 1);
+        assert!(fresh.recency > stale.recency);
+
+        let mut results = vec![file_result("legacy.rs"), file_result("fresh.rs")];
+        ranker.rerank(&mut results, &stats);
+        assert_eq!(results[0].path, "fresh.rs");
+        assert!(
+            ranker.calculate_score(&results[0], &stats)
+                > ranker.calculate_score(&results[1], &stats)
+        );
+    }
+
+    #[tokio::test]
+    async fn equal_frequency_prefers_more_recent_file() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+
+        for i in 0..3 {
+            commit_file(&repo, "old.rs", &format!("v{i}"), now - (60 - i) * DAY_SECS);
+        }
+        for i in 0..3 {
+            commit_file(&repo, "new.rs", &format!("v{i}"), now - (3 - i) * DAY_SECS);
+        }
+
+        let ranker = FileRanker::with_recency_halflife(14);
+        let stats = ranker.get_stats(dir.path()).await.unwrap();
+        assert_eq!(stats["old.rs"].commit_count, stats["new.rs"].commit_count);
+
+        assert!(
+            ranker.calculate_score(&file_result("new.rs"), &stats)
+                > ranker.calculate_score(&file_result("old.rs"), &stats)
+        );
+    }
+
+    #[tokio::test]
+    async fn history_depth_limits_scanned_commits() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let now = Utc::now().timestamp();
+
+        for i in 0..5 {
+            commit_file(&repo, "a.rs", &format!("v{i}"), now - (10 - i) * DAY_SECS);
+        }
+        commit_file(&repo, "b.rs", "b", now);
+
+        let ranker = FileRanker::with_config(FileRankerConfig {
+            history_depth: 3,
+            ..Default::default()
+        });
+        let stats = ranker.get_stats(dir.path()).await.unwrap();
+
+        assert_eq!(stats["a.rs"].commit_count, 2);
+        assert_eq!(stats["b.rs"].commit_count, 1);
+    }
+
+    fn stat_last_touched(last_time: DateTime<Utc>) -> FileStat {
+        FileStat {
+            last_index: 0,
+            commit_count: 3,
+            last_time,
+            commit_times: vec![last_time],
+            recency: 0.5,
+        }
+    }
+
+    #[test]
+    fn recency_boost_by_last_commit_age() {
+        let now = Utc::now();
+        let mut stats = FileStats::new();
+        stats.insert(
+            "hour.rs".to_string(),
+            stat_last_touched(now - chrono::Duration::hours(1)),
+        );
+        stats.insert(
+            "days.rs".to_string(),
+            stat_last_touched(now - chrono::Duration::days(3)),
+        );
+        stats.insert(
+            "month.rs".to_string(),
+            stat_last_touched(now - chrono::Duration::days(30)),
+        );
+
+        let ranker = FileRanker::new();
+        assert_eq!(ranker.calculate_recency_boost("hour.rs", &stats), 3);
+        assert_eq!(ranker.calculate_recency_boost("days.rs", &stats), 2);
+        assert_eq!(ranker.calculate_recency_boost("month.rs", &stats), 1);
+        assert_eq!(ranker.calculate_recency_boost("untracked.rs", &stats), 1);
+    }
+
+    #[test]
+    fn recency_boost_multiplies_history_bonus() {
+        let now = Utc::now();
+        let mut stats = FileStats::new();
+        stats.insert(
+            "recent.rs".to_string(),
+            stat_last_touched(now - chrono::Duration::hours(1)),
+        );
+        stats.insert(
+            "stale.rs".to_string(),
+            stat_last_touched(now - chrono::Duration::days(30)),
+        );
+
+        let ranker = FileRanker::new();
+        let base = BASE_MATCH_SCORE_FILENAME * 1000;
+        let recent = ranker.calculate_score(&file_result("recent.rs"), &stats) - base;
+        let stale = ranker.calculate_score(&file_result("stale.rs"), &stats) - base;
+        assert_eq!(recent, stale * 3);
+    }
+
+    #[test]
+    fn decay_halves_weight_every_half_life() {
+        let now = Utc::now();
+        let half_life = Duration::from_secs(DAY_SECS as u64);
+        let stat = |times: Vec<DateTime<Utc>>| FileStat {
+            last_index: 0,
+            commit_count: times.len() as u32,
+            last_time: times[0],
+            commit_times: times,
+            recency: 0.0,
+        };
+
+        let mut stats = FileStats::new();
+        stats.insert("now.rs".to_string(), stat(vec![now]));
+        stats.insert(
+            "day_old.rs".to_string(),
+            stat(vec![now - chrono::Duration::days(1)]),
+        );
+        apply_recency_decay(&mut stats, half_life, now);
+
+        assert!((stats["now.rs"].recency - 1.0).abs() < 1e-9);
+        assert!((stats["day_old.rs"].recency - 0.5).abs() < 1e-9);
+    }
+}
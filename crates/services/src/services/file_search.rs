@@ -20,6 +20,7 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 use ts_rs::TS;
+use utils::shell::resolve_executable_path;
 
 use super::{
     file_ranker::{FileRanker, FileStats},
@@ -34,16 +35,107 @@ pub enum SearchMode {
     #[default]
     TaskForm, // Default: exclude ignored files (clean results)
     Settings, // Include ignored files (for project config like .env)
+    /// Like `TaskForm`, but only directories are returned (e.g. picking a working directory)
+    DirectoriesOnly,
+    /// Files whose contents contain `pattern` (case-insensitive, literal). Requested with
+    /// `mode=content`, using `q` as the pattern; only available when enabled in the config.
+    #[serde(skip)]
+    #[ts(skip)]
+    ContentSearch {
+        pattern: String,
+    },
+}
+
+impl SearchMode {
+    pub fn is_content_search(&self) -> bool {
+        matches!(self, SearchMode::ContentSearch { .. })
+    }
 }
 
 /// Search query parameters for typed Axum extraction
 #[derive(Debug, Clone, Deserialize)]
+#[serde(from = "SearchQueryParams")]
 pub struct SearchQuery {
     pub q: String,
-    #[serde(default)]
     pub mode: SearchMode,
+    /// Collapse results that share a relative path across repos into the best-scoring one
+    pub deduplicate: bool,
+    /// Maximum number of results to return, capped at [`MAX_SEARCH_LIMIT`]
+    pub limit: usize,
+}
+
+impl SearchQuery {
+    /// Reject queries the search routes can't run, returning the message to show the user.
+    /// Content search is only allowed when enabled in settings.
+    pub fn validate(&self, content_search_enabled: bool) -> Result<(), &'static str> {
+        if self.q.trim().is_empty() {
+            return Err("Query parameter 'q' is required and cannot be empty");
+        }
+        if self.mode.is_content_search() && !content_search_enabled {
+            return Err("Content search is disabled in settings");
+        }
+        Ok(())
+    }
 }
 
+/// Wire format of [`SearchQuery`]; `mode` is a plain string in the query string
+#[derive(Deserialize)]
+struct SearchQueryParams {
+    q: String,
+    #[serde(default)]
+    mode: SearchModeParam,
+    #[serde(default = "default_deduplicate")]
+    deduplicate: bool,
+    #[serde(default = "default_search_limit")]
+    limit: usize,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SearchModeParam {
+    #[default]
+    TaskForm,
+    Settings,
+    DirectoriesOnly,
+    Content,
+}
+
+fn default_deduplicate() -> bool {
+    true
+}
+
+/// Number of results returned when a search doesn't specify a limit
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+/// Upper bound on the requested limit so a single query can't return a whole monorepo
+pub const MAX_SEARCH_LIMIT: usize = 200;
+
+fn default_search_limit() -> usize {
+    DEFAULT_SEARCH_LIMIT
+}
+
+impl From<SearchQueryParams> for SearchQuery {
+    fn from(params: SearchQueryParams) -> Self {
+        let mode = match params.mode {
+            SearchModeParam::TaskForm => SearchMode::TaskForm,
+            SearchModeParam::Settings => SearchMode::Settings,
+            SearchModeParam::DirectoriesOnly => SearchMode::DirectoriesOnly,
+            SearchModeParam::Content => SearchMode::ContentSearch {
+                pattern: params.q.clone(),
+            },
+        };
+        Self {
+            q: params.q,
+            mode,
+            deduplicate: params.deduplicate,
+            limit: params.limit.clamp(1, MAX_SEARCH_LIMIT),
+        }
+    }
+}
+
+/// Files larger than this are skipped when searching contents without ripgrep
+const MAX_CONTENT_SEARCH_FILE_SIZE: u64 = 1024 * 1024;
+
 /// FST-indexed file search result
 #[derive(Clone, Debug)]
 pub struct IndexedFile {
@@ -142,6 +234,7 @@ impl FileSearchCache {
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        limit: usize,
     ) -> Result<Vec<SearchResult>, CacheError> {
         let repo_path_buf = repo_path.to_path_buf();
 
@@ -151,7 +244,7 @@ impl FileSearchCache {
             && head_info.oid == cached.head_sha
         {
             // Cache hit - perform fast search with mode-based filtering
-            return Ok(self.search_in_cache(&cached, query, mode).await);
+            return Ok(self.search_in_cache(&cached, query, mode, limit).await);
         }
 
         // Cache miss - trigger background refresh and return error
@@ -162,6 +255,23 @@ impl FileSearchCache {
         Err(CacheError::Miss)
     }
 
+    /// Build the index for a repository now and store it, unless the cached index is
+    /// already at the current HEAD. Unlike [`Self::warm_repos`] this waits for the build.
+    pub async fn build(&self, repo_path: &Path) -> Result<(), String> {
+        let repo_path_buf = repo_path.to_path_buf();
+
+        if let Some(cached) = self.cache.get(&repo_path_buf).await
+            && let Ok(head_info) = self.git_service.get_head_info(&repo_path_buf)
+            && head_info.oid == cached.head_sha
+        {
+            return Ok(());
+        }
+
+        let cached_repo = self.build_repo_cache(repo_path).await?;
+        self.cache.insert(repo_path_buf, cached_repo).await;
+        Ok(())
+    }
+
     /// Pre-warm cache for given repositories
     pub async fn warm_repos(&self, repo_paths: Vec<PathBuf>) -> Result<(), String> {
         for repo_path in repo_paths {
@@ -233,6 +343,7 @@ impl FileSearchCache {
         cached: &CachedRepo,
         query: &str,
         mode: SearchMode,
+        limit: usize,
     ) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
         let mut results = Vec::new();
@@ -242,7 +353,7 @@ impl FileSearchCache {
             if indexed_file.path_lowercase.contains(&query_lower) {
                 // Apply mode-based filtering
                 match mode {
-                    SearchMode::TaskForm => {
+                    SearchMode::TaskForm | SearchMode::ContentSearch { .. } => {
                         // Exclude ignored files for task forms
                         if indexed_file.is_ignored {
                             continue;
@@ -252,6 +363,11 @@ impl FileSearchCache {
                         // Include all files (including ignored) for project settings
                         // No filtering needed
                     }
+                    SearchMode::DirectoriesOnly => {
+                        if indexed_file.is_ignored || indexed_file.is_file {
+                            continue;
+                        }
+                    }
                 }
 
                 results.push(SearchResult {
@@ -271,29 +387,36 @@ impl FileSearchCache {
             result.score = self.file_ranker.calculate_score(result, &cached.stats);
         }
 
-        // Limit to top 10 results
-        results.truncate(10);
+        results.truncate(limit);
         results
     }
 
-    /// Search files in a single repository with cache + fallback
+    /// Search files in a single repository with cache + fallback, returning at most `limit`
+    /// results
     pub async fn search_repo(
         &self,
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        limit: usize,
     ) -> Result<Vec<SearchResult>, String> {
         let query = query.trim();
         if query.is_empty() {
             return Ok(vec![]);
         }
 
+        // The path index can't answer content queries
+        if let SearchMode::ContentSearch { pattern } = &mode {
+            return self.search_content(repo_path, pattern, limit).await;
+        }
+
         // Try cache first
-        match self.search(repo_path, query, mode.clone()).await {
+        match self.search(repo_path, query, mode.clone(), limit).await {
             Ok(results) => Ok(results),
             Err(CacheError::Miss) | Err(CacheError::BuildError(_)) => {
                 // Fall back to filesystem search
-                self.search_files_no_cache(repo_path, query, mode).await
+                self.search_files_no_cache(repo_path, query, mode, limit)
+                    .await
             }
         }
     }
@@ -304,6 +427,7 @@ impl FileSearchCache {
         repo_path: &Path,
         query: &str,
         mode: SearchMode,
+        limit: usize,
     ) -> Result<Vec<SearchResult>, String> {
         if !repo_path.exists() {
             return Err(format!("Path not found: {:?}", repo_path));
@@ -330,7 +454,9 @@ impl FileSearchCache {
                     })
                     .build()
             }
-            SearchMode::TaskForm => WalkBuilder::new(repo_path)
+            SearchMode::TaskForm
+            | SearchMode::DirectoriesOnly
+            | SearchMode::ContentSearch { .. } => WalkBuilder::new(repo_path)
                 .git_ignore(true)
                 .git_global(true)
                 .git_exclude(true)
@@ -354,6 +480,10 @@ impl FileSearchCache {
                 continue;
             }
 
+            if matches!(mode, SearchMode::DirectoriesOnly) && !path.is_dir() {
+                continue;
+            }
+
             let relative_path = match path.strip_prefix(repo_path) {
                 Ok(p) => p,
                 Err(_) => continue,
@@ -410,6 +540,7 @@ impl FileSearchCache {
                         SearchMatchType::FileName => 0,
                         SearchMatchType::DirectoryName => 1,
                         SearchMatchType::FullPath => 2,
+                        SearchMatchType::Content => 3,
                     };
 
                     priority(&a.match_type)
@@ -419,7 +550,58 @@ impl FileSearchCache {
             }
         }
 
-        results.truncate(10);
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Find files whose contents contain `pattern`, using ripgrep when it is installed
+    async fn search_content(
+        &self,
+        repo_path: &Path,
+        pattern: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Ok(vec![]);
+        }
+        if !repo_path.exists() {
+            return Err(format!("Path not found: {:?}", repo_path));
+        }
+
+        let paths = match ripgrep_matching_files(repo_path, pattern).await {
+            Ok(paths) => paths,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let repo_path = repo_path.to_path_buf();
+                let pattern = pattern.to_string();
+                tokio::task::spawn_blocking(move || walk_matching_files(&repo_path, &pattern))
+                    .await
+                    .map_err(|e| format!("Content search task failed: {e}"))?
+            }
+            Err(e) => return Err(format!("Failed to run ripgrep: {e}")),
+        };
+
+        let mut results: Vec<SearchResult> = paths
+            .into_iter()
+            .map(|path| SearchResult {
+                path,
+                is_file: true,
+                match_type: SearchMatchType::Content,
+                score: 0,
+            })
+            .collect();
+
+        match self.file_ranker.get_stats(repo_path).await {
+            Ok(stats) => {
+                self.file_ranker.rerank(&mut results, &stats);
+                for result in &mut results {
+                    result.score = self.file_ranker.calculate_score(result, &stats);
+                }
+            }
+            Err(_) => results.sort_by(|a, b| a.path.cmp(&b.path)),
+        }
+
+        results.truncate(limit);
         Ok(results)
     }
 
@@ -620,6 +802,7 @@ impl FileSearchCache {
         }
 
         let build_queue = self.build_queue.clone();
+        let file_ranker = self.file_ranker.clone();
         let watched_path = repo_path_buf.clone();
 
         let (tx, mut rx) = mpsc::unbounded_channel();
@@ -653,6 +836,7 @@ impl FileSearchCache {
         tokio::spawn(async move {
             while rx.recv().await.is_some() {
                 info!("HEAD changed for repo: {:?}", watched_path);
+                file_ranker.invalidate(&watched_path);
                 if let Err(e) = build_queue.send(watched_path.clone()) {
                     error!("Failed to enqueue cache refresh: {}", e);
                 }
@@ -669,3 +853,326 @@ impl Default for FileSearchCache {
         Self::new()
     }
 }
+
+/// Run `rg` over `repo_path` for the literal, case-insensitive `pattern` with the ignore rules
+/// shared by every content search, plus `extra_args`. Each output line is passed to `parse`,
+/// and rg is killed once `limit` lines have been kept. Returns an `ErrorKind::NotFound` error
+/// when ripgrep isn't installed.
+async fn run_ripgrep<T>(
+    repo_path: &Path,
+    extra_args: &[&str],
+    pattern: &str,
+    limit: usize,
+    parse: impl Fn(&str) -> Option<T>,
+) -> std::io::Result<Vec<T>> {
+    let rg = resolve_executable_path("rg").await.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "ripgrep (rg) is not installed",
+        )
+    })?;
+    let mut child = tokio::process::Command::new(rg)
+        .args([
+            "--fixed-strings",
+            "--ignore-case",
+            "--no-messages",
+            "--hidden",
+            "--glob",
+            "!.git",
+        ])
+        .arg("--max-filesize")
+        .arg(MAX_CONTENT_SEARCH_FILE_SIZE.to_string())
+        .args(extra_args)
+        .arg("--")
+        .arg(pattern)
+        .current_dir(repo_path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let Some(stdout) = child.stdout.take() else {
+        return Ok(vec![]);
+    };
+    // rg exits with 1 when nothing matched and 2 on errors (e.g. unreadable files), so its
+    // status is ignored and whatever it printed is used
+    let mut reader = tokio::io::BufReader::new(stdout);
+    let mut results = Vec::new();
+    let mut buf = Vec::new();
+    // Stop reading once the cap is hit; dropping the child kills rg
+    while results.len() < limit {
+        buf.clear();
+        if tokio::io::AsyncBufReadExt::read_until(&mut reader, b'\n', &mut buf).await? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        if let Some(result) = parse(line.trim_end_matches(['\n', '\r'])) {
+            results.push(result);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Relative paths of files under `repo_path` containing `pattern`, via `rg`. Returns an
+/// `ErrorKind::NotFound` error when ripgrep isn't installed.
+async fn ripgrep_matching_files(repo_path: &Path, pattern: &str) -> std::io::Result<Vec<String>> {
+    run_ripgrep(
+        repo_path,
+        &["--files-with-matches"],
+        pattern,
+        usize::MAX,
+        |line| {
+            let path = line.trim_start_matches("./");
+            (!path.is_empty()).then(|| path.to_string())
+        },
+    )
+    .await
+}
+
+/// Limits applied by [`grep_repo`]
+#[derive(Debug, Clone)]
+pub struct GrepOptions {
+    /// Stop after this many matching lines in total
+    pub max_matches: usize,
+    /// Report at most this many matching lines from a single file
+    pub max_matches_per_file: usize,
+}
+
+impl Default for GrepOptions {
+    fn default() -> Self {
+        Self {
+            max_matches: 100,
+            max_matches_per_file: 5,
+        }
+    }
+}
+
+/// A line containing the searched-for text
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct GrepMatch {
+    pub path: String,
+    /// 1-based line number
+    pub line: u64,
+    /// The matching line, trimmed and cut to [`MAX_GREP_SNIPPET_CHARS`]
+    pub snippet: String,
+}
+
+/// Longest snippet returned for a single matching line
+pub const MAX_GREP_SNIPPET_CHARS: usize = 200;
+
+/// Find lines containing `pattern` (case-insensitive, literal) with ripgrep, using the same
+/// ignore rules as task form searches. Fails with [`std::io::ErrorKind::NotFound`] when `rg`
+/// isn't installed.
+pub async fn grep_repo(
+    repo_path: &Path,
+    pattern: &str,
+    options: &GrepOptions,
+) -> std::io::Result<Vec<GrepMatch>> {
+    // Checked up front so a missing repo isn't mistaken for a missing `rg`
+    if !repo_path.is_dir() {
+        return Err(std::io::Error::other(format!(
+            "Path not found: {}",
+            repo_path.display()
+        )));
+    }
+
+    let max_count = options.max_matches_per_file.to_string();
+    let args = [
+        "--line-number",
+        "--with-filename",
+        "--no-heading",
+        "--null",
+        "--color",
+        "never",
+        "--max-count",
+        &max_count,
+    ];
+    run_ripgrep(
+        repo_path,
+        &args,
+        pattern,
+        options.max_matches,
+        parse_grep_line,
+    )
+    .await
+}
+
+/// Parse one line of `rg --null --line-number` output: `path\0line:text`
+fn parse_grep_line(line: &str) -> Option<GrepMatch> {
+    let (path, rest) = line.split_once('\0')?;
+    let (line_number, text) = rest.split_once(':')?;
+    Some(GrepMatch {
+        path: path.trim_start_matches("./").to_string(),
+        line: line_number.parse().ok()?,
+        snippet: text.trim().chars().take(MAX_GREP_SNIPPET_CHARS).collect(),
+    })
+}
+
+/// Fallback for [`ripgrep_matching_files`]: walk the repo honouring .gitignore and scan
+/// each text file for `pattern`
+fn walk_matching_files(repo_path: &Path, pattern: &str) -> Vec<String> {
+    let pattern_lower = pattern.to_lowercase();
+    let walker = WalkBuilder::new(repo_path)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+
+    let mut matches = Vec::new();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !metadata.is_file() || metadata.len() > MAX_CONTENT_SEARCH_FILE_SIZE {
+            continue;
+        }
+        // Binary or non-UTF-8 files are skipped
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        if contents.to_lowercase().contains(&pattern_lower)
+            && let Ok(relative) = path.strip_prefix(repo_path)
+        {
+            matches.push(relative.to_string_lossy().to_string());
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn content_mode_uses_query_as_pattern() {
+        let query: SearchQuery =
+            serde_json::from_str(r#"{"q": "TODO", "mode": "content"}"#).unwrap();
+        assert!(matches!(
+            query.mode,
+            SearchMode::ContentSearch { ref pattern } if pattern == "TODO"
+        ));
+        assert!(query.deduplicate);
+        assert_eq!(query.limit, DEFAULT_SEARCH_LIMIT);
+
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "main"}"#).unwrap();
+        assert!(matches!(query.mode, SearchMode::TaskForm));
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "main", "limit": 0}"#).unwrap();
+        assert_eq!(query.limit, 1);
+
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "main", "limit": 100000}"#).unwrap();
+        assert_eq!(query.limit, MAX_SEARCH_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn single_repo_search_honours_limit() {
+        let dir = TempDir::new().unwrap();
+        for i in 0..15 {
+            std::fs::write(dir.path().join(format!("module_{i}.rs")), "").unwrap();
+        }
+
+        let cache = FileSearchCache::new();
+        let results = cache
+            .search_files_no_cache(dir.path(), "module", SearchMode::TaskForm, 12)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 12);
+
+        let results = cache
+            .search_files_no_cache(dir.path(), "module", SearchMode::TaskForm, 3)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn directories_only_excludes_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("app/src")).unwrap();
+        std::fs::write(dir.path().join("app/src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("app/src.rs"), "mod src;\n").unwrap();
+        std::fs::write(dir.path().join("src_notes.md"), "notes\n").unwrap();
+
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        let cache = FileSearchCache::new();
+        let paths = |results: Vec<SearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.path).collect()
+        };
+
+        // Filesystem fallback (nothing cached yet)
+        let uncached = cache
+            .search_files_no_cache(
+                dir.path(),
+                "src",
+                SearchMode::DirectoriesOnly,
+                DEFAULT_SEARCH_LIMIT,
+            )
+            .await
+            .unwrap();
+        assert_eq!(paths(uncached), vec!["app/src".to_string()]);
+
+        // Cached index
+        cache.build(dir.path()).await.unwrap();
+        let cached = cache
+            .search(
+                dir.path(),
+                "src",
+                SearchMode::DirectoriesOnly,
+                DEFAULT_SEARCH_LIMIT,
+            )
+            .await
+            .unwrap();
+        assert_eq!(paths(cached), vec!["app/src".to_string()]);
+    }
+
+    #[test]
+    fn grep_lines_are_parsed() {
+        let parsed =
+            parse_grep_line("./src/lib.rs\x0042:    // TODO: handle errors: all of them\n")
+                .unwrap();
+        assert_eq!(parsed.path, "src/lib.rs");
+        assert_eq!(parsed.line, 42);
+        assert_eq!(parsed.snippet, "// TODO: handle errors: all of them");
+
+        let long = format!("a.txt\x001:{}", "x".repeat(MAX_GREP_SNIPPET_CHARS * 2));
+        assert_eq!(
+            parse_grep_line(&long).unwrap().snippet.len(),
+            MAX_GREP_SNIPPET_CHARS
+        );
+
+        assert!(parse_grep_line("no separators here").is_none());
+    }
+
+    #[test]
+    fn walk_fallback_matches_contents_and_honours_gitignore() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "// todo: handle errors\n").unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("ignored.txt"), "TODO\n").unwrap();
+
+        let matches = walk_matching_files(dir.path(), "TODO");
+        assert_eq!(matches, vec!["src/lib.rs".to_string()]);
+    }
+}
@@ -41,6 +41,18 @@ pub enum GitServiceError {
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
 }
+
+/// Outcome of [`GitService::with_autostash`]
+#[derive(Debug)]
+pub struct Autostashed<T> {
+    /// What the operation returned
+    pub result: Result<T, GitServiceError>,
+    /// Sha of the stash still holding the uncommitted changes when they weren't reapplied,
+    /// either because the operation stopped on conflicts or because reapplying conflicted.
+    /// They can be recovered with `git stash apply <sha>`.
+    pub kept_stash: Option<String>,
+}
+
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
 pub struct GitService {}
@@ -1101,6 +1113,32 @@ impl GitService {
         Ok((ahead, behind))
     }
 
+    /// Count the commits a rebase rewrote from `old_head` into `new_head`: those reachable
+    /// from the new HEAD but neither from the old HEAD nor from `new_base_branch`.
+    pub fn count_replayed_commits(
+        &self,
+        repo_path: &Path,
+        old_head: &str,
+        new_head: &str,
+        new_base_branch: &str,
+    ) -> Result<usize, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let parse = |sha: &str| {
+            git2::Oid::from_str(sha)
+                .map_err(|_| GitServiceError::InvalidRepository("Invalid commit SHA".into()))
+        };
+        let new_base = Self::find_branch(&repo, new_base_branch)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut walk = repo.revwalk()?;
+        walk.push(parse(new_head)?)?;
+        walk.hide(parse(old_head)?)?;
+        walk.hide(new_base)?;
+        Ok(walk.collect::<Result<Vec<_>, _>>()?.len())
+    }
+
     /// Return (uncommitted_tracked_changes, untracked_files) counts in worktree
     pub fn get_worktree_change_counts(
         &self,
@@ -1485,6 +1523,60 @@ impl GitService {
         Ok(())
     }
 
+    /// Run `op` with the worktree's uncommitted changes stashed away, then reapply them.
+    ///
+    /// If `op` fails and leaves a rebase or merge in progress the stash is kept so conflict
+    /// resolution starts from a clean tree. A reapply that conflicts doesn't undo `op`: its
+    /// result is returned as-is and the stash stays in the stash list. Either way the stash
+    /// sha is reported in [`Autostashed::kept_stash`].
+    pub fn with_autostash<T>(
+        &self,
+        worktree_path: &Path,
+        include_untracked: bool,
+        op: impl FnOnce() -> Result<T, GitServiceError>,
+    ) -> Result<Autostashed<T>, GitServiceError> {
+        let git = GitCli::new();
+        let stash_ref = if git.has_changes(worktree_path)? {
+            self.ensure_cli_commit_identity(worktree_path)?;
+            git.stash_push(worktree_path, "vibe-kanban autostash", include_untracked)?
+        } else {
+            None
+        };
+        let Some(stash_ref) = stash_ref else {
+            return Ok(Autostashed {
+                result: op(),
+                kept_stash: None,
+            });
+        };
+
+        let result = op();
+        if result.is_err() && self.detect_conflict_op(worktree_path)?.is_some() {
+            tracing::warn!(
+                "Operation in {} stopped with conflicts; uncommitted changes kept in stash {}",
+                worktree_path.display(),
+                stash_ref
+            );
+            return Ok(Autostashed {
+                result,
+                kept_stash: Some(stash_ref),
+            });
+        }
+
+        let kept_stash = match git.stash_pop(worktree_path, &stash_ref) {
+            Ok(()) => None,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to reapply stash {} in {}: {}",
+                    stash_ref,
+                    worktree_path.display(),
+                    e
+                );
+                Some(stash_ref)
+            }
+        };
+        Ok(Autostashed { result, kept_stash })
+    }
+
     /// Return true if a rebase is currently in progress in this worktree.
     pub fn is_rebase_in_progress(&self, worktree_path: &Path) -> Result<bool, GitServiceError> {
         let git = GitCli::new();
@@ -1616,6 +1708,17 @@ impl GitService {
             .map_err(GitServiceError::GitCLI)
     }
 
+    /// Default branch of `remote_name` (what its `HEAD` points at), e.g. `main`.
+    pub fn get_remote_default_branch(
+        &self,
+        repo_path: &Path,
+        remote_name: &str,
+    ) -> Result<String, GitServiceError> {
+        let cli = GitCli::new();
+        cli.get_remote_default_branch(repo_path, remote_name)
+            .map_err(GitServiceError::GitCLI)
+    }
+
     pub fn check_remote_branch_exists(
         &self,
         repo_path: &Path,
@@ -1665,6 +1768,20 @@ impl GitService {
         })
     }
 
+    /// Check whether a remote with the given name is configured for the repository
+    pub fn remote_exists(
+        &self,
+        repo_path: &Path,
+        remote_name: &str,
+    ) -> Result<bool, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        match repo.find_remote(remote_name) {
+            Ok(_) => Ok(true),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn push_to_remote(
         &self,
         worktree_path: &Path,
@@ -1672,17 +1789,69 @@ impl GitService {
         force: bool,
     ) -> Result<(), GitServiceError> {
         let repo = Repository::open(worktree_path)?;
-        self.check_worktree_clean(&repo)?;
+        let remote_name = self.default_remote_name(&repo);
+        self.push_to_named_remote(worktree_path, &remote_name, branch_name, force)
+    }
 
-        // Get the remote
+    /// Push a branch to a specific remote (e.g. a fork) and set it as the upstream
+    pub fn push_to_named_remote(
+        &self,
+        worktree_path: &Path,
+        remote_name: &str,
+        branch_name: &str,
+        force: bool,
+    ) -> Result<(), GitServiceError> {
+        self.push_branch(worktree_path, remote_name, branch_name, |cli, url| {
+            cli.push(worktree_path, url, branch_name, force)
+        })
+    }
+
+    /// Like [`Self::push_to_remote`], but overwrites a diverged remote branch with
+    /// `--force-with-lease`: the push is rejected if the remote has moved since our
+    /// remote-tracking ref was last updated.
+    pub fn push_to_remote_with_lease(
+        &self,
+        worktree_path: &Path,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
         let remote_name = self.default_remote_name(&repo);
-        let remote = repo.find_remote(&remote_name)?;
+        self.push_to_named_remote_with_lease(worktree_path, &remote_name, branch_name)
+    }
+
+    pub fn push_to_named_remote_with_lease(
+        &self,
+        worktree_path: &Path,
+        remote_name: &str,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let expected_oid = repo
+            .refname_to_id(&format!("refs/remotes/{remote_name}/{branch_name}"))
+            .ok()
+            .map(|oid| oid.to_string());
+        self.push_branch(worktree_path, remote_name, branch_name, |cli, url| {
+            cli.push_with_lease(worktree_path, url, branch_name, expected_oid.as_deref())
+        })
+    }
+
+    fn push_branch(
+        &self,
+        worktree_path: &Path,
+        remote_name: &str,
+        branch_name: &str,
+        push: impl FnOnce(&GitCli, &str) -> Result<(), GitCliError>,
+    ) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        self.check_worktree_clean(&repo)?;
+
+        let remote = repo.find_remote(remote_name)?;
 
         let remote_url = remote
             .url()
             .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
         let git_cli = GitCli::new();
-        if let Err(e) = git_cli.push(worktree_path, remote_url, branch_name, force) {
+        if let Err(e) = push(&git_cli, remote_url) {
             tracing::error!("Push to remote failed: {}", e);
             return Err(e.into());
         }
@@ -1754,6 +1923,30 @@ impl GitService {
         self.fetch_from_remote(repo, remote, &refspec)
     }
 
+    /// Clone `remote_url` into `target_path` with the git CLI, creating parent directories as
+    /// needed. Authentication uses the user's own git setup (credential helpers, SSH agent).
+    pub fn clone_from_url(
+        &self,
+        remote_url: &str,
+        target_path: &Path,
+    ) -> Result<(), GitServiceError> {
+        let parent = target_path.parent().ok_or_else(|| {
+            GitServiceError::InvalidRepository(format!(
+                "Clone target has no parent directory: {}",
+                target_path.display()
+            ))
+        })?;
+        std::fs::create_dir_all(parent)?;
+
+        GitCli::new().clone(parent, remote_url, target_path)?;
+        tracing::info!(
+            "Cloned repository from {} to {}",
+            remote_url,
+            target_path.display()
+        );
+        Ok(())
+    }
+
     /// Clone a repository to the specified directory
     #[cfg(feature = "cloud")]
     pub fn clone_repository(
@@ -1861,10 +2054,13 @@ impl GitService {
                             last_index: commit_index,
                             commit_count: 0,
                             last_time: commit_time,
+                            commit_times: Vec::new(),
+                            recency: 0.0,
                         });
 
                         // Increment commit count
                         stat.commit_count += 1;
+                        stat.commit_times.push(commit_time);
 
                         // Keep the most recent change (smallest index)
                         if commit_index < stat.last_index {
@@ -44,6 +44,14 @@ pub enum GitCliError {
 #[derive(Clone, Default)]
 pub struct GitCli;
 
+/// How `push` treats a remote branch that is not an ancestor of the local one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PushMode<'a> {
+    FastForward,
+    Force,
+    ForceWithLease { expected_oid: Option<&'a str> },
+}
+
 /// Parsed change type from `git diff --name-status` output
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ChangeType {
@@ -372,20 +380,86 @@ impl GitCli {
         branch: &str,
         force: bool,
     ) -> Result<(), GitCliError> {
-        let refspec = if force {
-            format!("+refs/heads/{branch}:refs/heads/{branch}")
+        let mode = if force {
+            PushMode::Force
         } else {
-            format!("refs/heads/{branch}:refs/heads/{branch}")
+            PushMode::FastForward
+        };
+        self.push_with_mode(repo_path, remote_url, branch, mode)
+    }
+
+    /// Push a branch with `--force-with-lease`, overwriting the remote branch only
+    /// if it still points at `expected_oid` (or does not exist when `None`).
+    pub fn push_with_lease(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        expected_oid: Option<&str>,
+    ) -> Result<(), GitCliError> {
+        self.push_with_mode(
+            repo_path,
+            remote_url,
+            branch,
+            PushMode::ForceWithLease { expected_oid },
+        )
+    }
+
+    fn push_with_mode(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        mode: PushMode<'_>,
+    ) -> Result<(), GitCliError> {
+        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let args = Self::push_args(remote_url, branch, mode);
+
+        match self.git_with_env(repo_path, args, &envs) {
+            Ok(_) => Ok(()),
+            Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
+            Err(err) => Err(err),
+        }
+    }
+
+    // The lease names its expected value explicitly because we push to a URL, for
+    // which git has no remote-tracking ref to infer it from.
+    fn push_args(remote_url: &str, branch: &str, mode: PushMode<'_>) -> Vec<OsString> {
+        let mut args = vec![OsString::from("push")];
+        let refspec = match mode {
+            PushMode::FastForward => format!("refs/heads/{branch}:refs/heads/{branch}"),
+            PushMode::Force => format!("+refs/heads/{branch}:refs/heads/{branch}"),
+            PushMode::ForceWithLease { expected_oid } => {
+                args.push(OsString::from(format!(
+                    "--force-with-lease=refs/heads/{branch}:{}",
+                    expected_oid.unwrap_or_default()
+                )));
+                format!("refs/heads/{branch}:refs/heads/{branch}")
+            }
         };
+        args.push(OsString::from(remote_url));
+        args.push(OsString::from(refspec));
+        args
+    }
+
+    /// Clone `remote_url` into `target_path` using native git authentication. Prompts are
+    /// disabled so missing credentials fail with [`GitCliError::AuthFailed`] instead of hanging.
+    pub fn clone(
+        &self,
+        parent_dir: &Path,
+        remote_url: &str,
+        target_path: &Path,
+    ) -> Result<(), GitCliError> {
         let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
 
         let args = [
-            OsString::from("push"),
+            OsString::from("clone"),
+            OsString::from("--"),
             OsString::from(remote_url),
-            OsString::from(refspec),
+            target_path.as_os_str().to_os_string(),
         ];
 
-        match self.git_with_env(repo_path, args, &envs) {
+        match self.git_with_env(parent_dir, args, &envs) {
             Ok(_) => Ok(()),
             Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
             Err(err) => Err(err),
@@ -424,6 +498,54 @@ impl GitCli {
         Ok(output.trim().to_string())
     }
 
+    /// Resolve the default branch of `remote_name`.
+    ///
+    /// Reads the local `refs/remotes/<remote>/HEAD` symref first; if it is missing
+    /// (e.g. the remote was added rather than cloned) falls back to querying the
+    /// remote with `git remote show`.
+    pub fn get_remote_default_branch(
+        &self,
+        repo_path: &Path,
+        remote_name: &str,
+    ) -> Result<String, GitCliError> {
+        let symref = format!("refs/remotes/{remote_name}/HEAD");
+        if let Ok(output) = self.git(repo_path, ["symbolic-ref", "--short", symref.as_str()]) {
+            let short = output.trim();
+            let branch = short
+                .strip_prefix(&format!("{remote_name}/"))
+                .unwrap_or(short);
+            if !branch.is_empty() {
+                return Ok(branch.to_string());
+            }
+        }
+
+        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let args = [
+            OsString::from("remote"),
+            OsString::from("show"),
+            OsString::from(remote_name),
+        ];
+        let output = match self.git_with_env(repo_path, args, &envs) {
+            Ok(output) => output,
+            Err(GitCliError::CommandFailed(msg)) => return Err(self.classify_cli_error(msg)),
+            Err(err) => return Err(err),
+        };
+        Self::parse_remote_show_head_branch(&output).ok_or_else(|| {
+            GitCliError::CommandFailed(format!(
+                "could not determine default branch of remote '{remote_name}'"
+            ))
+        })
+    }
+
+    // Extract the branch from the `HEAD branch: <name>` line of `git remote show`.
+    // Git prints `(unknown)` when the remote HEAD is ambiguous or detached.
+    fn parse_remote_show_head_branch(output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let branch = line.trim().strip_prefix("HEAD branch:")?.trim();
+            (!branch.is_empty() && branch != "(unknown)").then(|| branch.to_string())
+        })
+    }
+
     // Parse `git diff --name-status` output into structured entries.
     // Handles rename/copy scores like `R100` by matching the first letter.
     fn parse_name_status(output: &str) -> Vec<StatusDiffEntry> {
@@ -589,6 +711,39 @@ impl GitCli {
         }
     }
 
+    /// Stash working tree changes under `message`. Returns the stash commit sha, or None when
+    /// there was nothing to stash. Untracked files are only stashed when `include_untracked`.
+    pub fn stash_push(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        include_untracked: bool,
+    ) -> Result<Option<String>, GitCliError> {
+        let before = self.stash_top(worktree_path)?;
+        let mut args = vec!["stash", "push", "-m", message];
+        if include_untracked {
+            args.push("--include-untracked");
+        }
+        self.git(worktree_path, args)?;
+        let after = self.stash_top(worktree_path)?;
+        Ok(after.filter(|sha| before.as_deref() != Some(sha.as_str())))
+    }
+
+    /// Pop the stash entry whose commit is `stash_sha`. Git keeps the entry when the pop
+    /// fails (e.g. on conflicts), so the caller can still recover it by sha.
+    pub fn stash_pop(&self, worktree_path: &Path, stash_sha: &str) -> Result<(), GitCliError> {
+        let list = self.git(worktree_path, ["stash", "list", "--format=%H"])?;
+        let index = list
+            .lines()
+            .position(|line| line.trim() == stash_sha)
+            .ok_or_else(|| {
+                GitCliError::CommandFailed(format!("stash {stash_sha} not found in stash list"))
+            })?;
+        let stash_ref = format!("stash@{{{index}}}");
+        self.git(worktree_path, ["stash", "pop", &stash_ref])
+            .map(|_| ())
+    }
+
     /// Checkout base branch, squash-merge from_branch, and commit with message. Returns new HEAD sha.
     pub fn merge_squash_commit(
         &self,
@@ -658,6 +813,15 @@ impl GitCli {
 
 // Private methods
 impl GitCli {
+    /// Sha of the newest stash entry, if any.
+    fn stash_top(&self, worktree_path: &Path) -> Result<Option<String>, GitCliError> {
+        match self.git(worktree_path, ["rev-parse", "--verify", "-q", "refs/stash"]) {
+            Ok(out) => Ok(Some(out.trim().to_string())),
+            Err(GitCliError::CommandFailed(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     fn classify_cli_error(&self, msg: String) -> GitCliError {
         let lower = msg.to_ascii_lowercase();
         if lower.contains("authentication failed")
@@ -886,3 +1050,67 @@ pub struct WorktreeStatus {
     pub untracked: usize,
     pub entries: Vec<StatusEntry>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(mode: PushMode<'_>) -> Vec<String> {
+        GitCli::push_args("https://example.com/repo.git", "feature", mode)
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn push_args_fast_forward_has_no_force() {
+        assert_eq!(
+            args(PushMode::FastForward),
+            [
+                "push",
+                "https://example.com/repo.git",
+                "refs/heads/feature:refs/heads/feature",
+            ]
+        );
+    }
+
+    #[test]
+    fn push_args_force_uses_plus_refspec() {
+        assert_eq!(
+            args(PushMode::Force),
+            [
+                "push",
+                "https://example.com/repo.git",
+                "+refs/heads/feature:refs/heads/feature",
+            ]
+        );
+    }
+
+    #[test]
+    fn push_args_force_with_lease_pins_expected_oid() {
+        let oid = "0123456789abcdef0123456789abcdef01234567";
+        let args = args(PushMode::ForceWithLease {
+            expected_oid: Some(oid),
+        });
+        assert_eq!(
+            args,
+            [
+                "push".to_string(),
+                format!("--force-with-lease=refs/heads/feature:{oid}"),
+                "https://example.com/repo.git".to_string(),
+                "refs/heads/feature:refs/heads/feature".to_string(),
+            ]
+        );
+        assert!(
+            !args
+                .iter()
+                .any(|arg| arg == "--force" || arg.starts_with('+'))
+        );
+    }
+
+    #[test]
+    fn push_args_force_with_lease_without_tracking_ref_expects_absent_branch() {
+        let args = args(PushMode::ForceWithLease { expected_oid: None });
+        assert_eq!(args[1], "--force-with-lease=refs/heads/feature:");
+    }
+}
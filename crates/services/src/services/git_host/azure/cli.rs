@@ -15,7 +15,9 @@ use serde::Deserialize;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::git_host::types::{CreatePrRequest, UnifiedPrComment};
+use crate::services::git_host::types::{
+    ChecksStatus, CreatePrRequest, PrChecks, ReviewDecision, UnifiedPrComment,
+};
 
 #[derive(Debug, Clone)]
 pub struct AzureRepoInfo {
@@ -48,6 +50,31 @@ struct AzCommit {
     commit_id: Option<String>,
 }
 
+/// Subset of `az repos pr show` needed for review and merge state
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzPrMergeState {
+    merge_status: Option<String>,
+    #[serde(default)]
+    reviewers: Vec<AzReviewer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzReviewer {
+    /// 10 approved, 5 approved with suggestions, 0 no vote, -5 waiting for author, -10 rejected
+    #[serde(default)]
+    vote: i32,
+    #[serde(default)]
+    is_required: bool,
+}
+
+/// Item from `az repos pr policy list`
+#[derive(Deserialize)]
+struct AzPolicyEvaluation {
+    status: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct AzThreadsResponse {
     value: Vec<AzThread>,
@@ -65,6 +92,8 @@ struct AzThread {
 struct AzThreadContext {
     file_path: Option<String>,
     right_file_start: Option<AzFilePosition>,
+    /// Set instead of `right_file_start` for comments on removed lines
+    left_file_start: Option<AzFilePosition>,
 }
 
 #[derive(Deserialize)]
@@ -304,6 +333,44 @@ impl AzCli {
             args.push(OsString::from("--draft"));
         }
 
+        if !request.labels.is_empty() {
+            args.push(OsString::from("--labels"));
+            args.extend(request.labels.iter().map(OsString::from));
+        }
+
+        let raw = self.run(args, None)?;
+        Self::parse_pr_response(&raw)
+    }
+
+    /// Run `az repos pr update` to change the title and/or description of a pull request.
+    pub fn update_pr(
+        &self,
+        organization_url: &str,
+        pr_id: i64,
+        title: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<PullRequestInfo, AzCliError> {
+        let mut args: Vec<OsString> = Vec::with_capacity(13);
+        args.push(OsString::from("repos"));
+        args.push(OsString::from("pr"));
+        args.push(OsString::from("update"));
+        args.push(OsString::from("--id"));
+        args.push(OsString::from(pr_id.to_string()));
+        args.push(OsString::from("--organization"));
+        args.push(OsString::from(organization_url));
+
+        if let Some(title) = title {
+            args.push(OsString::from("--title"));
+            args.push(OsString::from(title));
+        }
+        if let Some(description) = description {
+            args.push(OsString::from("--description"));
+            args.push(OsString::from(description));
+        }
+
+        args.push(OsString::from("--output"));
+        args.push(OsString::from("json"));
+
         let raw = self.run(args, None)?;
         Self::parse_pr_response(&raw)
     }
@@ -333,6 +400,46 @@ impl AzCli {
         Self::parse_pr_response(&raw)
     }
 
+    /// Fetch policy (build/check) results, reviewer votes and merge status for a PR.
+    pub fn get_pr_checks(
+        &self,
+        organization_url: &str,
+        pr_id: i64,
+    ) -> Result<PrChecks, AzCliError> {
+        let pr_id = pr_id.to_string();
+        let pr_raw = self.run(
+            [
+                "repos",
+                "pr",
+                "show",
+                "--id",
+                &pr_id,
+                "--organization",
+                organization_url,
+                "--output",
+                "json",
+            ],
+            None,
+        )?;
+        let policies_raw = self.run(
+            [
+                "repos",
+                "pr",
+                "policy",
+                "list",
+                "--id",
+                &pr_id,
+                "--organization",
+                organization_url,
+                "--output",
+                "json",
+            ],
+            None,
+        )?;
+
+        Self::parse_pr_checks(&pr_raw, &policies_raw)
+    }
+
     pub fn list_prs_for_branch(
         &self,
         organization_url: &str,
@@ -474,6 +581,49 @@ impl AzCli {
         }
     }
 
+    fn parse_pr_checks(pr_raw: &str, policies_raw: &str) -> Result<PrChecks, AzCliError> {
+        let pr: AzPrMergeState = serde_json::from_str(pr_raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!("Failed to parse PR response: {e}; raw: {pr_raw}"))
+        })?;
+        let policies: Vec<AzPolicyEvaluation> =
+            serde_json::from_str(policies_raw.trim()).map_err(|e| {
+                AzCliError::UnexpectedOutput(format!(
+                    "Failed to parse PR policy list: {e}; raw: {policies_raw}"
+                ))
+            })?;
+
+        let checks_status = ChecksStatus::rollup(policies.iter().map(|policy| {
+            match policy.status.as_deref().unwrap_or_default() {
+                "approved" => ChecksStatus::Passing,
+                "rejected" | "broken" => ChecksStatus::Failing,
+                "queued" | "running" => ChecksStatus::Pending,
+                _ => ChecksStatus::None,
+            }
+        }));
+
+        let review_decision = if pr.reviewers.iter().any(|r| r.vote < 0) {
+            Some(ReviewDecision::ChangesRequested)
+        } else if pr.reviewers.iter().any(|r| r.is_required && r.vote == 0) {
+            Some(ReviewDecision::ReviewRequired)
+        } else if pr.reviewers.iter().any(|r| r.vote > 0) {
+            Some(ReviewDecision::Approved)
+        } else {
+            None
+        };
+
+        let mergeable = match pr.merge_status.as_deref() {
+            Some("succeeded") => Some(true),
+            Some("conflicts" | "failure" | "rejectedByPolicy") => Some(false),
+            _ => None,
+        };
+
+        Ok(PrChecks {
+            checks_status,
+            review_decision,
+            mergeable,
+        })
+    }
+
     fn parse_pr_threads(raw: &str) -> Result<Vec<UnifiedPrComment>, AzCliError> {
         // REST API returns { "value": [...threads...] } wrapper
         let response: AzThreadsResponse = serde_json::from_str(raw.trim()).map_err(|e| {
@@ -484,15 +634,22 @@ impl AzCli {
         let mut comments = Vec::new();
 
         for thread in threads {
-            let file_path = thread
-                .thread_context
-                .as_ref()
-                .and_then(|c| c.file_path.clone());
-            let line = thread
-                .thread_context
-                .as_ref()
-                .and_then(|c| c.right_file_start.as_ref())
-                .and_then(|p| p.line);
+            let context = thread.thread_context.as_ref();
+            // Azure paths are rooted ("/src/main.rs"); other providers report repo-relative paths
+            let file_path = context
+                .and_then(|c| c.file_path.as_deref())
+                .map(|p| p.trim_start_matches('/').to_string());
+            let (line, side) = match context {
+                Some(AzThreadContext {
+                    right_file_start: Some(pos),
+                    ..
+                }) => (pos.line, Some("RIGHT".to_string())),
+                Some(AzThreadContext {
+                    left_file_start: Some(pos),
+                    ..
+                }) => (pos.line, Some("LEFT".to_string())),
+                _ => (None, None),
+            };
 
             if let Some(thread_comments) = thread.comments {
                 for c in thread_comments {
@@ -523,7 +680,7 @@ impl AzCli {
                             url: None,
                             path: path.clone(),
                             line,
-                            side: None,
+                            side: side.clone(),
                             diff_hunk: None,
                         });
                     } else {
@@ -559,6 +716,55 @@ impl AzCli {
 mod tests {
     use super::*;
 
+    const PR_THREADS_FIXTURE: &str = include_str!("fixtures/pr_threads.json");
+
+    #[test]
+    fn test_parse_pr_threads() {
+        let comments = AzCli::parse_pr_threads(PR_THREADS_FIXTURE).unwrap();
+        assert_eq!(comments.len(), 3);
+
+        match &comments[0] {
+            UnifiedPrComment::General {
+                author,
+                body,
+                created_at,
+                ..
+            } => {
+                assert_eq!(author, "Alice Smith");
+                assert_eq!(body, "Looks good overall");
+                assert_eq!(created_at.to_rfc3339(), "2024-03-01T09:00:00+00:00");
+            }
+            other => panic!("expected general comment, got {other:?}"),
+        }
+
+        match &comments[1] {
+            UnifiedPrComment::Review {
+                author,
+                path,
+                line,
+                side,
+                ..
+            } => {
+                assert_eq!(author, "Bob Jones");
+                assert_eq!(path, "src/main.rs");
+                assert_eq!(*line, Some(42));
+                assert_eq!(side.as_deref(), Some("RIGHT"));
+            }
+            other => panic!("expected review comment, got {other:?}"),
+        }
+
+        match &comments[2] {
+            UnifiedPrComment::Review {
+                path, line, side, ..
+            } => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(7));
+                assert_eq!(side.as_deref(), Some("LEFT"));
+            }
+            other => panic!("expected review comment, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_parse_pr_url() {
         // dev.azure.com format
@@ -674,4 +880,19 @@ mod tests {
     fn test_extract_organization_url_invalid() {
         assert!(AzCli::extract_organization_url("https://github.com/owner/repo").is_none());
     }
+
+    #[test]
+    fn test_parse_pr_checks() {
+        let pr = r#"{
+            "mergeStatus": "succeeded",
+            "reviewers": [{"vote": 10, "isRequired": true}, {"vote": 0}]
+        }"#;
+        let policies =
+            r#"[{"status": "approved"}, {"status": "running"}, {"status": "notApplicable"}]"#;
+
+        let checks = AzCli::parse_pr_checks(pr, policies).unwrap();
+        assert_eq!(checks.checks_status, ChecksStatus::Pending);
+        assert_eq!(checks.review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(checks.mergeable, Some(true));
+    }
 }
@@ -14,7 +14,10 @@ use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, ProviderKind, UnifiedPrComment},
+    types::{
+        CreatePrRequest, GitHostError, PrChecks, ProviderCapabilities, ProviderKind,
+        UnifiedPrComment,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -246,7 +249,90 @@ impl GitHostProvider for AzureDevOpsProvider {
         .await
     }
 
+    async fn get_pr_checks(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<PrChecks, GitHostError> {
+        let repo_info = self.get_repo_info(repo_path, remote_url).await?;
+
+        (|| async {
+            let cli = self.az_cli.clone();
+            let organization_url = repo_info.organization_url.clone();
+
+            let checks =
+                task::spawn_blocking(move || cli.get_pr_checks(&organization_url, pr_number))
+                    .await
+                    .map_err(|err| {
+                        GitHostError::PullRequest(format!(
+                            "Failed to execute Azure CLI for fetching PR checks: {err}"
+                        ))
+                    })?;
+            checks.map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "Azure DevOps API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn update_pr(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(repo_path, remote_url).await?;
+        let cli = self.az_cli.clone();
+        let title = title.map(str::to_string);
+        let body = body.map(str::to_string);
+
+        task::spawn_blocking(move || {
+            cli.update_pr(
+                &repo_info.organization_url,
+                pr_number,
+                title.as_deref(),
+                body.as_deref(),
+            )
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute Azure CLI for updating PR: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)?;
+
+        info!("Updated Azure DevOps PR #{}", pr_number);
+        Ok(())
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::AzureDevOps
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_draft: true,
+            supports_reviewers: false,
+            supports_labels: true,
+            supports_comment_resolve: false,
+            supports_merge_methods: false,
+        }
+    }
 }
@@ -0,0 +1,693 @@
+//! Minimal client for the Bitbucket Cloud REST API (v2.0).
+//!
+//! Bitbucket has no CLI comparable to `gh`/`az`, so pull request operations go
+//! straight to the HTTP API using an access token from the environment.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use reqwest::{Client, Method, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+use crate::services::git_host::types::{
+    ChecksStatus, CreatePrRequest, PrChecks, ReviewDecision, UnifiedPrComment,
+};
+
+const API_BASE_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Bitbucket access token (repository, project or workspace access token, or an API token
+/// when combined with [`USERNAME_ENV_VAR`]).
+pub const TOKEN_ENV_VAR: &str = "BITBUCKET_TOKEN";
+/// Optional username; when set the token is sent with basic auth instead of as a bearer token.
+pub const USERNAME_ENV_VAR: &str = "BITBUCKET_USERNAME";
+
+/// Safety cap on paginated listings
+const MAX_PAGES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitbucketRepoInfo {
+    pub workspace: String,
+    pub repo_slug: String,
+}
+
+#[derive(Debug, Error)]
+pub enum BitbucketApiError {
+    #[error("Bitbucket authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("Bitbucket request forbidden: {0}")]
+    Forbidden(String),
+    #[error("Bitbucket resource not found: {0}")]
+    NotFound(String),
+    #[error("Bitbucket request failed: {0}")]
+    RequestFailed(String),
+    #[error("Bitbucket returned unexpected output: {0}")]
+    UnexpectedOutput(String),
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    #[serde(default)]
+    values: Vec<T>,
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BbPullRequest {
+    id: i64,
+    state: String,
+    updated_on: Option<DateTime<Utc>>,
+    merge_commit: Option<BbCommit>,
+    links: BbLinks,
+    #[serde(default)]
+    participants: Vec<BbParticipant>,
+}
+
+#[derive(Deserialize)]
+struct BbCommit {
+    hash: String,
+}
+
+#[derive(Deserialize)]
+struct BbLinks {
+    html: Option<BbLink>,
+}
+
+#[derive(Deserialize)]
+struct BbLink {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct BbParticipant {
+    #[serde(default)]
+    approved: bool,
+    /// `approved`, `changes_requested` or null
+    state: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BbComment {
+    id: i64,
+    content: BbContent,
+    user: Option<BbUser>,
+    created_on: DateTime<Utc>,
+    links: Option<BbLinks>,
+    inline: Option<BbInline>,
+    #[serde(default)]
+    deleted: bool,
+}
+
+#[derive(Deserialize)]
+struct BbContent {
+    #[serde(default)]
+    raw: String,
+}
+
+#[derive(Deserialize)]
+struct BbUser {
+    display_name: Option<String>,
+    nickname: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BbInline {
+    path: String,
+    /// Line in the new file; null for comments on removed lines
+    to: Option<i64>,
+    /// Line in the old file
+    from: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct BbCommitStatus {
+    /// `SUCCESSFUL`, `FAILED`, `INPROGRESS` or `STOPPED`
+    state: String,
+}
+
+#[derive(Serialize)]
+struct BbCreatePr<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    source: BbEndpoint<'a>,
+    destination: BbEndpoint<'a>,
+    close_source_branch: bool,
+}
+
+#[derive(Serialize)]
+struct BbEndpoint<'a> {
+    branch: BbBranch<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repository: Option<BbRepositoryRef>,
+}
+
+#[derive(Serialize)]
+struct BbBranch<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct BbRepositoryRef {
+    full_name: String,
+}
+
+#[derive(Serialize)]
+struct BbUpdatePr<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+#[derive(Clone)]
+enum Credentials {
+    Bearer(String),
+    Basic { username: String, token: String },
+}
+
+// Keep tokens out of debug logs
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Credentials::Bearer(_) => f.write_str("Bearer(..)"),
+            Credentials::Basic { username, .. } => write!(f, "Basic({username}, ..)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitbucketApi {
+    client: Client,
+    credentials: Credentials,
+    base_url: String,
+}
+
+impl BitbucketApi {
+    /// Build a client from [`TOKEN_ENV_VAR`]. Returns `None` when the token is not set.
+    pub fn from_env() -> Option<Self> {
+        let token = std::env::var(TOKEN_ENV_VAR)
+            .ok()
+            .filter(|t| !t.trim().is_empty())?;
+        let credentials = match std::env::var(USERNAME_ENV_VAR) {
+            Ok(username) if !username.trim().is_empty() => Credentials::Basic { username, token },
+            _ => Credentials::Bearer(token),
+        };
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .ok()?;
+        Some(Self {
+            client,
+            credentials,
+            base_url: API_BASE_URL.to_string(),
+        })
+    }
+
+    /// Parse `workspace/repo_slug` from a Bitbucket remote URL.
+    ///
+    /// Supports `https://[user@]bitbucket.org/ws/repo(.git)`, `git@bitbucket.org:ws/repo.git`
+    /// and `ssh://git@bitbucket.org/ws/repo.git`.
+    pub fn parse_repo_url(url: &str) -> Option<BitbucketRepoInfo> {
+        let url = url.trim();
+        let lower = url.to_ascii_lowercase();
+        let host_idx = lower.find("bitbucket.org")?;
+        let rest = &url[host_idx + "bitbucket.org".len()..];
+        let rest = rest.strip_prefix(':').or_else(|| rest.strip_prefix('/'))?;
+        let mut parts = rest.split('/').filter(|p| !p.is_empty());
+        let workspace = parts.next()?;
+        let repo_slug = parts.next()?;
+        let repo_slug = repo_slug.strip_suffix(".git").unwrap_or(repo_slug);
+        if workspace.is_empty() || repo_slug.is_empty() {
+            return None;
+        }
+        Some(BitbucketRepoInfo {
+            workspace: workspace.to_string(),
+            repo_slug: repo_slug.to_string(),
+        })
+    }
+
+    /// Parse repo and PR id from `https://bitbucket.org/ws/repo/pull-requests/123[/...]`.
+    pub fn parse_pr_url(url: &str) -> Option<(BitbucketRepoInfo, i64)> {
+        let repo = Self::parse_repo_url(url)?;
+        let (_, after) = url.split_once("/pull-requests/")?;
+        let id = after.split(['/', '?', '#']).next()?.parse().ok()?;
+        Some((repo, id))
+    }
+
+    pub async fn create_pr(
+        &self,
+        repo: &BitbucketRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, BitbucketApiError> {
+        let source_repository = match &request.head_repo_url {
+            Some(head_url) => {
+                let head = Self::parse_repo_url(head_url).ok_or_else(|| {
+                    BitbucketApiError::RequestFailed(format!(
+                        "Head repository is not a Bitbucket repository: {head_url}"
+                    ))
+                })?;
+                (head != *repo).then(|| BbRepositoryRef {
+                    full_name: format!("{}/{}", head.workspace, head.repo_slug),
+                })
+            }
+            None => None,
+        };
+        let body = BbCreatePr {
+            title: &request.title,
+            description: request.body.as_deref(),
+            source: BbEndpoint {
+                branch: BbBranch {
+                    name: &request.head_branch,
+                },
+                repository: source_repository,
+            },
+            destination: BbEndpoint {
+                branch: BbBranch {
+                    name: &request.base_branch,
+                },
+                repository: None,
+            },
+            close_source_branch: false,
+        };
+        let pr: BbPullRequest = self
+            .send(Method::POST, &Self::pr_path(repo, None), Some(&body))
+            .await?;
+        Self::to_pull_request_info(pr)
+    }
+
+    pub async fn get_pr(
+        &self,
+        repo: &BitbucketRepoInfo,
+        pr_id: i64,
+    ) -> Result<PullRequestInfo, BitbucketApiError> {
+        let pr = self.fetch_pr(repo, pr_id).await?;
+        Self::to_pull_request_info(pr)
+    }
+
+    pub async fn list_prs_for_branch(
+        &self,
+        repo: &BitbucketRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, BitbucketApiError> {
+        // Without explicit states the API only returns open PRs
+        let query = format!("source.branch.name=\"{}\"", branch.replace('"', "\\\""));
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}{}", self.base_url, Self::pr_path(repo, None)),
+            [
+                ("q", query.as_str()),
+                ("state", "OPEN"),
+                ("state", "MERGED"),
+                ("state", "DECLINED"),
+                ("state", "SUPERSEDED"),
+            ],
+        )
+        .map_err(|e| BitbucketApiError::RequestFailed(e.to_string()))?;
+        let prs: Vec<BbPullRequest> = self.fetch_all(url.to_string()).await?;
+        prs.into_iter().map(Self::to_pull_request_info).collect()
+    }
+
+    pub async fn get_pr_comments(
+        &self,
+        repo: &BitbucketRepoInfo,
+        pr_id: i64,
+    ) -> Result<Vec<UnifiedPrComment>, BitbucketApiError> {
+        let url = format!(
+            "{}{}/comments?pagelen=100",
+            self.base_url,
+            Self::pr_path(repo, Some(pr_id))
+        );
+        let comments: Vec<BbComment> = self.fetch_all(url).await?;
+        let mut unified: Vec<UnifiedPrComment> = comments
+            .into_iter()
+            .filter_map(Self::to_unified_comment)
+            .collect();
+        unified.sort_by_key(|c| c.created_at());
+        Ok(unified)
+    }
+
+    pub async fn get_pr_checks(
+        &self,
+        repo: &BitbucketRepoInfo,
+        pr_id: i64,
+    ) -> Result<PrChecks, BitbucketApiError> {
+        let pr = self.fetch_pr(repo, pr_id).await?;
+        let url = format!(
+            "{}{}/statuses?pagelen=100",
+            self.base_url,
+            Self::pr_path(repo, Some(pr_id))
+        );
+        let statuses: Vec<BbCommitStatus> = self.fetch_all(url).await?;
+        Ok(Self::to_pr_checks(&pr, &statuses))
+    }
+
+    pub async fn update_pr(
+        &self,
+        repo: &BitbucketRepoInfo,
+        pr_id: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), BitbucketApiError> {
+        let update = BbUpdatePr {
+            title,
+            description: body,
+        };
+        let _: serde_json::Value = self
+            .send(
+                Method::PUT,
+                &Self::pr_path(repo, Some(pr_id)),
+                Some(&update),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn fetch_pr(
+        &self,
+        repo: &BitbucketRepoInfo,
+        pr_id: i64,
+    ) -> Result<BbPullRequest, BitbucketApiError> {
+        self.send::<(), _>(Method::GET, &Self::pr_path(repo, Some(pr_id)), None)
+            .await
+    }
+
+    fn pr_path(repo: &BitbucketRepoInfo, pr_id: Option<i64>) -> String {
+        let base = format!(
+            "/repositories/{}/{}/pullrequests",
+            repo.workspace, repo.repo_slug
+        );
+        match pr_id {
+            Some(id) => format!("{base}/{id}"),
+            None => base,
+        }
+    }
+
+    async fn send<B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<T, BitbucketApiError> {
+        let mut req = self
+            .authorize(
+                self.client
+                    .request(method, format!("{}{}", self.base_url, path)),
+            )
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(body) = body {
+            req = req.json(body);
+        }
+        Self::read_json(req.send().await).await
+    }
+
+    async fn fetch_all<T: DeserializeOwned>(
+        &self,
+        first_url: String,
+    ) -> Result<Vec<T>, BitbucketApiError> {
+        let mut items = Vec::new();
+        let mut next = Some(first_url);
+        for _ in 0..MAX_PAGES {
+            let Some(url) = next.take() else {
+                break;
+            };
+            let req = self
+                .authorize(self.client.get(url))
+                .header(reqwest::header::ACCEPT, "application/json");
+            let page: Page<T> = Self::read_json(req.send().await).await?;
+            items.extend(page.values);
+            next = page.next;
+        }
+        Ok(items)
+    }
+
+    fn authorize(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.credentials {
+            Credentials::Bearer(token) => req.bearer_auth(token),
+            Credentials::Basic { username, token } => req.basic_auth(username, Some(token)),
+        }
+    }
+
+    async fn read_json<T: DeserializeOwned>(
+        response: Result<reqwest::Response, reqwest::Error>,
+    ) -> Result<T, BitbucketApiError> {
+        let response = response.map_err(|e| BitbucketApiError::RequestFailed(e.to_string()))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| BitbucketApiError::RequestFailed(e.to_string()))?;
+        if !status.is_success() {
+            return Err(Self::classify_status(status, text));
+        }
+        serde_json::from_str(&text).map_err(|e| {
+            BitbucketApiError::UnexpectedOutput(format!("Failed to parse response: {e}"))
+        })
+    }
+
+    fn classify_status(status: StatusCode, body: String) -> BitbucketApiError {
+        let msg = format!("{status}: {}", body.trim());
+        match status {
+            StatusCode::UNAUTHORIZED => BitbucketApiError::AuthFailed(msg),
+            StatusCode::FORBIDDEN => BitbucketApiError::Forbidden(msg),
+            StatusCode::NOT_FOUND => BitbucketApiError::NotFound(msg),
+            _ => BitbucketApiError::RequestFailed(msg),
+        }
+    }
+
+    fn map_state(state: &str) -> MergeStatus {
+        match state.to_ascii_uppercase().as_str() {
+            "OPEN" => MergeStatus::Open,
+            "MERGED" => MergeStatus::Merged,
+            "DECLINED" | "SUPERSEDED" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        }
+    }
+
+    fn to_pull_request_info(pr: BbPullRequest) -> Result<PullRequestInfo, BitbucketApiError> {
+        let url = pr.links.html.map(|l| l.href).ok_or_else(|| {
+            BitbucketApiError::UnexpectedOutput(format!("PR #{} has no html link", pr.id))
+        })?;
+        let status = Self::map_state(&pr.state);
+        let merged_at = matches!(status, MergeStatus::Merged)
+            .then_some(pr.updated_on)
+            .flatten();
+        Ok(PullRequestInfo {
+            number: pr.id,
+            url,
+            status,
+            merged_at,
+            merge_commit_sha: pr.merge_commit.map(|c| c.hash),
+        })
+    }
+
+    fn to_unified_comment(comment: BbComment) -> Option<UnifiedPrComment> {
+        if comment.deleted {
+            return None;
+        }
+        let author = comment
+            .user
+            .and_then(|u| u.display_name.or(u.nickname))
+            .unwrap_or_else(|| "unknown".to_string());
+        let url = comment.links.and_then(|l| l.html).map(|l| l.href);
+        Some(match comment.inline {
+            Some(inline) => UnifiedPrComment::Review {
+                id: comment.id,
+                author,
+                author_association: None,
+                body: comment.content.raw,
+                created_at: comment.created_on,
+                url,
+                path: inline.path,
+                line: inline.to.or(inline.from),
+                side: Some(if inline.to.is_some() { "RIGHT" } else { "LEFT" }.to_string()),
+                diff_hunk: None,
+            },
+            None => UnifiedPrComment::General {
+                id: comment.id.to_string(),
+                author,
+                author_association: None,
+                body: comment.content.raw,
+                created_at: comment.created_on,
+                url,
+            },
+        })
+    }
+
+    fn to_pr_checks(pr: &BbPullRequest, statuses: &[BbCommitStatus]) -> PrChecks {
+        let checks_status =
+            ChecksStatus::rollup(statuses.iter().map(
+                |s| match s.state.to_ascii_uppercase().as_str() {
+                    "SUCCESSFUL" => ChecksStatus::Passing,
+                    "FAILED" | "STOPPED" => ChecksStatus::Failing,
+                    "INPROGRESS" => ChecksStatus::Pending,
+                    _ => ChecksStatus::None,
+                },
+            ));
+        let review_decision = if pr
+            .participants
+            .iter()
+            .any(|p| p.state.as_deref() == Some("changes_requested"))
+        {
+            Some(ReviewDecision::ChangesRequested)
+        } else if pr.participants.iter().any(|p| p.approved) {
+            Some(ReviewDecision::Approved)
+        } else {
+            None
+        };
+        PrChecks {
+            checks_status,
+            review_decision,
+            // Bitbucket does not expose a precomputed mergeability flag
+            mergeable: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PULL_REQUEST_FIXTURE: &str = include_str!("fixtures/pull_request.json");
+    const PULL_REQUESTS_PAGE_FIXTURE: &str = include_str!("fixtures/pull_requests_page.json");
+    const COMMENTS_PAGE_FIXTURE: &str = include_str!("fixtures/comments_page.json");
+    const STATUSES_PAGE_FIXTURE: &str = include_str!("fixtures/statuses_page.json");
+
+    #[test]
+    fn test_parse_repo_url() {
+        let expected = BitbucketRepoInfo {
+            workspace: "acme".to_string(),
+            repo_slug: "widgets".to_string(),
+        };
+        for url in [
+            "https://bitbucket.org/acme/widgets",
+            "https://bitbucket.org/acme/widgets.git",
+            "https://jdoe@bitbucket.org/acme/widgets.git",
+            "git@bitbucket.org:acme/widgets.git",
+            "ssh://git@bitbucket.org/acme/widgets.git",
+        ] {
+            assert_eq!(
+                BitbucketApi::parse_repo_url(url).as_ref(),
+                Some(&expected),
+                "{url}"
+            );
+        }
+        assert!(BitbucketApi::parse_repo_url("https://github.com/acme/widgets").is_none());
+        assert!(BitbucketApi::parse_repo_url("https://bitbucket.org/acme").is_none());
+    }
+
+    #[test]
+    fn test_parse_pr_url() {
+        let (repo, id) =
+            BitbucketApi::parse_pr_url("https://bitbucket.org/acme/widgets/pull-requests/42")
+                .unwrap();
+        assert_eq!(repo.workspace, "acme");
+        assert_eq!(repo.repo_slug, "widgets");
+        assert_eq!(id, 42);
+
+        let (_, id) = BitbucketApi::parse_pr_url(
+            "https://bitbucket.org/acme/widgets/pull-requests/7/diff#comment-1",
+        )
+        .unwrap();
+        assert_eq!(id, 7);
+
+        assert!(BitbucketApi::parse_pr_url("https://bitbucket.org/acme/widgets").is_none());
+    }
+
+    #[test]
+    fn test_pull_request_fixture() {
+        let pr: BbPullRequest = serde_json::from_str(PULL_REQUEST_FIXTURE).unwrap();
+        let info = BitbucketApi::to_pull_request_info(pr).unwrap();
+        assert_eq!(info.number, 42);
+        assert_eq!(
+            info.url,
+            "https://bitbucket.org/acme/widgets/pull-requests/42"
+        );
+        assert!(matches!(info.status, MergeStatus::Merged));
+        assert!(info.merged_at.is_some());
+        assert_eq!(info.merge_commit_sha.as_deref(), Some("9f8e7d6c5b4a"));
+    }
+
+    #[test]
+    fn test_pull_requests_page_fixture() {
+        let page: Page<BbPullRequest> = serde_json::from_str(PULL_REQUESTS_PAGE_FIXTURE).unwrap();
+        assert!(page.next.is_some());
+        let infos: Vec<PullRequestInfo> = page
+            .values
+            .into_iter()
+            .map(|pr| BitbucketApi::to_pull_request_info(pr).unwrap())
+            .collect();
+        assert_eq!(infos.len(), 2);
+        assert!(matches!(infos[0].status, MergeStatus::Open));
+        assert!(infos[0].merged_at.is_none());
+        assert!(matches!(infos[1].status, MergeStatus::Closed));
+    }
+
+    #[test]
+    fn test_comments_page_fixture() {
+        let page: Page<BbComment> = serde_json::from_str(COMMENTS_PAGE_FIXTURE).unwrap();
+        let comments: Vec<UnifiedPrComment> = page
+            .values
+            .into_iter()
+            .filter_map(BitbucketApi::to_unified_comment)
+            .collect();
+        // The deleted comment is dropped
+        assert_eq!(comments.len(), 2);
+        match &comments[0] {
+            UnifiedPrComment::General {
+                id, author, body, ..
+            } => {
+                assert_eq!(id, "1001");
+                assert_eq!(author, "Jane Doe");
+                assert_eq!(body, "Looks good overall");
+            }
+            other => panic!("expected general comment, got {other:?}"),
+        }
+        match &comments[1] {
+            UnifiedPrComment::Review {
+                id,
+                path,
+                line,
+                side,
+                ..
+            } => {
+                assert_eq!(*id, 1002);
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(*line, Some(12));
+                assert_eq!(side.as_deref(), Some("RIGHT"));
+            }
+            other => panic!("expected review comment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_checks_from_fixtures() {
+        let pr: BbPullRequest = serde_json::from_str(PULL_REQUEST_FIXTURE).unwrap();
+        let statuses: Page<BbCommitStatus> = serde_json::from_str(STATUSES_PAGE_FIXTURE).unwrap();
+        let checks = BitbucketApi::to_pr_checks(&pr, &statuses.values);
+        assert_eq!(checks.checks_status, ChecksStatus::Failing);
+        assert_eq!(checks.review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(checks.mergeable, None);
+    }
+
+    #[test]
+    fn test_map_state() {
+        assert!(matches!(BitbucketApi::map_state("OPEN"), MergeStatus::Open));
+        assert!(matches!(
+            BitbucketApi::map_state("MERGED"),
+            MergeStatus::Merged
+        ));
+        assert!(matches!(
+            BitbucketApi::map_state("DECLINED"),
+            MergeStatus::Closed
+        ));
+        assert!(matches!(
+            BitbucketApi::map_state("SUPERSEDED"),
+            MergeStatus::Closed
+        ));
+        assert!(matches!(
+            BitbucketApi::map_state("DRAFT"),
+            MergeStatus::Unknown
+        ));
+    }
+}
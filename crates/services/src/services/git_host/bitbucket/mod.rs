@@ -0,0 +1,162 @@
+//! Bitbucket Cloud hosting service implementation.
+
+mod api;
+
+use std::{future::Future, path::Path, time::Duration};
+
+pub use api::BitbucketApi;
+use api::{BitbucketApiError, BitbucketRepoInfo};
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use db::models::merge::PullRequestInfo;
+use tracing::info;
+
+use super::{
+    GitHostProvider,
+    types::{
+        CreatePrRequest, GitHostError, PrChecks, ProviderCapabilities, ProviderKind,
+        UnifiedPrComment,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct BitbucketProvider {
+    api: BitbucketApi,
+}
+
+impl BitbucketProvider {
+    pub fn new() -> Result<Self, GitHostError> {
+        let api = BitbucketApi::from_env().ok_or_else(|| GitHostError::TokenNotConfigured {
+            provider: ProviderKind::Bitbucket,
+            env_var: api::TOKEN_ENV_VAR.to_string(),
+        })?;
+        Ok(Self { api })
+    }
+
+    fn repo_info(remote_url: &str) -> Result<BitbucketRepoInfo, GitHostError> {
+        BitbucketApi::parse_repo_url(remote_url).ok_or_else(|| {
+            GitHostError::Repository(format!("Not a Bitbucket repository URL: {remote_url}"))
+        })
+    }
+
+    async fn with_retry<T, F, Fut>(operation: F) -> Result<T, GitHostError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, GitHostError>>,
+    {
+        operation
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &GitHostError| e.should_retry())
+            .notify(|err: &GitHostError, dur: Duration| {
+                tracing::warn!(
+                    "Bitbucket API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+}
+
+impl From<BitbucketApiError> for GitHostError {
+    fn from(error: BitbucketApiError) -> Self {
+        match error {
+            BitbucketApiError::AuthFailed(msg) => GitHostError::AuthFailed(msg),
+            BitbucketApiError::Forbidden(msg) => GitHostError::InsufficientPermissions(msg),
+            BitbucketApiError::NotFound(msg) => GitHostError::RepoNotFoundOrNoAccess(msg),
+            BitbucketApiError::RequestFailed(msg) => GitHostError::PullRequest(msg),
+            BitbucketApiError::UnexpectedOutput(msg) => GitHostError::UnexpectedOutput(msg),
+        }
+    }
+}
+
+#[async_trait]
+impl GitHostProvider for BitbucketProvider {
+    async fn create_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitHostError> {
+        let repo = Self::repo_info(remote_url)?;
+        // Not retried: a timed-out request may still have created the PR
+        let pr = self.api.create_pr(&repo, request).await?;
+        info!(
+            "Created Bitbucket PR #{} for branch {}",
+            pr.number, request.head_branch
+        );
+        Ok(pr)
+    }
+
+    async fn get_pr_status(&self, pr_url: &str) -> Result<PullRequestInfo, GitHostError> {
+        let (repo, pr_id) = BitbucketApi::parse_pr_url(pr_url).ok_or_else(|| {
+            GitHostError::PullRequest(format!("Not a Bitbucket pull request URL: {pr_url}"))
+        })?;
+        Self::with_retry(|| async { Ok(self.api.get_pr(&repo, pr_id).await?) }).await
+    }
+
+    async fn list_prs_for_branch(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        branch_name: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitHostError> {
+        let repo = Self::repo_info(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.list_prs_for_branch(&repo, branch_name).await?) })
+            .await
+    }
+
+    async fn get_pr_comments(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<Vec<UnifiedPrComment>, GitHostError> {
+        let repo = Self::repo_info(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.get_pr_comments(&repo, pr_number).await?) }).await
+    }
+
+    async fn get_pr_checks(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<PrChecks, GitHostError> {
+        let repo = Self::repo_info(remote_url)?;
+        Self::with_retry(|| async { Ok(self.api.get_pr_checks(&repo, pr_number).await?) }).await
+    }
+
+    async fn update_pr(
+        &self,
+        _repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), GitHostError> {
+        let repo = Self::repo_info(remote_url)?;
+        self.api.update_pr(&repo, pr_number, title, body).await?;
+        info!("Updated Bitbucket PR #{}", pr_number);
+        Ok(())
+    }
+
+    fn provider_kind(&self) -> ProviderKind {
+        ProviderKind::Bitbucket
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_draft: false,
+            supports_reviewers: false,
+            supports_labels: false,
+            supports_comment_resolve: false,
+            supports_merge_methods: false,
+        }
+    }
+}
@@ -8,6 +8,7 @@ use super::types::ProviderKind;
 /// - GitHub.com: `https://github.com/owner/repo` or `git@github.com:owner/repo.git`
 /// - GitHub Enterprise: URLs containing `github.` (e.g., `https://github.company.com/owner/repo`)
 /// - Azure DevOps: `https://dev.azure.com/org/project/_git/repo` or legacy `https://org.visualstudio.com/...`
+/// - Bitbucket Cloud: `https://bitbucket.org/workspace/repo` or `git@bitbucket.org:workspace/repo.git`
 pub fn detect_provider_from_url(url: &str) -> ProviderKind {
     let url_lower = url.to_lowercase();
 
@@ -28,6 +29,10 @@ pub fn detect_provider_from_url(url: &str) -> ProviderKind {
         return ProviderKind::AzureDevOps;
     }
 
+    if url_lower.contains("bitbucket.org") {
+        return ProviderKind::Bitbucket;
+    }
+
     // GitHub Enterprise (contains "github." but not the Azure patterns above)
     if url_lower.contains("github.") {
         return ProviderKind::GitHub;
@@ -42,6 +47,7 @@ pub fn detect_provider_from_url(url: &str) -> ProviderKind {
 /// - GitHub: `https://github.com/owner/repo/pull/123`
 /// - GitHub Enterprise: `https://github.company.com/owner/repo/pull/123`
 /// - Azure DevOps: `https://dev.azure.com/org/project/_git/repo/pullrequest/123`
+/// - Bitbucket Cloud: `https://bitbucket.org/workspace/repo/pull-requests/123`
 #[cfg(test)]
 fn detect_provider_from_pr_url(pr_url: &str) -> ProviderKind {
     let url_lower = pr_url.to_lowercase();
@@ -59,6 +65,11 @@ fn detect_provider_from_pr_url(pr_url: &str) -> ProviderKind {
         return ProviderKind::AzureDevOps;
     }
 
+    // Bitbucket pattern: contains /pull-requests/ in the path
+    if url_lower.contains("/pull-requests/") && url_lower.contains("bitbucket.org") {
+        return ProviderKind::Bitbucket;
+    }
+
     // Fall back to general URL detection
     detect_provider_from_url(pr_url)
 }
@@ -142,12 +153,29 @@ mod tests {
             detect_provider_from_url("https://gitlab.com/owner/repo"),
             ProviderKind::Unknown
         );
+        // Self-hosted Bitbucket Data Center uses a different API
         assert_eq!(
-            detect_provider_from_url("https://bitbucket.org/owner/repo"),
+            detect_provider_from_url("https://bitbucket.example.com/scm/proj/repo.git"),
             ProviderKind::Unknown
         );
     }
 
+    #[test]
+    fn test_bitbucket_cloud() {
+        assert_eq!(
+            detect_provider_from_url("https://bitbucket.org/owner/repo"),
+            ProviderKind::Bitbucket
+        );
+        assert_eq!(
+            detect_provider_from_url("https://user@bitbucket.org/owner/repo.git"),
+            ProviderKind::Bitbucket
+        );
+        assert_eq!(
+            detect_provider_from_url("git@bitbucket.org:owner/repo.git"),
+            ProviderKind::Bitbucket
+        );
+    }
+
     #[test]
     fn test_pr_url_github() {
         assert_eq!(
@@ -175,4 +203,12 @@ mod tests {
             ProviderKind::AzureDevOps
         );
     }
+
+    #[test]
+    fn test_pr_url_bitbucket() {
+        assert_eq!(
+            detect_provider_from_pr_url("https://bitbucket.org/owner/repo/pull-requests/123"),
+            ProviderKind::Bitbucket
+        );
+    }
 }
@@ -18,7 +18,8 @@ use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking;
 
 use crate::services::git_host::types::{
-    CreatePrRequest, PrComment, PrCommentAuthor, PrReviewComment, ReviewCommentUser,
+    ChecksStatus, CreatePrRequest, PrChecks, PrComment, PrCommentAuthor, PrReviewComment,
+    ReviewCommentUser, ReviewDecision,
 };
 
 #[derive(Debug, Clone)]
@@ -97,6 +98,25 @@ struct GhPrResponse {
     merge_commit: Option<GhMergeCommit>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPrChecksResponse {
+    #[serde(default)]
+    status_check_rollup: Option<Vec<GhCheckRollupItem>>,
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    mergeable: Option<String>,
+}
+
+/// Either a `CheckRun` (status + conclusion) or a commit `StatusContext` (state).
+#[derive(Deserialize)]
+struct GhCheckRollupItem {
+    status: Option<String>,
+    conclusion: Option<String>,
+    state: Option<String>,
+}
+
 #[derive(Debug, Error)]
 pub enum GhCliError {
     #[error("GitHub CLI (`gh`) executable not found or not runnable")]
@@ -227,10 +247,54 @@ impl GhCli {
             args.push(OsString::from("--draft"));
         }
 
+        if !request.labels.is_empty() {
+            args.push(OsString::from("--label"));
+            args.push(OsString::from(request.labels.join(",")));
+        }
+
         let raw = self.run(args, Some(repo_path))?;
         Self::parse_pr_create_text(&raw)
     }
 
+    /// Run `gh pr edit` to change the title and/or body of a pull request.
+    pub fn edit_pr(
+        &self,
+        owner: &str,
+        repo_name: &str,
+        pr_number: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), GhCliError> {
+        let mut args: Vec<OsString> = Vec::with_capacity(9);
+        args.push(OsString::from("pr"));
+        args.push(OsString::from("edit"));
+        args.push(OsString::from(pr_number.to_string()));
+        args.push(OsString::from("--repo"));
+        args.push(OsString::from(format!("{}/{}", owner, repo_name)));
+
+        if let Some(title) = title {
+            args.push(OsString::from("--title"));
+            args.push(OsString::from(title));
+        }
+
+        // Keep the temp file alive until the command has run
+        let mut body_file = None;
+        if let Some(body) = body {
+            let mut file = NamedTempFile::new().map_err(|e| {
+                GhCliError::CommandFailed(format!("Failed to create temp file: {e}"))
+            })?;
+            file.write_all(body.as_bytes())
+                .map_err(|e| GhCliError::CommandFailed(format!("Failed to write body: {e}")))?;
+            args.push(OsString::from("--body-file"));
+            args.push(file.path().as_os_str().to_os_string());
+            body_file = Some(file);
+        }
+
+        self.run(args, None)?;
+        drop(body_file);
+        Ok(())
+    }
+
     /// Retrieve details for a pull request by URL.
     pub fn view_pr(&self, pr_url: &str) -> Result<PullRequestInfo, GhCliError> {
         let raw = self.run(
@@ -293,6 +357,28 @@ impl GhCli {
         Self::parse_pr_comments(&raw)
     }
 
+    /// Fetch CI status, review decision and mergeability for a pull request.
+    pub fn get_pr_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: i64,
+    ) -> Result<PrChecks, GhCliError> {
+        let raw = self.run(
+            [
+                "pr",
+                "view",
+                &pr_number.to_string(),
+                "--repo",
+                &format!("{owner}/{repo}"),
+                "--json",
+                "statusCheckRollup,reviewDecision,mergeable",
+            ],
+            None,
+        )?;
+        Self::parse_pr_checks(&raw)
+    }
+
     /// Fetch inline review comments for a pull request via API.
     pub fn get_pr_review_comments(
         &self,
@@ -416,6 +502,82 @@ impl GhCli {
             .collect())
     }
 
+    fn parse_pr_checks(raw: &str) -> Result<PrChecks, GhCliError> {
+        let response: GhPrChecksResponse = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh pr view checks response: {err}; raw: {raw}"
+            ))
+        })?;
+
+        let checks_status = ChecksStatus::rollup(
+            response
+                .status_check_rollup
+                .unwrap_or_default()
+                .iter()
+                .map(Self::check_item_status),
+        );
+
+        let review_decision = match response
+            .review_decision
+            .unwrap_or_default()
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "APPROVED" => Some(ReviewDecision::Approved),
+            "CHANGES_REQUESTED" => Some(ReviewDecision::ChangesRequested),
+            "REVIEW_REQUIRED" => Some(ReviewDecision::ReviewRequired),
+            _ => None,
+        };
+
+        let mergeable = match response
+            .mergeable
+            .unwrap_or_default()
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "MERGEABLE" => Some(true),
+            "CONFLICTING" => Some(false),
+            _ => None,
+        };
+
+        Ok(PrChecks {
+            checks_status,
+            review_decision,
+            mergeable,
+        })
+    }
+
+    fn check_item_status(item: &GhCheckRollupItem) -> ChecksStatus {
+        if let Some(state) = &item.state {
+            return match state.to_ascii_uppercase().as_str() {
+                "SUCCESS" => ChecksStatus::Passing,
+                "FAILURE" | "ERROR" => ChecksStatus::Failing,
+                "PENDING" | "EXPECTED" => ChecksStatus::Pending,
+                _ => ChecksStatus::None,
+            };
+        }
+
+        let completed = item
+            .status
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("COMPLETED"));
+        if !completed {
+            return ChecksStatus::Pending;
+        }
+
+        match item
+            .conclusion
+            .as_deref()
+            .unwrap_or_default()
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "SUCCESS" | "NEUTRAL" | "SKIPPED" => ChecksStatus::Passing,
+            "" => ChecksStatus::Pending,
+            _ => ChecksStatus::Failing,
+        }
+    }
+
     fn parse_pr_review_comments(raw: &str) -> Result<Vec<PrReviewComment>, GhCliError> {
         let items: Vec<GhReviewCommentResponse> =
             serde_json::from_str(raw.trim()).map_err(|err| {
@@ -446,3 +608,76 @@ impl GhCli {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PR_COMMENTS_FIXTURE: &str = include_str!("fixtures/pr_comments.json");
+    const PR_REVIEW_COMMENTS_FIXTURE: &str = include_str!("fixtures/pr_review_comments.json");
+
+    #[test]
+    fn parse_pr_comments_fixture() {
+        let comments = GhCli::parse_pr_comments(PR_COMMENTS_FIXTURE).unwrap();
+        assert_eq!(comments.len(), 2);
+
+        assert_eq!(comments[0].author.login, "octocat");
+        assert_eq!(comments[0].author_association, "MEMBER");
+        assert_eq!(
+            comments[0].created_at.to_rfc3339(),
+            "2024-03-01T09:00:00+00:00"
+        );
+        // Deleted accounts come back with a null author
+        assert_eq!(comments[1].author.login, "unknown");
+    }
+
+    #[test]
+    fn parse_pr_review_comments_fixture() {
+        let comments = GhCli::parse_pr_review_comments(PR_REVIEW_COMMENTS_FIXTURE).unwrap();
+        assert_eq!(comments.len(), 2);
+
+        let inline = &comments[0];
+        assert_eq!(inline.user.login, "reviewer");
+        assert_eq!(inline.path, "src/main.rs");
+        assert_eq!(inline.line, Some(42));
+        assert_eq!(inline.side.as_deref(), Some("RIGHT"));
+        assert_eq!(inline.created_at.to_rfc3339(), "2024-03-01T10:15:00+00:00");
+
+        // Comments on outdated diff lines have no current line number
+        let outdated = &comments[1];
+        assert_eq!(outdated.path, "src/lib.rs");
+        assert_eq!(outdated.line, None);
+        assert_eq!(outdated.side.as_deref(), Some("LEFT"));
+    }
+
+    #[test]
+    fn parse_pr_checks_rolls_up_failures() {
+        let raw = r#"{
+            "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"__typename": "CheckRun", "status": "IN_PROGRESS", "conclusion": ""},
+                {"__typename": "StatusContext", "state": "FAILURE"}
+            ],
+            "reviewDecision": "CHANGES_REQUESTED",
+            "mergeable": "CONFLICTING"
+        }"#;
+
+        let checks = GhCli::parse_pr_checks(raw).unwrap();
+        assert_eq!(checks.checks_status, ChecksStatus::Failing);
+        assert_eq!(
+            checks.review_decision,
+            Some(ReviewDecision::ChangesRequested)
+        );
+        assert_eq!(checks.mergeable, Some(false));
+    }
+
+    #[test]
+    fn parse_pr_checks_without_checks_or_reviews() {
+        let raw = r#"{"statusCheckRollup": null, "reviewDecision": "", "mergeable": "UNKNOWN"}"#;
+
+        let checks = GhCli::parse_pr_checks(raw).unwrap();
+        assert_eq!(checks.checks_status, ChecksStatus::None);
+        assert_eq!(checks.review_decision, None);
+        assert_eq!(checks.mergeable, None);
+    }
+}
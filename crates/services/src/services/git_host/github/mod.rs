@@ -14,7 +14,10 @@ use tracing::info;
 
 use super::{
     GitHostProvider,
-    types::{CreatePrRequest, GitHostError, ProviderKind, UnifiedPrComment},
+    types::{
+        CreatePrRequest, GitHostError, PrChecks, ProviderCapabilities, ProviderKind,
+        UnifiedPrComment,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -359,7 +362,92 @@ impl GitHostProvider for GitHubProvider {
         Ok(unified)
     }
 
+    async fn get_pr_checks(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<PrChecks, GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+
+        (|| async {
+            let cli = self.gh_cli.clone();
+            let owner = repo_info.owner.clone();
+            let repo_name = repo_info.repo_name.clone();
+
+            let checks =
+                task::spawn_blocking(move || cli.get_pr_checks(&owner, &repo_name, pr_number))
+                    .await
+                    .map_err(|err| {
+                        GitHostError::PullRequest(format!(
+                            "Failed to execute GitHub CLI for fetching PR checks: {err}"
+                        ))
+                    })?;
+            checks.map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn update_pr(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let cli = self.gh_cli.clone();
+        let title = title.map(str::to_string);
+        let body = body.map(str::to_string);
+
+        task::spawn_blocking(move || {
+            cli.edit_pr(
+                &repo_info.owner,
+                &repo_info.repo_name,
+                pr_number,
+                title.as_deref(),
+                body.as_deref(),
+            )
+        })
+        .await
+        .map_err(|err| {
+            GitHostError::PullRequest(format!(
+                "Failed to execute GitHub CLI for editing PR: {err}"
+            ))
+        })?
+        .map_err(GitHostError::from)?;
+
+        info!("Updated GitHub PR #{}", pr_number);
+        Ok(())
+    }
+
     fn provider_kind(&self) -> ProviderKind {
         ProviderKind::GitHub
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_draft: true,
+            supports_reviewers: false,
+            supports_labels: true,
+            supports_comment_resolve: false,
+            supports_merge_methods: false,
+        }
+    }
 }
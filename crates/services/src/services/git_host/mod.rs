@@ -2,6 +2,7 @@ mod detection;
 mod types;
 
 pub mod azure;
+pub mod bitbucket;
 pub mod github;
 
 use std::path::Path;
@@ -11,11 +12,12 @@ use db::models::merge::PullRequestInfo;
 use detection::detect_provider_from_url;
 use enum_dispatch::enum_dispatch;
 pub use types::{
-    CreatePrRequest, GitHostError, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind,
-    ReviewCommentUser, UnifiedPrComment,
+    ChecksStatus, CreatePrRequest, GitHostError, PrChecks, PrComment, PrCommentAuthor,
+    PrReviewComment, ProviderCapabilities, ProviderKind, ReviewCommentUser, ReviewDecision,
+    UnifiedPrComment,
 };
 
-use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
+use self::{azure::AzureDevOpsProvider, bitbucket::BitbucketProvider, github::GitHubProvider};
 
 #[async_trait]
 #[enum_dispatch(GitHostService)]
@@ -43,13 +45,34 @@ pub trait GitHostProvider: Send + Sync {
         pr_number: i64,
     ) -> Result<Vec<UnifiedPrComment>, GitHostError>;
 
+    /// Fetch CI check results, review decision and mergeability for a PR.
+    async fn get_pr_checks(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+    ) -> Result<PrChecks, GitHostError>;
+
+    /// Edit the title and/or body of an existing PR. `None` leaves that field unchanged.
+    async fn update_pr(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        pr_number: i64,
+        title: Option<&str>,
+        body: Option<&str>,
+    ) -> Result<(), GitHostError>;
+
     fn provider_kind(&self) -> ProviderKind;
+
+    fn capabilities(&self) -> ProviderCapabilities;
 }
 
 #[enum_dispatch]
 pub enum GitHostService {
     GitHub(GitHubProvider),
     AzureDevOps(AzureDevOpsProvider),
+    Bitbucket(BitbucketProvider),
 }
 
 impl GitHostService {
@@ -57,6 +80,7 @@ impl GitHostService {
         match detect_provider_from_url(url) {
             ProviderKind::GitHub => Ok(Self::GitHub(GitHubProvider::new()?)),
             ProviderKind::AzureDevOps => Ok(Self::AzureDevOps(AzureDevOpsProvider::new()?)),
+            ProviderKind::Bitbucket => Ok(Self::Bitbucket(BitbucketProvider::new()?)),
             ProviderKind::Unknown => Err(GitHostError::UnsupportedProvider),
         }
     }
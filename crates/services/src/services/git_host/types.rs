@@ -3,11 +3,12 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderKind {
     GitHub,
     AzureDevOps,
+    Bitbucket,
     Unknown,
 }
 
@@ -16,11 +17,67 @@ impl std::fmt::Display for ProviderKind {
         match self {
             ProviderKind::GitHub => write!(f, "GitHub"),
             ProviderKind::AzureDevOps => write!(f, "Azure DevOps"),
+            ProviderKind::Bitbucket => write!(f, "Bitbucket"),
             ProviderKind::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// Pull request operations supported by a git host, so clients can hide controls for
+/// operations that would otherwise fail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct ProviderCapabilities {
+    pub supports_draft: bool,
+    pub supports_reviewers: bool,
+    pub supports_labels: bool,
+    pub supports_comment_resolve: bool,
+    pub supports_merge_methods: bool,
+}
+
+/// Combined outcome of the CI checks reported on a pull request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksStatus {
+    Passing,
+    Failing,
+    Pending,
+    /// No checks are configured or reported for the PR
+    None,
+}
+
+impl ChecksStatus {
+    /// Roll individual check results up into one status: any failure wins, then any
+    /// check still running, then passing.
+    pub fn rollup(statuses: impl IntoIterator<Item = ChecksStatus>) -> Self {
+        statuses
+            .into_iter()
+            .fold(ChecksStatus::None, |acc, status| match (acc, status) {
+                (ChecksStatus::Failing, _) | (_, ChecksStatus::Failing) => ChecksStatus::Failing,
+                (ChecksStatus::Pending, _) | (_, ChecksStatus::Pending) => ChecksStatus::Pending,
+                (ChecksStatus::Passing, _) | (_, ChecksStatus::Passing) => ChecksStatus::Passing,
+                _ => ChecksStatus::None,
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+/// CI, review and mergeability state of a pull request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct PrChecks {
+    pub checks_status: ChecksStatus,
+    /// `None` when the host has no review requirement for the PR
+    pub review_decision: Option<ReviewDecision>,
+    /// `None` while the host is still computing mergeability
+    pub mergeable: Option<bool>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreatePrRequest {
     pub title: String,
@@ -30,6 +87,7 @@ pub struct CreatePrRequest {
     pub draft: Option<bool>,
     /// URL of the repo containing the head branch (for cross-fork PRs).
     pub head_repo_url: Option<String>,
+    pub labels: Vec<String>,
 }
 
 #[derive(Debug, Error)]
@@ -46,6 +104,11 @@ pub enum GitHostError {
     RepoNotFoundOrNoAccess(String),
     #[error("{provider} CLI is not installed or not available in PATH")]
     CliNotInstalled { provider: ProviderKind },
+    #[error("{provider} access token is not configured; set {env_var}")]
+    TokenNotConfigured {
+        provider: ProviderKind,
+        env_var: String,
+    },
     #[error("Unsupported git hosting provider")]
     UnsupportedProvider,
     #[error("CLI returned unexpected output: {0}")]
@@ -60,6 +123,7 @@ impl GitHostError {
                 | GitHostError::InsufficientPermissions(_)
                 | GitHostError::RepoNotFoundOrNoAccess(_)
                 | GitHostError::CliNotInstalled { .. }
+                | GitHostError::TokenNotConfigured { .. }
                 | GitHostError::UnsupportedProvider
         )
     }
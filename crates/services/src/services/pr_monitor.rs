@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use db::{
     DBService,
@@ -9,16 +9,20 @@ use db::{
     },
 };
 use serde_json::json;
-use sqlx::error::Error as SqlxError;
+use sqlx::{SqlitePool, error::Error as SqlxError};
 use thiserror::Error;
-use tokio::time::interval;
-use tracing::{debug, error, info};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
 
 use crate::services::{
     analytics::AnalyticsContext,
-    git_host::{self, GitHostError, GitHostProvider},
+    config::Config,
+    git_host::{GitHostError, GitHostProvider, GitHostService, ProviderKind},
 };
 
+/// Lower bound on the configured poll interval so a bad value can't hammer the host CLIs
+const MIN_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error(transparent)]
@@ -32,43 +36,52 @@ enum PrMonitorError {
 /// Service to monitor PRs and update task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
-    poll_interval: Duration,
+    config: Arc<RwLock<Config>>,
     analytics: Option<AnalyticsContext>,
+    /// Providers whose CLI was missing or logged out on the last sweep, so the
+    /// failure is only logged once until it recovers
+    unavailable_providers: HashSet<ProviderKind>,
 }
 
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
+        config: Arc<RwLock<Config>>,
         analytics: Option<AnalyticsContext>,
     ) -> tokio::task::JoinHandle<()> {
-        let service = Self {
+        let mut service = Self {
             db,
-            poll_interval: Duration::from_secs(60), // Check every minute
+            config,
             analytics,
+            unavailable_providers: HashSet::new(),
         };
         tokio::spawn(async move {
             service.start().await;
         })
     }
 
-    async fn start(&self) {
+    async fn poll_interval(&self) -> Duration {
+        let secs = self.config.read().await.pr_poll_interval_secs;
+        Duration::from_secs(secs.into()).max(MIN_POLL_INTERVAL)
+    }
+
+    async fn start(&mut self) {
         info!(
             "Starting PR monitoring service with interval {:?}",
-            self.poll_interval
+            self.poll_interval().await
         );
 
-        let mut interval = interval(self.poll_interval);
-
         loop {
-            interval.tick().await;
             if let Err(e) = self.check_all_open_prs().await {
                 error!("Error checking open PRs: {}", e);
             }
+            // Re-read each round so interval changes apply without a restart
+            tokio::time::sleep(self.poll_interval().await).await;
         }
     }
 
-    /// Check all open PRs for updates with the provided GitHub token
-    async fn check_all_open_prs(&self) -> Result<(), PrMonitorError> {
+    /// Check all open PRs for updates, skipping providers whose CLI is unavailable
+    async fn check_all_open_prs(&mut self) -> Result<(), PrMonitorError> {
         let open_prs = Merge::get_open_prs(&self.db.pool).await?;
 
         if open_prs.is_empty() {
@@ -78,20 +91,52 @@ impl PrMonitorService {
 
         info!("Checking {} open PRs", open_prs.len());
 
+        let mut skipped: HashSet<ProviderKind> = HashSet::new();
         for pr_merge in open_prs {
-            if let Err(e) = self.check_pr_status(&pr_merge).await {
-                error!(
-                    "Error checking PR #{} for workspace {}: {}",
-                    pr_merge.pr_info.number, pr_merge.workspace_id, e
-                );
+            let git_host = match GitHostService::from_url(&pr_merge.pr_info.url) {
+                Ok(host) => host,
+                Err(e) => {
+                    debug!("Skipping PR {}: {}", pr_merge.pr_info.url, e);
+                    continue;
+                }
+            };
+            let provider = git_host.provider_kind();
+            if skipped.contains(&provider) {
+                continue;
+            }
+
+            match self.check_pr_status(&git_host, &pr_merge).await {
+                Ok(()) => {
+                    if self.unavailable_providers.remove(&provider) {
+                        info!("{} CLI is available again, resuming PR checks", provider);
+                    }
+                }
+                Err(PrMonitorError::GitHostError(
+                    e @ (GitHostError::AuthFailed(_) | GitHostError::CliNotInstalled { .. }),
+                )) => {
+                    // Every other PR on this provider would fail the same way
+                    skipped.insert(provider);
+                    if self.unavailable_providers.insert(provider) {
+                        warn!("Skipping {} PR status checks: {}", provider, e);
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        "Error checking PR #{} for workspace {}: {}",
+                        pr_merge.pr_info.number, pr_merge.workspace_id, e
+                    );
+                }
             }
         }
         Ok(())
     }
 
     /// Check the status of a specific PR
-    async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        let git_host = git_host::GitHostService::from_url(&pr_merge.pr_info.url)?;
+    async fn check_pr_status(
+        &self,
+        git_host: &GitHostService,
+        pr_merge: &PrMerge,
+    ) -> Result<(), PrMonitorError> {
         let pr_status = git_host.get_pr_status(&pr_merge.pr_info.url).await?;
 
         debug!(
@@ -99,50 +144,66 @@ impl PrMonitorService {
             pr_merge.pr_info.number, pr_status.status
         );
 
-        // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from git host
-            Merge::update_status(
+            record_pr_status(
                 &self.db.pool,
-                pr_merge.id,
-                pr_status.status.clone(),
+                self.analytics.as_ref(),
+                pr_merge,
+                pr_status.status,
                 pr_status.merge_commit_sha,
             )
             .await?;
-
-            // If the PR was merged, update the task status to done
-            if matches!(&pr_status.status, MergeStatus::Merged)
-                && let Some(workspace) =
-                    Workspace::find_by_id(&self.db.pool, pr_merge.workspace_id).await?
-            {
-                info!(
-                    "PR #{} was merged, updating task {} to done and archiving workspace",
-                    pr_merge.pr_info.number, workspace.task_id
-                );
-                Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
-
-                // Archive workspace unless pinned
-                if !workspace.pinned {
-                    Workspace::set_archived(&self.db.pool, workspace.id, true).await?;
-                }
-
-                // Track analytics event
-                if let Some(analytics) = &self.analytics
-                    && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
-                {
-                    analytics.analytics_service.track_event(
-                        &analytics.user_id,
-                        "pr_merged",
-                        Some(json!({
-                            "task_id": workspace.task_id.to_string(),
-                            "workspace_id": workspace.id.to_string(),
-                            "project_id": task.project_id.to_string(),
-                        })),
-                    );
-                }
-            }
         }
 
         Ok(())
     }
 }
+
+/// Persist the status the git host reports for a PR. When this moves the PR to merged, the
+/// task is marked done, the workspace is archived unless pinned and `pr_merged` is tracked.
+pub async fn record_pr_status(
+    pool: &SqlitePool,
+    analytics: Option<&AnalyticsContext>,
+    pr_merge: &PrMerge,
+    status: MergeStatus,
+    merge_commit_sha: Option<String>,
+) -> Result<(), SqlxError> {
+    let newly_merged = matches!(status, MergeStatus::Merged)
+        && !matches!(pr_merge.pr_info.status, MergeStatus::Merged);
+
+    Merge::update_status(pool, pr_merge.id, status, merge_commit_sha).await?;
+
+    if !newly_merged {
+        return Ok(());
+    }
+    let Some(workspace) = Workspace::find_by_id(pool, pr_merge.workspace_id).await? else {
+        return Ok(());
+    };
+
+    info!(
+        "PR #{} was merged, updating task {} to done and archiving workspace",
+        pr_merge.pr_info.number, workspace.task_id
+    );
+    Task::update_status(pool, workspace.task_id, TaskStatus::Done).await?;
+
+    // Archive workspace unless pinned
+    if !workspace.pinned {
+        Workspace::set_archived(pool, workspace.id, true).await?;
+    }
+
+    if let Some(analytics) = analytics
+        && let Ok(Some(task)) = Task::find_by_id(pool, workspace.task_id).await
+    {
+        analytics.analytics_service.track_event(
+            &analytics.user_id,
+            "pr_merged",
+            Some(json!({
+                "task_id": workspace.task_id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            })),
+        );
+    }
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
@@ -14,7 +14,8 @@ use utils::api::projects::RemoteProject;
 use uuid::Uuid;
 
 use super::{
-    file_search::{FileSearchCache, SearchQuery},
+    file_search::{FileSearchCache, GrepMatch, GrepOptions, SearchQuery, grep_repo},
+    git::{GitCliError, GitService, GitServiceError},
     repo::{RepoError, RepoService},
 };
 
@@ -32,6 +33,8 @@ pub enum ProjectServiceError {
     PathNotDirectory(PathBuf),
     #[error("Path is not a git repository: {0}")]
     NotGitRepository(PathBuf),
+    #[error("Permission denied reading path: {0}")]
+    AccessDenied(PathBuf),
     #[error("Duplicate git repository path")]
     DuplicateGitRepoPath,
     #[error("Duplicate repository name in project")]
@@ -42,10 +45,37 @@ pub enum ProjectServiceError {
     GitError(String),
     #[error("Remote client error: {0}")]
     RemoteClient(String),
+    #[error("Content search unavailable: ripgrep (rg) is not installed")]
+    ContentSearchUnavailable,
+    #[error("Invalid repository URL: {0}")]
+    InvalidRemoteUrl(String),
+    #[error("Clone destination already exists: {0}")]
+    CloneDestinationExists(PathBuf),
 }
 
 pub type Result<T> = std::result::Result<T, ProjectServiceError>;
 
+/// Results of a multi-repo file search along with the repositories that could not be searched.
+#[derive(Debug, Default)]
+pub struct SearchFilesOutput {
+    pub results: Vec<SearchResult>,
+    pub failed_repos: Vec<FailedRepoSearch>,
+}
+
+#[derive(Debug)]
+pub struct FailedRepoSearch {
+    pub repo_name: String,
+    pub error: String,
+}
+
+/// Repository to clone for [`ProjectService::add_repository_from_url`]
+pub struct CloneProjectRepoParams<'a> {
+    pub url: &'a str,
+    pub display_name: &'a str,
+    /// Directory the clone is created in, under a subdirectory named after the repository
+    pub dest_dir: &'a Path,
+}
+
 impl From<RepoError> for ProjectServiceError {
     fn from(e: RepoError) -> Self {
         match e {
@@ -88,7 +118,8 @@ impl ProjectService {
                 return Err(ProjectServiceError::DuplicateRepositoryName);
             }
 
-            if !seen_paths.insert(normalized_path.clone()) {
+            // Compare canonical paths so `/a/b/` and symlinks to `/a/b` count as the same repo
+            if !seen_paths.insert(repo_service.canonicalize_repo_path(&path)?) {
                 return Err(ProjectServiceError::DuplicateGitRepoPath);
             }
 
@@ -173,6 +204,7 @@ impl ProjectService {
 
         let path = repo_service.normalize_path(&payload.git_repo_path)?;
         repo_service.validate_git_repo_path(&path)?;
+        self.validate_repo_accessibility(&path)?;
 
         let repository = ProjectRepo::add_repo_to_project(
             pool,
@@ -201,6 +233,81 @@ impl ProjectService {
         Ok(repository)
     }
 
+    /// Clone `params.url` into a new directory under `params.dest_dir` (named after the
+    /// repository) and add the clone to the project like [`Self::add_repository`]. The URL is
+    /// checked before anything touches the filesystem, and the clone is removed again if it
+    /// can't be added.
+    pub async fn add_repository_from_url(
+        &self,
+        pool: &SqlitePool,
+        repo_service: &RepoService,
+        git: &GitService,
+        project_id: Uuid,
+        params: &CloneProjectRepoParams<'_>,
+    ) -> Result<Repo> {
+        let url = params.url.trim();
+        let dir_name = clone_dir_name(url)
+            .ok_or_else(|| ProjectServiceError::InvalidRemoteUrl(url.to_string()))?;
+
+        let dest_dir = repo_service.normalize_path(&params.dest_dir.to_string_lossy())?;
+        let target_path = dest_dir.join(&dir_name);
+        if target_path.exists() {
+            return Err(ProjectServiceError::CloneDestinationExists(target_path));
+        }
+
+        tracing::info!("Cloning {} into {}", url, target_path.display());
+        let clone_url = url.to_string();
+        let clone_target = target_path.clone();
+        let git = git.clone();
+        tokio::task::spawn_blocking(move || git.clone_from_url(&clone_url, &clone_target))
+            .await
+            .map_err(|e| ProjectServiceError::GitError(format!("Clone task failed: {e}")))?
+            .map_err(|e| match e {
+                GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
+                    ProjectServiceError::GitError(format!(
+                        "Authentication failed cloning {url}; configure git credentials for this remote and retry"
+                    ))
+                }
+                other => ProjectServiceError::GitError(format!("Failed to clone {url}: {other}")),
+            })?;
+
+        let payload = CreateProjectRepo {
+            display_name: params.display_name.to_string(),
+            git_repo_path: target_path.to_string_lossy().to_string(),
+        };
+        let result = self
+            .add_repository(pool, repo_service, project_id, &payload)
+            .await;
+        if result.is_err()
+            && let Err(e) = tokio::fs::remove_dir_all(&target_path).await
+        {
+            tracing::warn!(
+                "Failed to remove clone {} after adding it failed: {}",
+                target_path.display(),
+                e
+            );
+        }
+        result
+    }
+
+    /// Check that the current process can read the repository's working tree, which
+    /// `validate_git_repo_path` doesn't cover.
+    pub fn validate_repo_accessibility(&self, path: &Path) -> Result<()> {
+        let map_err = |e: std::io::Error| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => {
+                ProjectServiceError::AccessDenied(path.to_path_buf())
+            }
+            _ => ProjectServiceError::Io(e),
+        };
+
+        let mut entries = std::fs::read_dir(path).map_err(map_err)?;
+        if let Some(entry) = entries.next() {
+            entry.map_err(map_err)?;
+        }
+
+        Ok(())
+    }
+
     pub async fn delete_repository(
         &self,
         pool: &SqlitePool,
@@ -249,17 +356,44 @@ impl ProjectService {
         Ok(repos)
     }
 
+    /// Build the file search index for each repository concurrently so the first search
+    /// doesn't have to wait for it. Failures are logged and don't affect other repos.
+    pub async fn warm_search_cache(&self, cache: &FileSearchCache, repositories: &[Repo]) {
+        let builds = repositories.iter().map(|repo| async move {
+            if let Err(e) = cache.build(&repo.path).await {
+                tracing::warn!("Failed to warm search cache for repo {}: {}", repo.name, e);
+            }
+        });
+        futures::future::join_all(builds).await;
+    }
+
     pub async fn search_files(
         &self,
         cache: &FileSearchCache,
         repositories: &[Repo],
         query: &SearchQuery,
     ) -> Result<Vec<SearchResult>> {
+        Ok(self
+            .search_files_verbose(cache, repositories, query)
+            .await?
+            .results)
+    }
+
+    /// Like [`Self::search_files`], but also reports the repositories whose search failed
+    /// instead of treating them as having no matches.
+    pub async fn search_files_verbose(
+        &self,
+        cache: &FileSearchCache,
+        repositories: &[Repo],
+        query: &SearchQuery,
+    ) -> Result<SearchFilesOutput> {
         let query_str = query.q.trim();
         if query_str.is_empty() || repositories.is_empty() {
-            return Ok(vec![]);
+            return Ok(SearchFilesOutput::default());
         }
 
+        let repo_limit = per_repo_limit(query.limit, repositories.len());
+
         // Search in parallel and prefix paths with repo name
         let search_futures: Vec<_> = repositories
             .iter()
@@ -270,28 +404,35 @@ impl ProjectService {
                 let query_str = query_str.to_string();
                 async move {
                     let results = cache
-                        .search_repo(&repo_path, &query_str, mode)
-                        .await
-                        .unwrap_or_else(|e| {
-                            tracing::warn!("Search failed for repo {}: {}", repo_name, e);
-                            vec![]
-                        });
+                        .search_repo(&repo_path, &query_str, mode, repo_limit)
+                        .await;
                     (repo_name, results)
                 }
             })
             .collect();
 
-        let repo_results = futures::future::join_all(search_futures).await;
+        let mut failed_repos = Vec::new();
+        let mut repo_results: Vec<(String, SearchResult)> = Vec::new();
+        for (repo_name, results) in futures::future::join_all(search_futures).await {
+            match results {
+                Ok(results) => {
+                    repo_results.extend(results.into_iter().map(|r| (repo_name.clone(), r)))
+                }
+                Err(error) => {
+                    tracing::warn!("Search failed for repo {}: {}", repo_name, error);
+                    failed_repos.push(FailedRepoSearch { repo_name, error });
+                }
+            }
+        }
+        if query.deduplicate {
+            repo_results = deduplicate_by_relative_path(repo_results);
+        }
 
         let mut all_results: Vec<SearchResult> = repo_results
             .into_iter()
-            .flat_map(|(repo_name, results)| {
-                results.into_iter().map(move |r| SearchResult {
-                    path: format!("{}/{}", repo_name, r.path),
-                    is_file: r.is_file,
-                    match_type: r.match_type.clone(),
-                    score: r.score,
-                })
+            .map(|(repo_name, r)| SearchResult {
+                path: format!("{}/{}", repo_name, r.path),
+                ..r
             })
             .collect();
 
@@ -300,13 +441,569 @@ impl ProjectService {
                 SearchMatchType::FileName => 0,
                 SearchMatchType::DirectoryName => 1,
                 SearchMatchType::FullPath => 2,
+                SearchMatchType::Content => 3,
             };
             priority(&a.match_type)
                 .cmp(&priority(&b.match_type))
                 .then_with(|| b.score.cmp(&a.score)) // Higher scores first
         });
 
-        all_results.truncate(10);
+        all_results.truncate(query.limit);
+        Ok(SearchFilesOutput {
+            results: all_results,
+            failed_repos,
+        })
+    }
+
+    /// Find lines containing `query` across the project's repositories. Paths are prefixed
+    /// with the repo name like [`Self::search_files`], and at most `options.max_matches` hits
+    /// are returned in repository order, each repo contributing its [`per_repo_limit`] share.
+    pub async fn grep_files(
+        &self,
+        repositories: &[Repo],
+        query: &str,
+        options: &GrepOptions,
+    ) -> Result<Vec<GrepMatch>> {
+        let query = query.trim();
+        if query.is_empty() || repositories.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let repo_options = GrepOptions {
+            max_matches: per_repo_limit(options.max_matches, repositories.len()),
+            ..options.clone()
+        };
+        let repo_options = &repo_options;
+        let grep_futures = repositories
+            .iter()
+            .map(|repo| async move { (repo, grep_repo(&repo.path, query, repo_options).await) });
+
+        let mut all_matches = Vec::new();
+        for (repo, result) in futures::future::join_all(grep_futures).await {
+            match result {
+                Ok(matches) => all_matches.extend(matches.into_iter().map(|m| GrepMatch {
+                    path: format!("{}/{}", repo.name, m.path),
+                    ..m
+                })),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(ProjectServiceError::ContentSearchUnavailable);
+                }
+                Err(e) => tracing::warn!("Content search failed for repo {}: {}", repo.name, e),
+            }
+        }
+
+        all_matches.truncate(options.max_matches);
+        Ok(all_matches)
+    }
+
+    /// Files most recently touched by commits across the project's repositories, newest first.
+    /// Reads the last [`RECENT_FILES_COMMIT_DEPTH`] commits of each repo from `git log` rather
+    /// than the search index, so it is cheap to call before the index is built.
+    pub async fn list_recent_files(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let repositories = self.get_repositories(pool, project_id).await?;
+
+        let log_futures = repositories
+            .iter()
+            .map(|repo| async move { (repo, recent_repo_files(&repo.path).await) });
+
+        let mut all_results = Vec::new();
+        for (repo, result) in futures::future::join_all(log_futures).await {
+            match result {
+                Ok(files) => {
+                    all_results.extend(files.into_iter().map(|(path, committed_at)| SearchResult {
+                        path: format!("{}/{}", repo.name, path),
+                        is_file: true,
+                        match_type: SearchMatchType::FullPath,
+                        score: committed_at,
+                    }))
+                }
+                Err(e) => tracing::warn!("Failed to read git log for repo {}: {}", repo.name, e),
+            }
+        }
+
+        // Stable sort keeps each repo's own log order for commits with the same timestamp
+        all_results.sort_by(|a, b| b.score.cmp(&a.score));
+        all_results.truncate(limit);
         Ok(all_results)
     }
 }
+
+/// How many commits per repo [`ProjectService::list_recent_files`] looks back through
+const RECENT_FILES_COMMIT_DEPTH: usize = 200;
+
+/// Files changed in the repo's recent history that still exist, paired with the commit time
+/// (unix seconds) of their latest change.
+async fn recent_repo_files(repo_path: &Path) -> std::io::Result<Vec<(String, i64)>> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args([
+            "-c",
+            "core.quotepath=off",
+            "log",
+            "--name-only",
+            "--no-renames",
+            "--pretty=format:%x00%ct",
+            "-n",
+        ])
+        .arg(RECENT_FILES_COMMIT_DEPTH.to_string())
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(parse_recent_files(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|(path, _)| repo_path.join(path).is_file())
+        .collect())
+}
+
+/// Parse `git log --name-only --pretty=format:%x00%ct` output into unique paths, keeping the
+/// first (most recent) commit time seen for each.
+fn parse_recent_files(log: &str) -> Vec<(String, i64)> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    let mut committed_at = 0;
+
+    for line in log.lines() {
+        if let Some(timestamp) = line.strip_prefix('\0') {
+            committed_at = timestamp.trim().parse().unwrap_or(0);
+            continue;
+        }
+        let path = line.trim();
+        if !path.is_empty() && seen.insert(path.to_string()) {
+            files.push((path.to_string(), committed_at));
+        }
+    }
+
+    files
+}
+
+/// Directory name for a clone of `url`, e.g. `repo` for `https://github.com/org/repo.git`,
+/// or `None` when `url` doesn't look like something `git clone` accepts. Option-like URLs and
+/// names that would escape the destination directory are rejected.
+fn clone_dir_name(url: &str) -> Option<String> {
+    if url.is_empty() || url.starts_with('-') || url.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    let path = if let Some((scheme, rest)) = url.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "ssh" | "git" | "file") {
+            return None;
+        }
+        // The host is required except for file:// URLs
+        let (host, path) = rest.split_once('/')?;
+        if host.is_empty() && scheme != "file" {
+            return None;
+        }
+        path
+    } else {
+        // scp-like syntax: [user@]host:path
+        let (host, path) = url.split_once(':')?;
+        if host.is_empty() || host.contains('/') {
+            return None;
+        }
+        path
+    };
+
+    let name = path.trim_end_matches('/').rsplit('/').next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    if name.is_empty() || name == "." || name == ".." {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Fewest results a single repo may contribute to a multi-repo search
+const MIN_RESULTS_PER_REPO: usize = 3;
+
+/// How many results to take from each repo so one large repo can't crowd out the others in
+/// the merged results. Never more than `limit`, and never below [`MIN_RESULTS_PER_REPO`].
+fn per_repo_limit(limit: usize, repo_count: usize) -> usize {
+    if repo_count <= 1 {
+        return limit;
+    }
+    limit
+        .div_ceil(repo_count)
+        .max(MIN_RESULTS_PER_REPO)
+        .min(limit)
+}
+
+/// Keep only the highest-scoring result for each relative path that appears in more than one
+/// repo, e.g. `src/main.rs` in two repos of the same project. Ties go to the earlier repo.
+fn deduplicate_by_relative_path(
+    results: Vec<(String, SearchResult)>,
+) -> Vec<(String, SearchResult)> {
+    let mut kept: Vec<(String, SearchResult)> = Vec::with_capacity(results.len());
+    let mut index_by_path: HashMap<String, usize> = HashMap::new();
+
+    for (repo_name, result) in results {
+        match index_by_path.get(&result.path) {
+            Some(&index) => {
+                if result.score > kept[index].1.score {
+                    kept[index] = (repo_name, result);
+                }
+            }
+            None => {
+                index_by_path.insert(result.path.clone(), kept.len());
+                kept.push((repo_name, result));
+            }
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(repo: &str, path: &str, score: i64) -> (String, SearchResult) {
+        (
+            repo.to_string(),
+            SearchResult {
+                path: path.to_string(),
+                is_file: true,
+                match_type: SearchMatchType::FileName,
+                score,
+            },
+        )
+    }
+
+    #[test]
+    fn duplicate_paths_keep_highest_score() {
+        let deduped = deduplicate_by_relative_path(vec![
+            result("frontend", "src/main.rs", 3),
+            result("backend", "src/main.rs", 7),
+            result("backend", "src/lib.rs", 1),
+        ]);
+
+        let summary: Vec<_> = deduped
+            .iter()
+            .map(|(repo, r)| (repo.as_str(), r.path.as_str(), r.score))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("backend", "src/main.rs", 7), ("backend", "src/lib.rs", 1)]
+        );
+    }
+
+    #[test]
+    fn equal_scores_keep_first_repo() {
+        let deduped = deduplicate_by_relative_path(vec![
+            result("frontend", "README.md", 2),
+            result("backend", "README.md", 2),
+        ]);
+
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].0, "frontend");
+    }
+
+    #[test]
+    fn single_repo_gets_the_whole_limit() {
+        assert_eq!(per_repo_limit(10, 1), 10);
+        assert_eq!(per_repo_limit(50, 1), 50);
+    }
+
+    #[test]
+    fn multi_repo_limit_is_split_with_a_floor() {
+        assert_eq!(per_repo_limit(10, 2), 5);
+        assert_eq!(per_repo_limit(10, 3), 4);
+        assert_eq!(per_repo_limit(10, 8), MIN_RESULTS_PER_REPO);
+        assert_eq!(per_repo_limit(2, 4), 2);
+    }
+
+    fn repo(name: &str, path: &Path) -> Repo {
+        Repo {
+            id: Uuid::new_v4(),
+            path: path.to_path_buf(),
+            name: name.to_string(),
+            display_name: name.to_string(),
+            setup_script: None,
+            cleanup_script: None,
+            copy_files: None,
+            parallel_setup_script: false,
+            dev_server_script: None,
+            default_target_branch: None,
+            env_vars: sqlx::types::Json(HashMap::new()),
+            commit_check: Default::default(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn multi_repo_search_shares_the_limit() {
+        let big = tempfile::tempdir().unwrap();
+        let small = tempfile::tempdir().unwrap();
+        for i in 0..20 {
+            std::fs::write(big.path().join(format!("handler_{i}.rs")), "").unwrap();
+        }
+        std::fs::write(small.path().join("handler.rs"), "").unwrap();
+
+        let repositories = vec![repo("big", big.path()), repo("small", small.path())];
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "handler", "limit": 6}"#).unwrap();
+
+        let results = ProjectService::new()
+            .search_files(&FileSearchCache::new(), &repositories, &query)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results
+                .iter()
+                .filter(|r| r.path.starts_with("big/"))
+                .count(),
+            3
+        );
+        assert!(results.iter().any(|r| r.path == "small/handler.rs"));
+    }
+
+    #[tokio::test]
+    async fn shared_paths_are_kept_per_repo_without_deduplication() {
+        let frontend = tempfile::tempdir().unwrap();
+        let backend = tempfile::tempdir().unwrap();
+        std::fs::write(frontend.path().join("main.rs"), "").unwrap();
+        std::fs::write(backend.path().join("main.rs"), "").unwrap();
+        let repositories = vec![
+            repo("frontend", frontend.path()),
+            repo("backend", backend.path()),
+        ];
+        let service = ProjectService::new();
+        let cache = FileSearchCache::new();
+
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "main"}"#).unwrap();
+        let deduped = service
+            .search_files(&cache, &repositories, &query)
+            .await
+            .unwrap();
+        assert_eq!(deduped.len(), 1);
+
+        let query: SearchQuery =
+            serde_json::from_str(r#"{"q": "main", "deduplicate": false}"#).unwrap();
+        let mut paths: Vec<_> = service
+            .search_files(&cache, &repositories, &query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["backend/main.rs", "frontend/main.rs"]);
+    }
+
+    #[tokio::test]
+    async fn failed_repo_is_reported_in_verbose_search() {
+        let present = tempfile::tempdir().unwrap();
+        std::fs::write(present.path().join("handler.rs"), "").unwrap();
+        let missing = present.path().join("does-not-exist");
+        let repositories = vec![repo("present", present.path()), repo("missing", &missing)];
+        let query: SearchQuery = serde_json::from_str(r#"{"q": "handler"}"#).unwrap();
+
+        let output = ProjectService::new()
+            .search_files_verbose(&FileSearchCache::new(), &repositories, &query)
+            .await
+            .unwrap();
+
+        assert!(
+            output
+                .results
+                .iter()
+                .any(|r| r.path == "present/handler.rs")
+        );
+        assert_eq!(output.failed_repos.len(), 1);
+        assert_eq!(output.failed_repos[0].repo_name, "missing");
+    }
+
+    #[test]
+    fn recent_files_keep_latest_commit_time() {
+        let log = "\01700000300\nsrc/main.rs\nREADME.md\n\n\01700000200\nsrc/lib.rs\nsrc/main.rs\n";
+
+        assert_eq!(
+            parse_recent_files(log),
+            vec![
+                ("src/main.rs".to_string(), 1700000300),
+                ("README.md".to_string(), 1700000300),
+                ("src/lib.rs".to_string(), 1700000200),
+            ]
+        );
+    }
+
+    /// Creating a project with `first` and `second` as separate repos. The duplicate check runs
+    /// before any query, so the pool never has to connect.
+    async fn create_with_paths(first: &Path, second: &Path) -> Result<Project> {
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+        let payload = CreateProject {
+            name: "dupes".to_string(),
+            repositories: vec![
+                CreateProjectRepo {
+                    display_name: "first".to_string(),
+                    git_repo_path: first.to_string_lossy().to_string(),
+                },
+                CreateProjectRepo {
+                    display_name: "second".to_string(),
+                    git_repo_path: second.to_string_lossy().to_string(),
+                },
+            ],
+        };
+        ProjectService::new()
+            .create_project(&pool, &RepoService::new(), payload)
+            .await
+    }
+
+    fn git_dir(root: &Path) -> PathBuf {
+        let repo = root.join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        repo
+    }
+
+    #[tokio::test]
+    async fn trailing_slash_path_is_duplicate() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git_dir(dir.path());
+        let trailing = PathBuf::from(format!("{}/", repo.to_string_lossy()));
+
+        assert!(matches!(
+            create_with_paths(&repo, &trailing).await,
+            Err(ProjectServiceError::DuplicateGitRepoPath)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinked_path_is_duplicate() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git_dir(dir.path());
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&repo, &link).unwrap();
+
+        assert!(matches!(
+            create_with_paths(&repo, &link).await,
+            Err(ProjectServiceError::DuplicateGitRepoPath)
+        ));
+    }
+
+    #[test]
+    fn clone_dir_name_from_common_urls() {
+        assert_eq!(
+            clone_dir_name("https://github.com/org/repo.git").as_deref(),
+            Some("repo")
+        );
+        assert_eq!(
+            clone_dir_name("git@github.com:org/repo.git").as_deref(),
+            Some("repo")
+        );
+        assert_eq!(
+            clone_dir_name("ssh://git@host/org/repo/").as_deref(),
+            Some("repo")
+        );
+    }
+
+    #[test]
+    fn malformed_urls_have_no_clone_dir() {
+        for url in [
+            "",
+            "not a url",
+            "--upload-pack=touch /tmp/x",
+            "ftp://host/repo.git",
+            "https://",
+            "https://github.com/org/..",
+            "/local/path/repo",
+        ] {
+            assert_eq!(clone_dir_name(url), None, "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_url_is_rejected_before_filesystem_work() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest_dir = dir.path().join("clones");
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+
+        let result = ProjectService::new()
+            .add_repository_from_url(
+                &pool,
+                &RepoService::new(),
+                &GitService::new(),
+                Uuid::new_v4(),
+                &CloneProjectRepoParams {
+                    url: "not a url",
+                    display_name: "repo",
+                    dest_dir: &dest_dir,
+                },
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ProjectServiceError::InvalidRemoteUrl(_))
+        ));
+        assert!(!dest_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn clone_is_removed_when_adding_it_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        GitService::new()
+            .initialize_repo_with_main_branch(&source)
+            .unwrap();
+        let dest_dir = dir.path().join("clones");
+        // No migrations, so recording the repo fails after the clone succeeds
+        let pool = SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+
+        let result = ProjectService::new()
+            .add_repository_from_url(
+                &pool,
+                &RepoService::new(),
+                &GitService::new(),
+                Uuid::new_v4(),
+                &CloneProjectRepoParams {
+                    url: &format!("file://{}", source.display()),
+                    display_name: "source",
+                    dest_dir: &dest_dir,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(ProjectServiceError::Database(_))));
+        assert!(dest_dir.exists());
+        assert!(!dest_dir.join("source").exists());
+    }
+
+    #[test]
+    fn readable_directory_is_accessible() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("README.md"), "hello").unwrap();
+
+        assert!(
+            ProjectService::new()
+                .validate_repo_accessibility(dir.path())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn missing_directory_is_io_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+
+        assert!(matches!(
+            ProjectService::new().validate_repo_accessibility(&missing),
+            Err(ProjectServiceError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+}
@@ -60,6 +60,12 @@ impl RepoService {
         std::path::absolute(expand_tilde(path))
     }
 
+    /// Resolve symlinks, `..` and trailing separators so two spellings of the same repo compare
+    /// equal. The path must exist; run [`Self::validate_git_repo_path`] first.
+    pub fn canonicalize_repo_path(&self, path: &Path) -> std::io::Result<PathBuf> {
+        dunce::canonicalize(path)
+    }
+
     pub async fn register(
         &self,
         pool: &SqlitePool,
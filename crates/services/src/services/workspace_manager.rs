@@ -1,9 +1,17 @@
-use std::path::{Path, PathBuf};
-
-use db::models::{repo::Repo, workspace::Workspace as DbWorkspace};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use db::models::{
+    repo::Repo,
+    workspace::{Workspace as DbWorkspace, WorkspaceError as DbWorkspaceError},
+};
+use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
+use ts_rs::TS;
 use uuid::Uuid;
 
 use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
@@ -51,6 +59,24 @@ pub struct WorktreeContainer {
     pub worktrees: Vec<RepoWorktree>,
 }
 
+/// Summary of an orphan cleanup run
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct OrphanCleanupReport {
+    /// When true nothing was removed; the report lists what would have been
+    pub dry_run: bool,
+    /// Workspace directories that were (or would be) removed
+    pub removed: Vec<String>,
+    /// Archived workspaces whose stale container ref was (or would be) cleared
+    pub cleared_container_refs: Vec<Uuid>,
+    pub failures: Vec<OrphanCleanupFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct OrphanCleanupFailure {
+    pub path: String,
+    pub error: String,
+}
+
 pub struct WorkspaceManager;
 
 impl WorkspaceManager {
@@ -284,26 +310,57 @@ impl WorkspaceManager {
         }
     }
 
-    pub async fn cleanup_orphan_workspaces(db: &Pool<Sqlite>) {
-        if std::env::var("DISABLE_WORKTREE_ORPHAN_CLEANUP").is_ok() {
-            debug!(
-                "Orphan workspace cleanup is disabled via DISABLE_WORKTREE_ORPHAN_CLEANUP environment variable"
-            );
-            return;
-        }
+    /// Remove workspace directories that are no longer referenced by any workspace in the DB,
+    /// and clear container refs of archived workspaces whose directory is already gone.
+    ///
+    /// Directories referenced by any workspace are never touched; archived workspaces that
+    /// still have their directory are left to the regular expiry cleanup.
+    pub async fn cleanup_orphan_workspaces(
+        db: &Pool<Sqlite>,
+        dry_run: bool,
+    ) -> Result<OrphanCleanupReport, DbWorkspaceError> {
+        let workspaces = DbWorkspace::fetch_all(db, None).await?;
+        let referenced: HashSet<PathBuf> = workspaces
+            .iter()
+            .filter_map(|w| w.container_ref.as_deref().map(PathBuf::from))
+            .collect();
+
+        let mut report = OrphanCleanupReport {
+            dry_run,
+            ..Default::default()
+        };
 
         // Always clean up the default directory
         let default_dir = WorktreeManager::get_default_worktree_base_dir();
-        Self::cleanup_orphans_in_directory(db, &default_dir).await;
+        Self::cleanup_orphans_in_directory(&default_dir, &referenced, &mut report).await;
 
         // Also clean up custom directory if it's different from the default
         let current_dir = Self::get_workspace_base_dir();
         if current_dir != default_dir {
-            Self::cleanup_orphans_in_directory(db, &current_dir).await;
+            Self::cleanup_orphans_in_directory(&current_dir, &referenced, &mut report).await;
         }
+
+        for workspace in workspaces.iter().filter(|w| w.archived) {
+            let Some(container_ref) = workspace.container_ref.as_deref() else {
+                continue;
+            };
+            if Path::new(container_ref).exists() {
+                continue;
+            }
+            if !dry_run {
+                DbWorkspace::clear_container_ref(db, workspace.id).await?;
+            }
+            report.cleared_container_refs.push(workspace.id);
+        }
+
+        Ok(report)
     }
 
-    async fn cleanup_orphans_in_directory(db: &Pool<Sqlite>, workspace_base_dir: &Path) {
+    async fn cleanup_orphans_in_directory(
+        workspace_base_dir: &Path,
+        referenced: &HashSet<PathBuf>,
+        report: &mut OrphanCleanupReport,
+    ) {
         if !workspace_base_dir.exists() {
             debug!(
                 "Workspace base directory {} does not exist, skipping orphan cleanup",
@@ -320,6 +377,10 @@ impl WorkspaceManager {
                     workspace_base_dir.display(),
                     e
                 );
+                report.failures.push(OrphanCleanupFailure {
+                    path: workspace_base_dir.to_string_lossy().to_string(),
+                    error: e.to_string(),
+                });
                 return;
             }
         };
@@ -334,23 +395,34 @@ impl WorkspaceManager {
             };
 
             let path = entry.path();
-            if !path.is_dir() {
+            if !path.is_dir() || referenced.contains(&path) {
                 continue;
             }
 
             let workspace_path_str = path.to_string_lossy().to_string();
-            if let Ok(false) = DbWorkspace::container_ref_exists(db, &workspace_path_str).await {
-                info!("Found orphaned workspace: {}", workspace_path_str);
-                if let Err(e) = Self::cleanup_workspace_without_repos(&path).await {
-                    error!(
-                        "Failed to remove orphaned workspace {}: {}",
-                        workspace_path_str, e
-                    );
-                } else {
+            info!("Found orphaned workspace: {}", workspace_path_str);
+            if report.dry_run {
+                report.removed.push(workspace_path_str);
+                continue;
+            }
+
+            match Self::cleanup_workspace_without_repos(&path).await {
+                Ok(()) => {
                     info!(
                         "Successfully removed orphaned workspace: {}",
                         workspace_path_str
                     );
+                    report.removed.push(workspace_path_str);
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to remove orphaned workspace {}: {}",
+                        workspace_path_str, e
+                    );
+                    report.failures.push(OrphanCleanupFailure {
+                        path: workspace_path_str,
+                        error: e.to_string(),
+                    });
                 }
             }
         }
@@ -247,11 +247,17 @@ impl WorktreeManager {
 
         let git_repo_path = Self::get_git_repo_path(repo)?;
 
-        // Step 1: Use GitService to remove the worktree registration (force) if present
+        // Step 1: Use GitService to remove the worktree registration if present
         // The Git CLI is more robust than libgit2 for mutable worktree operations
         let git_service = GitService::new();
-        if let Err(e) = git_service.remove_worktree(&git_repo_path, worktree_path, true) {
+        if let Err(e) = git_service.remove_worktree(&git_repo_path, worktree_path, false) {
             debug!("git worktree remove non-fatal error: {}", e);
+            // Only force-remove worktrees that live in directories we created
+            if Self::is_managed_worktree_path(worktree_path)
+                && let Err(e) = git_service.remove_worktree(&git_repo_path, worktree_path, true)
+            {
+                debug!("git worktree remove --force non-fatal error: {}", e);
+            }
         }
 
         // Step 2: Always force cleanup metadata directory (proactive cleanup)
@@ -550,6 +556,12 @@ impl WorktreeManager {
         utils::path::get_vibe_kanban_temp_dir().join("worktrees")
     }
 
+    /// Whether a path lives under one of the worktree base directories owned by the app
+    fn is_managed_worktree_path(path: &Path) -> bool {
+        path.starts_with(Self::get_worktree_base_dir())
+            || path.starts_with(Self::get_default_worktree_base_dir())
+    }
+
     pub async fn cleanup_suspected_worktree(path: &Path) -> Result<bool, WorktreeError> {
         let git_marker = path.join(".git");
         if !git_marker.exists() || !git_marker.is_file() {
@@ -5,7 +5,7 @@ use std::{
 };
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
-use services::services::git::{GitCli, GitCliError, GitService};
+use services::services::git::{GitCli, GitCliError, GitService, GitServiceError};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
 
@@ -406,6 +406,59 @@ fn push_and_fetch_roundtrip_updates_tracking_branch() {
     );
 }
 
+#[test]
+fn push_to_named_remote_pushes_to_fork_not_upstream() {
+    let temp_dir = TempDir::new().unwrap();
+    let upstream_path = temp_dir.path().join("upstream.git");
+    let fork_path = temp_dir.path().join("fork.git");
+    Repository::init_bare(&upstream_path).expect("init bare upstream");
+    Repository::init_bare(&fork_path).expect("init bare fork");
+    let upstream_url = upstream_path.to_str().expect("upstream path str");
+    let fork_url = fork_path.to_str().expect("fork path str");
+
+    let local_path = temp_dir.path().join("local");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&local_path)
+        .expect("init local repo");
+    let local_repo = Repository::open(&local_path).expect("open local repo");
+    configure_user(&local_repo);
+    local_repo
+        .remote("origin", upstream_url)
+        .expect("add origin");
+    local_repo.remote("fork", fork_url).expect("add fork");
+    push_ref(&local_repo, "refs/heads/main", "refs/heads/main");
+
+    create_branch_from_head(&local_repo, "feature");
+    checkout_branch(&local_repo, "feature");
+    write_file(&local_path, "feat.txt", "feature work\n");
+    commit_all(&local_repo, "feature commit");
+
+    assert!(service.remote_exists(&local_path, "fork").unwrap());
+    assert!(!service.remote_exists(&local_path, "missing").unwrap());
+
+    service
+        .push_to_named_remote(&local_path, "fork", "feature", false)
+        .expect("push to fork");
+
+    let fork_repo = Repository::open_bare(&fork_path).expect("open fork");
+    assert!(
+        fork_repo.find_reference("refs/heads/feature").is_ok(),
+        "feature branch pushed to fork"
+    );
+    let upstream_repo = Repository::open_bare(&upstream_path).expect("open upstream");
+    assert!(
+        upstream_repo.find_reference("refs/heads/feature").is_err(),
+        "feature branch must not be pushed to upstream"
+    );
+
+    let branch = local_repo
+        .find_branch("feature", git2::BranchType::Local)
+        .expect("local feature branch");
+    let tracking = branch.upstream().expect("upstream set");
+    assert_eq!(tracking.name().unwrap(), Some("fork/feature"));
+}
+
 #[test]
 fn rebase_preserves_untracked_files() {
     let td = TempDir::new().unwrap();
@@ -473,6 +526,147 @@ fn rebase_aborts_if_untracked_would_be_overwritten_by_base() {
     assert_eq!(content, "my scratch note\n");
 }
 
+fn stash_list(repo_path: &Path) -> Vec<String> {
+    let git = GitCli::new();
+    git.git(repo_path, ["stash", "list", "--format=%H"])
+        .unwrap()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn autostash_rebase_restores_tracked_changes() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+
+    write_file(&worktree_path, "feat.txt", "feat change (edited)\n");
+
+    let service = GitService::new();
+    let autostashed = service
+        .with_autostash(&worktree_path, true, || {
+            service.rebase_branch(
+                &repo_path,
+                &worktree_path,
+                "new-base",
+                "old-base",
+                "feature",
+            )
+        })
+        .unwrap();
+    autostashed
+        .result
+        .expect("rebase with autostash should succeed");
+    assert_eq!(autostashed.kept_stash, None);
+
+    let base = fs::read_to_string(worktree_path.join("base.txt")).unwrap();
+    assert_eq!(base, "from new-base\n");
+    let edited = fs::read_to_string(worktree_path.join("feat.txt")).unwrap();
+    assert_eq!(edited, "feat change (edited)\n");
+    assert!(stash_list(&worktree_path).is_empty());
+}
+
+#[test]
+fn autostash_handles_untracked_only_dirt() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+
+    write_file(&worktree_path, "notes/scratch.txt", "agent scratch\n");
+
+    let service = GitService::new();
+    service
+        .with_autostash(&worktree_path, true, || {
+            // The untracked file is out of the way while the operation runs
+            assert!(!worktree_path.join("notes/scratch.txt").exists());
+            service.rebase_branch(
+                &repo_path,
+                &worktree_path,
+                "new-base",
+                "old-base",
+                "feature",
+            )
+        })
+        .unwrap()
+        .result
+        .expect("rebase with autostash should succeed");
+
+    let scratch = fs::read_to_string(worktree_path.join("notes/scratch.txt")).unwrap();
+    assert_eq!(scratch, "agent scratch\n");
+    assert!(stash_list(&worktree_path).is_empty());
+
+    // Without untracked stashing there is nothing to stash and the op runs as-is
+    let autostashed = service
+        .with_autostash(&worktree_path, false, || Ok(()))
+        .unwrap();
+    assert!(autostashed.result.is_ok());
+    assert_eq!(autostashed.kept_stash, None);
+    assert!(worktree_path.join("notes/scratch.txt").exists());
+    assert!(stash_list(&worktree_path).is_empty());
+}
+
+#[test]
+fn autostash_pop_conflict_keeps_stash() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+
+    // base.txt also changes on new-base, so reapplying this edit conflicts
+    write_file(&worktree_path, "base.txt", "local edit\n");
+
+    let service = GitService::new();
+    let autostashed = service
+        .with_autostash(&worktree_path, true, || {
+            service.rebase_branch(
+                &repo_path,
+                &worktree_path,
+                "new-base",
+                "old-base",
+                "feature",
+            )
+        })
+        .unwrap();
+
+    // The rebase still succeeds; the conflicting changes are reported instead of an error
+    let head_sha = autostashed.result.expect("rebase should succeed");
+    let stash_ref = autostashed.kept_stash.expect("stash should be kept");
+    // The rebase itself went through and the stash entry is still recoverable
+    assert_eq!(
+        service.get_branch_oid(&repo_path, "feature").unwrap(),
+        head_sha
+    );
+    assert!(stash_list(&worktree_path).contains(&stash_ref));
+}
+
+#[test]
+fn autostash_keeps_stash_when_rebase_stops_on_conflicts() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+
+    write_file(&worktree_path, "notes.txt", "uncommitted\n");
+
+    let service = GitService::new();
+    let autostashed = service
+        .with_autostash(&worktree_path, true, || {
+            service.rebase_branch(
+                &repo_path,
+                &worktree_path,
+                "new-base",
+                "old-base",
+                "feature",
+            )
+        })
+        .unwrap();
+
+    assert!(matches!(
+        autostashed.result,
+        Err(GitServiceError::MergeConflicts { .. })
+    ));
+    // The changes stay stashed so conflict resolution starts from a clean tree
+    let stash_ref = autostashed.kept_stash.expect("stash should be kept");
+    assert!(stash_list(&worktree_path).contains(&stash_ref));
+    assert!(!worktree_path.join("notes.txt").exists());
+    assert!(service.is_rebase_in_progress(&worktree_path).unwrap());
+}
+
 #[test]
 fn merge_does_not_overwrite_main_repo_untracked_files() {
     let td = TempDir::new().unwrap();
@@ -769,6 +963,75 @@ fn rebase_applies_multiple_commits_onto_ahead_base() {
     assert_eq!(feat2, "second change\n");
 }
 
+#[test]
+fn rebase_counts_replayed_commits() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    write_file(&worktree_path, "feat2.txt", "second change\n");
+    commit_all(&wt_repo, "feature second commit");
+
+    let service = GitService::new();
+    let old_head = service.get_branch_oid(&worktree_path, "feature").unwrap();
+    let new_head = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .expect("rebase should succeed");
+    assert_eq!(
+        service
+            .count_replayed_commits(&worktree_path, &old_head, &new_head, "new-base")
+            .unwrap(),
+        2
+    );
+
+    // Rebasing again onto the same base rewrites nothing, although the branch is still ahead
+    let second_head = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "new-base",
+            "feature",
+        )
+        .expect("rebase should succeed");
+    assert_eq!(second_head, new_head);
+    assert_eq!(
+        service
+            .count_replayed_commits(&worktree_path, &new_head, &second_head, "new-base")
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn fast_forward_rebase_replays_no_commits() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_no_unique_feature_repo(&td);
+    let service = GitService::new();
+    let old_head = service.get_branch_oid(&worktree_path, "feature").unwrap();
+
+    let new_head = service
+        .rebase_branch(
+            &repo_path,
+            &worktree_path,
+            "new-base",
+            "old-base",
+            "feature",
+        )
+        .expect("rebase should succeed");
+    assert_eq!(
+        service
+            .count_replayed_commits(&worktree_path, &old_head, &new_head, "new-base")
+            .unwrap(),
+        0
+    );
+}
+
 #[test]
 fn merge_when_base_ahead_and_feature_ahead_fails() {
     let td = TempDir::new().unwrap();
@@ -520,3 +520,63 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+fn init_origin_with_master(root: &TempDir) -> PathBuf {
+    let origin = root.path().join("origin");
+    let repo = Repository::init(&origin).unwrap();
+    repo.set_head("refs/heads/master").unwrap();
+    configure_user(&origin, "Test User", "test@example.com");
+    write_file(&origin, "README.md", "hello\n");
+    let git = GitCli::new();
+    git.git(&origin, ["add", "README.md"]).unwrap();
+    git.git(&origin, ["commit", "-m", "init"]).unwrap();
+    git.git(&origin, ["branch", "develop"]).unwrap();
+    origin
+}
+
+#[test]
+fn remote_default_branch_from_clone_symref() {
+    let td = TempDir::new().unwrap();
+    let origin = init_origin_with_master(&td);
+    let clone = td.path().join("clone");
+    let git = GitCli::new();
+    git.git(
+        td.path(),
+        ["clone", origin.to_str().unwrap(), clone.to_str().unwrap()],
+    )
+    .unwrap();
+    // Check out another branch locally so the answer can't come from local HEAD.
+    git.git(&clone, ["checkout", "-b", "feature"]).unwrap();
+
+    let s = GitService::new();
+    let branch = s.get_remote_default_branch(&clone, "origin").unwrap();
+    assert_eq!(branch, "master");
+}
+
+#[test]
+fn remote_default_branch_falls_back_to_remote_show() {
+    let td = TempDir::new().unwrap();
+    let origin = init_origin_with_master(&td);
+    let repo_path = init_repo_main(&td);
+    let git = GitCli::new();
+    git.git(
+        &repo_path,
+        ["remote", "add", "origin", origin.to_str().unwrap()],
+    )
+    .unwrap();
+    git.git(&repo_path, ["fetch", "origin"]).unwrap();
+    // Newer git may record the remote HEAD on fetch; drop it to force the fallback.
+    let _ = git.git(&repo_path, ["remote", "set-head", "origin", "--delete"]);
+
+    let s = GitService::new();
+    let branch = s.get_remote_default_branch(&repo_path, "origin").unwrap();
+    assert_eq!(branch, "master");
+}
+
+#[test]
+fn remote_default_branch_unknown_remote_errors() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    assert!(s.get_remote_default_branch(&repo_path, "nope").is_err());
+}
@@ -15,6 +15,21 @@ pub enum MemberRole {
     Member,
 }
 
+impl MemberRole {
+    /// Privilege level of the role; higher ranks include everything lower ranks may do.
+    /// Spelled out rather than derived so reordering variants can't change permissions.
+    pub fn rank(self) -> u8 {
+        match self {
+            MemberRole::Member => 0,
+            MemberRole::Admin => 1,
+        }
+    }
+
+    pub fn is_at_least(self, min_role: MemberRole) -> bool {
+        self.rank() >= min_role.rank()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[sqlx(type_name = "invitation_status", rename_all = "lowercase")]
@@ -98,6 +113,9 @@ pub struct Invitation {
     pub token: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    /// Whether the invitation can no longer be accepted because it has expired.
+    #[serde(default)]
+    pub is_expired: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -17,7 +17,11 @@ pub struct ApprovalRequest {
 }
 
 impl ApprovalRequest {
-    pub fn from_create(request: CreateApprovalRequest, execution_process_id: Uuid) -> Self {
+    pub fn from_create(
+        request: CreateApprovalRequest,
+        execution_process_id: Uuid,
+        timeout_secs: i64,
+    ) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4().to_string(),
@@ -26,7 +30,7 @@ impl ApprovalRequest {
             tool_call_id: request.tool_call_id,
             execution_process_id,
             created_at: now,
-            timeout_at: now + Duration::seconds(APPROVAL_TIMEOUT_SECONDS),
+            timeout_at: now + Duration::seconds(timeout_secs),
         }
     }
 }
@@ -45,11 +49,20 @@ pub struct CreateApprovalRequest {
 pub enum ApprovalStatus {
     Pending,
     Approved,
+    /// Approved, and the agent may stop asking about the same tool and input pattern for the
+    /// rest of the session.
+    ApprovedForSession,
+    /// Approved, but the tool should run with this input instead of the one the agent proposed.
+    ApprovedWithEdits {
+        updated_input: serde_json::Value,
+    },
     Denied {
         #[ts(optional)]
         reason: Option<String>,
     },
     TimedOut,
+    /// Allowed without prompting because the tool matched the executor's auto-approve allowlist.
+    AutoAllowlisted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
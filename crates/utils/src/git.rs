@@ -1,35 +1,54 @@
-use std::path::PathBuf;
+use std::{collections::HashSet, path::PathBuf, time::Duration};
 
+use futures::future::join_all;
 use tokio::process::Command;
 
-pub async fn check_uncommitted_changes(repo_paths: &[PathBuf]) -> String {
-    if repo_paths.is_empty() {
-        return String::new();
-    }
+/// How long a single `git status` may run before the stop check gives up on that repo.
+const GIT_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
 
-    let mut all_status = String::new();
+/// `git status --porcelain` output for each repo with changes, prefixed by its path.
+/// Untracked files are not reported for repos in `tracked_only`. Repos are checked
+/// concurrently; one whose status fails or times out is treated as clean so a hung git
+/// process can't block the session from stopping.
+pub async fn check_uncommitted_changes(
+    repo_paths: &[PathBuf],
+    tracked_only: &HashSet<PathBuf>,
+) -> String {
+    let checks = repo_paths
+        .iter()
+        .filter(|repo_path| repo_path.join(".git").exists())
+        .map(|repo_path| async move {
+            let mut command = Command::new("git");
+            command
+                .args(["status", "--porcelain"])
+                .current_dir(repo_path)
+                .env("GIT_TERMINAL_PROMPT", "0");
+            if tracked_only.contains(repo_path) {
+                command.arg("--untracked-files=no");
+            }
 
-    for repo_path in repo_paths {
-        if !repo_path.join(".git").exists() {
-            continue;
-        }
+            let status = run_status(command, GIT_STATUS_TIMEOUT).await;
+            if status.is_none() {
+                tracing::warn!(
+                    "git status failed or timed out for {}, not blocking stop",
+                    repo_path.display()
+                );
+            }
+            status
+                .filter(|status| !status.is_empty())
+                .map(|status| format!("\n{}:\n{}", repo_path.display(), status))
+        });
 
-        let output = Command::new("git")
-            .args(["status", "--porcelain"])
-            .current_dir(repo_path)
-            .env("GIT_TERMINAL_PROMPT", "0")
-            .output()
-            .await;
+    join_all(checks).await.into_iter().flatten().collect()
+}
 
-        if let Ok(out) = output
-            && !out.stdout.is_empty()
-        {
-            let status = String::from_utf8_lossy(&out.stdout);
-            all_status.push_str(&format!("\n{}:\n{}", repo_path.display(), status));
-        }
+/// Stdout of `command`, or `None` if it couldn't run or didn't finish within `timeout`.
+async fn run_status(mut command: Command, timeout: Duration) -> Option<String> {
+    command.kill_on_drop(true);
+    match tokio::time::timeout(timeout, command.output()).await {
+        Ok(Ok(output)) => Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+        Ok(Err(_)) | Err(_) => None,
     }
-
-    all_status
 }
 
 pub fn is_valid_branch_prefix(prefix: &str) -> bool {
@@ -78,4 +97,15 @@ mod tests {
         assert!(!is_valid_branch_prefix("foo/"));
         assert!(!is_valid_branch_prefix(".foo"));
     }
+
+    #[tokio::test]
+    async fn slow_status_times_out_as_clean() {
+        let mut command = Command::new("sleep");
+        command.arg("5");
+        assert!(
+            run_status(command, Duration::from_millis(50))
+                .await
+                .is_none()
+        );
+    }
 }
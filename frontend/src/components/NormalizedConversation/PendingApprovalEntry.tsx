@@ -16,7 +16,7 @@ import {
   TooltipTrigger,
 } from '@/components/ui/tooltip';
 import { approvalsApi } from '@/lib/api';
-import { Check, X } from 'lucide-react';
+import { Check, CheckCheck, X } from 'lucide-react';
 import WYSIWYGEditor from '@/components/ui/wysiwyg';
 
 import { useHotkeysContext } from 'react-hotkeys-hook';
@@ -76,11 +76,13 @@ function ActionButtons({
   disabled,
   isResponding,
   onApprove,
+  onApproveForSession,
   onStartDeny,
 }: {
   disabled: boolean;
   isResponding: boolean;
   onApprove: () => void;
+  onApproveForSession: () => void;
   onStartDeny: () => void;
 }) {
   return (
@@ -103,6 +105,30 @@ function ActionButtons({
         </TooltipContent>
       </Tooltip>
 
+      <Tooltip>
+        <TooltipTrigger asChild>
+          <Button
+            onClick={onApproveForSession}
+            variant="ghost"
+            className="h-8 w-8 rounded-full p-0"
+            disabled={disabled}
+            aria-label={
+              isResponding ? 'Submitting approval' : 'Approve for session'
+            }
+            aria-busy={isResponding}
+          >
+            <CheckCheck className="h-5 w-5" />
+          </Button>
+        </TooltipTrigger>
+        <TooltipContent>
+          <p>
+            {isResponding
+              ? 'Submitting…'
+              : 'Approve similar requests for the rest of the session'}
+          </p>
+        </TooltipContent>
+      </Tooltip>
+
       <Tooltip>
         <TooltipTrigger asChild>
           <Button
@@ -241,7 +267,7 @@ const PendingApprovalEntry = ({
   ]);
 
   const respond = useCallback(
-    async (approved: boolean, reason?: string) => {
+    async (status: ApprovalStatus) => {
       if (disabled) return;
       if (!executionProcessId) {
         setError('Missing executionProcessId');
@@ -251,10 +277,6 @@ const PendingApprovalEntry = ({
       setIsResponding(true);
       setError(null);
 
-      const status: ApprovalStatus = approved
-        ? { status: 'approved' }
-        : { status: 'denied', reason };
-
       try {
         await approvalsApi.respond(pendingStatus.approval_id, {
           execution_process_id: executionProcessId,
@@ -274,7 +296,14 @@ const PendingApprovalEntry = ({
     [disabled, executionProcessId, pendingStatus.approval_id, clear]
   );
 
-  const handleApprove = useCallback(() => respond(true), [respond]);
+  const handleApprove = useCallback(
+    () => respond({ status: 'approved' }),
+    [respond]
+  );
+  const handleApproveForSession = useCallback(
+    () => respond({ status: 'approved_for_session' }),
+    [respond]
+  );
   const handleStartDeny = useCallback(() => {
     if (disabled) return;
     setError(null);
@@ -288,7 +317,7 @@ const PendingApprovalEntry = ({
 
   const handleSubmitDeny = useCallback(() => {
     const trimmed = denyReason.trim();
-    respond(false, trimmed || DEFAULT_DENIAL_REASON);
+    respond({ status: 'denied', reason: trimmed || DEFAULT_DENIAL_REASON });
   }, [denyReason, respond]);
 
   const triggerDeny = useCallback(
@@ -333,6 +362,7 @@ const PendingApprovalEntry = ({
                   disabled={disabled}
                   isResponding={isResponding}
                   onApprove={handleApprove}
+                  onApproveForSession={handleApproveForSession}
                   onStartDeny={handleStartDeny}
                 />
               )}
@@ -146,6 +146,9 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         draft: isDraft,
         auto_generate_description: autoGenerateDescription,
         repo_id: repoId,
+        push_remote: null,
+        labels: [],
+        force_with_lease: null,
       });
 
       if (result.success) {
@@ -189,7 +192,9 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
                 ? 'GitHub'
                 : result.error.provider === 'azure_dev_ops'
                   ? 'Azure DevOps'
-                  : 'Git host';
+                  : result.error.provider === 'bitbucket'
+                    ? 'Bitbucket'
+                    : 'Git host';
             const action =
               result.error.type === 'cli_not_installed'
                 ? 'not installed'
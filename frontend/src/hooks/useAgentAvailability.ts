@@ -35,6 +35,7 @@ export function useAgentAvailability(
             setAvailability({ status: 'installation_found' });
             break;
           case 'NOT_FOUND':
+          case 'NPX_NOT_FOUND':
             setAvailability({ status: 'not_found' });
             break;
         }
@@ -28,6 +28,7 @@ export function useRebase(
           repo_id: repoId,
           old_base_branch: oldBaseBranch ?? null,
           new_base_branch: newBaseBranch ?? null,
+          abort_on_conflict: false,
         };
 
         return attemptsApi.rebase(attemptId, data).then((res) => {
@@ -14,6 +14,7 @@ import {
   DirectoryListResponse,
   DirectoryEntry,
   ExecutionProcess,
+  ExecutionProcessEnv,
   ExecutionProcessRepoState,
   GitBranch,
   Project,
@@ -40,6 +41,7 @@ import {
   GitOperationError,
   ApprovalResponse,
   RebaseTaskAttemptRequest,
+  RebaseTaskAttemptResponse,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
   RenameBranchRequest,
@@ -72,10 +74,15 @@ import {
   OpenEditorResponse,
   OpenEditorRequest,
   PrError,
+  CreatePrResponse,
   Scratch,
   ScratchType,
   CreateScratch,
   UpdateScratch,
+  UpdatePrApiRequest,
+  PrRouteError,
+  PrChecks,
+  PrStatusResponse,
   PushError,
   TokenResponse,
   CurrentUserResponse,
@@ -623,7 +630,7 @@ export const attemptsApi = {
   rebase: async (
     attemptId: string,
     data: RebaseTaskAttemptRequest
-  ): Promise<Result<void, GitOperationError>> => {
+  ): Promise<Result<RebaseTaskAttemptResponse, GitOperationError>> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/rebase`,
       {
@@ -631,7 +638,10 @@ export const attemptsApi = {
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponseAsResult<void, GitOperationError>(response);
+    return handleApiResponseAsResult<
+      RebaseTaskAttemptResponse,
+      GitOperationError
+    >(response);
   },
 
   change_target_branch: async (
@@ -682,12 +692,23 @@ export const attemptsApi = {
   createPR: async (
     attemptId: string,
     data: CreatePrApiRequest
-  ): Promise<Result<string, PrError>> => {
+  ): Promise<Result<CreatePrResponse, PrError>> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/pr`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponseAsResult<string, PrError>(response);
+    return handleApiResponseAsResult<CreatePrResponse, PrError>(response);
+  },
+
+  updatePR: async (
+    attemptId: string,
+    data: UpdatePrApiRequest
+  ): Promise<Result<void, PrRouteError>> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pr`, {
+      method: 'PATCH',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponseAsResult<void, PrRouteError>(response);
   },
 
   startDevServer: async (attemptId: string): Promise<ExecutionProcess[]> => {
@@ -748,6 +769,26 @@ export const attemptsApi = {
     return handleApiResponse<PrCommentsResponse>(response);
   },
 
+  getPrChecks: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<Result<PrChecks, PrRouteError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/checks?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponseAsResult<PrChecks, PrRouteError>(response);
+  },
+
+  getPrStatus: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<Result<PrStatusResponse, PrRouteError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/status?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponseAsResult<PrStatusResponse, PrRouteError>(response);
+  },
+
   /** Mark all coding agent turns for a workspace as seen */
   markSeen: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
@@ -776,6 +817,13 @@ export const executionProcessesApi = {
     return handleApiResponse<ExecutionProcessRepoState[]>(response);
   },
 
+  getEnv: async (processId: string): Promise<ExecutionProcessEnv | null> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/env`
+    );
+    return handleApiResponse<ExecutionProcessEnv | null>(response);
+  },
+
   stopExecutionProcess: async (processId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/execution-processes/${processId}/stop`,
@@ -8,13 +8,39 @@ export type Project = { id: string, organization_id: string, name: string, color
 
 export type Notification = { id: string, organization_id: string, user_id: string, notification_type: NotificationType, payload: JsonValue, issue_id: string | null, comment_id: string | null, seen: boolean, dismissed_at: string | null, created_at: string, };
 
-export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted";
+export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted" | "WorkspaceReviewSubmitted";
+
+/**
+ * Project-wide override on top of the per-event toggles.
+ */
+export type NotificationMode = "default" | "watch" | "mute";
+
+export type ProjectNotificationPreference = { project_id: string, user_id: string, notify_on_issue_created: boolean, notify_on_issue_assigned: boolean, mode: NotificationMode, };
 
 export type Workspace = { id: string, project_id: string, owner_user_id: string, issue_id: string | null, local_workspace_id: string | null, archived: boolean, files_changed: number | null, lines_added: number | null, lines_removed: number | null, created_at: string, updated_at: string, };
 
+/**
+ * A workspace together with the aggregate outcome of its teammate reviews
+ */
+export type WorkspaceWithReviewState = { review_state: AggregateReviewState, } & { id: string, project_id: string, owner_user_id: string, issue_id: string | null, local_workspace_id: string | null, archived: boolean, files_changed: number | null, lines_added: number | null, lines_removed: number | null, created_at: string, updated_at: string, };
+
+export type WorkspaceReview = { id: string, workspace_id: string, reviewer_user_id: string, state: WorkspaceReviewState, body: string, created_at: string, };
+
+export type WorkspaceReviewState = "approved" | "changes_requested" | "commented";
+
+/**
+ * Overall review outcome of a workspace, from each reviewer's latest approval or change
+ * request. Comments don't change it.
+ */
+export type AggregateReviewState = "pending" | "approved" | "changes_requested";
+
+export type CreateWorkspaceReviewRequest = { state: WorkspaceReviewState, body: string, };
+
+export type ListWorkspaceReviewsResponse = { reviews: Array<WorkspaceReview>, review_state: AggregateReviewState, };
+
 export type ProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, created_at: string, };
 
-export type Tag = { id: string, project_id: string, name: string, color: string, };
+export type Tag = { id: string, project_id: string, name: string, color: string, created_at: string, updated_at: string, };
 
 export type Issue = { id: string, project_id: string, issue_number: number, simple_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority, start_date: string | null, target_date: string | null, completed_at: string | null, sort_order: number, parent_issue_id: string | null, extension_metadata: JsonValue, created_at: string, updated_at: string, };
 
@@ -28,10 +54,22 @@ export type IssueRelationship = { id: string, issue_id: string, related_issue_id
 
 export type IssueRelationshipType = "blocking" | "related" | "has_duplicate";
 
-export type IssueComment = { id: string, issue_id: string, author_id: string, message: string, created_at: string, updated_at: string, };
+export type IssueComment = { id: string, issue_id: string, author_id: string, parent_comment_id: string | null, message: string, created_at: string, updated_at: string, };
+
+export type IssueCommentEntry = { deleted_at: string | null, id: string, issue_id: string, author_id: string, parent_comment_id: string | null, message: string, created_at: string, updated_at: string, };
 
 export type IssueCommentReaction = { id: string, comment_id: string, user_id: string, emoji: string, created_at: string, };
 
+/**
+ * An issue together with everything the issue detail view needs.
+ */
+export type IssueDetail = { issue: Issue, comments: Array<IssueComment>, assignees: Array<IssueAssignee>, tags: Array<IssueTag>, followers: Array<IssueFollower>, relationships: Array<IssueRelationship>, };
+
+/**
+ * One field of an issue changing value, e.g. `priority` going from `"high"` to `"urgent"`.
+ */
+export type IssueActivity = { id: string, issue_id: string, project_id: string, actor_id: string | null, field: string, old_value: JsonValue | null, new_value: JsonValue | null, created_at: string, };
+
 export type IssuePriority = "urgent" | "high" | "medium" | "low";
 
 export type PullRequestStatus = "open" | "merged" | "closed";
@@ -40,12 +78,56 @@ export type PullRequest = { id: string, url: string, number: number, status: Pul
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
 
-export type User = { id: string, email: string, first_name: string | null, last_name: string | null, username: string | null, created_at: string, updated_at: string, };
+export type User = { id: string, email: string, first_name: string | null, last_name: string | null, username: string | null, display_name: string | null, avatar_url: string | null, created_at: string, updated_at: string, };
+
+/**
+ * Partial profile update; omitted fields are left unchanged.
+ */
+export type UpdateUserProfileRequest = { display_name?: string, avatar_url?: string, };
+
+export type MemberProfile = { display_name: string | null, avatar_url: string | null, };
+
+export type MemberProfilesResponse = { profiles: { [key in string]?: MemberProfile }, };
+
+/**
+ * A sign-in provider linked to the current user, as shown in account settings.
+ */
+export type LinkedOAuthAccount = { id: string, provider: string, username: string | null, display_name: string | null, email: string | null, avatar_url: string | null, created_at: string, };
+
+export type ListOAuthAccountsResponse = { accounts: Array<LinkedOAuthAccount>, };
 
 export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
 
 export type OrganizationMember = { organization_id: string, user_id: string, role: MemberRole, joined_at: string, last_seen_at: string | null, };
 
+export type ProjectExport = { version: number, exported_at: string, project: ExportedProject, statuses: Array<ExportedStatus>, tags: Array<ExportedTag>, 
+/**
+ * Everyone referenced as an assignee or comment author.
+ */
+users: Array<ExportedUser>, 
+/**
+ * Ordered by issue number, so re-importing keeps the numbering order.
+ */
+issues: Array<ExportedIssue>, issue_tags: Array<ExportedIssueTag>, assignees: Array<ExportedAssignee>, comments: Array<ExportedComment>, relationships: Array<ExportedRelationship>, };
+
+export type ExportedProject = { id: string, name: string, color: string, };
+
+export type ExportedStatus = { id: string, name: string, color: string, sort_order: number, hidden: boolean, };
+
+export type ExportedTag = { id: string, name: string, color: string, };
+
+export type ExportedUser = { id: string, email: string, first_name: string | null, last_name: string | null, username: string | null, };
+
+export type ExportedIssue = { id: string, status_id: string, title: string, description: string | null, priority: IssuePriority, start_date: string | null, target_date: string | null, completed_at: string | null, sort_order: number, parent_issue_id: string | null, extension_metadata: JsonValue, created_at: string, };
+
+export type ExportedIssueTag = { issue_id: string, tag_id: string, };
+
+export type ExportedAssignee = { issue_id: string, user_id: string, };
+
+export type ExportedComment = { id: string, issue_id: string, author_id: string, parent_comment_id: string | null, message: string, created_at: string, };
+
+export type ExportedRelationship = { issue_id: string, related_issue_id: string, relationship_type: IssueRelationshipType, };
+
 export type CreateProjectRequest = { 
 /**
  * Optional client-generated ID. If not provided, server generates one.
@@ -132,9 +214,9 @@ export type CreateIssueCommentRequest = {
  * Optional client-generated ID. If not provided, server generates one.
  * Using client-generated IDs enables stable optimistic updates.
  */
-id?: string, issue_id: string, message: string, };
+id?: string, issue_id: string, parent_comment_id: string | null, message: string, };
 
-export type UpdateIssueCommentRequest = { message: string | null, };
+export type UpdateIssueCommentRequest = { parent_comment_id?: string | null, message: string | null, };
 
 export type CreateIssueCommentReactionRequest = { 
 /**
@@ -145,6 +227,17 @@ id?: string, comment_id: string, emoji: string, };
 
 export type UpdateIssueCommentReactionRequest = { emoji: string | null, };
 
+/**
+ * Machine-readable error category so clients can branch without parsing messages.
+ */
+export type ErrorCode = "bad_request" | "unauthorized" | "forbidden" | "not_found" | "conflict" | "duplicate" | "invalid_reference" | "unprocessable" | "unavailable" | "internal";
+
+/**
+ * JSON body of every [`ErrorResponse`]. The message is serialized as `error` so clients
+ * that only read the message keep working.
+ */
+export type ErrorBody = { code: ErrorCode, error: string, };
+
 // Shape definition interface
 export interface ShapeDefinition<T> {
   readonly table: string;
@@ -241,6 +334,12 @@ export const PULL_REQUESTS_SHAPE = defineShape<PullRequest>(
   '/v1/shape/project/{project_id}/pull_requests'
 );
 
+export const ISSUE_ACTIVITY_SHAPE = defineShape<IssueActivity>(
+  'issue_activity',
+  ['project_id'] as const,
+  '/v1/shape/project/{project_id}/issue_activity'
+);
+
 export const ISSUE_COMMENTS_SHAPE = defineShape<IssueComment>(
   'issue_comments',
   ['issue_id'] as const,
@@ -253,6 +352,24 @@ export const ISSUE_COMMENT_REACTIONS_SHAPE = defineShape<IssueCommentReaction>(
   '/v1/shape/issue/{issue_id}/reactions'
 );
 
+export const MY_ASSIGNED_ISSUES_SHAPE = defineShape<Issue>(
+  'issues',
+  ['project_id', 'user_id'] as const,
+  '/v1/shape/project/{project_id}/my_assigned_issues'
+);
+
+export const SINGLE_ISSUE_SHAPE = defineShape<Issue>(
+  'issues',
+  ['issue_id'] as const,
+  '/v1/shape/issue/{issue_id}'
+);
+
+export const SUB_ISSUES_SHAPE = defineShape<Issue>(
+  'issues',
+  ['issue_id'] as const,
+  '/v1/shape/issue/{issue_id}/sub_issues'
+);
+
 // =============================================================================
 // Entity Definitions for SDK Generation
 // =============================================================================
@@ -16,11 +16,26 @@ export type SearchResult = { path: string, is_file: boolean, match_type: SearchM
  */
 score: bigint, };
 
-export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
+export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath" | "Content";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, created_at: Date, updated_at: Date, };
+/**
+ * Which changes in a repo make the agent commit before it stops.
+ */
+export type CommitCheck = "all" | "tracked_only" | "off";
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, 
+/**
+ * Environment variables injected into every execution in this repo. Values are redacted
+ * when serialized unless the key is allowlisted as safe.
+ */
+env_vars: Record<string, string>, commit_check: CommitCheck, created_at: Date, updated_at: Date, };
+
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, 
+/**
+ * Replaces the repo's env vars. A value of `***` keeps the stored value for that key,
+ * so a redacted map read from the API can be sent back unchanged.
+ */
+env_vars?: { [key in string]?: string }, commit_check?: CommitCheck, };
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
@@ -46,10 +61,20 @@ export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_att
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, 
+/**
+ * Task template whose content is prepended to the description on creation.
+ */
+template_id?: string, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
 
+export type TaskTemplate = { id: string, project_id: string | null, title: string, description: string, created_at: string, updated_at: string, };
+
+export type CreateTaskTemplate = { title: string, description: string, };
+
+export type UpdateTaskTemplate = { title: string | null, description: string | null, };
+
 export type DraftFollowUpData = { message: string, executor_profile_id: ExecutorProfileId, };
 
 export type DraftWorkspaceData = { message: string, project_id: string | null, repos: Array<DraftWorkspaceRepo>, selected_profile: ExecutorProfileId | null, };
@@ -86,14 +111,41 @@ export type ExecutionProcess = { id: string, session_id: string, run_reason: Exe
  * history view (due to restore/trimming). Hidden from logs/timeline;
  * still listed in the Processes tab.
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+dropped: boolean, 
+/**
+ * Set when the process was stopped rather than exiting by itself
+ */
+termination: ExecutionProcessTermination | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
+/**
+ * How a stopped execution process went away
+ */
+export type ExecutionProcessTermination = "graceful_interrupt" | "force_kill";
+
 export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
 
 export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
 
+export type EnvVarSource = "runtime" | "repo";
+
+export type ExecutionEnvVar = { key: string, 
+/**
+ * Already redacted for secrets.
+ */
+value: string, source: EnvVarSource, 
+/**
+ * Repository the var was configured on, when `source` is `repo`.
+ */
+repo_name: string | null, };
+
+/**
+ * Redacted snapshot of the environment an execution process was started with. Profile
+ * env and request-specific vars are applied later by the executor and are not included.
+ */
+export type ExecutionProcessEnv = { execution_process_id: string, vars: Array<ExecutionEnvVar>, created_at: Date, };
+
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
 export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
@@ -104,7 +156,7 @@ export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
 export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
 
-export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
+export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "approved_for_session" } | { "status": "approved_with_edits", updated_input: JsonValue, } | { "status": "denied", reason?: string, } | { "status": "timed_out" } | { "status": "auto_allowlisted" };
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
@@ -150,7 +202,11 @@ export type CreateOrganizationResponse = { organization: OrganizationWithRole, }
 
 export type UpdateOrganizationRequest = { name: string, };
 
-export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, };
+export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, 
+/**
+ * Whether the invitation can no longer be accepted because it has expired.
+ */
+is_expired: boolean, };
 
 export type CreateInvitationRequest = { email: string, role: MemberRole, };
 
@@ -184,10 +240,33 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type ArchiveCompletedQuery = { dry_run: boolean, };
+
+export type ArchiveSkipReason = "pinned" | "running" | "merge_pending" | "cleanup_failed";
+
+export type SkippedWorkspace = { workspace_id: string, task_id: string, reason: ArchiveSkipReason, };
+
+/**
+ * Summary of archiving the workspaces of a project's done tasks
+ */
+export type ArchiveCompletedResponse = { 
+/**
+ * When true nothing was archived; the response lists what would have been
+ */
+dry_run: boolean, 
+/**
+ * Workspaces that were (or would be) archived and had their worktrees removed
+ */
+archived: Array<string>, skipped: Array<SkippedWorkspace>, };
+
+export type CleanupRequest = { dry_run: boolean, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
+export type GitHostCapabilitiesResponse = { provider: ProviderKind, capabilities: ProviderCapabilities, };
+
 export type TagSearchParams = { search: string | null, };
 
 export type TokenResponse = { access_token: string, expires_at: string | null, };
@@ -238,7 +317,15 @@ export type OpenEditorResponse = { url: string | null, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
-export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, 
+/**
+ * Remote to push the branch to (e.g. a fork) when it differs from the PR target.
+ */
+push_remote: string | null, labels: Array<string>, 
+/**
+ * Overwrite a diverged remote branch (e.g. after a rebase) using `--force-with-lease`
+ */
+force_with_lease: boolean | null, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
@@ -254,31 +341,87 @@ export type RunAgentSetupResponse = Record<string, never>;
 
 export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
 
-export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
+export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, 
+/**
+ * Abort the rebase on conflict instead of leaving it in progress for resolution
+ */
+abort_on_conflict: boolean, };
+
+export type RebaseTaskAttemptResponse = { head_sha: string, 
+/**
+ * Commits on the workspace branch that were replayed onto the new base
+ */
+commits_replayed: number, 
+/**
+ * Set when uncommitted changes couldn't be reapplied after the rebase. They are kept in
+ * this stash and can be recovered with `git stash apply <sha>`.
+ */
+kept_stash: string | null, };
 
 export type AbortConflictsRequest = { repo_id: string, };
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, target_branch: string, } | { "type": "rebase_in_progress" };
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, target_branch: string, kept_stash: string | null, } | { "type": "rebase_in_progress" } | { "type": "execution_process_running" };
 
 export type PushError = { "type": "force_push_required" };
 
-export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" };
+export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "push_remote_not_found", remote: string, } | { "type": "push_rejected_non_fast_forward" } | { "type": "unsupported_provider" };
 
 export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
 
+export type CreatePrResponse = { url: string, 
+/**
+ * Commits the target branch has that the workspace branch lacks, when known
+ */
+commits_behind: number | null, 
+/**
+ * Non-fatal hint for the UI, e.g. suggesting a rebase before merging
+ */
+warning: string | null, };
+
 export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
 
 export type AttachExistingPrRequest = { repo_id: string, };
 
 export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
 
-export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, };
+/**
+ * Failures shared by the routes that act on a workspace's attached PR.
+ */
+export type PrRouteError = { "type": "no_pr_attached" } | { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "unsupported_provider" };
+
+export type UpdatePrApiRequest = { repo_id: string, title: string | null, body: string | null, };
 
 export type GetPrCommentsQuery = { repo_id: string, };
 
+export type GetPrChecksQuery = { repo_id: string, };
+
+export type GetPrStatusQuery = { repo_id: string, };
+
+export type PrStatusResponse = { status: MergeStatus, merge_commit_sha: string | null, };
+
 export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string | null, body: string, created_at: string, url: string | null, } | { "comment_type": "review", id: bigint, author: string, author_association: string | null, body: string, created_at: string, url: string | null, path: string, line: bigint | null, side: string | null, diff_hunk: string | null, };
 
-export type ProviderKind = "git_hub" | "azure_dev_ops" | "unknown";
+export type ProviderKind = "git_hub" | "azure_dev_ops" | "bitbucket" | "unknown";
+
+/**
+ * Pull request operations supported by a git host, so clients can hide controls for
+ * operations that would otherwise fail.
+ */
+export type ProviderCapabilities = { supports_draft: boolean, supports_reviewers: boolean, supports_labels: boolean, supports_comment_resolve: boolean, supports_merge_methods: boolean, };
+
+export type PrChecks = { checks_status: ChecksStatus, 
+/**
+ * `None` when the host has no review requirement for the PR
+ */
+review_decision: ReviewDecision | null, 
+/**
+ * `None` while the host is still computing mergeability
+ */
+mergeable: boolean | null, };
+
+export type ChecksStatus = "passing" | "failing" | "pending" | "none";
+
+export type ReviewDecision = "approved" | "changes_requested" | "review_required";
 
 export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
@@ -352,9 +495,59 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type SearchMode = "taskform" | "settings";
+/**
+ * Summary of an orphan cleanup run
+ */
+export type OrphanCleanupReport = { 
+/**
+ * When true nothing was removed; the report lists what would have been
+ */
+dry_run: boolean, 
+/**
+ * Workspace directories that were (or would be) removed
+ */
+removed: Array<string>, 
+/**
+ * Archived workspaces whose stale container ref was (or would be) cleared
+ */
+cleared_container_refs: Array<string>, failures: Array<OrphanCleanupFailure>, };
+
+export type OrphanCleanupFailure = { path: string, error: string, };
+
+export type SearchMode = "taskform" | "settings" | "directoriesonly";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, };
+/**
+ * A line containing the searched-for text
+ */
+export type GrepMatch = { path: string, 
+/**
+ * 1-based line number
+ */
+line: bigint, 
+/**
+ * The matching line, trimmed and cut to [`MAX_GREP_SNIPPET_CHARS`]
+ */
+snippet: string, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, cleanup_orphans_on_startup: boolean, 
+/**
+ * How long a tool approval may stay pending before it times out. A reminder
+ * notification is sent halfway through.
+ */
+approval_timeout_secs: number, 
+/**
+ * How long a stopped execution gets to exit after being interrupted before its
+ * process group is killed.
+ */
+stop_grace_period_secs: number, 
+/**
+ * How often open PRs are checked for merges or closure.
+ */
+pr_poll_interval_secs: number, 
+/**
+ * Allow file searches that match on file contents (`mode=content`).
+ */
+content_search_enabled: boolean, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -423,7 +616,7 @@ export type SlashCommandDescription = {
  */
 name: string, description?: string | null, };
 
-export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
+export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" } | { "type": "NPX_NOT_FOUND" };
 
 export type CommandBuilder = { 
 /**
@@ -451,7 +644,12 @@ export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: Executor
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, 
+/**
+ * Tools allowed without prompting when approvals are enabled. Supports exact names
+ * and `*` globs, e.g. `["Read", "Grep", "mcp__*"]`.
+ */
+auto_approve_tools?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
 export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
@@ -478,10 +676,31 @@ export type Opencode = { append_prompt: AppendPrompt, model?: string | null, var
  * Auto-approve agent actions
  */
 auto_approve: boolean, 
+/**
+ * Reply "always" to permission requests approved for the session so OpenCode stops asking
+ * for the same tool and pattern; other approvals are still answered "once"
+ */
+remember_approvals: boolean, 
 /**
  * Enable auto-compaction when the context length approaches the model's context window limit
  */
-auto_compact: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+auto_compact: boolean, 
+/**
+ * Seconds to wait for the OpenCode server to start (defaults to 180)
+ */
+startup_timeout_secs?: bigint | null, 
+/**
+ * Shell command to run before starting OpenCode, e.g. to pre-warm the npx cache
+ */
+pre_start_command?: string | null, 
+/**
+ * Consecutive event stream reconnect failures tolerated before giving up (defaults to 20)
+ */
+max_reconnect_attempts?: number | null, 
+/**
+ * Buffered control events between the event stream and the session loop (defaults to 32)
+ */
+control_channel_capacity?: number | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
 export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 